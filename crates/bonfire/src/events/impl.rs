@@ -1,10 +1,11 @@
 use std::collections::HashSet;
 
 use futures::future::join_all;
+use revolt_config::config;
 use revolt_database::{
     events::client::{EventV1, ReadyPayloadFields},
     util::permissions::DatabasePermissionQuery,
-    Channel, Database, Member, MemberCompositeKey, Presence, RelationshipStatus,
+    Channel, Database, File, Member, MemberCompositeKey, Presence, RelationshipStatus,
 };
 use revolt_models::v0;
 use revolt_permissions::{calculate_channel_permissions, ChannelPermission};
@@ -187,6 +188,23 @@ impl State {
             None
         };
 
+        let stickers = if fields.stickers {
+            Some(
+                db.fetch_stickers_by_parent_ids(
+                    &servers
+                        .iter()
+                        .map(|x| x.id.to_string())
+                        .collect::<Vec<String>>(),
+                )
+                .await?
+                .into_iter()
+                .map(|sticker| sticker.into())
+                .collect(),
+            )
+        } else {
+            None
+        };
+
         // Fetch user settings
         let user_settings = if !fields.user_settings.is_empty() {
             Some(
@@ -269,6 +287,7 @@ impl State {
                 None
             },
             emojis,
+            stickers,
             user_settings,
             channel_unreads,
 
@@ -465,6 +484,7 @@ impl State {
                 server,
                 channels,
                 emojis: _,
+                stickers: _,
             } => {
                 self.insert_subscription(id.clone()).await;
 
@@ -494,14 +514,18 @@ impl State {
                 id, data, clear, ..
             } => {
                 if let Some(server) = self.cache.servers.get_mut(id) {
-                    for field in clear {
+                    for field in clear.iter() {
                         server.remove_field(&field.clone().into());
                     }
 
                     server.apply_options(data.clone().into());
                 }
 
-                if data.default_permissions.is_some() {
+                // Category overrides also affect which channels we can see
+                if data.default_permissions.is_some()
+                    || data.categories.is_some()
+                    || clear.contains(&v0::FieldsServer::Categories)
+                {
                     queue_server = Some(id.clone());
                 }
             }
@@ -617,6 +641,24 @@ impl State {
                         .relationship_with(&message.author)
                         .into();
                 }
+
+                // Attachments from channels which aren't publicly viewable
+                // must be signed for each individual viewer.
+                if let Some(attachments) = &mut message.attachments {
+                    let config = config().await;
+                    let channel = self.cache.channels.get(&message.channel);
+                    let server = match channel {
+                        Some(
+                            Channel::TextChannel { server, .. }
+                            | Channel::VoiceChannel { server, .. },
+                        ) => self.cache.servers.get(server),
+                        _ => None,
+                    };
+
+                    if !channel.is_some_and(|channel| channel.is_publicly_viewable(server)) {
+                        File::sign_for(attachments, &self.cache.user_id, &config.files.signing);
+                    }
+                }
             }
 
             _ => {}
@@ -153,6 +153,13 @@ pub struct PushVapid {
     pub public_key: String,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+pub struct PushUnifiedPush {
+    pub queue: String,
+    pub enabled: bool,
+    pub timeout: u64,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct PushFcm {
     pub queue: String,
@@ -191,6 +198,7 @@ pub struct ApiSecurity {
     pub trust_cloudflare: bool,
     pub easypwned: String,
     pub tenor_key: String,
+    pub giphy_key: String,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -218,6 +226,10 @@ pub struct Pushd {
     pub exchange: String,
     pub mass_mention_chunk_size: usize,
 
+    // Delivery
+    pub max_delivery_attempts: u32,
+    pub retry_delay: u32,
+
     // Queues
     pub message_queue: String,
     pub mass_mention_queue: String,
@@ -229,6 +241,7 @@ pub struct Pushd {
     pub vapid: PushVapid,
     pub fcm: PushFcm,
     pub apn: PushApn,
+    pub unifiedpush: PushUnifiedPush,
 }
 
 impl Pushd {
@@ -262,6 +275,16 @@ impl Pushd {
     pub fn get_generic_routing_key(&self) -> String {
         self.get_routing_key(self.generic_queue.clone())
     }
+
+    /// Name of the queue declared for a given queue key
+    pub fn get_queue_name(&self, queue: &str) -> String {
+        self.get_routing_key(queue.to_string())
+    }
+
+    /// Name of the delayed queue transient failures on an outbound queue are parked in
+    pub fn get_retry_queue(&self, queue: &str) -> String {
+        self.get_routing_key(format!("{queue}.retry"))
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -282,19 +305,59 @@ pub struct FilesS3 {
     pub default_bucket: String,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+pub struct FilesSigning {
+    pub key: String,
+    pub expiry: u64,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct Files {
     pub encryption_key: String,
     pub webp_quality: f32,
+    pub avif_quality: u8,
+    pub avif_speed: u8,
+    pub jpeg_quality: u8,
     pub blocked_mime_types: Vec<String>,
     pub clamd_host: String,
     pub scan_mime_types: Vec<String>,
 
     pub limit: FilesLimit,
     pub preview: HashMap<String, [usize; 2]>,
+    pub preview_sizes: Vec<usize>,
+    pub store_preview_variants: bool,
+    pub signing: FilesSigning,
     pub s3: FilesS3,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+pub struct JanuaryCache {
+    pub embed_capacity: u64,
+    pub proxy_capacity: u64,
+    pub memory_ttl: u64,
+    pub shared: bool,
+    pub default_ttl: u64,
+    pub min_ttl: u64,
+    pub max_ttl: u64,
+    pub store_proxied_files: bool,
+    pub redis_max_size: usize,
+    pub failure_backoff: u64,
+    pub max_failure_backoff: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct January {
+    pub max_body_size: usize,
+    pub allowed_hosts: Vec<String>,
+    pub blocked_hosts: Vec<String>,
+    pub cache: JanuaryCache,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Gifbox {
+    pub providers: HashMap<String, u32>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct GlobalLimits {
     pub group_size: usize,
@@ -302,6 +365,8 @@ pub struct GlobalLimits {
     pub message_replies: usize,
     pub message_reactions: usize,
     pub server_emoji: usize,
+    pub server_stickers: usize,
+    pub server_templates: usize,
     pub server_roles: usize,
     pub server_channels: usize,
 
@@ -318,8 +383,22 @@ pub struct FeaturesLimits {
     pub message_length: usize,
     pub message_attachments: usize,
     pub servers: usize,
+    #[serde(default = "default_favourites")]
+    pub favourites: usize,
 
     pub file_upload_size_limit: HashMap<String, usize>,
+    #[serde(default = "default_storage_quota")]
+    pub storage_quota: usize,
+}
+
+/// Configurations predating favourites get the default user limit
+fn default_favourites() -> usize {
+    50
+}
+
+/// Configurations predating storage quotas are left unlimited
+fn default_storage_quota() -> usize {
+    usize::MAX
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -377,6 +456,8 @@ pub struct Settings {
     pub api: Api,
     pub pushd: Pushd,
     pub files: Files,
+    pub january: January,
+    pub gifbox: Gifbox,
     pub features: Features,
     pub sentry: Sentry,
     pub production: bool,
@@ -3,10 +3,11 @@ use revolt_result::Error;
 use serde::{Deserialize, Serialize};
 
 use revolt_models::v0::{
-    AppendMessage, Channel, ChannelUnread, Emoji, FieldsChannel, FieldsMember, FieldsMessage,
-    FieldsRole, FieldsServer, FieldsUser, FieldsWebhook, Member, MemberCompositeKey, Message,
-    PartialChannel, PartialMember, PartialMessage, PartialRole, PartialServer, PartialUser,
-    PartialWebhook, PolicyChange, RemovalIntention, Report, Server, User, UserSettings, Webhook,
+    AppendMessage, Channel, ChannelUnread, Emoji, Favourite, FieldsChannel, FieldsMember,
+    FieldsMessage, FieldsRole, FieldsServer, FieldsSticker, FieldsUser, FieldsWebhook, Member,
+    MemberCompositeKey, Message, PartialChannel, PartialMember, PartialMessage, PartialRole,
+    PartialServer, PartialSticker, PartialUser, PartialWebhook, PolicyChange, RemovalIntention,
+    Report, Server, Sticker, User, UserSettings, Webhook,
 };
 
 use crate::Database;
@@ -27,6 +28,7 @@ pub struct ReadyPayloadFields {
     pub channels: bool,
     pub members: bool,
     pub emojis: bool,
+    pub stickers: bool,
     pub user_settings: Vec<String>,
     pub channel_unreads: bool,
     pub policy_changes: bool,
@@ -40,6 +42,7 @@ impl Default for ReadyPayloadFields {
             channels: true,
             members: true,
             emojis: true,
+            stickers: true,
             user_settings: Vec::new(),
             channel_unreads: false,
             policy_changes: true,
@@ -72,6 +75,8 @@ pub enum EventV1 {
         members: Option<Vec<Member>>,
         #[serde(skip_serializing_if = "Option::is_none")]
         emojis: Option<Vec<Emoji>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stickers: Option<Vec<Sticker>>,
 
         #[serde(skip_serializing_if = "Option::is_none")]
         user_settings: Option<UserSettings>,
@@ -138,6 +143,8 @@ pub enum EventV1 {
         server: Server,
         channels: Vec<Channel>,
         emojis: Vec<Emoji>,
+        #[serde(default)]
+        stickers: Vec<Sticker>,
     },
 
     /// Update existing server
@@ -203,6 +210,11 @@ pub enum EventV1 {
     UserRelationship { id: String, user: User },
     /// Settings updated remotely
     UserSettingsUpdate { id: String, update: UserSettings },
+    /// Favourites updated remotely
+    UserFavouritesUpdate {
+        id: String,
+        favourites: Vec<Favourite>,
+    },
 
     /// User has been platform banned or deleted their account
     ///
@@ -220,6 +232,20 @@ pub enum EventV1 {
     /// Delete emoji
     EmojiDelete { id: String },
 
+    /// New sticker
+    StickerCreate(Sticker),
+
+    /// Update existing sticker
+    StickerUpdate {
+        id: String,
+        data: PartialSticker,
+        #[serde(default)]
+        clear: Vec<FieldsSticker>,
+    },
+
+    /// Delete sticker
+    StickerDelete { id: String },
+
     /// New report
     ReportCreate(Report),
     /// New channel
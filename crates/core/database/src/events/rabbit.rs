@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use revolt_models::v0::PushNotification;
 use serde::{Deserialize, Serialize};
 
@@ -55,6 +56,40 @@ pub struct PayloadToService {
     pub session_id: String,
     pub token: String,
     pub extras: HashMap<String, String>,
+    /// Number of times delivery of this payload has already been attempted
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+/// Key registered by a mobile session to receive end-to-end encrypted notifications
+///
+/// See `revolt_models::v0::EncryptedPushNotification` for the format.
+pub struct DeviceKey {
+    /// Uncompressed P-256 public key
+    pub public_key: Vec<u8>,
+    /// Authentication secret
+    pub auth_secret: Vec<u8>,
+}
+
+impl DeviceKey {
+    /// Parse a device key from the `p256dh` field of a push subscription
+    pub fn parse(value: &str) -> Option<DeviceKey> {
+        let (public_key, auth_secret) = value.split_once('.')?;
+        let public_key = URL_SAFE_NO_PAD.decode(public_key).ok()?;
+        let auth_secret = URL_SAFE_NO_PAD.decode(auth_secret).ok()?;
+
+        if public_key.len() != 65 || public_key[0] != 0x04 || auth_secret.len() != 16 {
+            return None;
+        }
+
+        // Make sure the key is actually a point on the curve
+        p256::PublicKey::from_sec1_bytes(&public_key).ok()?;
+
+        Some(DeviceKey {
+            public_key,
+            auth_secret,
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize)]
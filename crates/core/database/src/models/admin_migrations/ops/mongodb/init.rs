@@ -88,6 +88,34 @@ pub async fn create_database(db: &MongoDb) {
         .await
         .expect("Failed to create ratelimit_events collection.");
 
+    db.create_collection("storage_usage")
+        .await
+        .expect("Failed to create storage_usage collection.");
+
+    db.create_collection("user_favourites")
+        .await
+        .expect("Failed to create user_favourites collection.");
+
+    db.create_collection("stickers")
+        .await
+        .expect("Failed to create stickers collection.");
+
+    db.create_collection("server_templates")
+        .await
+        .expect("Failed to create server_templates collection.");
+
+    db.create_collection("discovery_listings")
+        .await
+        .expect("Failed to create discovery_listings collection.");
+
+    db.create_collection("reaction_roles")
+        .await
+        .expect("Failed to create reaction_roles collection.");
+
+    db.create_collection("automod_configs")
+        .await
+        .expect("Failed to create automod_configs collection.");
+
     db.create_collection("pubsub")
         .with_options(
             CreateCollectionOptions::builder()
@@ -263,5 +291,70 @@ pub async fn create_database(db: &MongoDb) {
     .await
     .expect("Failed to create ratelimit_events index.");
 
+    db.run_command(doc! {
+        "createIndexes": "stickers",
+        "indexes": [
+            {
+                "key": {
+                    "parent.id": 1_i32,
+                },
+                "name": "parent_id"
+            }
+        ]
+    })
+    .await
+    .expect("Failed to create sticker parent index.");
+
+    db.run_command(doc! {
+        "createIndexes": "server_templates",
+        "indexes": [
+            {
+                "key": {
+                    "source_server_id": 1_i32,
+                },
+                "name": "source_server_id"
+            }
+        ]
+    })
+    .await
+    .expect("Failed to create server template source index.");
+
+    db.run_command(doc! {
+        "createIndexes": "discovery_listings",
+        "indexes": [
+            {
+                "key": {
+                    "state": 1_i32,
+                    "category": 1_i32,
+                },
+                "name": "state_category"
+            },
+            {
+                "key": {
+                    "stats.name": "text",
+                    "stats.description": "text",
+                    "tags": "text"
+                },
+                "name": "search"
+            },
+            {
+                "key": {
+                    "state": 1_i32,
+                    "stats.member_count": -1_i32
+                },
+                "name": "state_members"
+            },
+            {
+                "key": {
+                    "state": 1_i32,
+                    "stats.last_message_id": -1_i32
+                },
+                "name": "state_activity"
+            }
+        ]
+    })
+    .await
+    .expect("Failed to create discovery listing index.");
+
     info!("Created database.");
 }
@@ -26,7 +26,7 @@ struct MigrationInfo {
     revision: i32,
 }
 
-pub const LATEST_REVISION: i32 = 42; // MUST BE +1 to last migration
+pub const LATEST_REVISION: i32 = 52; // MUST BE +1 to last migration
 
 pub async fn migrate_database(db: &MongoDb) {
     let migrations = db.col::<Document>("migrations");
@@ -1226,6 +1226,264 @@ pub async fn run_migrations(db: &MongoDb, revision: i32) -> i32 {
         }
     }
 
+    if revision <= 42 {
+        info!("Running migration [revision 42 / 18-10-2026]: Add attachment hash metadata type index.");
+
+        // Placeholders for existing images are backfilled by crond
+        db.db()
+            .run_command(doc! {
+                "createIndexes": "attachment_hashes",
+                "indexes": [
+                    {
+                        "key": {
+                            "metadata.type": 1_i32
+                        },
+                        "name": "metadata_type"
+                    }
+                ]
+            })
+            .await
+            .expect("Failed to create attachment hash index.");
+    }
+
+    if revision <= 43 {
+        info!("Running migration [revision 43 / 18-10-2026]: Add collection `storage_usage` and backfill usage.");
+
+        db.db().create_collection("storage_usage").await.ok();
+
+        #[derive(Deserialize)]
+        struct UsageKey {
+            owner: String,
+            tag: String,
+        }
+
+        #[derive(Deserialize)]
+        struct UsageShell {
+            #[serde(rename = "_id")]
+            key: UsageKey,
+            size: i64,
+        }
+
+        let group = |owner: &str| {
+            doc! {
+                "$group": {
+                    "_id": {
+                        "owner": owner,
+                        "tag": "$tag"
+                    },
+                    "size": {
+                        "$sum": "$size"
+                    }
+                }
+            }
+        };
+
+        let pipelines = [
+            // Files charged to their uploader
+            vec![
+                doc! {
+                    "$match": {
+                        "deleted": { "$ne": true },
+                        "uploader_id": { "$exists": true }
+                    }
+                },
+                group("$uploader_id"),
+            ],
+            // Server icons and banners charged to their server
+            vec![
+                doc! {
+                    "$match": {
+                        "deleted": { "$ne": true },
+                        "used_for.type": { "$in": ["ServerIcon", "ServerBanner"] }
+                    }
+                },
+                group("$used_for.id"),
+            ],
+            // Emojis charged to their server
+            vec![
+                doc! {
+                    "$match": {
+                        "deleted": { "$ne": true },
+                        "used_for.type": "Emoji"
+                    }
+                },
+                doc! {
+                    "$lookup": {
+                        "from": "emojis",
+                        "localField": "used_for.id",
+                        "foreignField": "_id",
+                        "as": "emoji"
+                    }
+                },
+                doc! {
+                    "$unwind": "$emoji"
+                },
+                doc! {
+                    "$match": {
+                        "emoji.parent.type": "Server"
+                    }
+                },
+                group("$emoji.parent.id"),
+            ],
+        ];
+
+        for pipeline in pipelines {
+            let mut usage = db
+                .db()
+                .collection::<Document>("attachments")
+                .aggregate(pipeline)
+                .await
+                .expect("storage usage")
+                .filter_map(|s| async { s.ok() })
+                .filter_map(|doc| async { from_document::<UsageShell>(doc).ok() })
+                .boxed();
+
+            while let Some(UsageShell {
+                key: UsageKey { owner, tag },
+                size,
+            }) = usage.next().await
+            {
+                db.db()
+                    .collection::<Document>("storage_usage")
+                    .update_one(
+                        doc! { "_id": owner },
+                        doc! {
+                            "$inc": {
+                                "total": size,
+                                format!("tags.{tag}"): size
+                            }
+                        },
+                    )
+                    .upsert(true)
+                    .await
+                    .expect("update storage usage");
+            }
+        }
+    }
+
+    if revision <= 44 {
+        info!("Running migration [revision 44 / 18-10-2026]: Add collection `user_favourites`.");
+
+        db.db().create_collection("user_favourites").await.ok();
+    }
+
+    if revision <= 45 {
+        info!("Running migration [revision 45 / 18-10-2026]: Add `stickers` collection.");
+
+        db.db().create_collection("stickers").await.ok();
+
+        db.db()
+            .run_command(doc! {
+                "createIndexes": "stickers",
+                "indexes": [
+                    {
+                        "key": {
+                            "parent.id": 1_i32,
+                        },
+                        "name": "parent_id"
+                    }
+                ]
+            })
+            .await
+            .expect("Failed to create sticker parent index.");
+    }
+
+    if revision <= 46 {
+        info!("Running migration [revision 46 / 19-10-2026]: Add `server_templates` collection.");
+
+        db.db().create_collection("server_templates").await.ok();
+    }
+
+    if revision <= 47 {
+        info!("Running migration [revision 47 / 19-10-2026]: Add `discovery_listings` collection.");
+
+        db.db().create_collection("discovery_listings").await.ok();
+
+        db.db()
+            .run_command(doc! {
+                "createIndexes": "discovery_listings",
+                "indexes": [
+                    {
+                        "key": {
+                            "state": 1_i32,
+                            "category": 1_i32,
+                        },
+                        "name": "state_category"
+                    }
+                ]
+            })
+            .await
+            .expect("Failed to create discovery listing index.");
+    }
+
+    if revision <= 48 {
+        info!("Running migration [revision 48 / 19-10-2026]: Add `reaction_roles` collection.");
+
+        db.db().create_collection("reaction_roles").await.ok();
+    }
+
+    if revision <= 49 {
+        info!("Running migration [revision 49 / 19-10-2026]: Add `automod_configs` collection.");
+
+        db.db().create_collection("automod_configs").await.ok();
+    }
+
+    if revision <= 50 {
+        info!(
+            "Running migration [revision 50 / 19-10-2026]: Add discovery listing search indexes."
+        );
+
+        db.db()
+            .run_command(doc! {
+                "createIndexes": "discovery_listings",
+                "indexes": [
+                    {
+                        "key": {
+                            "stats.name": "text",
+                            "stats.description": "text",
+                            "tags": "text"
+                        },
+                        "name": "search"
+                    },
+                    {
+                        "key": {
+                            "state": 1_i32,
+                            "stats.member_count": -1_i32
+                        },
+                        "name": "state_members"
+                    },
+                    {
+                        "key": {
+                            "state": 1_i32,
+                            "stats.last_message_id": -1_i32
+                        },
+                        "name": "state_activity"
+                    }
+                ]
+            })
+            .await
+            .expect("Failed to create discovery listing indexes.");
+    }
+
+    if revision <= 51 {
+        info!("Running migration [revision 51 / 19-10-2026]: Add server template source index.");
+
+        db.db()
+            .run_command(doc! {
+                "createIndexes": "server_templates",
+                "indexes": [
+                    {
+                        "key": {
+                            "source_server_id": 1_i32,
+                        },
+                        "name": "source_server_id"
+                    }
+                ]
+            })
+            .await
+            .expect("Failed to create server template source index.");
+    }
+
     // Reminder to update LATEST_REVISION when adding new migrations.
     LATEST_REVISION.max(revision)
 }
@@ -0,0 +1,503 @@
+use std::{
+    num::NonZeroUsize,
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_std::sync::Mutex;
+use iso8601_timestamp::Timestamp;
+use once_cell::sync::Lazy;
+use redis_kiss::{get_connection, AsyncCommands};
+use regex::{Regex, RegexBuilder, RegexSet, RegexSetBuilder};
+use revolt_result::{create_error, ErrorType, Result};
+
+use crate::{Database, Member, PartialMember, Server, SystemMessage};
+
+/// Matches any web link
+static RE_LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\bhttps?://\S+").unwrap());
+
+/// Matches invite links to other servers
+static RE_INVITE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:rvlt\.gg|(?:app\.)?revolt\.chat/invite)/[a-z0-9]+").unwrap()
+});
+
+/// Maximum compiled size of a user-provided pattern
+const REGEX_SIZE_LIMIT: usize = 1 << 16;
+
+/// How long a cached configuration is trusted before it is fetched again
+///
+/// Changes reach other nodes through a generation counter kept in Redis, this
+/// only bounds how stale a node can be while Redis is unreachable.
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Compiled configuration along with when it was fetched and the generation it was
+/// fetched at, `None` if a server has none
+type CachedConfig = (Instant, Option<u64>, Option<Arc<CompiledAutoModConfig>>);
+
+/// Configurations of recently active servers
+static CONFIG_CACHE: Lazy<Mutex<lru::LruCache<String, CachedConfig>>> =
+    Lazy::new(|| Mutex::new(lru::LruCache::new(NonZeroUsize::new(1000).unwrap())));
+
+auto_derived!(
+    /// Auto-moderation configuration for a server
+    pub struct AutoModConfig {
+        /// Id of the server this configuration belongs to
+        #[serde(rename = "_id")]
+        pub id: String,
+        /// Rules evaluated against every message, in order
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub rules: Vec<AutoModRule>,
+        /// Id of the channel to post alerts in
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub alert_channel: Option<String>,
+        /// Roles whose members are never moderated
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub exempt_roles: Vec<String>,
+        /// Channels in which messages are never moderated
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub exempt_channels: Vec<String>,
+    }
+
+    /// Single auto-moderation rule
+    pub struct AutoModRule {
+        /// Name of the rule
+        pub name: String,
+        /// Condition that triggers this rule
+        pub trigger: AutoModTrigger,
+        /// Action taken when this rule is triggered
+        pub action: AutoModAction,
+    }
+
+    /// Condition that triggers an auto-moderation rule
+    #[serde(tag = "type")]
+    pub enum AutoModTrigger {
+        /// Message contains any of the given keywords, ignoring case
+        Keyword { keywords: Vec<String> },
+        /// Message matches any of the given regular expressions
+        Regex { patterns: Vec<String> },
+        /// Message mentions more users and roles than allowed
+        MentionSpam { limit: u32 },
+        /// Message contains a link
+        Links,
+        /// Message contains an invite to a server
+        Invites,
+        /// Message has an attachment of a blocked type
+        ///
+        /// Types may end in `/*` to match an entire family, such as `image/*`.
+        Attachments { content_types: Vec<String> },
+    }
+
+    /// Action taken when an auto-moderation rule is triggered
+    #[serde(tag = "type")]
+    pub enum AutoModAction {
+        /// Reject the message
+        Block,
+        /// Reject the message and post an alert
+        DeleteAndAlert,
+        /// Reject the message, post an alert and time out the author
+        Timeout { duration: u32 },
+    }
+);
+
+/// Auto-moderation configuration with its patterns compiled ahead of time
+pub struct CompiledAutoModConfig {
+    config: AutoModConfig,
+    /// Compiled patterns of each rule, in the same order as the rules
+    patterns: Vec<Option<RegexSet>>,
+}
+
+/// Message content being checked by auto-moderation
+pub struct AutoModContent<'a> {
+    /// Text content of the message
+    pub content: Option<&'a str>,
+    /// Content types of the message's attachments
+    pub content_types: &'a [String],
+}
+
+impl AutoModTrigger {
+    /// Compile a user-provided pattern
+    pub fn compile_pattern(pattern: &str) -> Option<Regex> {
+        RegexBuilder::new(pattern)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .build()
+            .ok()
+    }
+
+    /// Check whether the given content triggers this condition
+    pub fn matches(&self, message: &AutoModContent) -> bool {
+        let content = message.content.unwrap_or_default();
+        match self {
+            AutoModTrigger::Keyword { keywords } => {
+                let content = content.to_lowercase();
+                keywords
+                    .iter()
+                    .any(|keyword| content.contains(&keyword.to_lowercase()))
+            }
+            AutoModTrigger::Regex { patterns } => patterns
+                .iter()
+                .filter_map(|pattern| AutoModTrigger::compile_pattern(pattern))
+                .any(|regex| regex.is_match(content)),
+            AutoModTrigger::MentionSpam { limit } => {
+                let results = revolt_parser::parse_message(content);
+                let mentions = results.user_mentions.len()
+                    + results.role_mentions.len()
+                    + results.mentions_everyone as usize
+                    + results.mentions_online as usize;
+
+                mentions > *limit as usize
+            }
+            AutoModTrigger::Links => RE_LINK.is_match(content),
+            AutoModTrigger::Invites => RE_INVITE.is_match(content),
+            AutoModTrigger::Attachments { content_types } => {
+                message.content_types.iter().any(|content_type| {
+                    content_types.iter().any(|blocked| {
+                        if let Some(family) = blocked.strip_suffix("/*") {
+                            content_type
+                                .split_once('/')
+                                .is_some_and(|(prefix, _)| prefix == family)
+                        } else {
+                            content_type == blocked
+                        }
+                    })
+                })
+            }
+        }
+    }
+}
+
+impl AutoModConfig {
+    /// Fetch a server's compiled configuration, going to the database only on a cache miss
+    pub async fn fetch_compiled(
+        db: &Database,
+        server_id: &str,
+    ) -> Result<Option<Arc<CompiledAutoModConfig>>> {
+        let generation = AutoModConfig::fetch_generation(server_id).await;
+        if let Some((fetched_at, cached_generation, config)) =
+            CONFIG_CACHE.lock().await.get(server_id)
+        {
+            if is_fresh(*fetched_at, *cached_generation, generation) {
+                return Ok(config.clone());
+            }
+        }
+
+        let config = match db.fetch_automod_config(server_id).await {
+            Ok(config) => Some(Arc::new(config.compile())),
+            Err(error) if matches!(error.error_type, ErrorType::NotFound) => None,
+            Err(error) => return Err(error),
+        };
+
+        CONFIG_CACHE.lock().await.put(
+            server_id.to_string(),
+            (Instant::now(), generation, config.clone()),
+        );
+
+        Ok(config)
+    }
+
+    /// Drop a server's configuration from the cache after it changes
+    ///
+    /// Other nodes notice the change once they see the bumped generation.
+    pub async fn invalidate_cache(server_id: &str) {
+        CONFIG_CACHE.lock().await.pop(server_id);
+
+        if let Ok(mut conn) = get_connection().await {
+            let bumped: Result<u64, _> = conn.incr(generation_key(server_id), 1).await;
+            if let Err(err) = bumped {
+                log::warn!("Failed to bump auto-moderation generation of {server_id}: {err:?}");
+            }
+        }
+    }
+
+    /// Fetch the generation of a server's configuration, `None` if Redis is unreachable
+    async fn fetch_generation(server_id: &str) -> Option<u64> {
+        let mut conn = get_connection().await.ok()?;
+        let generation: Option<u64> = conn.get(generation_key(server_id)).await.ok()?;
+        Some(generation.unwrap_or_default())
+    }
+
+    /// Compile all of this configuration's patterns
+    pub fn compile(self) -> CompiledAutoModConfig {
+        let patterns = self
+            .rules
+            .iter()
+            .map(|rule| match &rule.trigger {
+                AutoModTrigger::Regex { patterns } => {
+                    // Skip any pattern that no longer compiles on its own
+                    let patterns: Vec<&String> = patterns
+                        .iter()
+                        .filter(|pattern| AutoModTrigger::compile_pattern(pattern).is_some())
+                        .collect();
+
+                    RegexSetBuilder::new(&patterns)
+                        .size_limit(REGEX_SIZE_LIMIT * patterns.len().max(1))
+                        .build()
+                        .ok()
+                }
+                _ => None,
+            })
+            .collect();
+
+        CompiledAutoModConfig {
+            config: self,
+            patterns,
+        }
+    }
+
+    /// Check whether a member or channel is exempt from moderation
+    pub fn is_exempt(&self, server: &Server, member: Option<&Member>, channel_id: &str) -> bool {
+        self.exempt_channels.iter().any(|id| id == channel_id)
+            || member.is_some_and(|member| {
+                server.owner == member.id.user
+                    || member
+                        .roles
+                        .iter()
+                        .any(|role| self.exempt_roles.contains(role))
+            })
+    }
+
+    /// Post an alert to the configured channel
+    async fn alert(&self, db: &Database, rule: &AutoModRule, author: &str, channel_id: &str) {
+        if let Some(alert_channel) = &self.alert_channel {
+            SystemMessage::Text {
+                content: format!(
+                    "Blocked a message from <@{}> in <#{}> (rule: {})",
+                    author, channel_id, rule.name
+                ),
+            }
+            .into_message(alert_channel.to_string())
+            .send_without_notifications(db, None, None, false, false, false)
+            .await
+            .ok();
+        }
+    }
+}
+
+/// Redis key holding the generation of a server's configuration
+fn generation_key(server_id: &str) -> String {
+    format!("automod_generation:{server_id}")
+}
+
+/// Check whether a cached configuration can still be used
+///
+/// Entries are dropped as soon as the generation moves on, or after `CACHE_TTL`
+/// if the generation could not be read either time.
+fn is_fresh(fetched_at: Instant, cached: Option<u64>, current: Option<u64>) -> bool {
+    fetched_at.elapsed() < CACHE_TTL
+        && match (cached, current) {
+            (Some(cached), Some(current)) => cached == current,
+            _ => true,
+        }
+}
+
+impl Deref for CompiledAutoModConfig {
+    type Target = AutoModConfig;
+
+    fn deref(&self) -> &AutoModConfig {
+        &self.config
+    }
+}
+
+impl CompiledAutoModConfig {
+    /// Find the first rule triggered by a message, ignoring exemptions
+    pub fn evaluate(&self, message: &AutoModContent) -> Option<&AutoModRule> {
+        self.rules
+            .iter()
+            .zip(&self.patterns)
+            .find(|(rule, patterns)| match patterns {
+                Some(patterns) => patterns.is_match(message.content.unwrap_or_default()),
+                None => rule.trigger.matches(message),
+            })
+            .map(|(rule, _)| rule)
+    }
+
+    /// Check a message before it is sent or edited, taking action if any rule is triggered
+    ///
+    /// Webhooks have no member, so they are never exempt by role and cannot be timed out.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn check_message(
+        &self,
+        db: &Database,
+        server: &Server,
+        author: &str,
+        member: Option<&Member>,
+        channel_id: &str,
+        content: Option<&str>,
+        attachments: &[String],
+    ) -> Result<()> {
+        if self.rules.is_empty() || self.is_exempt(server, member, channel_id) {
+            return Ok(());
+        }
+
+        // Only look up attachments if a rule cares about them
+        let mut content_types = vec![];
+        if self
+            .rules
+            .iter()
+            .any(|rule| matches!(rule.trigger, AutoModTrigger::Attachments { .. }))
+        {
+            for id in attachments {
+                if let Ok(file) = db.fetch_attachment("attachments", id).await {
+                    content_types.push(file.content_type);
+                }
+            }
+        }
+
+        let Some(rule) = self.evaluate(&AutoModContent {
+            content,
+            content_types: &content_types,
+        }) else {
+            return Ok(());
+        };
+
+        match &rule.action {
+            AutoModAction::Block => {}
+            AutoModAction::DeleteAndAlert => {
+                self.alert(db, rule, author, channel_id).await;
+            }
+            AutoModAction::Timeout { duration } => {
+                if let Some(member) = member {
+                    member
+                        .clone()
+                        .update(
+                            db,
+                            PartialMember {
+                                timeout: Some(
+                                    Timestamp::now_utc() + Duration::from_secs(*duration as u64),
+                                ),
+                                ..Default::default()
+                            },
+                            vec![],
+                        )
+                        .await?;
+                }
+
+                self.alert(db, rule, author, channel_id).await;
+            }
+        }
+
+        Err(create_error!(BlockedByAutoMod {
+            rule: rule.name.clone()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::{
+        is_fresh, AutoModAction, AutoModConfig, AutoModContent, AutoModRule, AutoModTrigger,
+        CACHE_TTL,
+    };
+
+    fn text(content: &str) -> AutoModContent<'_> {
+        AutoModContent {
+            content: Some(content),
+            content_types: &[],
+        }
+    }
+
+    #[test]
+    fn triggers() {
+        let keyword = AutoModTrigger::Keyword {
+            keywords: vec!["Spam".to_string()],
+        };
+        assert!(keyword.matches(&text("buy SPAM now")));
+        assert!(!keyword.matches(&text("hello")));
+
+        let regex = AutoModTrigger::Regex {
+            patterns: vec!["(".to_string(), r"\d{4}-\d{4}".to_string()],
+        };
+        assert!(regex.matches(&text("call 1234-5678")));
+        assert!(!regex.matches(&text("call me")));
+
+        let mentions = AutoModTrigger::MentionSpam { limit: 1 };
+        assert!(!mentions.matches(&text("<@01FD58YK5W7QRV5H3D64S0BGPS>")));
+        assert!(mentions.matches(&text(
+            "<@01FD58YK5W7QRV5H3D64S0BGPS> <@01FEEFJCKY5C4DMMJYZ20ACWWC>"
+        )));
+
+        assert!(AutoModTrigger::Links.matches(&text("see https://example.com")));
+        assert!(!AutoModTrigger::Links.matches(&text("see example")));
+
+        assert!(AutoModTrigger::Invites.matches(&text("join rvlt.gg/Testers")));
+        assert!(!AutoModTrigger::Invites.matches(&text("join https://example.com")));
+
+        let attachments = AutoModTrigger::Attachments {
+            content_types: vec!["image/*".to_string(), "application/zip".to_string()],
+        };
+        for (content_type, blocked) in [
+            ("image/png", true),
+            ("application/zip", true),
+            ("application/pdf", false),
+        ] {
+            let content_types = [content_type.to_string()];
+            assert_eq!(
+                attachments.matches(&AutoModContent {
+                    content: None,
+                    content_types: &content_types,
+                }),
+                blocked
+            );
+        }
+    }
+
+    #[test]
+    fn compiled() {
+        let rule = |name: &str, trigger| AutoModRule {
+            name: name.to_string(),
+            trigger,
+            action: AutoModAction::Block,
+        };
+
+        let config = AutoModConfig {
+            id: "server".to_string(),
+            rules: vec![
+                rule("Links", AutoModTrigger::Links),
+                rule(
+                    "Numbers",
+                    AutoModTrigger::Regex {
+                        patterns: vec!["(".to_string(), r"\d{4}-\d{4}".to_string()],
+                    },
+                ),
+                rule(
+                    "Broken",
+                    AutoModTrigger::Regex {
+                        patterns: vec!["(".to_string()],
+                    },
+                ),
+            ],
+            alert_channel: None,
+            exempt_roles: vec![],
+            exempt_channels: vec![],
+        }
+        .compile();
+
+        let name = |content| {
+            config
+                .evaluate(&text(content))
+                .map(|rule| rule.name.as_str())
+        };
+        assert_eq!(name("see https://example.com"), Some("Links"));
+        assert_eq!(name("call 1234-5678"), Some("Numbers"));
+        assert_eq!(name("("), None);
+    }
+
+    #[test]
+    fn cache_freshness() {
+        let now = Instant::now();
+        assert!(is_fresh(now, Some(1), Some(1)));
+        assert!(!is_fresh(now, Some(1), Some(2)));
+
+        // Without Redis, only the TTL bounds staleness
+        assert!(is_fresh(now, None, None));
+        assert!(is_fresh(now, Some(1), None));
+
+        let Some(expired) = now.checked_sub(CACHE_TTL) else {
+            return;
+        };
+        assert!(!is_fresh(expired, Some(1), Some(1)));
+        assert!(!is_fresh(expired, None, None));
+    }
+}
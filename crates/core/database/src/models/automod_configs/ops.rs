@@ -0,0 +1,19 @@
+use revolt_result::Result;
+
+use crate::AutoModConfig;
+
+#[cfg(feature = "mongodb")]
+mod mongodb;
+mod reference;
+
+#[async_trait]
+pub trait AbstractAutoModConfigs: Sync + Send {
+    /// Insert or replace a server's auto-moderation configuration
+    async fn upsert_automod_config(&self, config: &AutoModConfig) -> Result<()>;
+
+    /// Fetch a server's auto-moderation configuration
+    async fn fetch_automod_config(&self, server_id: &str) -> Result<AutoModConfig>;
+
+    /// Delete a server's auto-moderation configuration
+    async fn delete_automod_config(&self, server_id: &str) -> Result<()>;
+}
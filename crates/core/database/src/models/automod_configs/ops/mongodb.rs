@@ -0,0 +1,36 @@
+use revolt_result::Result;
+
+use crate::AutoModConfig;
+use crate::MongoDb;
+
+use super::AbstractAutoModConfigs;
+
+static COL: &str = "automod_configs";
+
+#[async_trait]
+impl AbstractAutoModConfigs for MongoDb {
+    /// Insert or replace a server's auto-moderation configuration
+    async fn upsert_automod_config(&self, config: &AutoModConfig) -> Result<()> {
+        self.col::<AutoModConfig>(COL)
+            .replace_one(
+                doc! {
+                    "_id": &config.id
+                },
+                config,
+            )
+            .upsert(true)
+            .await
+            .map(|_| ())
+            .map_err(|_| create_database_error!("replace_one", COL))
+    }
+
+    /// Fetch a server's auto-moderation configuration
+    async fn fetch_automod_config(&self, server_id: &str) -> Result<AutoModConfig> {
+        query!(self, find_one_by_id, COL, server_id)?.ok_or_else(|| create_error!(NotFound))
+    }
+
+    /// Delete a server's auto-moderation configuration
+    async fn delete_automod_config(&self, server_id: &str) -> Result<()> {
+        query!(self, delete_one_by_id, COL, server_id).map(|_| ())
+    }
+}
@@ -0,0 +1,35 @@
+use revolt_result::Result;
+
+use crate::AutoModConfig;
+use crate::ReferenceDb;
+
+use super::AbstractAutoModConfigs;
+
+#[async_trait]
+impl AbstractAutoModConfigs for ReferenceDb {
+    /// Insert or replace a server's auto-moderation configuration
+    async fn upsert_automod_config(&self, config: &AutoModConfig) -> Result<()> {
+        let mut automod_configs = self.automod_configs.lock().await;
+        automod_configs.insert(config.id.to_string(), config.clone());
+        Ok(())
+    }
+
+    /// Fetch a server's auto-moderation configuration
+    async fn fetch_automod_config(&self, server_id: &str) -> Result<AutoModConfig> {
+        let automod_configs = self.automod_configs.lock().await;
+        automod_configs
+            .get(server_id)
+            .cloned()
+            .ok_or_else(|| create_error!(NotFound))
+    }
+
+    /// Delete a server's auto-moderation configuration
+    async fn delete_automod_config(&self, server_id: &str) -> Result<()> {
+        let mut automod_configs = self.automod_configs.lock().await;
+        if automod_configs.remove(server_id).is_some() {
+            Ok(())
+        } else {
+            Err(create_error!(NotFound))
+        }
+    }
+}
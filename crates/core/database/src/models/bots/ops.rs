@@ -20,6 +20,16 @@ pub trait AbstractBots: Sync + Send {
     /// Fetch bots owned by a user
     async fn fetch_bots_by_user(&self, user_id: &str) -> Result<Vec<Bot>>;
 
+    /// Fetch a page of bots which are public and discoverable, sorted by username
+    ///
+    /// If given, only bots whose username or profile contains the query are included.
+    async fn fetch_discoverable_bots(
+        &self,
+        query: Option<&str>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Bot>>;
+
     /// Get the number of bots owned by a user
     async fn get_number_of_bots_by_user(&self, user_id: &str) -> Result<usize>;
 
@@ -1,3 +1,6 @@
+use ::mongodb::options::{Collation, CollationStrength};
+use bson::{from_document, Document};
+use futures::StreamExt;
 use revolt_result::Result;
 
 use crate::{Bot, FieldsBot, PartialBot};
@@ -44,6 +47,80 @@ impl AbstractBots for MongoDb {
         )
     }
 
+    /// Fetch a page of bots which are public and discoverable, sorted by username
+    async fn fetch_discoverable_bots(
+        &self,
+        query: Option<&str>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Bot>> {
+        let mut pipeline = vec![
+            doc! {
+                "$match": {
+                    "public": true,
+                    "discoverable": true
+                }
+            },
+            doc! {
+                "$lookup": {
+                    "from": "users",
+                    "localField": "_id",
+                    "foreignField": "_id",
+                    "as": "user"
+                }
+            },
+            doc! {
+                "$unwind": "$user"
+            },
+        ];
+
+        if let Some(query) = query {
+            let pattern = regex::escape(query);
+            pipeline.push(doc! {
+                "$match": {
+                    "$or": [
+                        { "user.username": { "$regex": &pattern, "$options": "i" } },
+                        { "user.profile.content": { "$regex": &pattern, "$options": "i" } }
+                    ]
+                }
+            });
+        }
+
+        pipeline.extend([
+            doc! {
+                "$sort": {
+                    "user.username": 1_i32,
+                    "_id": 1_i32
+                }
+            },
+            doc! {
+                "$skip": offset as i64
+            },
+            doc! {
+                "$limit": limit as i64
+            },
+            doc! {
+                "$unset": "user"
+            },
+        ]);
+
+        Ok(self
+            .col::<Document>(COL)
+            .aggregate(pipeline)
+            .collation(
+                Collation::builder()
+                    .locale("en")
+                    .strength(CollationStrength::Secondary)
+                    .build(),
+            )
+            .await
+            .map_err(|_| create_database_error!("aggregate", COL))?
+            .filter_map(|s| async { s.ok() })
+            .filter_map(|doc| async move { from_document(doc).ok() })
+            .collect()
+            .await)
+    }
+
     /// Get the number of bots owned by a user
     async fn get_number_of_bots_by_user(&self, user_id: &str) -> Result<usize> {
         query!(
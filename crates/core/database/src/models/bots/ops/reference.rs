@@ -43,6 +43,44 @@ impl AbstractBots for ReferenceDb {
             .collect())
     }
 
+    /// Fetch a page of bots which are public and discoverable, sorted by username
+    async fn fetch_discoverable_bots(
+        &self,
+        query: Option<&str>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Bot>> {
+        let bots = self.bots.lock().await;
+        let users = self.users.lock().await;
+        let query = query.map(|query| query.to_lowercase());
+
+        let mut results = bots
+            .values()
+            .filter(|bot| bot.public && bot.discoverable)
+            .filter_map(|bot| users.get(&bot.id).map(|user| (user, bot)))
+            .filter(|(user, _)| {
+                query.as_ref().is_none_or(|query| {
+                    user.username.to_lowercase().contains(query)
+                        || user
+                            .profile
+                            .as_ref()
+                            .and_then(|profile| profile.content.as_ref())
+                            .is_some_and(|content| content.to_lowercase().contains(query))
+                })
+            })
+            .map(|(user, bot)| (user.username.to_lowercase(), bot.clone()))
+            .collect::<Vec<(String, Bot)>>();
+
+        results.sort_by(|(a, a_bot), (b, b_bot)| a.cmp(b).then_with(|| a_bot.id.cmp(&b_bot.id)));
+
+        Ok(results
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(_, bot)| bot)
+            .collect())
+    }
+
     /// Get the number of bots owned by a user
     async fn get_number_of_bots_by_user(&self, user_id: &str) -> Result<usize> {
         let bots = self.bots.lock().await;
@@ -1,14 +1,15 @@
 use std::collections::HashMap;
 
+use redis_kiss::{get_connection, redis, AsyncCommands};
 use revolt_config::config;
 use revolt_models::v0::{self, MessageAuthor};
-use revolt_permissions::OverrideField;
+use revolt_permissions::{ChannelPermission, OverrideField, PermissionValue};
 use revolt_result::Result;
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
 use crate::{
-    events::client::EventV1, Database, File, PartialServer,
+    events::client::EventV1, Category, Database, File, PartialServer,
     Server, SystemMessage, User, AMQP,
 };
 
@@ -102,6 +103,18 @@ auto_derived!(
                 skip_serializing_if = "HashMap::<String, OverrideField>::is_empty"
             )]
             role_permissions: HashMap<String, OverrideField>,
+            /// Permissions assigned to individual members in this channel
+            #[serde(
+                default = "HashMap::<String, OverrideField>::new",
+                skip_serializing_if = "HashMap::<String, OverrideField>::is_empty"
+            )]
+            member_permissions: HashMap<String, OverrideField>,
+            /// Whether this channel's permissions are kept in sync with its category
+            #[serde(skip_serializing_if = "crate::if_false", default)]
+            permissions_synced: bool,
+            /// Minimum number of seconds members must wait between messages
+            #[serde(skip_serializing_if = "revolt_models::if_zero_u32", default)]
+            slowmode: u32,
 
             /// Whether this channel is marked as not safe for work
             #[serde(skip_serializing_if = "crate::if_false", default)]
@@ -133,6 +146,15 @@ auto_derived!(
                 skip_serializing_if = "HashMap::<String, OverrideField>::is_empty"
             )]
             role_permissions: HashMap<String, OverrideField>,
+            /// Permissions assigned to individual members in this channel
+            #[serde(
+                default = "HashMap::<String, OverrideField>::new",
+                skip_serializing_if = "HashMap::<String, OverrideField>::is_empty"
+            )]
+            member_permissions: HashMap<String, OverrideField>,
+            /// Whether this channel's permissions are kept in sync with its category
+            #[serde(skip_serializing_if = "crate::if_false", default)]
+            permissions_synced: bool,
 
             /// Whether this channel is marked as not safe for work
             #[serde(skip_serializing_if = "crate::if_false", default)]
@@ -161,8 +183,14 @@ auto_derived!(
         #[serde(skip_serializing_if = "Option::is_none")]
         pub role_permissions: Option<HashMap<String, OverrideField>>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        pub member_permissions: Option<HashMap<String, OverrideField>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub default_permissions: Option<OverrideField>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        pub permissions_synced: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub slowmode: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub last_message_id: Option<String>,
     }
 
@@ -221,6 +249,9 @@ impl Channel {
                 last_message_id: None,
                 default_permissions: None,
                 role_permissions: HashMap::new(),
+                member_permissions: HashMap::new(),
+                permissions_synced: false,
+                slowmode: 0,
                 nsfw: data.nsfw.unwrap_or(false),
             },
             v0::LegacyServerChannelType::Voice => Channel::VoiceChannel {
@@ -231,6 +262,8 @@ impl Channel {
                 icon: None,
                 default_permissions: None,
                 role_permissions: HashMap::new(),
+                member_permissions: HashMap::new(),
+                permissions_synced: false,
                 nsfw: data.nsfw.unwrap_or(false),
             },
         };
@@ -410,6 +443,52 @@ impl Channel {
         matches!(self, Channel::DirectMessage { .. })
     }
 
+    /// Check whether any member of the server can view this channel by default
+    ///
+    /// Direct messages, groups and saved messages are never publicly viewable
+    pub fn is_publicly_viewable(&self, server: Option<&Server>) -> bool {
+        match (self, server) {
+            (
+                Channel::TextChannel {
+                    id,
+                    default_permissions,
+                    ..
+                }
+                | Channel::VoiceChannel {
+                    id,
+                    default_permissions,
+                    ..
+                },
+                Some(server),
+            ) => {
+                let mut permissions = PermissionValue::from(server.default_permissions);
+                if let Some(default_permissions) = server
+                    .category_of(id)
+                    .and_then(|category| category.default_permissions)
+                {
+                    permissions.apply(default_permissions.into());
+                }
+
+                if let Some(default_permissions) = default_permissions {
+                    permissions.apply((*default_permissions).into());
+                }
+
+                permissions.has_channel_permission(ChannelPermission::ViewChannel)
+            }
+            _ => false,
+        }
+    }
+
+    /// Check whether this channel is marked as NSFW
+    pub fn is_nsfw(&self) -> bool {
+        match self {
+            Channel::Group { nsfw, .. }
+            | Channel::TextChannel { nsfw, .. }
+            | Channel::VoiceChannel { nsfw, .. } => *nsfw,
+            _ => false,
+        }
+    }
+
     /// Check whether has a user as a recipient
     pub fn contains_user(&self, user_id: &str) -> bool {
         match self {
@@ -437,6 +516,110 @@ impl Channel {
         }
     }
 
+    /// Copy a category's permission overrides onto this channel and keep it in sync
+    pub async fn sync_permissions(&mut self, db: &Database, category: &Category) -> Result<()> {
+        match self {
+            Channel::TextChannel { .. } | Channel::VoiceChannel { .. } => {
+                self.update(
+                    db,
+                    PartialChannel {
+                        default_permissions: category.default_permissions,
+                        role_permissions: Some(category.role_permissions.clone()),
+                        permissions_synced: Some(true),
+                        ..Default::default()
+                    },
+                    if category.default_permissions.is_none() {
+                        vec![FieldsChannel::DefaultPermissions]
+                    } else {
+                        vec![]
+                    },
+                )
+                .await
+            }
+            _ => Err(create_error!(InvalidOperation)),
+        }
+    }
+
+    /// Set member permission on a channel
+    pub async fn set_member_permission(
+        &mut self,
+        db: &Database,
+        user_id: &str,
+        permissions: OverrideField,
+    ) -> Result<()> {
+        match self {
+            Channel::TextChannel {
+                id,
+                server,
+                member_permissions,
+                ..
+            }
+            | Channel::VoiceChannel {
+                id,
+                server,
+                member_permissions,
+                ..
+            } => {
+                db.set_channel_member_permission(id, user_id, permissions)
+                    .await?;
+
+                member_permissions.insert(user_id.to_string(), permissions);
+
+                EventV1::ChannelUpdate {
+                    id: id.clone(),
+                    data: PartialChannel {
+                        member_permissions: Some(member_permissions.clone()),
+                        ..Default::default()
+                    }
+                    .into(),
+                    clear: vec![],
+                }
+                .p(server.clone())
+                .await;
+
+                Ok(())
+            }
+            _ => Err(create_error!(InvalidOperation)),
+        }
+    }
+
+    /// Remove member permission from a channel
+    pub async fn remove_member_permission(&mut self, db: &Database, user_id: &str) -> Result<()> {
+        match self {
+            Channel::TextChannel {
+                id,
+                server,
+                member_permissions,
+                ..
+            }
+            | Channel::VoiceChannel {
+                id,
+                server,
+                member_permissions,
+                ..
+            } => {
+                db.remove_channel_member_permission(id, user_id).await?;
+
+                member_permissions.remove(user_id);
+
+                EventV1::ChannelUpdate {
+                    id: id.clone(),
+                    data: PartialChannel {
+                        member_permissions: Some(member_permissions.clone()),
+                        ..Default::default()
+                    }
+                    .into(),
+                    clear: vec![],
+                }
+                .p(server.clone())
+                .await;
+
+                Ok(())
+            }
+            _ => Err(create_error!(InvalidOperation)),
+        }
+    }
+
     /// Set role permission on a channel
     pub async fn set_role_permission(
         &mut self,
@@ -449,12 +632,14 @@ impl Channel {
                 id,
                 server,
                 role_permissions,
+                permissions_synced,
                 ..
             }
             | Channel::VoiceChannel {
                 id,
                 server,
                 role_permissions,
+                permissions_synced,
                 ..
             } => {
                 db.set_channel_role_permission(id, role_id, permissions)
@@ -462,10 +647,26 @@ impl Channel {
 
                 role_permissions.insert(role_id.to_string(), permissions);
 
+                // Channel no longer matches its category
+                let unsync = if *permissions_synced {
+                    *permissions_synced = false;
+
+                    let partial = PartialChannel {
+                        permissions_synced: Some(false),
+                        ..Default::default()
+                    };
+
+                    db.update_channel(id, &partial, vec![]).await?;
+                    Some(false)
+                } else {
+                    None
+                };
+
                 EventV1::ChannelUpdate {
                     id: id.clone(),
                     data: PartialChannel {
                         role_permissions: Some(role_permissions.clone()),
+                        permissions_synced: unsync,
                         ..Default::default()
                     }
                     .into(),
@@ -510,6 +711,69 @@ impl Channel {
         Ok(())
     }
 
+    /// Start a user's slowmode cooldown in this channel
+    ///
+    /// Cooldowns are kept in Redis so they hold across every API node, if Redis
+    /// is unreachable the message is let through.
+    pub async fn consume_slowmode(&self, user_id: &str) -> Result<()> {
+        let Channel::TextChannel { id, slowmode, .. } = self else {
+            return Ok(());
+        };
+
+        if *slowmode == 0 {
+            return Ok(());
+        }
+
+        let mut conn = match get_connection().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                log::warn!("Letting message through slowmode, Redis is unreachable: {err:?}");
+                return Ok(());
+            }
+        };
+
+        let key = format!("slowmode:{id}:{user_id}");
+        let started: Option<String> = match redis::cmd("SET")
+            .arg(&key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(*slowmode)
+            .query_async(&mut *conn)
+            .await
+        {
+            Ok(value) => value,
+            Err(err) => {
+                log::warn!("Letting message through slowmode, failed to start cooldown: {err:?}");
+                return Ok(());
+            }
+        };
+
+        if started.is_some() {
+            return Ok(());
+        }
+
+        let remaining: i64 = conn.ttl(&key).await.unwrap_or(*slowmode as i64);
+        Err(create_error!(InSlowmode {
+            retry_after: remaining.max(1) as u64
+        }))
+    }
+
+    /// Give back a user's slowmode cooldown if their message was rejected
+    pub async fn release_slowmode(&self, user_id: &str) {
+        let Channel::TextChannel { id, slowmode, .. } = self else {
+            return;
+        };
+
+        if *slowmode == 0 {
+            return;
+        }
+
+        if let Ok(mut conn) = get_connection().await {
+            let _: Result<(), _> = conn.del(format!("slowmode:{id}:{user_id}")).await;
+        }
+    }
+
     /// Remove a field from Channel object
     pub fn remove_field(&mut self, field: &FieldsChannel) {
         match field {
@@ -554,6 +818,10 @@ impl Channel {
 
     /// Apply partial channel to channel
     pub fn apply_options(&mut self, partial: PartialChannel) {
+        if let (Self::TextChannel { slowmode, .. }, Some(v)) = (&mut *self, partial.slowmode) {
+            *slowmode = v;
+        }
+
         match self {
             Self::SavedMessages { .. } => {}
             Self::DirectMessage { active, .. } => {
@@ -601,6 +869,8 @@ impl Channel {
                 nsfw,
                 default_permissions,
                 role_permissions,
+                member_permissions,
+                permissions_synced,
                 ..
             }
             | Self::VoiceChannel {
@@ -610,6 +880,8 @@ impl Channel {
                 nsfw,
                 default_permissions,
                 role_permissions,
+                member_permissions,
+                permissions_synced,
                 ..
             } => {
                 if let Some(v) = partial.name {
@@ -632,9 +904,17 @@ impl Channel {
                     *role_permissions = v;
                 }
 
+                if let Some(v) = partial.member_permissions {
+                    *member_permissions = v;
+                }
+
                 if let Some(v) = partial.default_permissions {
                     default_permissions.replace(v);
                 }
+
+                if let Some(v) = partial.permissions_synced {
+                    *permissions_synced = v;
+                }
             }
         }
     }
@@ -36,6 +36,18 @@ pub trait AbstractChannels: Sync + Send {
         permissions: OverrideField,
     ) -> Result<()>;
 
+    /// Insert channel member permissions
+    async fn set_channel_member_permission(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+        permissions: OverrideField,
+    ) -> Result<()>;
+
+    /// Remove channel member permissions
+    async fn remove_channel_member_permission(&self, channel_id: &str, user_id: &str)
+        -> Result<()>;
+
     // Update channel
     async fn update_channel(
         &self,
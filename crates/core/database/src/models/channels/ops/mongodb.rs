@@ -143,6 +143,41 @@ impl AbstractChannels for MongoDb {
             .map_err(|_| create_database_error!("update_one", "channel"))
     }
 
+    async fn set_channel_member_permission(
+        &self,
+        channel: &str,
+        user: &str,
+        permissions: OverrideField,
+    ) -> Result<()> {
+        self.col::<Document>(COL)
+            .update_one(
+                doc! { "_id": channel },
+                doc! {
+                "$set": {
+                    "member_permissions.".to_owned() + user: permissions
+                }
+                },
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| create_database_error!("update_one", "channel"))
+    }
+
+    async fn remove_channel_member_permission(&self, channel: &str, user: &str) -> Result<()> {
+        self.col::<Document>(COL)
+            .update_one(
+                doc! { "_id": channel },
+                doc! {
+                "$unset": {
+                    "member_permissions.".to_owned() + user: 1_i32
+                }
+                },
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| create_database_error!("update_one", "channel"))
+    }
+
     // Update channel
     async fn update_channel(
         &self,
@@ -284,6 +319,14 @@ impl MongoDb {
 
         // update many attachments with parent id
 
+        // Delete all reaction roles on messages in these channels.
+        self.col::<Document>("reaction_roles")
+            .delete_many(doc! {
+                "channel_id": &id
+            })
+            .await
+            .map_err(|_| create_database_error!("delete_many", "reaction_roles"))?;
+
         // Delete all webhooks on this channel.
         self.col::<Document>("webhooks")
             .delete_many(doc! {
@@ -114,6 +114,57 @@ impl AbstractChannels for ReferenceDb {
         }
     }
 
+    async fn set_channel_member_permission(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+        permissions: OverrideField,
+    ) -> Result<()> {
+        let mut channels = self.channels.lock().await;
+
+        if let Some(mut channel) = channels.get_mut(channel_id) {
+            match &mut channel {
+                Channel::TextChannel {
+                    member_permissions, ..
+                }
+                | Channel::VoiceChannel {
+                    member_permissions, ..
+                } => {
+                    member_permissions.insert(String::from(user_id), permissions);
+                    Ok(())
+                }
+                _ => Err(create_error!(NotFound)),
+            }
+        } else {
+            Err(create_error!(NotFound))
+        }
+    }
+
+    async fn remove_channel_member_permission(
+        &self,
+        channel_id: &str,
+        user_id: &str,
+    ) -> Result<()> {
+        let mut channels = self.channels.lock().await;
+
+        if let Some(mut channel) = channels.get_mut(channel_id) {
+            match &mut channel {
+                Channel::TextChannel {
+                    member_permissions, ..
+                }
+                | Channel::VoiceChannel {
+                    member_permissions, ..
+                } => {
+                    member_permissions.remove(user_id);
+                    Ok(())
+                }
+                _ => Err(create_error!(NotFound)),
+            }
+        } else {
+            Err(create_error!(NotFound))
+        }
+    }
+
     // Update channel
     async fn update_channel(
         &self,
@@ -0,0 +1,195 @@
+use revolt_result::Result;
+
+use crate::{Channel, Database, Server};
+
+auto_derived!(
+    /// Discovery listing for a server
+    pub struct DiscoveryListing {
+        /// Id of the server being listed
+        #[serde(rename = "_id")]
+        pub id: String,
+        /// Category this server is listed under
+        pub category: DiscoveryCategory,
+        /// Tags describing this server
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub tags: Vec<String>,
+        /// Primary language of this server
+        pub language: String,
+        /// Review state of this listing
+        pub state: DiscoveryListingState,
+        /// Id of the user who applied for this listing
+        pub applicant_id: String,
+        /// Id of the user who last reviewed this listing
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub reviewer_id: Option<String>,
+        /// Reason given when this listing was rejected
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub rejection_reason: Option<String>,
+        /// Details of the server kept for searching and sorting
+        #[serde(default)]
+        pub stats: DiscoveryListingStats,
+    }
+);
+
+auto_derived!(
+    /// Category of a discovery listing
+    pub enum DiscoveryCategory {
+        Gaming,
+        Music,
+        Art,
+        Technology,
+        Education,
+        Science,
+        Entertainment,
+        Community,
+        Other,
+    }
+
+    /// Review state of a discovery listing
+    pub enum DiscoveryListingState {
+        /// Waiting to be reviewed
+        Pending,
+        /// Visible in the directory
+        Approved,
+        /// Refused by a reviewer
+        Rejected,
+    }
+
+    /// Details of a listed server, refreshed periodically
+    #[derive(Default)]
+    pub struct DiscoveryListingStats {
+        /// Name of the server
+        pub name: String,
+        /// Description of the server
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub description: Option<String>,
+        /// Number of members in the server
+        pub member_count: i64,
+        /// Id of the most recent message sent in the server
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub last_message_id: Option<String>,
+    }
+
+    /// Sort order of discovery listings
+    #[derive(Default)]
+    pub enum DiscoveryListingSort {
+        /// Largest servers first
+        #[default]
+        Members,
+        /// Most recently active servers first
+        Activity,
+    }
+
+    /// Query for approved discovery listings
+    #[derive(Default)]
+    pub struct DiscoveryListingQuery {
+        /// Text to search for in server names, descriptions and tags
+        pub text: Option<String>,
+        /// Only include listings in this category
+        pub category: Option<DiscoveryCategory>,
+        /// Only include listings in this language
+        pub language: Option<String>,
+        /// Only include listings with this tag
+        pub tag: Option<String>,
+        /// Sort order of the results
+        pub sort: DiscoveryListingSort,
+        /// Number of listings to skip
+        pub offset: usize,
+        /// Maximum number of listings to return, or all if zero
+        pub limit: usize,
+    }
+);
+
+impl DiscoveryListingStats {
+    /// Collect the current details of a server
+    pub async fn collect(db: &Database, server: &Server) -> Result<DiscoveryListingStats> {
+        // Message ids are ULIDs so the greatest one is also the most recent
+        let last_message_id = db
+            .fetch_channels(&server.channels)
+            .await?
+            .into_iter()
+            .filter_map(|channel| match channel {
+                Channel::TextChannel {
+                    last_message_id, ..
+                } => last_message_id,
+                _ => None,
+            })
+            .max();
+
+        Ok(DiscoveryListingStats {
+            name: server.name.clone(),
+            description: server.description.clone(),
+            member_count: db.fetch_member_count(&server.id).await? as i64,
+            last_message_id,
+        })
+    }
+}
+
+impl DiscoveryListing {
+    /// Apply for a server to be listed, replacing any existing listing
+    ///
+    /// The server is hidden from discovery until the listing is reviewed.
+    pub async fn apply(
+        db: &Database,
+        server: &mut Server,
+        applicant_id: &str,
+        category: DiscoveryCategory,
+        tags: Vec<String>,
+        language: String,
+    ) -> Result<DiscoveryListing> {
+        let listing = DiscoveryListing {
+            id: server.id.clone(),
+            category,
+            tags,
+            language,
+            state: DiscoveryListingState::Pending,
+            applicant_id: applicant_id.to_string(),
+            reviewer_id: None,
+            rejection_reason: None,
+            stats: DiscoveryListingStats::collect(db, server).await?,
+        };
+
+        db.upsert_discovery_listing(&listing).await?;
+        server.set_discoverable(db, false).await?;
+        Ok(listing)
+    }
+
+    /// Review this listing, updating whether the server is discoverable
+    pub async fn review(
+        &mut self,
+        db: &Database,
+        server: &mut Server,
+        reviewer_id: &str,
+        approve: bool,
+        rejection_reason: Option<String>,
+    ) -> Result<()> {
+        self.state = if approve {
+            DiscoveryListingState::Approved
+        } else {
+            DiscoveryListingState::Rejected
+        };
+        self.reviewer_id = Some(reviewer_id.to_string());
+        self.rejection_reason = if approve { None } else { rejection_reason };
+        self.stats = DiscoveryListingStats::collect(db, server).await?;
+        db.upsert_discovery_listing(self).await?;
+
+        server.set_discoverable(db, approve).await?;
+
+        Ok(())
+    }
+
+    /// Refresh the details kept for searching and sorting a server's listing
+    pub async fn refresh_stats(db: &Database, server: &Server) -> Result<()> {
+        let stats = DiscoveryListingStats::collect(db, server).await?;
+        db.update_discovery_listing_stats(&server.id, &stats).await
+    }
+
+    /// Remove this listing and hide the server from discovery
+    pub async fn delete(self, db: &Database, server: &mut Server) -> Result<()> {
+        db.delete_discovery_listing(&self.id).await?;
+
+        server.set_discoverable(db, false).await?;
+
+        Ok(())
+    }
+}
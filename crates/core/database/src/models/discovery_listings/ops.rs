@@ -0,0 +1,40 @@
+use revolt_result::Result;
+
+use crate::{
+    DiscoveryListing, DiscoveryListingQuery, DiscoveryListingState, DiscoveryListingStats,
+};
+
+#[cfg(feature = "mongodb")]
+mod mongodb;
+mod reference;
+
+#[async_trait]
+pub trait AbstractDiscoveryListings: Sync + Send {
+    /// Insert or replace a discovery listing
+    async fn upsert_discovery_listing(&self, listing: &DiscoveryListing) -> Result<()>;
+
+    /// Fetch a discovery listing by its server id
+    async fn fetch_discovery_listing(&self, id: &str) -> Result<DiscoveryListing>;
+
+    /// Fetch a page of approved discovery listings matching the given query
+    async fn fetch_approved_discovery_listings(
+        &self,
+        query: &DiscoveryListingQuery,
+    ) -> Result<Vec<DiscoveryListing>>;
+
+    /// Fetch discovery listings in a given review state
+    async fn fetch_discovery_listings_by_state(
+        &self,
+        state: &DiscoveryListingState,
+    ) -> Result<Vec<DiscoveryListing>>;
+
+    /// Update the details kept for searching and sorting a discovery listing
+    async fn update_discovery_listing_stats(
+        &self,
+        id: &str,
+        stats: &DiscoveryListingStats,
+    ) -> Result<()>;
+
+    /// Delete a discovery listing by its server id
+    async fn delete_discovery_listing(&self, id: &str) -> Result<()>;
+}
@@ -0,0 +1,134 @@
+use bson::{to_bson, to_document, Document};
+use mongodb::options::FindOptions;
+use revolt_config::report_internal_error;
+use revolt_result::Result;
+
+use crate::MongoDb;
+use crate::{
+    DiscoveryListing, DiscoveryListingQuery, DiscoveryListingSort, DiscoveryListingState,
+    DiscoveryListingStats,
+};
+
+use super::AbstractDiscoveryListings;
+
+static COL: &str = "discovery_listings";
+
+#[async_trait]
+impl AbstractDiscoveryListings for MongoDb {
+    /// Insert or replace a discovery listing
+    async fn upsert_discovery_listing(&self, listing: &DiscoveryListing) -> Result<()> {
+        self.col::<DiscoveryListing>(COL)
+            .replace_one(
+                doc! {
+                    "_id": &listing.id
+                },
+                listing,
+            )
+            .upsert(true)
+            .await
+            .map(|_| ())
+            .map_err(|_| create_database_error!("replace_one", COL))
+    }
+
+    /// Fetch a discovery listing by its server id
+    async fn fetch_discovery_listing(&self, id: &str) -> Result<DiscoveryListing> {
+        query!(self, find_one_by_id, COL, id)?.ok_or_else(|| create_error!(NotFound))
+    }
+
+    /// Fetch a page of approved discovery listings matching the given query
+    async fn fetch_approved_discovery_listings(
+        &self,
+        query: &DiscoveryListingQuery,
+    ) -> Result<Vec<DiscoveryListing>> {
+        let mut filter = doc! {
+            "state": "Approved"
+        };
+
+        if let Some(text) = &query.text {
+            filter.insert(
+                "$text",
+                doc! {
+                    "$search": text
+                },
+            );
+        }
+
+        if let Some(category) = &query.category {
+            filter.insert(
+                "category",
+                to_bson(category).map_err(|_| create_database_error!("to_bson", "category"))?,
+            );
+        }
+
+        if let Some(language) = &query.language {
+            filter.insert("language", language);
+        }
+
+        if let Some(tag) = &query.tag {
+            filter.insert("tags", tag);
+        }
+
+        query!(
+            self,
+            find_with_options,
+            COL,
+            filter,
+            FindOptions::builder()
+                .sort(match query.sort {
+                    DiscoveryListingSort::Members => doc! {
+                        "stats.member_count": -1_i32,
+                        "_id": 1_i32
+                    },
+                    DiscoveryListingSort::Activity => doc! {
+                        "stats.last_message_id": -1_i32,
+                        "_id": 1_i32
+                    },
+                })
+                .skip(query.offset as u64)
+                .limit(query.limit as i64)
+                .build()
+        )
+    }
+
+    /// Fetch discovery listings in a given review state
+    async fn fetch_discovery_listings_by_state(
+        &self,
+        state: &DiscoveryListingState,
+    ) -> Result<Vec<DiscoveryListing>> {
+        query!(
+            self,
+            find,
+            COL,
+            doc! {
+                "state": to_bson(state).map_err(|_| create_database_error!("to_bson", "state"))?
+            }
+        )
+    }
+
+    /// Update the details kept for searching and sorting a discovery listing
+    async fn update_discovery_listing_stats(
+        &self,
+        id: &str,
+        stats: &DiscoveryListingStats,
+    ) -> Result<()> {
+        self.col::<Document>(COL)
+            .update_one(
+                doc! {
+                    "_id": id
+                },
+                doc! {
+                    "$set": {
+                        "stats": report_internal_error!(to_document(stats))?
+                    }
+                },
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| create_database_error!("update_one", COL))
+    }
+
+    /// Delete a discovery listing by its server id
+    async fn delete_discovery_listing(&self, id: &str) -> Result<()> {
+        query!(self, delete_one_by_id, COL, id).map(|_| ())
+    }
+}
@@ -0,0 +1,138 @@
+use revolt_result::Result;
+
+use std::cmp::Reverse;
+
+use crate::ReferenceDb;
+use crate::{
+    DiscoveryListing, DiscoveryListingQuery, DiscoveryListingSort, DiscoveryListingState,
+    DiscoveryListingStats,
+};
+
+use super::AbstractDiscoveryListings;
+
+#[async_trait]
+impl AbstractDiscoveryListings for ReferenceDb {
+    /// Insert or replace a discovery listing
+    async fn upsert_discovery_listing(&self, listing: &DiscoveryListing) -> Result<()> {
+        let mut discovery_listings = self.discovery_listings.lock().await;
+        discovery_listings.insert(listing.id.to_string(), listing.clone());
+        Ok(())
+    }
+
+    /// Fetch a discovery listing by its server id
+    async fn fetch_discovery_listing(&self, id: &str) -> Result<DiscoveryListing> {
+        let discovery_listings = self.discovery_listings.lock().await;
+        discovery_listings
+            .get(id)
+            .cloned()
+            .ok_or_else(|| create_error!(NotFound))
+    }
+
+    /// Fetch a page of approved discovery listings matching the given query
+    async fn fetch_approved_discovery_listings(
+        &self,
+        query: &DiscoveryListingQuery,
+    ) -> Result<Vec<DiscoveryListing>> {
+        let discovery_listings = self.discovery_listings.lock().await;
+        let text = query.text.as_ref().map(|text| text.to_lowercase());
+        let mut listings = discovery_listings
+            .values()
+            .filter(|listing| listing.state == DiscoveryListingState::Approved)
+            .filter(|listing| {
+                text.as_ref()
+                    .map(|text| {
+                        listing.stats.name.to_lowercase().contains(text)
+                            || listing
+                                .stats
+                                .description
+                                .as_ref()
+                                .is_some_and(|description| {
+                                    description.to_lowercase().contains(text)
+                                })
+                            || listing.tags.iter().any(|tag| tag.contains(text))
+                    })
+                    .unwrap_or(true)
+            })
+            .filter(|listing| {
+                query
+                    .category
+                    .as_ref()
+                    .map(|category| &listing.category == category)
+                    .unwrap_or(true)
+            })
+            .filter(|listing| {
+                query
+                    .language
+                    .as_ref()
+                    .map(|language| &listing.language == language)
+                    .unwrap_or(true)
+            })
+            .filter(|listing| {
+                query
+                    .tag
+                    .as_ref()
+                    .map(|tag| listing.tags.contains(tag))
+                    .unwrap_or(true)
+            })
+            .cloned()
+            .collect::<Vec<DiscoveryListing>>();
+
+        listings.sort_by(|a, b| a.id.cmp(&b.id));
+        match query.sort {
+            DiscoveryListingSort::Members => {
+                listings.sort_by_key(|listing| Reverse(listing.stats.member_count))
+            }
+            DiscoveryListingSort::Activity => {
+                listings.sort_by_key(|listing| Reverse(listing.stats.last_message_id.clone()))
+            }
+        }
+
+        Ok(listings
+            .into_iter()
+            .skip(query.offset)
+            .take(if query.limit > 0 {
+                query.limit
+            } else {
+                usize::MAX
+            })
+            .collect())
+    }
+
+    /// Fetch discovery listings in a given review state
+    async fn fetch_discovery_listings_by_state(
+        &self,
+        state: &DiscoveryListingState,
+    ) -> Result<Vec<DiscoveryListing>> {
+        let discovery_listings = self.discovery_listings.lock().await;
+        Ok(discovery_listings
+            .values()
+            .filter(|listing| &listing.state == state)
+            .cloned()
+            .collect())
+    }
+
+    /// Update the details kept for searching and sorting a discovery listing
+    async fn update_discovery_listing_stats(
+        &self,
+        id: &str,
+        stats: &DiscoveryListingStats,
+    ) -> Result<()> {
+        let mut discovery_listings = self.discovery_listings.lock().await;
+        if let Some(listing) = discovery_listings.get_mut(id) {
+            listing.stats = stats.clone();
+            Ok(())
+        } else {
+            Err(create_error!(NotFound))
+        }
+    }
+
+    /// Delete a discovery listing by its server id
+    async fn delete_discovery_listing(&self, id: &str) -> Result<()> {
+        let mut discovery_listings = self.discovery_listings.lock().await;
+        if discovery_listings.remove(id).is_some() {
+            Ok(())
+        } else {
+            Err(create_error!(NotFound))
+        }
+    }
+}
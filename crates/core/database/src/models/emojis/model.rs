@@ -6,7 +6,7 @@ use revolt_result::Result;
 use ulid::Ulid;
 
 use crate::events::client::EventV1;
-use crate::Database;
+use crate::{Database, StorageUsage};
 
 static PERMISSIBLE_EMOJIS: Lazy<HashSet<String>> = Lazy::new(|| {
     include_str!("unicode_emoji.txt")
@@ -72,6 +72,16 @@ impl Emoji {
         .p(self.parent().to_string())
         .await;
 
+        // Release the emoji's file from the server's storage usage,
+        // deleted files have already been released by crond
+        if let EmojiParent::Server { id } = &self.parent {
+            if let Ok(file) = db.fetch_attachment("emojis", &self.id).await {
+                if file.deleted != Some(true) {
+                    StorageUsage::release(db, id, &file).await?;
+                }
+            }
+        }
+
         db.detach_emoji(&self).await
     }
 
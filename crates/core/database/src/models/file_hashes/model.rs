@@ -45,12 +45,30 @@ auto_derived!(
         Image {
             width: isize,
             height: isize,
-            // animated: bool // TODO: https://docs.rs/image/latest/image/trait.AnimationDecoder.html for APNG support
+            /// Blurhash placeholder to show while the image loads
+            #[serde(skip_serializing_if = "Option::is_none", default)]
+            placeholder: Option<String>,
+            /// Whether the image has more than one frame
+            #[serde(skip_serializing_if = "crate::if_false", default)]
+            animated: bool,
         },
         /// File is a video with specific dimensions
-        Video { width: isize, height: isize },
+        Video {
+            width: isize,
+            height: isize,
+            /// Blurhash placeholder of the first frame to show while the video loads
+            #[serde(skip_serializing_if = "Option::is_none", default)]
+            placeholder: Option<String>,
+        },
         /// File is audio
-        Audio,
+        Audio {
+            /// Duration of the audio (in milliseconds)
+            #[serde(skip_serializing_if = "Option::is_none", default)]
+            duration: Option<isize>,
+            /// Downsampled waveform of a voice message, one amplitude (0-255) per sample
+            #[serde(skip_serializing_if = "Option::is_none", default)]
+            waveform: Option<Vec<u8>>,
+        },
     }
 );
 
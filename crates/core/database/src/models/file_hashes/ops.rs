@@ -17,6 +17,16 @@ pub trait AbstractAttachmentHashes: Sync + Send {
     /// Update an attachment hash nonce value.
     async fn set_attachment_hash_nonce(&self, hash: &str, nonce: &str) -> Result<()>;
 
+    /// Fetch a batch of image hashes which have no placeholder yet, ordered by id.
+    async fn fetch_attachment_hashes_without_placeholder(
+        &self,
+        after: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<FileHash>>;
+
+    /// Update an attachment hash placeholder value.
+    async fn set_attachment_hash_placeholder(&self, hash: &str, placeholder: &str) -> Result<()>;
+
     /// Delete attachment hash by id.
     async fn delete_attachment_hash(&self, id: &str) -> Result<()>;
 }
@@ -1,3 +1,4 @@
+use mongodb::options::FindOptions;
 use revolt_result::Result;
 
 use crate::FileHash;
@@ -48,6 +49,53 @@ impl AbstractAttachmentHashes for MongoDb {
             .map_err(|_| create_database_error!("update_one", COL))
     }
 
+    /// Fetch a batch of image hashes which have no placeholder yet, ordered by id.
+    async fn fetch_attachment_hashes_without_placeholder(
+        &self,
+        after: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<FileHash>> {
+        let mut filter = doc! {
+            "metadata.type": "Image",
+            "metadata.placeholder": {
+                "$exists": false
+            }
+        };
+
+        if let Some(after) = after {
+            filter.insert("_id", doc! { "$gt": after });
+        }
+
+        query!(
+            self,
+            find_with_options,
+            COL,
+            filter,
+            FindOptions::builder()
+                .limit(limit)
+                .sort(doc! { "_id": 1_i32 })
+                .build()
+        )
+    }
+
+    /// Update an attachment hash placeholder value.
+    async fn set_attachment_hash_placeholder(&self, hash: &str, placeholder: &str) -> Result<()> {
+        self.col::<FileHash>(COL)
+            .update_one(
+                doc! {
+                    "_id": hash
+                },
+                doc! {
+                    "$set": {
+                        "metadata.placeholder": placeholder
+                    }
+                },
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| create_database_error!("update_one", COL))
+    }
+
     /// Delete attachment hash by id.
     async fn delete_attachment_hash(&self, id: &str) -> Result<()> {
         query!(self, delete_one_by_id, COL, id).map(|_| ())
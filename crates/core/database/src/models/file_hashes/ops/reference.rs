@@ -1,6 +1,7 @@
 use revolt_result::Result;
 
 use crate::FileHash;
+use crate::Metadata;
 use crate::ReferenceDb;
 
 use super::AbstractAttachmentHashes;
@@ -39,6 +40,46 @@ impl AbstractAttachmentHashes for ReferenceDb {
         }
     }
 
+    /// Fetch a batch of image hashes which have no placeholder yet, ordered by id.
+    async fn fetch_attachment_hashes_without_placeholder(
+        &self,
+        after: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<FileHash>> {
+        let hashes = self.file_hashes.lock().await;
+        let mut hashes: Vec<FileHash> = hashes
+            .values()
+            .filter(|hash| {
+                matches!(
+                    hash.metadata,
+                    Metadata::Image {
+                        placeholder: None,
+                        ..
+                    }
+                ) && after.is_none_or(|after| hash.id.as_str() > after)
+            })
+            .cloned()
+            .collect();
+
+        hashes.sort_by(|a, b| a.id.cmp(&b.id));
+        hashes.truncate(limit as usize);
+        Ok(hashes)
+    }
+
+    /// Update an attachment hash placeholder value.
+    async fn set_attachment_hash_placeholder(&self, hash: &str, placeholder: &str) -> Result<()> {
+        let mut hashes = self.file_hashes.lock().await;
+        if let Some(Metadata::Image {
+            placeholder: value, ..
+        }) = hashes.get_mut(hash).map(|file| &mut file.metadata)
+        {
+            value.replace(placeholder.to_owned());
+            Ok(())
+        } else {
+            Err(create_error!(NotFound))
+        }
+    }
+
     /// Delete attachment hash by id.
     async fn delete_attachment_hash(&self, id: &str) -> Result<()> {
         let mut file_hashes = self.file_hashes.lock().await;
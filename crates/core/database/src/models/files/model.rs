@@ -1,7 +1,17 @@
-use crate::{Database, FileHash, Metadata};
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::{Channel, Database, FileHash, Metadata, StorageUsage};
+
+use base64::prelude::*;
+use hmac::{Hmac, Mac};
 use iso8601_timestamp::Timestamp;
+use revolt_config::{config, FilesSigning};
+use revolt_models::v0;
 use revolt_result::Result;
+use sha2::Sha256;
+
+/// HMAC used to sign file URLs
+type HmacSha256 = Hmac<Sha256>;
 
 auto_derived_partial!(
     /// File
@@ -70,6 +80,7 @@ auto_derived!(
         LegacyGroupIcon,
         ChannelIcon,
         ServerIcon,
+        Sticker,
     }
 
     /// Information about what the file was used for
@@ -171,16 +182,20 @@ impl File {
         parent: &str,
         uploader_id: &str,
     ) -> Result<File> {
-        db.find_and_use_attachment(
-            id,
-            "icons",
-            FileUsedFor {
-                id: parent.to_owned(),
-                object_type: FileUsedForType::ServerIcon,
-            },
-            uploader_id.to_owned(),
-        )
-        .await
+        let file = db
+            .find_and_use_attachment(
+                id,
+                "icons",
+                FileUsedFor {
+                    id: parent.to_owned(),
+                    object_type: FileUsedForType::ServerIcon,
+                },
+                uploader_id.to_owned(),
+            )
+            .await?;
+
+        StorageUsage::charge(db, parent, &file).await?;
+        Ok(file)
     }
 
     /// Use a file for a channel icon
@@ -209,20 +224,86 @@ impl File {
         parent: &str,
         uploader_id: &str,
     ) -> Result<File> {
-        db.find_and_use_attachment(
-            id,
-            "banners",
-            FileUsedFor {
-                id: parent.to_owned(),
-                object_type: FileUsedForType::ServerBanner,
-            },
-            uploader_id.to_owned(),
-        )
-        .await
+        let file = db
+            .find_and_use_attachment(
+                id,
+                "banners",
+                FileUsedFor {
+                    id: parent.to_owned(),
+                    object_type: FileUsedForType::ServerBanner,
+                },
+                uploader_id.to_owned(),
+            )
+            .await?;
+
+        StorageUsage::charge(db, parent, &file).await?;
+        Ok(file)
     }
 
     /// Use a file for an emoji
+    ///
+    /// The file is charged to the server the emoji belongs to
     pub async fn use_emoji(
+        db: &Database,
+        id: &str,
+        parent: &str,
+        server_id: &str,
+        uploader_id: &str,
+    ) -> Result<File> {
+        let file = db
+            .find_and_use_attachment(
+                id,
+                "emojis",
+                FileUsedFor {
+                    id: parent.to_owned(),
+                    object_type: FileUsedForType::Emoji,
+                },
+                uploader_id.to_owned(),
+            )
+            .await?;
+
+        StorageUsage::charge(db, server_id, &file).await?;
+        Ok(file)
+    }
+
+    /// Copy an emoji file for use by a new emoji, such as when a server is created from a template
+    ///
+    /// Files are deduplicated by hash so the copy points to the same data. The copy is charged
+    /// to its uploader within their storage quota and to the server the emoji belongs to.
+    #[allow(clippy::disallowed_methods)]
+    pub async fn duplicate_for_emoji(
+        self,
+        db: &Database,
+        parent: &str,
+        server_id: &str,
+        uploader_id: &str,
+        storage_quota: usize,
+    ) -> Result<File> {
+        let file = File {
+            id: parent.to_owned(),
+            uploader_id: Some(uploader_id.to_owned()),
+            used_for: Some(FileUsedFor {
+                object_type: FileUsedForType::Emoji,
+                id: parent.to_owned(),
+            }),
+            deleted: None,
+            reported: None,
+            ..self
+        };
+
+        StorageUsage::charge_within_quota(db, uploader_id, &file, storage_quota).await?;
+
+        if let Err(err) = db.insert_attachment(&file).await {
+            StorageUsage::release(db, uploader_id, &file).await?;
+            return Err(err);
+        }
+
+        StorageUsage::charge(db, server_id, &file).await?;
+        Ok(file)
+    }
+
+    /// Use a file for a sticker
+    pub async fn use_sticker(
         db: &Database,
         id: &str,
         parent: &str,
@@ -230,13 +311,118 @@ impl File {
     ) -> Result<File> {
         db.find_and_use_attachment(
             id,
-            "emojis",
+            "stickers",
             FileUsedFor {
                 id: parent.to_owned(),
-                object_type: FileUsedForType::Emoji,
+                object_type: FileUsedForType::Sticker,
             },
             uploader_id.to_owned(),
         )
         .await
     }
+
+    /// Whether this file may only be fetched using a signed URL
+    ///
+    /// Only attachments of messages in channels that aren't publicly viewable require a signature
+    pub async fn requires_signature(&self, db: &Database) -> Result<bool> {
+        if config().await.files.signing.key.is_empty() {
+            return Ok(false);
+        }
+
+        let message_id = match &self.used_for {
+            Some(FileUsedFor {
+                object_type: FileUsedForType::Message,
+                id,
+            }) => id,
+            Some(_) => return Ok(false),
+            None => match &self.message_id {
+                Some(id) => id,
+                None => return Ok(false),
+            },
+        };
+
+        let Ok(message) = db.fetch_message(message_id).await else {
+            return Ok(true);
+        };
+
+        let channel = db.fetch_channel(&message.channel).await?;
+        let server = match &channel {
+            Channel::TextChannel { server, .. } | Channel::VoiceChannel { server, .. } => {
+                Some(db.fetch_server(server).await?)
+            }
+            _ => None,
+        };
+
+        Ok(!channel.is_publicly_viewable(server.as_ref()))
+    }
+
+    /// Sign files so that they can be fetched by the given viewer
+    ///
+    /// Expiry is rounded to keep URLs stable (and hence cacheable) for a while
+    pub fn sign_for(files: &mut [v0::File], viewer: &str, signing: &FilesSigning) {
+        if signing.key.is_empty() {
+            return;
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let window = (signing.expiry / 2).max(1);
+        let expires = (now / window + 2) * window;
+
+        for file in files {
+            file.signature = Some(v0::FileSignature {
+                expires,
+                viewer: viewer.to_owned(),
+                signature: File::sign_url(&signing.key, &file.tag, &file.id, expires, viewer),
+            });
+        }
+    }
+
+    /// Create a MAC over the parameters of a signed file URL
+    fn create_url_mac(key: &str, tag: &str, id: &str, expires: u64, viewer: &str) -> HmacSha256 {
+        let mut mac = <HmacSha256 as Mac>::new_from_slice(key.as_bytes())
+            .expect("HMAC can take key of any size");
+
+        mac.update(format!("{tag}/{id}/{expires}/{viewer}").as_bytes());
+        mac
+    }
+
+    /// Sign a file URL for a given viewer, valid until the given UNIX timestamp (in seconds)
+    pub fn sign_url(key: &str, tag: &str, id: &str, expires: u64, viewer: &str) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(
+            File::create_url_mac(key, tag, id, expires, viewer)
+                .finalize()
+                .into_bytes(),
+        )
+    }
+
+    /// Verify the signature of a file URL has not been tampered with and has not expired
+    pub fn verify_url(
+        key: &str,
+        tag: &str,
+        id: &str,
+        expires: u64,
+        viewer: &str,
+        signature: &str,
+    ) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        if expires < now {
+            return false;
+        }
+
+        let Ok(signature) = BASE64_URL_SAFE_NO_PAD.decode(signature) else {
+            return false;
+        };
+
+        File::create_url_mac(key, tag, id, expires, viewer)
+            .verify_slice(&signature)
+            .is_ok()
+    }
 }
@@ -34,6 +34,9 @@ pub trait AbstractAttachments: Sync + Send {
         uploader_id: String,
     ) -> Result<File>;
 
+    /// Set the placeholder of all image attachments sharing a given hash.
+    async fn set_attachments_placeholder(&self, hash: &str, placeholder: &str) -> Result<()>;
+
     /// Mark an attachment as having been reported.
     async fn mark_attachment_as_reported(&self, id: &str) -> Result<()>;
 
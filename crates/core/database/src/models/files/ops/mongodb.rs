@@ -117,6 +117,25 @@ impl AbstractAttachments for MongoDb {
         Ok(file)
     }
 
+    /// Set the placeholder of all image attachments sharing a given hash.
+    async fn set_attachments_placeholder(&self, hash: &str, placeholder: &str) -> Result<()> {
+        self.col::<Document>(COL)
+            .update_many(
+                doc! {
+                    "hash": hash,
+                    "metadata.type": "Image"
+                },
+                doc! {
+                    "$set": {
+                        "metadata.placeholder": placeholder
+                    }
+                },
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| create_database_error!("update_many", COL))
+    }
+
     /// Mark an attachment as having been reported.
     async fn mark_attachment_as_reported(&self, id: &str) -> Result<()> {
         self.col::<Document>(COL)
@@ -2,6 +2,7 @@ use revolt_result::Result;
 
 use crate::File;
 use crate::FileUsedFor;
+use crate::Metadata;
 use crate::ReferenceDb;
 
 use super::AbstractAttachments;
@@ -91,6 +92,24 @@ impl AbstractAttachments for ReferenceDb {
         }
     }
 
+    /// Set the placeholder of all image attachments sharing a given hash.
+    async fn set_attachments_placeholder(&self, hash: &str, placeholder: &str) -> Result<()> {
+        let mut files = self.files.lock().await;
+        for file in files
+            .values_mut()
+            .filter(|file| file.hash.as_ref().is_some_and(|h| h == hash))
+        {
+            if let Metadata::Image {
+                placeholder: value, ..
+            } = &mut file.metadata
+            {
+                value.replace(placeholder.to_owned());
+            }
+        }
+
+        Ok(())
+    }
+
     /// Mark an attachment as having been reported.
     async fn mark_attachment_as_reported(&self, id: &str) -> Result<()> {
         let mut files = self.files.lock().await;
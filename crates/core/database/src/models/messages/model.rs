@@ -18,7 +18,7 @@ use crate::{
         bulk_permissions::BulkDatabasePermissionQuery, idempotency::IdempotencyKey,
         permissions::DatabasePermissionQuery,
     },
-    Channel, Database, Emoji, File, User, AMQP,
+    Channel, Database, Emoji, File, Metadata, StickerParent, User, AMQP,
 };
 
 #[cfg(feature = "tasks")]
@@ -43,6 +43,9 @@ auto_derived_partial!(
         /// Message content
         #[serde(skip_serializing_if = "Option::is_none")]
         pub content: Option<String>,
+        /// Id of the sticker sent instead of content
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub sticker: Option<String>,
         /// System message
         #[serde(skip_serializing_if = "Option::is_none")]
         pub system: Option<SystemMessage>,
@@ -204,6 +207,7 @@ auto_derived!(
     /// Optional fields on message
     pub enum FieldsMessage {
         Pinned,
+        Embeds,
     }
 );
 
@@ -241,6 +245,7 @@ impl Default for Message {
             author: Default::default(),
             webhook: None,
             content: None,
+            sticker: None,
             system: None,
             attachments: None,
             edited: None,
@@ -291,6 +296,7 @@ impl Message {
         if (data.content.as_ref().is_none_or(|v| v.is_empty()))
             && (data.attachments.as_ref().is_none_or(|v| v.is_empty()))
             && (data.embeds.as_ref().is_none_or(|v| v.is_empty()))
+            && data.sticker.is_none()
         {
             return Err(create_error!(EmptyMessage));
         }
@@ -300,9 +306,11 @@ impl Message {
         let mut mentions_everyone = false;
         let mut mentions_online = false;
         let mut suppress_notifications = false;
+        let mut voice_message = false;
+        let mut suppress_embeds = false;
 
         if let Some(raw_flags) = &data.flags {
-            if raw_flags > &7 {
+            if raw_flags > &63 {
                 // quick path to failure: bigger than all the bits combined
                 return Err(create_error!(InvalidProperty));
             }
@@ -312,6 +320,7 @@ impl Message {
             suppress_notifications = flags.has(MessageFlags::SuppressNotifications);
             mentions_everyone = allow_mentions && flags.has(MessageFlags::MentionsEveryone);
             mentions_online = allow_mentions && flags.has(MessageFlags::MentionsOnline);
+            suppress_embeds = flags.has(MessageFlags::SuppressEmbeds);
 
             // Not a bot, and attempting to set mention flags
             if user.as_ref().is_some_and(|u| u.bot.as_ref().is_none())
@@ -323,6 +332,30 @@ impl Message {
             if mentions_everyone && mentions_online {
                 return Err(create_error!(InvalidFlagValue));
             }
+
+            // Voice messages must consist of exactly one recording processed by autumn,
+            // plain audio uploads have no waveform
+            voice_message = flags.has(MessageFlags::VoiceMessage);
+            if voice_message {
+                let [attachment_id] = data.attachments.as_deref().unwrap_or_default() else {
+                    return Err(create_error!(InvalidFlagValue));
+                };
+
+                if data.content.as_ref().is_some_and(|v| !v.is_empty())
+                    || data.embeds.as_ref().is_some_and(|v| !v.is_empty())
+                    || !matches!(
+                        db.fetch_attachment("attachments", attachment_id)
+                            .await?
+                            .metadata,
+                        Metadata::Audio {
+                            waveform: Some(_),
+                            ..
+                        }
+                    )
+                {
+                    return Err(create_error!(InvalidFlagValue));
+                }
+            }
         }
 
         let server_id = match channel {
@@ -332,6 +365,40 @@ impl Message {
             _ => None,
         };
 
+        // Stickers are sent on their own and must be usable by the author
+        if let Some(sticker_id) = &data.sticker {
+            if data.content.as_ref().is_some_and(|v| !v.is_empty())
+                || data.attachments.as_ref().is_some_and(|v| !v.is_empty())
+                || data.embeds.as_ref().is_some_and(|v| !v.is_empty())
+            {
+                return Err(create_error!(InvalidProperty));
+            }
+
+            let sticker = db.fetch_sticker(sticker_id).await?;
+            let StickerParent::Server {
+                id: sticker_server_id,
+            } = &sticker.parent
+            else {
+                return Err(create_error!(NotFound));
+            };
+
+            // NSFW stickers may only be sent in NSFW channels
+            if sticker.nsfw && !channel.is_nsfw() {
+                return Err(create_error!(InvalidOperation));
+            }
+
+            // Stickers from other servers may only be used by their members
+            if server_id.as_ref() != Some(sticker_server_id) {
+                let MessageAuthor::User(user) = &author else {
+                    return Err(create_error!(NotFound));
+                };
+
+                db.fetch_member(sticker_server_id, &user.id)
+                    .await
+                    .map_err(|_| create_error!(NotFound))?;
+            }
+        }
+
         // Ensure restrict_reactions is not specified without reactions list
         if let Some(interactions) = &data.interactions {
             if interactions.restrict_reactions {
@@ -365,6 +432,7 @@ impl Message {
                 .unwrap_or_default(),
             author: author_id,
             webhook: webhook.map(|w| w.into()),
+            sticker: data.sticker,
             flags: data.flags,
             ..Default::default()
         };
@@ -385,6 +453,7 @@ impl Message {
             mut role_mentions,
             mut mentions_everyone,
             mut mentions_online,
+            ..
         } = message_mentions;
 
         if allow_mass_mentions && server_id.is_some() && !role_mentions.is_empty() {
@@ -544,7 +613,9 @@ impl Message {
         flag_value
             .set(MessageFlags::SuppressNotifications, suppress_notifications)
             .set(MessageFlags::MentionsEveryone, mentions_everyone)
-            .set(MessageFlags::MentionsOnline, mentions_online);
+            .set(MessageFlags::MentionsOnline, mentions_online)
+            .set(MessageFlags::VoiceMessage, voice_message)
+            .set(MessageFlags::SuppressEmbeds, suppress_embeds);
 
         message.flags = Some(flag_value.0);
 
@@ -592,7 +663,15 @@ impl Message {
 
         // Send the message
         message
-            .send(db, amqp, author, user, member, &channel, generate_embeds)
+            .send(
+                db,
+                amqp,
+                author,
+                user,
+                member,
+                &channel,
+                generate_embeds && !suppress_embeds,
+            )
             .await?;
 
         Ok(message)
@@ -749,6 +828,12 @@ impl Message {
         }
     }
 
+    /// Whether this message has suppressed embeds
+    pub fn has_suppressed_embeds(&self) -> bool {
+        self.flags
+            .is_some_and(|flags| MessageFlagsValue(flags).has(MessageFlags::SuppressEmbeds))
+    }
+
     pub fn contains_mass_push_mention(&self) -> bool {
         let ping = if let Some(flags) = self.flags {
             let flags = MessageFlagsValue(flags);
@@ -788,21 +873,55 @@ impl Message {
         Ok(())
     }
 
+    /// Sign attachments of messages for the given viewer if the channel isn't publicly viewable
+    pub async fn sign_attachments(
+        db: &Database,
+        channel: &Channel,
+        messages: &mut [v0::Message],
+        viewer: &str,
+    ) -> Result<()> {
+        let config = config().await;
+        if config.files.signing.key.is_empty()
+            || messages.iter().all(|message| message.attachments.is_none())
+        {
+            return Ok(());
+        }
+
+        let server = match channel {
+            Channel::TextChannel { server, .. } | Channel::VoiceChannel { server, .. } => {
+                Some(db.fetch_server(server).await?)
+            }
+            _ => None,
+        };
+
+        if !channel.is_publicly_viewable(server.as_ref()) {
+            for message in messages {
+                if let Some(attachments) = &mut message.attachments {
+                    File::sign_for(attachments, viewer, &config.files.signing);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Helper function to fetch many messages with users
     pub async fn fetch_with_users(
         db: &Database,
         query: MessageQuery,
         perspective: &User,
         include_users: Option<bool>,
-        server_id: Option<String>,
+        channel: &Channel,
     ) -> Result<BulkMessageResponse> {
-        let messages: Vec<v0::Message> = db
+        let mut messages: Vec<v0::Message> = db
             .fetch_messages(query)
             .await?
             .into_iter()
             .map(|msg| msg.into_model(None, None))
             .collect();
 
+        Message::sign_attachments(db, channel, &mut messages, &perspective.id).await?;
+
         if let Some(true) = include_users {
             let user_ids = messages
                 .iter()
@@ -849,9 +968,11 @@ impl Message {
             Ok(BulkMessageResponse::MessagesAndUsers {
                 messages,
                 users,
-                members: if let Some(server_id) = server_id {
+                members: if let Channel::TextChannel { server, .. }
+                | Channel::VoiceChannel { server, .. } = channel
+                {
                     Some(
-                        db.fetch_members(&server_id, &user_ids)
+                        db.fetch_members(server, &user_ids)
                             .await?
                             .into_iter()
                             .map(Into::into)
@@ -990,6 +1111,8 @@ impl Message {
         }
 
         db.delete_message(&self.id).await?;
+        db.delete_reaction_roles_by_messages(std::slice::from_ref(&self.id))
+            .await?;
 
         EventV1::MessageDelete {
             id: self.id,
@@ -1011,6 +1134,7 @@ impl Message {
             .collect::<Vec<String>>();
 
         db.delete_messages(channel, &valid_ids).await?;
+        db.delete_reaction_roles_by_messages(&valid_ids).await?;
         EventV1::BulkMessageDelete {
             channel: channel.to_string(),
             ids: valid_ids,
@@ -1070,6 +1194,7 @@ impl Message {
     pub fn remove_field(&mut self, field: &FieldsMessage) {
         match field {
             FieldsMessage::Pinned => self.pinned = None,
+            FieldsMessage::Embeds => self.embeds = None,
         }
     }
 }
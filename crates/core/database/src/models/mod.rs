@@ -1,40 +1,54 @@
 mod admin_migrations;
+mod automod_configs;
 mod bots;
 mod channel_invites;
 mod channel_unreads;
 mod channel_webhooks;
 mod channels;
+mod discovery_listings;
 mod emojis;
 mod file_hashes;
 mod files;
 mod messages;
 mod policy_changes;
 mod ratelimit_events;
+mod reaction_roles;
 mod safety_reports;
 mod safety_snapshots;
 mod server_bans;
 mod server_members;
+mod server_templates;
 mod servers;
+mod stickers;
+mod storage_usage;
+mod user_favourites;
 mod user_settings;
 mod users;
 
 pub use admin_migrations::*;
+pub use automod_configs::*;
 pub use bots::*;
 pub use channel_invites::*;
 pub use channel_unreads::*;
 pub use channel_webhooks::*;
 pub use channels::*;
+pub use discovery_listings::*;
 pub use emojis::*;
 pub use file_hashes::*;
 pub use files::*;
 pub use messages::*;
 pub use policy_changes::*;
 pub use ratelimit_events::*;
+pub use reaction_roles::*;
 pub use safety_reports::*;
 pub use safety_snapshots::*;
 pub use server_bans::*;
 pub use server_members::*;
+pub use server_templates::*;
 pub use servers::*;
+pub use stickers::*;
+pub use storage_usage::*;
+pub use user_favourites::*;
 pub use user_settings::*;
 pub use users::*;
 
@@ -47,22 +61,29 @@ pub trait AbstractDatabase:
     Sync
     + Send
     + admin_migrations::AbstractMigrations
+    + automod_configs::AbstractAutoModConfigs
     + bots::AbstractBots
     + channels::AbstractChannels
     + channel_invites::AbstractChannelInvites
     + channel_unreads::AbstractChannelUnreads
     + channel_webhooks::AbstractWebhooks
+    + discovery_listings::AbstractDiscoveryListings
     + emojis::AbstractEmojis
     + file_hashes::AbstractAttachmentHashes
     + files::AbstractAttachments
     + messages::AbstractMessages
     + policy_changes::AbstractPolicyChange
     + ratelimit_events::AbstractRatelimitEvents
+    + reaction_roles::AbstractReactionRoles
     + safety_reports::AbstractReport
     + safety_snapshots::AbstractSnapshot
     + server_bans::AbstractServerBans
     + server_members::AbstractServerMembers
+    + server_templates::AbstractServerTemplates
     + servers::AbstractServers
+    + stickers::AbstractStickers
+    + storage_usage::AbstractStorageUsage
+    + user_favourites::AbstractUserFavourites
     + user_settings::AbstractUserSettings
     + users::AbstractUsers
 {
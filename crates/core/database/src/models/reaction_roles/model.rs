@@ -0,0 +1,43 @@
+use revolt_result::Result;
+
+use crate::Database;
+
+auto_derived!(
+    /// Binding of an emoji reaction on a message to a self-assignable role
+    pub struct ReactionRole {
+        /// Unique binding id
+        #[serde(rename = "_id")]
+        pub id: ReactionRoleCompositeKey,
+        /// Id of the server the role belongs to
+        pub server_id: String,
+        /// Id of the channel the message was sent in
+        pub channel_id: String,
+        /// Id of the role granted by reacting
+        pub role_id: String,
+    }
+
+    /// Composite primary key consisting of message id and emoji
+    #[derive(Hash)]
+    pub struct ReactionRoleCompositeKey {
+        /// Message Id
+        pub message: String,
+        /// Emoji Id or unicode emoji
+        pub emoji: String,
+    }
+);
+
+impl ReactionRole {
+    /// Grant the bound role to a user who reacted
+    pub async fn grant(&self, db: &Database, user_id: &str) -> Result<()> {
+        let server = db.fetch_server(&self.server_id).await?;
+        let mut member = db.fetch_member(&self.server_id, user_id).await?;
+        member.add_self_role(db, &server, &self.role_id).await
+    }
+
+    /// Revoke the bound role from a user whose reaction was removed
+    pub async fn revoke(&self, db: &Database, user_id: &str) -> Result<()> {
+        let server = db.fetch_server(&self.server_id).await?;
+        let mut member = db.fetch_member(&self.server_id, user_id).await?;
+        member.remove_self_role(db, &server, &self.role_id).await
+    }
+}
@@ -0,0 +1,28 @@
+use revolt_result::Result;
+
+use crate::{ReactionRole, ReactionRoleCompositeKey};
+
+#[cfg(feature = "mongodb")]
+mod mongodb;
+mod reference;
+
+#[async_trait]
+pub trait AbstractReactionRoles: Sync + Send {
+    /// Insert or replace a reaction role binding
+    async fn upsert_reaction_role(&self, reaction_role: &ReactionRole) -> Result<()>;
+
+    /// Fetch a reaction role binding by its message and emoji
+    async fn fetch_reaction_role(&self, id: &ReactionRoleCompositeKey) -> Result<ReactionRole>;
+
+    /// Fetch all reaction role bindings on a message
+    async fn fetch_reaction_roles_by_message(&self, message_id: &str) -> Result<Vec<ReactionRole>>;
+
+    /// Delete a reaction role binding
+    async fn delete_reaction_role(&self, id: &ReactionRoleCompositeKey) -> Result<()>;
+
+    /// Delete all reaction role bindings on the given messages
+    async fn delete_reaction_roles_by_messages(&self, message_ids: &[String]) -> Result<()>;
+
+    /// Delete all reaction role bindings granting a role
+    async fn delete_reaction_roles_by_role(&self, server_id: &str, role_id: &str) -> Result<()>;
+}
@@ -0,0 +1,92 @@
+use revolt_result::Result;
+
+use crate::MongoDb;
+use crate::{ReactionRole, ReactionRoleCompositeKey};
+
+use super::AbstractReactionRoles;
+
+static COL: &str = "reaction_roles";
+
+#[async_trait]
+impl AbstractReactionRoles for MongoDb {
+    /// Insert or replace a reaction role binding
+    async fn upsert_reaction_role(&self, reaction_role: &ReactionRole) -> Result<()> {
+        self.col::<ReactionRole>(COL)
+            .replace_one(
+                doc! {
+                    "_id.message": &reaction_role.id.message,
+                    "_id.emoji": &reaction_role.id.emoji,
+                },
+                reaction_role,
+            )
+            .upsert(true)
+            .await
+            .map(|_| ())
+            .map_err(|_| create_database_error!("replace_one", COL))
+    }
+
+    /// Fetch a reaction role binding by its message and emoji
+    async fn fetch_reaction_role(&self, id: &ReactionRoleCompositeKey) -> Result<ReactionRole> {
+        query!(
+            self,
+            find_one,
+            COL,
+            doc! {
+                "_id.message": &id.message,
+                "_id.emoji": &id.emoji,
+            }
+        )?
+        .ok_or_else(|| create_error!(NotFound))
+    }
+
+    /// Fetch all reaction role bindings on a message
+    async fn fetch_reaction_roles_by_message(&self, message_id: &str) -> Result<Vec<ReactionRole>> {
+        query!(
+            self,
+            find,
+            COL,
+            doc! {
+                "_id.message": message_id
+            }
+        )
+    }
+
+    /// Delete a reaction role binding
+    async fn delete_reaction_role(&self, id: &ReactionRoleCompositeKey) -> Result<()> {
+        query!(
+            self,
+            delete_one,
+            COL,
+            doc! {
+                "_id.message": &id.message,
+                "_id.emoji": &id.emoji,
+            }
+        )
+        .map(|_| ())
+    }
+
+    /// Delete all reaction role bindings on the given messages
+    async fn delete_reaction_roles_by_messages(&self, message_ids: &[String]) -> Result<()> {
+        self.col::<ReactionRole>(COL)
+            .delete_many(doc! {
+                "_id.message": {
+                    "$in": message_ids
+                }
+            })
+            .await
+            .map(|_| ())
+            .map_err(|_| create_database_error!("delete_many", COL))
+    }
+
+    /// Delete all reaction role bindings granting a role
+    async fn delete_reaction_roles_by_role(&self, server_id: &str, role_id: &str) -> Result<()> {
+        self.col::<ReactionRole>(COL)
+            .delete_many(doc! {
+                "server_id": server_id,
+                "role_id": role_id
+            })
+            .await
+            .map(|_| ())
+            .map_err(|_| create_database_error!("delete_many", COL))
+    }
+}
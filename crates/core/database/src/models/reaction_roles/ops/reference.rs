@@ -0,0 +1,61 @@
+use revolt_result::Result;
+
+use crate::ReferenceDb;
+use crate::{ReactionRole, ReactionRoleCompositeKey};
+
+use super::AbstractReactionRoles;
+
+#[async_trait]
+impl AbstractReactionRoles for ReferenceDb {
+    /// Insert or replace a reaction role binding
+    async fn upsert_reaction_role(&self, reaction_role: &ReactionRole) -> Result<()> {
+        let mut reaction_roles = self.reaction_roles.lock().await;
+        reaction_roles.insert(reaction_role.id.clone(), reaction_role.clone());
+        Ok(())
+    }
+
+    /// Fetch a reaction role binding by its message and emoji
+    async fn fetch_reaction_role(&self, id: &ReactionRoleCompositeKey) -> Result<ReactionRole> {
+        let reaction_roles = self.reaction_roles.lock().await;
+        reaction_roles
+            .get(id)
+            .cloned()
+            .ok_or_else(|| create_error!(NotFound))
+    }
+
+    /// Fetch all reaction role bindings on a message
+    async fn fetch_reaction_roles_by_message(&self, message_id: &str) -> Result<Vec<ReactionRole>> {
+        let reaction_roles = self.reaction_roles.lock().await;
+        Ok(reaction_roles
+            .values()
+            .filter(|reaction_role| reaction_role.id.message == message_id)
+            .cloned()
+            .collect())
+    }
+
+    /// Delete a reaction role binding
+    async fn delete_reaction_role(&self, id: &ReactionRoleCompositeKey) -> Result<()> {
+        let mut reaction_roles = self.reaction_roles.lock().await;
+        if reaction_roles.remove(id).is_some() {
+            Ok(())
+        } else {
+            Err(create_error!(NotFound))
+        }
+    }
+
+    /// Delete all reaction role bindings on the given messages
+    async fn delete_reaction_roles_by_messages(&self, message_ids: &[String]) -> Result<()> {
+        let mut reaction_roles = self.reaction_roles.lock().await;
+        reaction_roles.retain(|id, _| !message_ids.contains(&id.message));
+        Ok(())
+    }
+
+    /// Delete all reaction role bindings granting a role
+    async fn delete_reaction_roles_by_role(&self, server_id: &str, role_id: &str) -> Result<()> {
+        let mut reaction_roles = self.reaction_roles.lock().await;
+        reaction_roles.retain(|_, reaction_role| {
+            reaction_role.server_id != server_id || reaction_role.role_id != role_id
+        });
+        Ok(())
+    }
+}
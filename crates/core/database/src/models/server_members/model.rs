@@ -30,6 +30,9 @@ auto_derived_partial!(
         /// Timestamp this member is timed out until
         #[serde(skip_serializing_if = "Option::is_none")]
         pub timeout: Option<Timestamp>,
+        /// Whether this member has yet to pass the server's screening
+        #[serde(skip_serializing_if = "crate::if_false", default)]
+        pub pending: bool,
         // This value only exists in the database, not the models.
         // If it is not-None, the database layer should return None to member fetching queries.
         // pub pending_deletion_at: Option<Timestamp>
@@ -73,6 +76,7 @@ impl Default for Member {
             avatar: None,
             roles: vec![],
             timeout: None,
+            pending: false,
         }
     }
 }
@@ -99,6 +103,7 @@ impl Member {
                 server: server.id.to_string(),
                 user: user.id.to_string(),
             },
+            pending: server.screening.is_some() && user.bot.is_none(),
             ..Default::default()
         };
 
@@ -126,6 +131,7 @@ impl Member {
         }
 
         let emojis = db.fetch_emoji_by_parent_id(&server.id).await?;
+        let stickers = db.fetch_stickers_by_parent_id(&server.id).await?;
 
         EventV1::ServerMemberJoin {
             id: server.id.clone(),
@@ -144,25 +150,162 @@ impl Member {
                 .map(|channel| channel.into())
                 .collect(),
             emojis: emojis.into_iter().map(|emoji| emoji.into()).collect(),
+            stickers: stickers.into_iter().map(|sticker| sticker.into()).collect(),
         }
         .private(user.id.clone())
         .await;
 
+        // Members held by screening announce themselves once they accept
+        if !member.pending {
+            member.announce_join(db, server).await;
+        }
+
+        Ok((member, channels))
+    }
+
+    /// Accept the server's screening, lifting the view-only restriction
+    pub async fn accept_screening(&mut self, db: &Database, server: &Server) -> Result<()> {
+        if !self.pending {
+            return Err(create_error!(InvalidOperation));
+        }
+
+        self.update(
+            db,
+            PartialMember {
+                pending: Some(false),
+                ..Default::default()
+            },
+            vec![],
+        )
+        .await?;
+
+        self.announce_join(db, server).await;
+        Ok(())
+    }
+
+    /// Assign a self-assignable role to this member
+    ///
+    /// Any other role held from the same exclusive group is removed.
+    pub async fn add_self_role(
+        &mut self,
+        db: &Database,
+        server: &Server,
+        role_id: &str,
+    ) -> Result<()> {
+        // Members held by screening are restricted to viewing the server
+        if self.pending {
+            return Err(create_error!(MissingPermission {
+                permission: ChannelPermission::AssignRoles.to_string()
+            }));
+        }
+
+        let role = server
+            .roles
+            .get(role_id)
+            .ok_or_else(|| create_error!(NotFound))?;
+
+        if !role.self_assignable {
+            return Err(create_error!(MissingPermission {
+                permission: ChannelPermission::AssignRoles.to_string()
+            }));
+        }
+
+        let siblings: Vec<&String> = self
+            .roles
+            .iter()
+            .filter(|id| *id != role_id)
+            .filter(|id| {
+                role.group.is_some()
+                    && server.roles.get(*id).and_then(|r| r.group.as_ref()) == role.group.as_ref()
+            })
+            .collect();
+
+        let holds_role = self.roles.iter().any(|id| id == role_id);
+        if holds_role && siblings.is_empty() {
+            return Ok(());
+        }
+
+        let mut roles: Vec<String> = self
+            .roles
+            .iter()
+            .filter(|id| *id != role_id && !siblings.contains(id))
+            .cloned()
+            .collect();
+
+        roles.push(role_id.to_string());
+
+        self.update(
+            db,
+            PartialMember {
+                roles: Some(roles),
+                ..Default::default()
+            },
+            vec![],
+        )
+        .await
+    }
+
+    /// Remove a self-assignable role from this member
+    pub async fn remove_self_role(
+        &mut self,
+        db: &Database,
+        server: &Server,
+        role_id: &str,
+    ) -> Result<()> {
+        // Members held by screening are restricted to viewing the server
+        if self.pending {
+            return Err(create_error!(MissingPermission {
+                permission: ChannelPermission::AssignRoles.to_string()
+            }));
+        }
+
+        let role = server
+            .roles
+            .get(role_id)
+            .ok_or_else(|| create_error!(NotFound))?;
+
+        if !role.self_assignable {
+            return Err(create_error!(MissingPermission {
+                permission: ChannelPermission::AssignRoles.to_string()
+            }));
+        }
+
+        if !self.roles.iter().any(|id| id == role_id) {
+            return Ok(());
+        }
+
+        self.update(
+            db,
+            PartialMember {
+                roles: Some(
+                    self.roles
+                        .iter()
+                        .filter(|id| *id != role_id)
+                        .cloned()
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+            vec![],
+        )
+        .await
+    }
+
+    /// Send the join system message, if the server has one configured
+    async fn announce_join(&self, db: &Database, server: &Server) {
         if let Some(id) = server
             .system_messages
             .as_ref()
             .and_then(|x| x.user_joined.as_ref())
         {
             SystemMessage::UserJoined {
-                id: user.id.clone(),
+                id: self.id.user.clone(),
             }
             .into_message(id.to_string())
             .send_without_notifications(db, None, None, false, false, false)
             .await
             .ok();
         }
-
-        Ok((member, channels))
     }
 
     /// Update member data
@@ -234,6 +377,28 @@ impl Member {
     ) -> Result<()> {
         db.soft_delete_member(&self.id).await?;
 
+        // Drop this member's channel overrides so they are not granted again on rejoin
+        for mut channel in db
+            .fetch_channels(&server.channels)
+            .await
+            .unwrap_or_default()
+        {
+            if let Channel::TextChannel {
+                member_permissions, ..
+            }
+            | Channel::VoiceChannel {
+                member_permissions, ..
+            } = &channel
+            {
+                if member_permissions.contains_key(&self.id.user) {
+                    channel
+                        .remove_member_permission(db, &self.id.user)
+                        .await
+                        .ok();
+                }
+            }
+        }
+
         EventV1::ServerMemberLeave {
             id: self.id.server.to_string(),
             user: self.id.user.to_string(),
@@ -36,6 +36,7 @@ impl AbstractServerMembers for MongoDb {
                     doc! {
                         "$set": {
                             "joined_at": member.joined_at.duration_since(Timestamp::UNIX_EPOCH).whole_seconds(),
+                            "pending": member.pending,
                         },
                         "$unset": {
                             "pending_deletion_at": ""
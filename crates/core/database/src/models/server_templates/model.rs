@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+
+use revolt_config::config;
+use revolt_models::v0;
+use revolt_permissions::OverrideField;
+use revolt_result::{Error, ErrorType, Result};
+use ulid::Ulid;
+
+use crate::{
+    Category, Channel, Database, Emoji, EmojiParent, PartialChannel, PartialServer, Role, Server,
+    SystemMessageChannels, User,
+};
+
+static ALPHABET: [char; 54] = [
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J',
+    'K', 'M', 'N', 'P', 'Q', 'R', 'S', 'T', 'V', 'W', 'X', 'Y', 'Z', 'a', 'b', 'c', 'd', 'e', 'f',
+    'g', 'h', 'j', 'k', 'm', 'n', 'p', 'q', 'r', 's', 't', 'v', 'w', 'x', 'y', 'z',
+];
+
+auto_derived!(
+    /// Server template
+    pub struct ServerTemplate {
+        /// Template code
+        #[serde(rename = "_id")]
+        pub code: String,
+        /// Id of the server this template was created from
+        pub source_server_id: String,
+        /// Id of user who created this template
+        pub creator_id: String,
+        /// Name of the template
+        pub name: String,
+        /// Description of the template
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub description: Option<String>,
+        /// Captured structure of the server
+        pub layout: ServerLayout,
+    }
+
+    /// Structure of a server without any members or messages
+    ///
+    /// Roles, categories and channels are keyed by their ids in the source server,
+    /// new ids are generated when the layout is used to create a server.
+    pub struct ServerLayout {
+        /// Default set of server and channel permissions
+        pub default_permissions: i64,
+        /// Whether the server is flagged as not safe for work
+        #[serde(skip_serializing_if = "crate::if_false", default)]
+        pub nsfw: bool,
+        /// Roles for the server
+        #[serde(
+            default = "HashMap::<String, Role>::new",
+            skip_serializing_if = "HashMap::<String, Role>::is_empty"
+        )]
+        pub roles: HashMap<String, Role>,
+        /// Categories for the server
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub categories: Vec<Category>,
+        /// Channels within the server
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub channels: Vec<TemplateChannel>,
+        /// Configuration for sending system event messages
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub system_messages: Option<SystemMessageChannels>,
+        /// Emojis for the server
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub emojis: Vec<TemplateEmoji>,
+    }
+
+    /// Channel captured in a server template
+    pub struct TemplateChannel {
+        /// Id of the channel in the source server
+        pub id: String,
+        /// Type of channel
+        #[serde(rename = "type")]
+        pub channel_type: TemplateChannelType,
+        /// Display name of the channel
+        pub name: String,
+        /// Channel description
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub description: Option<String>,
+        /// Whether this channel is marked as not safe for work
+        #[serde(skip_serializing_if = "crate::if_false", default)]
+        pub nsfw: bool,
+        /// Default permissions assigned to users in this channel
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub default_permissions: Option<OverrideField>,
+        /// Permissions assigned based on role to this channel
+        #[serde(
+            default = "HashMap::<String, OverrideField>::new",
+            skip_serializing_if = "HashMap::<String, OverrideField>::is_empty"
+        )]
+        pub role_permissions: HashMap<String, OverrideField>,
+        /// Whether this channel's permissions are synced with its category
+        #[serde(skip_serializing_if = "crate::if_false", default)]
+        pub permissions_synced: bool,
+    }
+
+    /// Type of channel captured in a server template
+    pub enum TemplateChannelType {
+        Text,
+        Voice,
+    }
+
+    /// Emoji captured in a server template
+    pub struct TemplateEmoji {
+        /// Id of the emoji in the source server
+        pub id: String,
+        /// Emoji name
+        pub name: String,
+        /// Whether the emoji is animated
+        #[serde(skip_serializing_if = "crate::if_false", default)]
+        pub animated: bool,
+        /// Whether the emoji is marked as nsfw
+        #[serde(skip_serializing_if = "crate::if_false", default)]
+        pub nsfw: bool,
+    }
+);
+
+impl ServerTemplate {
+    /// Create a new template from the current structure of a server
+    pub async fn create(
+        db: &Database,
+        server: &Server,
+        creator: &User,
+        data: v0::DataCreateServerTemplate,
+    ) -> Result<ServerTemplate> {
+        // Check that we haven't hit the template limit
+        let max = config().await.features.limits.global.server_templates;
+        if db.fetch_server_templates_by_source(&server.id).await?.len() >= max {
+            return Err(create_error!(TooManyTemplates { max }));
+        }
+
+        let channels = db
+            .fetch_channels(&server.channels)
+            .await?
+            .into_iter()
+            .filter_map(|channel| match channel {
+                Channel::TextChannel {
+                    id,
+                    name,
+                    description,
+                    nsfw,
+                    default_permissions,
+                    role_permissions,
+                    permissions_synced,
+                    ..
+                } => Some(TemplateChannel {
+                    id,
+                    channel_type: TemplateChannelType::Text,
+                    name,
+                    description,
+                    nsfw,
+                    default_permissions,
+                    role_permissions,
+                    permissions_synced,
+                }),
+                Channel::VoiceChannel {
+                    id,
+                    name,
+                    description,
+                    nsfw,
+                    default_permissions,
+                    role_permissions,
+                    permissions_synced,
+                    ..
+                } => Some(TemplateChannel {
+                    id,
+                    channel_type: TemplateChannelType::Voice,
+                    name,
+                    description,
+                    nsfw,
+                    default_permissions,
+                    role_permissions,
+                    permissions_synced,
+                }),
+                _ => None,
+            })
+            .collect();
+
+        let emojis = if data.include_emojis.unwrap_or_default() {
+            db.fetch_emoji_by_parent_id(&server.id)
+                .await?
+                .into_iter()
+                .map(|emoji| TemplateEmoji {
+                    id: emoji.id,
+                    name: emoji.name,
+                    animated: emoji.animated,
+                    nsfw: emoji.nsfw,
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        let template = ServerTemplate {
+            code: nanoid::nanoid!(8, &ALPHABET),
+            source_server_id: server.id.clone(),
+            creator_id: creator.id.clone(),
+            name: data.name,
+            description: data.description,
+            layout: ServerLayout {
+                default_permissions: server.default_permissions,
+                nsfw: server.nsfw,
+                roles: server.roles.clone(),
+                categories: server.categories.clone().unwrap_or_default(),
+                channels,
+                system_messages: server.system_messages.clone(),
+                emojis,
+            },
+        };
+
+        db.insert_server_template(&template).await?;
+        Ok(template)
+    }
+
+    /// Delete this template
+    pub async fn delete(self, db: &Database) -> Result<()> {
+        db.delete_server_template(&self.code).await
+    }
+
+    /// Create a new server using the layout of this template
+    pub async fn create_server(
+        &self,
+        db: &Database,
+        data: v0::DataCreateServer,
+        owner: &User,
+    ) -> Result<(Server, Vec<Channel>)> {
+        let layout = &self.layout;
+        let (mut server, _) = Server::create(
+            db,
+            v0::DataCreateServer {
+                nsfw: Some(data.nsfw.unwrap_or(layout.nsfw)),
+                ..data
+            },
+            owner,
+            false,
+        )
+        .await?;
+
+        // Generate new ids for every role and channel
+        let role_ids: HashMap<&str, String> = layout
+            .roles
+            .keys()
+            .map(|id| (id.as_str(), Ulid::new().to_string()))
+            .collect();
+
+        let map_roles = |permissions: &HashMap<String, OverrideField>| {
+            permissions
+                .iter()
+                .filter_map(|(id, value)| {
+                    role_ids
+                        .get(id.as_str())
+                        .map(|new_id| (new_id.clone(), *value))
+                })
+                .collect::<HashMap<String, OverrideField>>()
+        };
+
+        let mut channel_ids: HashMap<&str, String> = HashMap::new();
+        let mut channels = vec![];
+        for template_channel in &layout.channels {
+            let mut channel = Channel::create_server_channel(
+                db,
+                &mut server,
+                v0::DataCreateServerChannel {
+                    channel_type: match template_channel.channel_type {
+                        TemplateChannelType::Text => v0::LegacyServerChannelType::Text,
+                        TemplateChannelType::Voice => v0::LegacyServerChannelType::Voice,
+                    },
+                    name: template_channel.name.clone(),
+                    description: template_channel.description.clone(),
+                    nsfw: Some(template_channel.nsfw),
+                },
+                false,
+            )
+            .await?;
+
+            if template_channel.default_permissions.is_some()
+                || !template_channel.role_permissions.is_empty()
+                || template_channel.permissions_synced
+            {
+                channel
+                    .update(
+                        db,
+                        PartialChannel {
+                            default_permissions: template_channel.default_permissions,
+                            role_permissions: Some(map_roles(&template_channel.role_permissions)),
+                            permissions_synced: Some(template_channel.permissions_synced),
+                            ..Default::default()
+                        },
+                        vec![],
+                    )
+                    .await?;
+            }
+
+            channel_ids.insert(&template_channel.id, channel.id().to_string());
+            server.channels.push(channel.id().to_string());
+            channels.push(channel);
+        }
+
+        let map_channel = |id: &Option<String>| {
+            id.as_ref()
+                .and_then(|id| channel_ids.get(id.as_str()).cloned())
+        };
+
+        let categories = layout
+            .categories
+            .iter()
+            .map(|category| Category {
+                id: Ulid::new().to_string(),
+                title: category.title.clone(),
+                channels: category
+                    .channels
+                    .iter()
+                    .filter_map(|id| channel_ids.get(id.as_str()).cloned())
+                    .collect(),
+                default_permissions: category.default_permissions,
+                role_permissions: map_roles(&category.role_permissions),
+            })
+            .collect::<Vec<Category>>();
+
+        let system_messages =
+            layout
+                .system_messages
+                .as_ref()
+                .map(|system_messages| SystemMessageChannels {
+                    user_joined: map_channel(&system_messages.user_joined),
+                    user_left: map_channel(&system_messages.user_left),
+                    user_kicked: map_channel(&system_messages.user_kicked),
+                    user_banned: map_channel(&system_messages.user_banned),
+                });
+
+        let roles = layout
+            .roles
+            .iter()
+            .map(|(id, role)| (role_ids[id.as_str()].clone(), role.clone()))
+            .collect::<HashMap<String, Role>>();
+
+        server
+            .update(
+                db,
+                PartialServer {
+                    channels: Some(server.channels.clone()),
+                    categories: if categories.is_empty() {
+                        None
+                    } else {
+                        Some(categories)
+                    },
+                    system_messages,
+                    roles: Some(roles),
+                    default_permissions: Some(layout.default_permissions),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await?;
+
+        let limits = owner.limits().await;
+        for template_emoji in &layout.emojis {
+            // Emoji files may have been deleted or reported since the template was created
+            let Ok(file) = db.fetch_attachment("emojis", &template_emoji.id).await else {
+                continue;
+            };
+
+            if file.deleted == Some(true) || file.reported == Some(true) {
+                continue;
+            }
+
+            // The copy is charged to the new owner like any other upload,
+            // emojis which don't fit in their storage are left out
+            let id = Ulid::new().to_string();
+            match file
+                .duplicate_for_emoji(db, &id, &server.id, &owner.id, limits.storage_quota)
+                .await
+            {
+                Ok(_) => {}
+                Err(Error {
+                    error_type: ErrorType::StorageQuotaExceeded { .. },
+                    ..
+                }) => continue,
+                Err(err) => return Err(err),
+            }
+
+            Emoji {
+                id,
+                parent: EmojiParent::Server {
+                    id: server.id.clone(),
+                },
+                creator_id: owner.id.clone(),
+                name: template_emoji.name.clone(),
+                animated: template_emoji.animated,
+                nsfw: template_emoji.nsfw,
+            }
+            .create(db)
+            .await?;
+        }
+
+        Ok((server, channels))
+    }
+}
@@ -0,0 +1,59 @@
+use revolt_result::Result;
+
+use crate::ReferenceDb;
+use crate::ServerTemplate;
+
+use super::AbstractServerTemplates;
+
+#[async_trait]
+impl AbstractServerTemplates for ReferenceDb {
+    /// Insert a new server template into the database
+    async fn insert_server_template(&self, template: &ServerTemplate) -> Result<()> {
+        let mut server_templates = self.server_templates.lock().await;
+        if server_templates.contains_key(&template.code) {
+            Err(create_database_error!("insert", "server_template"))
+        } else {
+            server_templates.insert(template.code.to_string(), template.clone());
+            Ok(())
+        }
+    }
+
+    /// Fetch a server template by its code
+    async fn fetch_server_template(&self, code: &str) -> Result<ServerTemplate> {
+        let server_templates = self.server_templates.lock().await;
+        server_templates
+            .get(code)
+            .cloned()
+            .ok_or_else(|| create_error!(NotFound))
+    }
+
+    /// Fetch all templates created from a server
+    async fn fetch_server_templates_by_source(
+        &self,
+        server_id: &str,
+    ) -> Result<Vec<ServerTemplate>> {
+        let server_templates = self.server_templates.lock().await;
+        Ok(server_templates
+            .values()
+            .filter(|template| template.source_server_id == server_id)
+            .cloned()
+            .collect())
+    }
+
+    /// Delete a server template by its code
+    async fn delete_server_template(&self, code: &str) -> Result<()> {
+        let mut server_templates = self.server_templates.lock().await;
+        if server_templates.remove(code).is_some() {
+            Ok(())
+        } else {
+            Err(create_error!(NotFound))
+        }
+    }
+
+    /// Delete all templates created from a server
+    async fn delete_server_templates_by_source(&self, server_id: &str) -> Result<()> {
+        let mut server_templates = self.server_templates.lock().await;
+        server_templates.retain(|_, template| template.source_server_id != server_id);
+        Ok(())
+    }
+}
@@ -5,7 +5,7 @@ use revolt_permissions::{OverrideField, DEFAULT_PERMISSION_SERVER};
 use revolt_result::Result;
 use ulid::Ulid;
 
-use crate::{events::client::EventV1, Channel, Database, File, User};
+use crate::{events::client::EventV1, AutoModConfig, Channel, Database, File, User};
 
 auto_derived_partial!(
     /// Server
@@ -31,6 +31,9 @@ auto_derived_partial!(
         /// Configuration for sending system event messages
         #[serde(skip_serializing_if = "Option::is_none")]
         pub system_messages: Option<SystemMessageChannels>,
+        /// Screening new members must pass before participating
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub screening: Option<MemberScreening>,
 
         /// Roles for this server
         #[serde(
@@ -83,6 +86,12 @@ auto_derived_partial!(
         /// Ranking of this role
         #[serde(default)]
         pub rank: i64,
+        /// Whether members may assign this role to themselves
+        #[serde(skip_serializing_if = "crate::if_false", default)]
+        pub self_assignable: bool,
+        /// Exclusive group of self-assignable roles, members may only hold one role per group
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub group: Option<String>,
     },
     "PartialRole"
 );
@@ -96,6 +105,16 @@ auto_derived!(
         pub title: String,
         /// Channels in this category
         pub channels: Vec<String>,
+
+        /// Default permissions assigned to users in this category
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub default_permissions: Option<OverrideField>,
+        /// Permissions assigned based on role to this category
+        #[serde(
+            default = "HashMap::<String, OverrideField>::new",
+            skip_serializing_if = "HashMap::<String, OverrideField>::is_empty"
+        )]
+        pub role_permissions: HashMap<String, OverrideField>,
     }
 
     /// System message channel assignments
@@ -114,6 +133,15 @@ auto_derived!(
         pub user_banned: Option<String>,
     }
 
+    /// Rules and questions new members must accept
+    pub struct MemberScreening {
+        /// Rules for this server
+        pub rules: String,
+        /// Statements members must agree to
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub questions: Vec<String>,
+    }
+
     /// Optional fields on server object
     pub enum FieldsServer {
         Description,
@@ -121,11 +149,13 @@ auto_derived!(
         SystemMessages,
         Icon,
         Banner,
+        Screening,
     }
 
     /// Optional fields on server object
     pub enum FieldsRole {
         Colour,
+        Group,
     }
 );
 
@@ -155,6 +185,7 @@ impl Server {
             icon: None,
             roles: HashMap::new(),
             system_messages: None,
+            screening: None,
         };
 
         let channels: Vec<Channel> = if create_default_channels {
@@ -206,6 +237,23 @@ impl Server {
         Ok(())
     }
 
+    /// Set whether this server is publicly discoverable
+    pub async fn set_discoverable(&mut self, db: &Database, discoverable: bool) -> Result<()> {
+        if self.discoverable == discoverable {
+            return Ok(());
+        }
+
+        self.update(
+            db,
+            PartialServer {
+                discoverable: Some(discoverable),
+                ..Default::default()
+            },
+            vec![],
+        )
+        .await
+    }
+
     /// Delete a server
     pub async fn delete(self, db: &Database) -> Result<()> {
         EventV1::ServerDelete {
@@ -214,6 +262,11 @@ impl Server {
         .p(self.id.clone())
         .await;
 
+        // Remove the server from the discovery directory, if it was listed
+        db.delete_discovery_listing(&self.id).await.ok();
+        db.delete_automod_config(&self.id).await.ok();
+        AutoModConfig::invalidate_cache(&self.id).await;
+        db.delete_server_templates_by_source(&self.id).await.ok();
         db.delete_server(&self.id).await
     }
 
@@ -225,6 +278,7 @@ impl Server {
             FieldsServer::SystemMessages => self.system_messages = None,
             FieldsServer::Icon => self.icon = None,
             FieldsServer::Banner => self.banner = None,
+            FieldsServer::Screening => self.screening = None,
         }
     }
 
@@ -235,6 +289,85 @@ impl Server {
         ordered_roles
     }
 
+    /// Find the category a channel belongs to
+    pub fn category_of(&self, channel_id: &str) -> Option<&Category> {
+        self.categories.as_ref().and_then(|categories| {
+            categories
+                .iter()
+                .find(|category| category.channels.iter().any(|id| id == channel_id))
+        })
+    }
+
+    /// Set default permissions on a category
+    pub async fn set_category_default_permissions(
+        &mut self,
+        db: &Database,
+        category_id: &str,
+        permissions: OverrideField,
+    ) -> Result<()> {
+        self.update_category(db, category_id, |category| {
+            category.default_permissions = Some(permissions);
+        })
+        .await
+    }
+
+    /// Set role permission on a category
+    pub async fn set_category_role_permission(
+        &mut self,
+        db: &Database,
+        category_id: &str,
+        role_id: &str,
+        permissions: OverrideField,
+    ) -> Result<()> {
+        self.update_category(db, category_id, |category| {
+            category
+                .role_permissions
+                .insert(role_id.to_string(), permissions);
+        })
+        .await
+    }
+
+    /// Apply a change to a category and propagate it to channels synced to it
+    async fn update_category<F>(&mut self, db: &Database, category_id: &str, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut Category),
+    {
+        let mut categories = self.categories.clone().unwrap_or_default();
+        let category = categories
+            .iter_mut()
+            .find(|category| category.id == category_id)
+            .ok_or_else(|| create_error!(NotFound))?;
+
+        f(category);
+        let category = category.clone();
+
+        self.update(
+            db,
+            PartialServer {
+                categories: Some(categories),
+                ..Default::default()
+            },
+            vec![],
+        )
+        .await?;
+
+        for mut channel in db.fetch_channels(&category.channels).await? {
+            if let Channel::TextChannel {
+                permissions_synced: true,
+                ..
+            }
+            | Channel::VoiceChannel {
+                permissions_synced: true,
+                ..
+            } = channel
+            {
+                channel.sync_permissions(db, &category).await?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Set role permission on a server
     pub async fn set_role_permission(
         &mut self,
@@ -302,6 +435,8 @@ impl Role {
             colour: self.colour,
             hoist: Some(self.hoist),
             rank: Some(self.rank),
+            self_assignable: Some(self.self_assignable),
+            group: self.group,
         }
     }
 
@@ -356,6 +491,7 @@ impl Role {
     pub fn remove_field(&mut self, field: &FieldsRole) {
         match field {
             FieldsRole::Colour => self.colour = None,
+            FieldsRole::Group => self.group = None,
         }
     }
 
@@ -368,6 +504,7 @@ impl Role {
         .p(server_id.to_string())
         .await;
 
+        db.delete_reaction_roles_by_role(server_id, role_id).await?;
         db.delete_role(server_id, role_id).await
     }
 }
@@ -160,6 +160,7 @@ impl IntoDocumentPath for FieldsServer {
     fn as_path(&self) -> Option<&'static str> {
         Some(match self {
             FieldsServer::Banner => "banner",
+            FieldsServer::Screening => "screening",
             FieldsServer::Categories => "categories",
             FieldsServer::Description => "description",
             FieldsServer::Icon => "icon",
@@ -172,6 +173,7 @@ impl IntoDocumentPath for FieldsRole {
     fn as_path(&self) -> Option<&'static str> {
         Some(match self {
             FieldsRole::Colour => "colour",
+            FieldsRole::Group => "group",
         })
     }
 }
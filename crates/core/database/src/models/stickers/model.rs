@@ -0,0 +1,130 @@
+use revolt_result::Result;
+
+use crate::events::client::EventV1;
+use crate::{Database, StorageUsage};
+
+auto_derived_partial!(
+    /// Sticker
+    pub struct Sticker {
+        /// Unique Id
+        #[serde(rename = "_id")]
+        pub id: String,
+        /// What owns this sticker
+        pub parent: StickerParent,
+        /// Uploader user id
+        pub creator_id: String,
+        /// Sticker name
+        pub name: String,
+        /// Sticker description
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub description: Option<String>,
+        /// Emoji this sticker is related to
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub emoji: Option<String>,
+        /// Whether the sticker is animated
+        #[serde(skip_serializing_if = "crate::if_false", default)]
+        pub animated: bool,
+        /// Whether the sticker is marked as nsfw
+        #[serde(skip_serializing_if = "crate::if_false", default)]
+        pub nsfw: bool,
+    },
+    "PartialSticker"
+);
+
+auto_derived!(
+    /// Parent Id of the sticker
+    #[serde(tag = "type")]
+    pub enum StickerParent {
+        Server { id: String },
+        Detached,
+    }
+
+    /// Optional fields on sticker object
+    pub enum FieldsSticker {
+        Description,
+        Emoji,
+    }
+);
+
+#[allow(clippy::disallowed_methods)]
+impl Sticker {
+    /// Get parent id
+    fn parent(&self) -> &str {
+        match &self.parent {
+            StickerParent::Server { id } => id,
+            StickerParent::Detached => "",
+        }
+    }
+
+    /// Create a sticker
+    pub async fn create(&self, db: &Database) -> Result<()> {
+        db.insert_sticker(self).await?;
+
+        // Account for the sticker's file against the server
+        if let StickerParent::Server { id } = &self.parent {
+            if let Ok(file) = db.fetch_attachment("stickers", &self.id).await {
+                StorageUsage::charge(db, id, &file).await?;
+            }
+        }
+
+        EventV1::StickerCreate(self.clone().into())
+            .p(self.parent().to_string())
+            .await;
+
+        Ok(())
+    }
+
+    /// Update sticker data
+    pub async fn update(
+        &mut self,
+        db: &Database,
+        partial: PartialSticker,
+        remove: Vec<FieldsSticker>,
+    ) -> Result<()> {
+        for field in &remove {
+            self.remove_field(field);
+        }
+
+        self.apply_options(partial.clone());
+
+        db.update_sticker(&self.id, &partial, &remove).await?;
+
+        EventV1::StickerUpdate {
+            id: self.id.clone(),
+            data: partial.into(),
+            clear: remove.into_iter().map(|field| field.into()).collect(),
+        }
+        .p(self.parent().to_string())
+        .await;
+
+        Ok(())
+    }
+
+    /// Remove a field from this object
+    pub fn remove_field(&mut self, field: &FieldsSticker) {
+        match field {
+            FieldsSticker::Description => self.description = None,
+            FieldsSticker::Emoji => self.emoji = None,
+        }
+    }
+
+    /// Delete a sticker
+    ///
+    /// Stickers are detached rather than removed so that existing messages can still display them.
+    pub async fn delete(self, db: &Database) -> Result<()> {
+        EventV1::StickerDelete {
+            id: self.id.to_string(),
+        }
+        .p(self.parent().to_string())
+        .await;
+
+        // Release the sticker's file from the server's storage usage
+        if let StickerParent::Server { id } = &self.parent {
+            if let Ok(file) = db.fetch_attachment("stickers", &self.id).await {
+                StorageUsage::release(db, id, &file).await?;
+            }
+        }
+
+        db.detach_sticker(&self).await
+    }
+}
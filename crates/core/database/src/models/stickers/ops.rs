@@ -0,0 +1,33 @@
+use revolt_result::Result;
+
+use crate::{FieldsSticker, PartialSticker, Sticker};
+
+#[cfg(feature = "mongodb")]
+mod mongodb;
+mod reference;
+
+#[async_trait]
+pub trait AbstractStickers: Sync + Send {
+    /// Insert sticker into database.
+    async fn insert_sticker(&self, sticker: &Sticker) -> Result<()>;
+
+    /// Fetch a sticker by its id
+    async fn fetch_sticker(&self, id: &str) -> Result<Sticker>;
+
+    /// Fetch stickers by their parent id
+    async fn fetch_stickers_by_parent_id(&self, parent_id: &str) -> Result<Vec<Sticker>>;
+
+    /// Fetch stickers by their parent ids
+    async fn fetch_stickers_by_parent_ids(&self, parent_ids: &[String]) -> Result<Vec<Sticker>>;
+
+    /// Update sticker with new information
+    async fn update_sticker(
+        &self,
+        id: &str,
+        partial: &PartialSticker,
+        remove: &[FieldsSticker],
+    ) -> Result<()>;
+
+    /// Detach a sticker by its id
+    async fn detach_sticker(&self, sticker: &Sticker) -> Result<()>;
+}
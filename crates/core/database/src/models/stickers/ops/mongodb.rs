@@ -0,0 +1,96 @@
+use bson::Document;
+use revolt_result::Result;
+
+use crate::{FieldsSticker, PartialSticker, Sticker};
+use crate::{IntoDocumentPath, MongoDb};
+
+use super::AbstractStickers;
+
+static COL: &str = "stickers";
+
+#[async_trait]
+impl AbstractStickers for MongoDb {
+    /// Insert sticker into database.
+    async fn insert_sticker(&self, sticker: &Sticker) -> Result<()> {
+        query!(self, insert_one, COL, &sticker).map(|_| ())
+    }
+
+    /// Fetch a sticker by its id
+    async fn fetch_sticker(&self, id: &str) -> Result<Sticker> {
+        query!(self, find_one_by_id, COL, id)?.ok_or_else(|| create_error!(NotFound))
+    }
+
+    /// Fetch stickers by their parent id
+    async fn fetch_stickers_by_parent_id(&self, parent_id: &str) -> Result<Vec<Sticker>> {
+        query!(
+            self,
+            find,
+            COL,
+            doc! {
+                "parent.id": parent_id
+            }
+        )
+    }
+
+    /// Fetch stickers by their parent ids
+    async fn fetch_stickers_by_parent_ids(&self, parent_ids: &[String]) -> Result<Vec<Sticker>> {
+        query!(
+            self,
+            find,
+            COL,
+            doc! {
+                "parent.id": {
+                    "$in": parent_ids
+                }
+            }
+        )
+    }
+
+    /// Update sticker with new information
+    async fn update_sticker(
+        &self,
+        id: &str,
+        partial: &PartialSticker,
+        remove: &[FieldsSticker],
+    ) -> Result<()> {
+        query!(
+            self,
+            update_one_by_id,
+            COL,
+            id,
+            partial,
+            remove.iter().map(|x| x as &dyn IntoDocumentPath).collect(),
+            None
+        )
+        .map(|_| ())
+    }
+
+    /// Detach a sticker by its id
+    async fn detach_sticker(&self, sticker: &Sticker) -> Result<()> {
+        self.col::<Document>(COL)
+            .update_one(
+                doc! {
+                    "_id": &sticker.id
+                },
+                doc! {
+                    "$set": {
+                        "parent": {
+                            "type": "Detached"
+                        }
+                    }
+                },
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| create_database_error!("update_one", COL))
+    }
+}
+
+impl IntoDocumentPath for FieldsSticker {
+    fn as_path(&self) -> Option<&'static str> {
+        Some(match self {
+            FieldsSticker::Description => "description",
+            FieldsSticker::Emoji => "emoji",
+        })
+    }
+}
@@ -0,0 +1,87 @@
+use revolt_result::Result;
+
+use crate::ReferenceDb;
+use crate::{FieldsSticker, PartialSticker, Sticker, StickerParent};
+
+use super::AbstractStickers;
+
+#[async_trait]
+impl AbstractStickers for ReferenceDb {
+    /// Insert sticker into database.
+    async fn insert_sticker(&self, sticker: &Sticker) -> Result<()> {
+        let mut stickers = self.stickers.lock().await;
+        if stickers.contains_key(&sticker.id) {
+            Err(create_database_error!("insert", "sticker"))
+        } else {
+            stickers.insert(sticker.id.to_string(), sticker.clone());
+            Ok(())
+        }
+    }
+
+    /// Fetch a sticker by its id
+    async fn fetch_sticker(&self, id: &str) -> Result<Sticker> {
+        let stickers = self.stickers.lock().await;
+        stickers
+            .get(id)
+            .cloned()
+            .ok_or_else(|| create_error!(NotFound))
+    }
+
+    /// Fetch stickers by their parent id
+    async fn fetch_stickers_by_parent_id(&self, parent_id: &str) -> Result<Vec<Sticker>> {
+        let stickers = self.stickers.lock().await;
+        Ok(stickers
+            .values()
+            .filter(|sticker| match &sticker.parent {
+                StickerParent::Server { id } => id == parent_id,
+                _ => false,
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Fetch stickers by their parent ids
+    async fn fetch_stickers_by_parent_ids(&self, parent_ids: &[String]) -> Result<Vec<Sticker>> {
+        let stickers = self.stickers.lock().await;
+        Ok(stickers
+            .values()
+            .filter(|sticker| match &sticker.parent {
+                StickerParent::Server { id } => parent_ids.contains(id),
+                _ => false,
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Update sticker with new information
+    async fn update_sticker(
+        &self,
+        id: &str,
+        partial: &PartialSticker,
+        remove: &[FieldsSticker],
+    ) -> Result<()> {
+        let mut stickers = self.stickers.lock().await;
+        if let Some(sticker) = stickers.get_mut(id) {
+            for field in remove {
+                #[allow(clippy::disallowed_methods)]
+                sticker.remove_field(field);
+            }
+
+            sticker.apply_options(partial.clone());
+            Ok(())
+        } else {
+            Err(create_error!(NotFound))
+        }
+    }
+
+    /// Detach a sticker by its id
+    async fn detach_sticker(&self, sticker: &Sticker) -> Result<()> {
+        let mut stickers = self.stickers.lock().await;
+        if let Some(sticker) = stickers.get_mut(&sticker.id) {
+            sticker.parent = StickerParent::Detached;
+            Ok(())
+        } else {
+            Err(create_error!(NotFound))
+        }
+    }
+}
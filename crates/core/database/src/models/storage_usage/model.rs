@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+use revolt_result::{create_error, Result};
+
+use crate::{Database, File};
+
+auto_derived!(
+    /// Storage used by files belonging to a user or server
+    #[derive(Default)]
+    pub struct StorageUsage {
+        /// Id of the user or server
+        #[serde(rename = "_id")]
+        pub id: String,
+        /// Total number of bytes used
+        pub total: i64,
+        /// Number of bytes used per tag
+        #[serde(default)]
+        pub tags: HashMap<String, i64>,
+    }
+);
+
+impl StorageUsage {
+    /// Account for a file being stored on behalf of a user or server
+    pub async fn charge(db: &Database, owner_id: &str, file: &File) -> Result<()> {
+        db.increment_storage_usage(owner_id, &file.tag, file.size as i64)
+            .await
+    }
+
+    /// Account for a file being stored on behalf of a user or server, failing if it would exceed their quota
+    pub async fn charge_within_quota(
+        db: &Database,
+        owner_id: &str,
+        file: &File,
+        quota: usize,
+    ) -> Result<()> {
+        if db
+            .increment_storage_usage_within_quota(
+                owner_id,
+                &file.tag,
+                file.size as i64,
+                quota.try_into().unwrap_or(i64::MAX),
+            )
+            .await?
+        {
+            Ok(())
+        } else {
+            Err(create_error!(StorageQuotaExceeded { max: quota }))
+        }
+    }
+
+    /// Account for a file belonging to a user or server being removed
+    pub async fn release(db: &Database, owner_id: &str, file: &File) -> Result<()> {
+        db.increment_storage_usage(owner_id, &file.tag, -(file.size as i64))
+            .await
+    }
+}
@@ -0,0 +1,27 @@
+use revolt_result::Result;
+
+use crate::StorageUsage;
+
+#[cfg(feature = "mongodb")]
+mod mongodb;
+mod reference;
+
+#[async_trait]
+pub trait AbstractStorageUsage: Sync + Send {
+    /// Fetch storage usage of a user or server
+    async fn fetch_storage_usage(&self, id: &str) -> Result<StorageUsage>;
+
+    /// Adjust storage usage of a user or server for a given tag
+    async fn increment_storage_usage(&self, id: &str, tag: &str, bytes: i64) -> Result<()>;
+
+    /// Adjust storage usage of a user or server for a given tag if the total stays within the quota
+    ///
+    /// Returns whether the usage was adjusted
+    async fn increment_storage_usage_within_quota(
+        &self,
+        id: &str,
+        tag: &str,
+        bytes: i64,
+        quota: i64,
+    ) -> Result<bool>;
+}
@@ -0,0 +1,99 @@
+use bson::Document;
+use mongodb::options::UpdateOptions;
+use revolt_result::Result;
+
+use crate::MongoDb;
+use crate::StorageUsage;
+
+use super::AbstractStorageUsage;
+
+static COL: &str = "storage_usage";
+
+#[async_trait]
+impl AbstractStorageUsage for MongoDb {
+    /// Fetch storage usage of a user or server
+    async fn fetch_storage_usage(&self, id: &str) -> Result<StorageUsage> {
+        Ok(query!(
+            self,
+            find_one,
+            COL,
+            doc! {
+                "_id": id
+            }
+        )?
+        .unwrap_or_else(|| StorageUsage {
+            id: id.to_string(),
+            ..Default::default()
+        }))
+    }
+
+    /// Adjust storage usage of a user or server for a given tag
+    async fn increment_storage_usage(&self, id: &str, tag: &str, bytes: i64) -> Result<()> {
+        self.col::<Document>(COL)
+            .update_one(
+                doc! {
+                    "_id": id
+                },
+                doc! {
+                    "$inc": {
+                        "total": bytes,
+                        format!("tags.{tag}"): bytes
+                    }
+                },
+            )
+            .with_options(UpdateOptions::builder().upsert(true).build())
+            .await
+            .map(|_| ())
+            .map_err(|_| create_database_error!("update_one", COL))
+    }
+
+    /// Adjust storage usage of a user or server for a given tag if the total stays within the quota
+    ///
+    /// Returns whether the usage was adjusted
+    async fn increment_storage_usage_within_quota(
+        &self,
+        id: &str,
+        tag: &str,
+        bytes: i64,
+        quota: i64,
+    ) -> Result<bool> {
+        if bytes > quota {
+            return Ok(false);
+        }
+
+        // Make sure there is a document for the conditional update to match
+        self.col::<Document>(COL)
+            .update_one(
+                doc! {
+                    "_id": id
+                },
+                doc! {
+                    "$setOnInsert": {
+                        "total": 0_i64
+                    }
+                },
+            )
+            .with_options(UpdateOptions::builder().upsert(true).build())
+            .await
+            .map_err(|_| create_database_error!("update_one", COL))?;
+
+        self.col::<Document>(COL)
+            .update_one(
+                doc! {
+                    "_id": id,
+                    "total": {
+                        "$lte": quota - bytes
+                    }
+                },
+                doc! {
+                    "$inc": {
+                        "total": bytes,
+                        format!("tags.{tag}"): bytes
+                    }
+                },
+            )
+            .await
+            .map(|result| result.matched_count > 0)
+            .map_err(|_| create_database_error!("update_one", COL))
+    }
+}
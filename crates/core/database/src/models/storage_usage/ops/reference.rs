@@ -0,0 +1,60 @@
+use revolt_result::Result;
+
+use crate::ReferenceDb;
+use crate::StorageUsage;
+
+use super::AbstractStorageUsage;
+
+#[async_trait]
+impl AbstractStorageUsage for ReferenceDb {
+    /// Fetch storage usage of a user or server
+    async fn fetch_storage_usage(&self, id: &str) -> Result<StorageUsage> {
+        let storage_usage = self.storage_usage.lock().await;
+        Ok(storage_usage.get(id).cloned().unwrap_or_else(|| StorageUsage {
+            id: id.to_string(),
+            ..Default::default()
+        }))
+    }
+
+    /// Adjust storage usage of a user or server for a given tag
+    async fn increment_storage_usage(&self, id: &str, tag: &str, bytes: i64) -> Result<()> {
+        let mut storage_usage = self.storage_usage.lock().await;
+        let usage = storage_usage
+            .entry(id.to_string())
+            .or_insert_with(|| StorageUsage {
+                id: id.to_string(),
+                ..Default::default()
+            });
+
+        usage.total += bytes;
+        *usage.tags.entry(tag.to_string()).or_default() += bytes;
+        Ok(())
+    }
+
+    /// Adjust storage usage of a user or server for a given tag if the total stays within the quota
+    ///
+    /// Returns whether the usage was adjusted
+    async fn increment_storage_usage_within_quota(
+        &self,
+        id: &str,
+        tag: &str,
+        bytes: i64,
+        quota: i64,
+    ) -> Result<bool> {
+        let mut storage_usage = self.storage_usage.lock().await;
+        let usage = storage_usage
+            .entry(id.to_string())
+            .or_insert_with(|| StorageUsage {
+                id: id.to_string(),
+                ..Default::default()
+            });
+
+        if usage.total + bytes > quota {
+            return Ok(false);
+        }
+
+        usage.total += bytes;
+        *usage.tags.entry(tag.to_string()).or_default() += bytes;
+        Ok(true)
+    }
+}
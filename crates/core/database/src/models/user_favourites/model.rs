@@ -0,0 +1,164 @@
+use revolt_result::Result;
+use ulid::Ulid;
+
+use crate::{events::client::EventV1, Database};
+
+auto_derived!(
+    /// Favourites saved by a user
+    #[derive(Default)]
+    pub struct UserFavourites {
+        /// Id of the user
+        #[serde(rename = "_id")]
+        pub id: String,
+        /// Favourites in the order the user arranged them
+        #[serde(default)]
+        pub favourites: Vec<Favourite>,
+    }
+
+    /// Saved favourite
+    pub struct Favourite {
+        /// Unique Id
+        #[serde(rename = "_id")]
+        pub id: String,
+        /// What was favourited
+        pub item: FavouriteItem,
+    }
+
+    /// Item that can be saved as a favourite
+    #[serde(tag = "type")]
+    pub enum FavouriteItem {
+        /// GIF found through the GIF search or an embed
+        Gif {
+            /// URL of the GIF
+            url: String,
+            /// Provider the GIF was found through
+            #[serde(skip_serializing_if = "Option::is_none")]
+            provider: Option<String>,
+        },
+        /// Uploaded attachment
+        Attachment {
+            /// Id of the attachment
+            attachment_id: String,
+        },
+        /// Custom emoji
+        Emoji {
+            /// Id of the emoji
+            emoji_id: String,
+        },
+    }
+);
+
+impl FavouriteItem {
+    /// Whether two items point at the same thing
+    fn is_same(&self, other: &FavouriteItem) -> bool {
+        match (self, other) {
+            (FavouriteItem::Gif { url: a, .. }, FavouriteItem::Gif { url: b, .. }) => a == b,
+            (
+                FavouriteItem::Attachment { attachment_id: a },
+                FavouriteItem::Attachment { attachment_id: b },
+            ) => a == b,
+            (FavouriteItem::Emoji { emoji_id: a }, FavouriteItem::Emoji { emoji_id: b }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl UserFavourites {
+    /// Save a new favourite at the front of the list
+    ///
+    /// Items which are already saved are returned as-is.
+    pub async fn add(
+        &mut self,
+        db: &Database,
+        item: FavouriteItem,
+        max: usize,
+    ) -> Result<Favourite> {
+        if let Some(favourite) = self
+            .favourites
+            .iter()
+            .find(|favourite| favourite.item.is_same(&item))
+        {
+            return Ok(favourite.clone());
+        }
+
+        let favourite = Favourite {
+            id: Ulid::new().to_string(),
+            item,
+        };
+
+        if !db.push_user_favourite(&self.id, &favourite, max).await? {
+            return Err(create_error!(TooManyFavourites { max }));
+        }
+
+        self.favourites.insert(0, favourite.clone());
+        self.publish_update().await;
+        Ok(favourite)
+    }
+
+    /// Remove a favourite
+    pub async fn remove(&mut self, db: &Database, id: &str) -> Result<()> {
+        if !db.pull_user_favourite(&self.id, id).await? {
+            return Err(create_error!(NotFound));
+        }
+
+        self.favourites.retain(|favourite| favourite.id != id);
+        self.publish_update().await;
+        Ok(())
+    }
+
+    /// Rearrange favourites, every saved favourite must be present exactly once
+    ///
+    /// Fails if the favourites were changed by another session in the meantime.
+    pub async fn reorder(&mut self, db: &Database, ids: &[String]) -> Result<()> {
+        if ids.len() != self.favourites.len() {
+            return Err(create_error!(InvalidOperation));
+        }
+
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let expected: Vec<String> = self
+            .favourites
+            .iter()
+            .map(|favourite| favourite.id.clone())
+            .collect();
+
+        let mut remaining = self.favourites.clone();
+        let mut favourites = Vec::with_capacity(ids.len());
+        for id in ids {
+            let index = remaining
+                .iter()
+                .position(|favourite| &favourite.id == id)
+                .ok_or_else(|| create_error!(InvalidOperation))?;
+
+            favourites.push(remaining.remove(index));
+        }
+
+        if !db
+            .reorder_user_favourites(&self.id, &expected, &favourites)
+            .await?
+        {
+            return Err(create_error!(FavouritesChanged));
+        }
+
+        self.favourites = favourites;
+        self.publish_update().await;
+        Ok(())
+    }
+
+    /// Sync favourites to the user's other sessions
+    async fn publish_update(&self) {
+        EventV1::UserFavouritesUpdate {
+            id: self.id.clone(),
+            favourites: self
+                .favourites
+                .iter()
+                .cloned()
+                .map(|favourite| favourite.into())
+                .collect(),
+        }
+        .private(self.id.clone())
+        .await;
+    }
+}
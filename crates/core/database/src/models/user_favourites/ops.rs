@@ -0,0 +1,38 @@
+use revolt_result::Result;
+
+use crate::{Favourite, UserFavourites};
+
+#[cfg(feature = "mongodb")]
+mod mongodb;
+mod reference;
+
+#[async_trait]
+pub trait AbstractUserFavourites: Sync + Send {
+    /// Fetch a user's favourites
+    async fn fetch_user_favourites(&self, id: &str) -> Result<UserFavourites>;
+
+    /// Save a favourite at the front of a user's favourites
+    ///
+    /// Returns false if the user already has `max` favourites.
+    async fn push_user_favourite(
+        &self,
+        id: &str,
+        favourite: &Favourite,
+        max: usize,
+    ) -> Result<bool>;
+
+    /// Remove a favourite from a user's favourites
+    ///
+    /// Returns false if no such favourite was saved.
+    async fn pull_user_favourite(&self, id: &str, favourite_id: &str) -> Result<bool>;
+
+    /// Replace a user's favourites with a new arrangement
+    ///
+    /// Returns false if the saved favourites no longer match the expected ids.
+    async fn reorder_user_favourites(
+        &self,
+        id: &str,
+        expected: &[String],
+        favourites: &[Favourite],
+    ) -> Result<bool>;
+}
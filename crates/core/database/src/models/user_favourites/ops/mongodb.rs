@@ -0,0 +1,128 @@
+use bson::{to_bson, Document};
+use mongodb::options::UpdateOptions;
+use revolt_result::Result;
+
+use crate::MongoDb;
+use crate::{Favourite, UserFavourites};
+
+use super::AbstractUserFavourites;
+
+static COL: &str = "user_favourites";
+
+#[async_trait]
+impl AbstractUserFavourites for MongoDb {
+    /// Fetch a user's favourites
+    async fn fetch_user_favourites(&self, id: &str) -> Result<UserFavourites> {
+        Ok(query!(
+            self,
+            find_one,
+            COL,
+            doc! {
+                "_id": id
+            }
+        )?
+        .unwrap_or_else(|| UserFavourites {
+            id: id.to_string(),
+            ..Default::default()
+        }))
+    }
+
+    /// Save a favourite at the front of a user's favourites
+    async fn push_user_favourite(
+        &self,
+        id: &str,
+        favourite: &Favourite,
+        max: usize,
+    ) -> Result<bool> {
+        if max == 0 {
+            return Ok(false);
+        }
+
+        // Make sure there is a document for the conditional update to match
+        self.col::<Document>(COL)
+            .update_one(
+                doc! {
+                    "_id": id
+                },
+                doc! {
+                    "$setOnInsert": {
+                        "favourites": []
+                    }
+                },
+            )
+            .with_options(UpdateOptions::builder().upsert(true).build())
+            .await
+            .map_err(|_| create_database_error!("update_one", COL))?;
+
+        self.col::<Document>(COL)
+            .update_one(
+                doc! {
+                    "_id": id,
+                    format!("favourites.{}", max - 1): {
+                        "$exists": false
+                    }
+                },
+                doc! {
+                    "$push": {
+                        "favourites": {
+                            "$each": [
+                                to_bson(favourite)
+                                    .map_err(|_| create_database_error!("to_bson", "favourite"))?
+                            ],
+                            "$position": 0
+                        }
+                    }
+                },
+            )
+            .await
+            .map(|result| result.matched_count > 0)
+            .map_err(|_| create_database_error!("update_one", COL))
+    }
+
+    /// Remove a favourite from a user's favourites
+    async fn pull_user_favourite(&self, id: &str, favourite_id: &str) -> Result<bool> {
+        self.col::<Document>(COL)
+            .update_one(
+                doc! {
+                    "_id": id
+                },
+                doc! {
+                    "$pull": {
+                        "favourites": {
+                            "_id": favourite_id
+                        }
+                    }
+                },
+            )
+            .await
+            .map(|result| result.modified_count > 0)
+            .map_err(|_| create_database_error!("update_one", COL))
+    }
+
+    /// Replace a user's favourites with a new arrangement
+    async fn reorder_user_favourites(
+        &self,
+        id: &str,
+        expected: &[String],
+        favourites: &[Favourite],
+    ) -> Result<bool> {
+        self.col::<Document>(COL)
+            .update_one(
+                doc! {
+                    "_id": id,
+                    "$expr": {
+                        "$eq": ["$favourites._id", expected]
+                    }
+                },
+                doc! {
+                    "$set": {
+                        "favourites": to_bson(favourites)
+                            .map_err(|_| create_database_error!("to_bson", "favourites"))?
+                    }
+                },
+            )
+            .await
+            .map(|result| result.matched_count > 0)
+            .map_err(|_| create_database_error!("update_one", COL))
+    }
+}
@@ -0,0 +1,84 @@
+use revolt_result::Result;
+
+use crate::ReferenceDb;
+use crate::{Favourite, UserFavourites};
+
+use super::AbstractUserFavourites;
+
+#[async_trait]
+impl AbstractUserFavourites for ReferenceDb {
+    /// Fetch a user's favourites
+    async fn fetch_user_favourites(&self, id: &str) -> Result<UserFavourites> {
+        let user_favourites = self.user_favourites.lock().await;
+        Ok(user_favourites
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| UserFavourites {
+                id: id.to_string(),
+                ..Default::default()
+            }))
+    }
+
+    /// Save a favourite at the front of a user's favourites
+    async fn push_user_favourite(
+        &self,
+        id: &str,
+        favourite: &Favourite,
+        max: usize,
+    ) -> Result<bool> {
+        let mut user_favourites = self.user_favourites.lock().await;
+        let entry = user_favourites
+            .entry(id.to_string())
+            .or_insert_with(|| UserFavourites {
+                id: id.to_string(),
+                ..Default::default()
+            });
+
+        if entry.favourites.len() >= max {
+            return Ok(false);
+        }
+
+        entry.favourites.insert(0, favourite.clone());
+        Ok(true)
+    }
+
+    /// Remove a favourite from a user's favourites
+    async fn pull_user_favourite(&self, id: &str, favourite_id: &str) -> Result<bool> {
+        let mut user_favourites = self.user_favourites.lock().await;
+        let Some(entry) = user_favourites.get_mut(id) else {
+            return Ok(false);
+        };
+
+        let length = entry.favourites.len();
+        entry
+            .favourites
+            .retain(|favourite| favourite.id != favourite_id);
+
+        Ok(entry.favourites.len() != length)
+    }
+
+    /// Replace a user's favourites with a new arrangement
+    async fn reorder_user_favourites(
+        &self,
+        id: &str,
+        expected: &[String],
+        favourites: &[Favourite],
+    ) -> Result<bool> {
+        let mut user_favourites = self.user_favourites.lock().await;
+        let Some(entry) = user_favourites.get_mut(id) else {
+            return Ok(false);
+        };
+
+        if !entry
+            .favourites
+            .iter()
+            .map(|favourite| &favourite.id)
+            .eq(expected.iter())
+        {
+            return Ok(false);
+        }
+
+        entry.favourites = favourites.to_vec();
+        Ok(true)
+    }
+}
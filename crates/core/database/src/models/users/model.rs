@@ -43,6 +43,9 @@ auto_derived_partial!(
         /// User's profile page
         #[serde(skip_serializing_if = "Option::is_none")]
         pub profile: Option<UserProfile>,
+        /// Who may interact with this user
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub privacy: Option<UserPrivacy>,
 
         /// Enum of user flags
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -132,6 +135,28 @@ auto_derived!(
         pub background: Option<File>,
     }
 
+    /// Who a user allows to interact with them in a given way
+    pub enum PrivacyLevel {
+        /// Anyone on the platform
+        Everyone,
+        /// Users who share a server or group with them
+        Mutual,
+        /// Only their friends
+        Friends,
+        /// No one at all
+        Nobody,
+    }
+
+    /// User's privacy settings
+    pub struct UserPrivacy {
+        /// Who may send direct messages to this user
+        pub direct_messages: PrivacyLevel,
+        /// Who may send friend requests to this user
+        pub friend_requests: PrivacyLevel,
+        /// Who may view this user's profile
+        pub profile: PrivacyLevel,
+    }
+
     /// Bot information for if the user is a bot
     pub struct BotInformation {
         /// Id of the owner of this bot
@@ -163,6 +188,16 @@ pub static DISCRIMINATOR_SEARCH_SPACE: Lazy<HashSet<String>> = Lazy::new(|| {
     set.into_iter().collect()
 });
 
+impl Default for UserPrivacy {
+    fn default() -> Self {
+        Self {
+            direct_messages: PrivacyLevel::Friends,
+            friend_requests: PrivacyLevel::Everyone,
+            profile: PrivacyLevel::Mutual,
+        }
+    }
+}
+
 #[allow(clippy::derivable_impls)]
 impl Default for User {
     fn default() -> Self {
@@ -176,6 +211,7 @@ impl Default for User {
             badges: Default::default(),
             status: Default::default(),
             profile: Default::default(),
+            privacy: Default::default(),
             flags: Default::default(),
             privileged: Default::default(),
             bot: Default::default(),
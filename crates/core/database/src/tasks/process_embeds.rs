@@ -52,6 +52,15 @@ pub async fn worker(db: Database) {
         let semaphore = semaphore.clone();
 
         spawn(async move {
+            // Embeds may have been suppressed since this task was queued
+            if db
+                .fetch_message(&task.id)
+                .await
+                .is_ok_and(|message| message.has_suppressed_embeds())
+            {
+                return;
+            }
+
             let config = config().await;
             let embeds = generate(
                 task.content,
@@ -80,20 +89,25 @@ pub async fn worker(db: Database) {
 }
 
 static RE_CODE: Lazy<Regex> = Lazy::new(|| Regex::new("```(?:.|\n)+?```|`(?:.|\n)+?`").unwrap());
-static RE_IGNORED: Lazy<Regex> = Lazy::new(|| Regex::new("(<http.+>)").unwrap());
 
 pub async fn generate(
-    content: String,
+    mut content: String,
     host: &str,
     max_embeds: usize,
     semaphore: Arc<Semaphore>,
 ) -> Result<Vec<Embed>> {
+    // Ignore links wrapped in angle brackets, other occurrences of the same link still embed.
+    for range in revolt_parser::parse_message(&content)
+        .suppressed_links
+        .into_iter()
+        .rev()
+    {
+        content.replace_range(range, "");
+    }
+
     // Ignore code blocks.
     let content = RE_CODE.replace_all(&content, "");
 
-    // Ignore all content between angle brackets starting with http.
-    let content = RE_IGNORED.replace_all(&content, "");
-
     let content = content
         // Ignore quoted lines.
         .split('\n')
@@ -21,6 +21,107 @@ impl crate::Bot {
     }
 }
 
+impl crate::Server {
+    pub fn into_discovery_server(self, listing: crate::DiscoveryListing) -> DiscoveryServer {
+        #[cfg(debug_assertions)]
+        assert_eq!(self.id, listing.id);
+
+        DiscoveryServer {
+            id: self.id,
+            name: self.name,
+            description: self.description,
+            icon: self.icon.map(|file| file.into()),
+            banner: self.banner.map(|file| file.into()),
+            nsfw: self.nsfw,
+            category: listing.category.into(),
+            tags: listing.tags,
+            language: listing.language,
+            member_count: listing.stats.member_count as usize,
+        }
+    }
+}
+
+impl From<crate::AutoModConfig> for AutoModConfig {
+    fn from(value: crate::AutoModConfig) -> Self {
+        AutoModConfig {
+            rules: value.rules.into_iter().map(Into::into).collect(),
+            alert_channel: value.alert_channel,
+            exempt_roles: value.exempt_roles,
+            exempt_channels: value.exempt_channels,
+        }
+    }
+}
+
+impl From<crate::AutoModRule> for AutoModRule {
+    fn from(value: crate::AutoModRule) -> Self {
+        AutoModRule {
+            name: value.name,
+            trigger: value.trigger.into(),
+            action: value.action.into(),
+        }
+    }
+}
+
+impl From<AutoModRule> for crate::AutoModRule {
+    fn from(value: AutoModRule) -> Self {
+        crate::AutoModRule {
+            name: value.name,
+            trigger: value.trigger.into(),
+            action: value.action.into(),
+        }
+    }
+}
+
+impl From<crate::AutoModTrigger> for AutoModTrigger {
+    fn from(value: crate::AutoModTrigger) -> Self {
+        match value {
+            crate::AutoModTrigger::Keyword { keywords } => AutoModTrigger::Keyword { keywords },
+            crate::AutoModTrigger::Regex { patterns } => AutoModTrigger::Regex { patterns },
+            crate::AutoModTrigger::MentionSpam { limit } => AutoModTrigger::MentionSpam { limit },
+            crate::AutoModTrigger::Links => AutoModTrigger::Links,
+            crate::AutoModTrigger::Invites => AutoModTrigger::Invites,
+            crate::AutoModTrigger::Attachments { content_types } => {
+                AutoModTrigger::Attachments { content_types }
+            }
+        }
+    }
+}
+
+impl From<AutoModTrigger> for crate::AutoModTrigger {
+    fn from(value: AutoModTrigger) -> Self {
+        match value {
+            AutoModTrigger::Keyword { keywords } => crate::AutoModTrigger::Keyword { keywords },
+            AutoModTrigger::Regex { patterns } => crate::AutoModTrigger::Regex { patterns },
+            AutoModTrigger::MentionSpam { limit } => crate::AutoModTrigger::MentionSpam { limit },
+            AutoModTrigger::Links => crate::AutoModTrigger::Links,
+            AutoModTrigger::Invites => crate::AutoModTrigger::Invites,
+            AutoModTrigger::Attachments { content_types } => {
+                crate::AutoModTrigger::Attachments { content_types }
+            }
+        }
+    }
+}
+
+impl From<crate::AutoModAction> for AutoModAction {
+    fn from(value: crate::AutoModAction) -> Self {
+        match value {
+            crate::AutoModAction::Block => AutoModAction::Block,
+            crate::AutoModAction::DeleteAndAlert => AutoModAction::DeleteAndAlert,
+            crate::AutoModAction::Timeout { duration } => AutoModAction::Timeout { duration },
+        }
+    }
+}
+
+impl From<AutoModAction> for crate::AutoModAction {
+    fn from(value: AutoModAction) -> Self {
+        match value {
+            AutoModAction::Block => crate::AutoModAction::Block,
+            AutoModAction::DeleteAndAlert => crate::AutoModAction::DeleteAndAlert,
+            AutoModAction::Timeout { duration } => crate::AutoModAction::Timeout { duration },
+        }
+    }
+}
+
 impl From<crate::Bot> for Bot {
     fn from(value: crate::Bot) -> Self {
         Bot {
@@ -187,6 +288,9 @@ impl From<crate::Channel> for Channel {
                 last_message_id,
                 default_permissions,
                 role_permissions,
+                member_permissions,
+                permissions_synced,
+                slowmode,
                 nsfw,
             } => Channel::TextChannel {
                 id,
@@ -197,6 +301,9 @@ impl From<crate::Channel> for Channel {
                 last_message_id,
                 default_permissions,
                 role_permissions,
+                member_permissions,
+                permissions_synced,
+                slowmode,
                 nsfw,
             },
             crate::Channel::VoiceChannel {
@@ -207,6 +314,8 @@ impl From<crate::Channel> for Channel {
                 icon,
                 default_permissions,
                 role_permissions,
+                member_permissions,
+                permissions_synced,
                 nsfw,
             } => Channel::VoiceChannel {
                 id,
@@ -216,6 +325,8 @@ impl From<crate::Channel> for Channel {
                 icon: icon.map(|file| file.into()),
                 default_permissions,
                 role_permissions,
+                member_permissions,
+                permissions_synced,
                 nsfw,
             },
         }
@@ -267,6 +378,9 @@ impl From<Channel> for crate::Channel {
                 last_message_id,
                 default_permissions,
                 role_permissions,
+                member_permissions,
+                permissions_synced,
+                slowmode,
                 nsfw,
             } => crate::Channel::TextChannel {
                 id,
@@ -277,6 +391,9 @@ impl From<Channel> for crate::Channel {
                 last_message_id,
                 default_permissions,
                 role_permissions,
+                member_permissions,
+                permissions_synced,
+                slowmode,
                 nsfw,
             },
             Channel::VoiceChannel {
@@ -287,6 +404,8 @@ impl From<Channel> for crate::Channel {
                 icon,
                 default_permissions,
                 role_permissions,
+                member_permissions,
+                permissions_synced,
                 nsfw,
             } => crate::Channel::VoiceChannel {
                 id,
@@ -296,6 +415,8 @@ impl From<Channel> for crate::Channel {
                 icon: icon.map(|file| file.into()),
                 default_permissions,
                 role_permissions,
+                member_permissions,
+                permissions_synced,
                 nsfw,
             },
         }
@@ -313,7 +434,10 @@ impl From<crate::PartialChannel> for PartialChannel {
             active: value.active,
             permissions: value.permissions,
             role_permissions: value.role_permissions,
+            member_permissions: value.member_permissions,
             default_permissions: value.default_permissions,
+            permissions_synced: value.permissions_synced,
+            slowmode: value.slowmode,
             last_message_id: value.last_message_id,
         }
     }
@@ -330,7 +454,10 @@ impl From<PartialChannel> for crate::PartialChannel {
             active: value.active,
             permissions: value.permissions,
             role_permissions: value.role_permissions,
+            member_permissions: value.member_permissions,
             default_permissions: value.default_permissions,
+            permissions_synced: value.permissions_synced,
+            slowmode: value.slowmode,
             last_message_id: value.last_message_id,
         }
     }
@@ -387,6 +514,105 @@ impl From<EmojiParent> for crate::EmojiParent {
     }
 }
 
+impl From<crate::Sticker> for Sticker {
+    fn from(value: crate::Sticker) -> Self {
+        Sticker {
+            id: value.id,
+            parent: value.parent.into(),
+            creator_id: value.creator_id,
+            name: value.name,
+            description: value.description,
+            emoji: value.emoji,
+            animated: value.animated,
+            nsfw: value.nsfw,
+        }
+    }
+}
+
+impl From<crate::PartialSticker> for PartialSticker {
+    fn from(value: crate::PartialSticker) -> Self {
+        PartialSticker {
+            id: value.id,
+            parent: value.parent.map(|parent| parent.into()),
+            creator_id: value.creator_id,
+            name: value.name,
+            description: value.description,
+            emoji: value.emoji,
+            animated: value.animated,
+            nsfw: value.nsfw,
+        }
+    }
+}
+
+impl From<crate::StickerParent> for StickerParent {
+    fn from(value: crate::StickerParent) -> Self {
+        match value {
+            crate::StickerParent::Detached => StickerParent::Detached,
+            crate::StickerParent::Server { id } => StickerParent::Server { id },
+        }
+    }
+}
+
+impl From<StickerParent> for crate::StickerParent {
+    fn from(value: StickerParent) -> Self {
+        match value {
+            StickerParent::Detached => crate::StickerParent::Detached,
+            StickerParent::Server { id } => crate::StickerParent::Server { id },
+        }
+    }
+}
+
+impl From<crate::FieldsSticker> for FieldsSticker {
+    fn from(value: crate::FieldsSticker) -> Self {
+        match value {
+            crate::FieldsSticker::Description => FieldsSticker::Description,
+            crate::FieldsSticker::Emoji => FieldsSticker::Emoji,
+        }
+    }
+}
+
+impl From<FieldsSticker> for crate::FieldsSticker {
+    fn from(value: FieldsSticker) -> Self {
+        match value {
+            FieldsSticker::Description => crate::FieldsSticker::Description,
+            FieldsSticker::Emoji => crate::FieldsSticker::Emoji,
+        }
+    }
+}
+
+impl From<crate::Favourite> for Favourite {
+    fn from(value: crate::Favourite) -> Self {
+        Favourite {
+            id: value.id,
+            item: value.item.into(),
+        }
+    }
+}
+
+impl From<crate::FavouriteItem> for FavouriteItem {
+    fn from(value: crate::FavouriteItem) -> Self {
+        match value {
+            crate::FavouriteItem::Gif { url, provider } => FavouriteItem::Gif { url, provider },
+            crate::FavouriteItem::Attachment { attachment_id } => {
+                FavouriteItem::Attachment { attachment_id }
+            }
+            crate::FavouriteItem::Emoji { emoji_id } => FavouriteItem::Emoji { emoji_id },
+        }
+    }
+}
+
+impl From<FavouriteItem> for crate::FavouriteItem {
+    fn from(value: FavouriteItem) -> Self {
+        match value {
+            FavouriteItem::Gif { url, provider } => crate::FavouriteItem::Gif { url, provider },
+            FavouriteItem::Attachment { attachment_id } => {
+                crate::FavouriteItem::Attachment { attachment_id }
+            }
+            FavouriteItem::Emoji { emoji_id } => crate::FavouriteItem::Emoji { emoji_id },
+        }
+    }
+}
+
 impl From<crate::File> for File {
     fn from(value: crate::File) -> Self {
         File {
@@ -402,6 +628,7 @@ impl From<crate::File> for File {
             user_id: value.user_id,
             server_id: value.server_id,
             object_id: value.object_id,
+            signature: None,
         }
     }
 }
@@ -434,15 +661,30 @@ impl From<crate::Metadata> for Metadata {
         match value {
             crate::Metadata::File => Metadata::File,
             crate::Metadata::Text => Metadata::Text,
-            crate::Metadata::Image { width, height } => Metadata::Image {
+            crate::Metadata::Image {
+                width,
+                height,
+                placeholder,
+                animated,
+            } => Metadata::Image {
                 width: width as usize,
                 height: height as usize,
+                placeholder,
+                animated,
             },
-            crate::Metadata::Video { width, height } => Metadata::Video {
+            crate::Metadata::Video {
+                width,
+                height,
+                placeholder,
+            } => Metadata::Video {
                 width: width as usize,
                 height: height as usize,
+                placeholder,
+            },
+            crate::Metadata::Audio { duration, waveform } => Metadata::Audio {
+                duration: duration.map(|duration| duration as usize),
+                waveform,
             },
-            crate::Metadata::Audio => Metadata::Audio,
         }
     }
 }
@@ -452,15 +694,30 @@ impl From<Metadata> for crate::Metadata {
         match value {
             Metadata::File => crate::Metadata::File,
             Metadata::Text => crate::Metadata::Text,
-            Metadata::Image { width, height } => crate::Metadata::Image {
+            Metadata::Image {
+                width,
+                height,
+                placeholder,
+                animated,
+            } => crate::Metadata::Image {
                 width: width as isize,
                 height: height as isize,
+                placeholder,
+                animated,
             },
-            Metadata::Video { width, height } => crate::Metadata::Video {
+            Metadata::Video {
+                width,
+                height,
+                placeholder,
+            } => crate::Metadata::Video {
                 width: width as isize,
                 height: height as isize,
+                placeholder,
+            },
+            Metadata::Audio { duration, waveform } => crate::Metadata::Audio {
+                duration: duration.map(|duration| duration as isize),
+                waveform,
             },
-            Metadata::Audio => crate::Metadata::Audio,
         }
     }
 }
@@ -476,6 +733,7 @@ impl crate::Message {
             member,
             webhook: self.webhook,
             content: self.content,
+            sticker: self.sticker,
             system: self.system.map(Into::into),
             attachments: self
                 .attachments
@@ -505,6 +763,7 @@ impl From<crate::PartialMessage> for PartialMessage {
             member: None,
             webhook: value.webhook,
             content: value.content,
+            sticker: value.sticker,
             system: value.system.map(Into::into),
             attachments: value
                 .attachments
@@ -639,6 +898,7 @@ impl From<crate::Member> for Member {
             avatar: value.avatar.map(|f| f.into()),
             roles: value.roles,
             timeout: value.timeout,
+            pending: value.pending,
         }
     }
 }
@@ -652,6 +912,7 @@ impl From<Member> for crate::Member {
             avatar: value.avatar.map(|f| f.into()),
             roles: value.roles,
             timeout: value.timeout,
+            pending: value.pending,
         }
     }
 }
@@ -665,6 +926,7 @@ impl From<crate::PartialMember> for PartialMember {
             avatar: value.avatar.map(|f| f.into()),
             roles: value.roles,
             timeout: value.timeout,
+            pending: value.pending,
         }
     }
 }
@@ -678,6 +940,7 @@ impl From<PartialMember> for crate::PartialMember {
             avatar: value.avatar.map(|f| f.into()),
             roles: value.roles,
             timeout: value.timeout,
+            pending: value.pending,
         }
     }
 }
@@ -746,6 +1009,7 @@ impl From<crate::Server> for Server {
                 .categories
                 .map(|categories| categories.into_iter().map(|v| v.into()).collect()),
             system_messages: value.system_messages.map(|v| v.into()),
+            screening: value.screening.map(|v| v.into()),
             roles: value
                 .roles
                 .into_iter()
@@ -774,6 +1038,7 @@ impl From<Server> for crate::Server {
                 .categories
                 .map(|categories| categories.into_iter().map(|v| v.into()).collect()),
             system_messages: value.system_messages.map(|v| v.into()),
+            screening: value.screening.map(|v| v.into()),
             roles: value
                 .roles
                 .into_iter()
@@ -802,6 +1067,7 @@ impl From<crate::PartialServer> for PartialServer {
                 .categories
                 .map(|categories| categories.into_iter().map(|v| v.into()).collect()),
             system_messages: value.system_messages.map(|v| v.into()),
+            screening: value.screening.map(|v| v.into()),
             roles: value
                 .roles
                 .map(|roles| roles.into_iter().map(|(k, v)| (k, v.into())).collect()),
@@ -828,6 +1094,7 @@ impl From<PartialServer> for crate::PartialServer {
                 .categories
                 .map(|categories| categories.into_iter().map(|v| v.into()).collect()),
             system_messages: value.system_messages.map(|v| v.into()),
+            screening: value.screening.map(|v| v.into()),
             roles: value
                 .roles
                 .map(|roles| roles.into_iter().map(|(k, v)| (k, v.into())).collect()),
@@ -850,6 +1117,7 @@ impl From<crate::FieldsServer> for FieldsServer {
             crate::FieldsServer::Description => FieldsServer::Description,
             crate::FieldsServer::Icon => FieldsServer::Icon,
             crate::FieldsServer::SystemMessages => FieldsServer::SystemMessages,
+            crate::FieldsServer::Screening => FieldsServer::Screening,
         }
     }
 }
@@ -862,6 +1130,7 @@ impl From<FieldsServer> for crate::FieldsServer {
             FieldsServer::Description => crate::FieldsServer::Description,
             FieldsServer::Icon => crate::FieldsServer::Icon,
             FieldsServer::SystemMessages => crate::FieldsServer::SystemMessages,
+            FieldsServer::Screening => crate::FieldsServer::Screening,
         }
     }
 }
@@ -872,6 +1141,8 @@ impl From<crate::Category> for Category {
             id: value.id,
             title: value.title,
             channels: value.channels,
+            default_permissions: value.default_permissions,
+            role_permissions: value.role_permissions,
         }
     }
 }
@@ -882,6 +1153,8 @@ impl From<Category> for crate::Category {
             id: value.id,
             title: value.title,
             channels: value.channels,
+            default_permissions: value.default_permissions,
+            role_permissions: value.role_permissions,
         }
     }
 }
@@ -908,6 +1181,161 @@ impl From<SystemMessageChannels> for crate::SystemMessageChannels {
     }
 }
 
+impl From<crate::DiscoveryListing> for DiscoveryListing {
+    fn from(value: crate::DiscoveryListing) -> Self {
+        DiscoveryListing {
+            id: value.id,
+            category: value.category.into(),
+            tags: value.tags,
+            language: value.language,
+            state: value.state.into(),
+            applicant_id: value.applicant_id,
+            reviewer_id: value.reviewer_id,
+            rejection_reason: value.rejection_reason,
+        }
+    }
+}
+
+impl From<crate::DiscoveryCategory> for DiscoveryCategory {
+    fn from(value: crate::DiscoveryCategory) -> Self {
+        match value {
+            crate::DiscoveryCategory::Gaming => DiscoveryCategory::Gaming,
+            crate::DiscoveryCategory::Music => DiscoveryCategory::Music,
+            crate::DiscoveryCategory::Art => DiscoveryCategory::Art,
+            crate::DiscoveryCategory::Technology => DiscoveryCategory::Technology,
+            crate::DiscoveryCategory::Education => DiscoveryCategory::Education,
+            crate::DiscoveryCategory::Science => DiscoveryCategory::Science,
+            crate::DiscoveryCategory::Entertainment => DiscoveryCategory::Entertainment,
+            crate::DiscoveryCategory::Community => DiscoveryCategory::Community,
+            crate::DiscoveryCategory::Other => DiscoveryCategory::Other,
+        }
+    }
+}
+
+impl From<DiscoveryCategory> for crate::DiscoveryCategory {
+    fn from(value: DiscoveryCategory) -> Self {
+        match value {
+            DiscoveryCategory::Gaming => crate::DiscoveryCategory::Gaming,
+            DiscoveryCategory::Music => crate::DiscoveryCategory::Music,
+            DiscoveryCategory::Art => crate::DiscoveryCategory::Art,
+            DiscoveryCategory::Technology => crate::DiscoveryCategory::Technology,
+            DiscoveryCategory::Education => crate::DiscoveryCategory::Education,
+            DiscoveryCategory::Science => crate::DiscoveryCategory::Science,
+            DiscoveryCategory::Entertainment => crate::DiscoveryCategory::Entertainment,
+            DiscoveryCategory::Community => crate::DiscoveryCategory::Community,
+            DiscoveryCategory::Other => crate::DiscoveryCategory::Other,
+        }
+    }
+}
+
+impl From<DiscoverySort> for crate::DiscoveryListingSort {
+    fn from(value: DiscoverySort) -> Self {
+        match value {
+            DiscoverySort::Members => crate::DiscoveryListingSort::Members,
+            DiscoverySort::Activity => crate::DiscoveryListingSort::Activity,
+        }
+    }
+}
+
+impl From<crate::DiscoveryListingState> for DiscoveryListingState {
+    fn from(value: crate::DiscoveryListingState) -> Self {
+        match value {
+            crate::DiscoveryListingState::Pending => DiscoveryListingState::Pending,
+            crate::DiscoveryListingState::Approved => DiscoveryListingState::Approved,
+            crate::DiscoveryListingState::Rejected => DiscoveryListingState::Rejected,
+        }
+    }
+}
+
+impl From<crate::ServerTemplate> for ServerTemplate {
+    fn from(value: crate::ServerTemplate) -> Self {
+        ServerTemplate {
+            code: value.code,
+            source_server_id: value.source_server_id,
+            creator_id: value.creator_id,
+            name: value.name,
+            description: value.description,
+            layout: value.layout.into(),
+        }
+    }
+}
+
+impl From<crate::ServerLayout> for ServerLayout {
+    fn from(value: crate::ServerLayout) -> Self {
+        ServerLayout {
+            default_permissions: value.default_permissions,
+            nsfw: value.nsfw,
+            roles: value
+                .roles
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect(),
+            categories: value.categories.into_iter().map(|v| v.into()).collect(),
+            channels: value.channels.into_iter().map(|v| v.into()).collect(),
+            system_messages: value.system_messages.map(|v| v.into()),
+            emojis: value.emojis.into_iter().map(|v| v.into()).collect(),
+        }
+    }
+}
+
+impl From<crate::TemplateChannel> for TemplateChannel {
+    fn from(value: crate::TemplateChannel) -> Self {
+        TemplateChannel {
+            id: value.id,
+            channel_type: match value.channel_type {
+                crate::TemplateChannelType::Text => TemplateChannelType::Text,
+                crate::TemplateChannelType::Voice => TemplateChannelType::Voice,
+            },
+            name: value.name,
+            description: value.description,
+            nsfw: value.nsfw,
+            default_permissions: value.default_permissions,
+            role_permissions: value.role_permissions,
+            permissions_synced: value.permissions_synced,
+        }
+    }
+}
+
+impl From<crate::TemplateEmoji> for TemplateEmoji {
+    fn from(value: crate::TemplateEmoji) -> Self {
+        TemplateEmoji {
+            id: value.id,
+            name: value.name,
+            animated: value.animated,
+            nsfw: value.nsfw,
+        }
+    }
+}
+
+impl From<crate::MemberScreening> for MemberScreening {
+    fn from(value: crate::MemberScreening) -> Self {
+        MemberScreening {
+            rules: value.rules,
+            questions: value.questions,
+        }
+    }
+}
+
+impl From<MemberScreening> for crate::MemberScreening {
+    fn from(value: MemberScreening) -> Self {
+        crate::MemberScreening {
+            rules: value.rules,
+            questions: value.questions,
+        }
+    }
+}
+
+impl From<crate::ReactionRole> for ReactionRole {
+    fn from(value: crate::ReactionRole) -> Self {
+        ReactionRole {
+            message_id: value.id.message,
+            channel_id: value.channel_id,
+            emoji: value.id.emoji,
+            role_id: value.role_id,
+        }
+    }
+}
+
 impl From<crate::Role> for Role {
     fn from(value: crate::Role) -> Self {
         Role {
@@ -916,6 +1344,8 @@ impl From<crate::Role> for Role {
             colour: value.colour,
             hoist: value.hoist,
             rank: value.rank,
+            self_assignable: value.self_assignable,
+            group: value.group,
         }
     }
 }
@@ -928,6 +1358,8 @@ impl From<Role> for crate::Role {
             colour: value.colour,
             hoist: value.hoist,
             rank: value.rank,
+            self_assignable: value.self_assignable,
+            group: value.group,
         }
     }
 }
@@ -940,6 +1372,8 @@ impl From<crate::PartialRole> for PartialRole {
             colour: value.colour,
             hoist: value.hoist,
             rank: value.rank,
+            self_assignable: value.self_assignable,
+            group: value.group,
         }
     }
 }
@@ -952,6 +1386,8 @@ impl From<PartialRole> for crate::PartialRole {
             colour: value.colour,
             hoist: value.hoist,
             rank: value.rank,
+            self_assignable: value.self_assignable,
+            group: value.group,
         }
     }
 }
@@ -960,6 +1396,7 @@ impl From<crate::FieldsRole> for FieldsRole {
     fn from(value: crate::FieldsRole) -> Self {
         match value {
             crate::FieldsRole::Colour => FieldsRole::Colour,
+            crate::FieldsRole::Group => FieldsRole::Group,
         }
     }
 }
@@ -968,6 +1405,7 @@ impl From<FieldsRole> for crate::FieldsRole {
     fn from(value: FieldsRole) -> Self {
         match value {
             FieldsRole::Colour => crate::FieldsRole::Colour,
+            FieldsRole::Group => crate::FieldsRole::Group,
         }
     }
 }
@@ -1197,6 +1635,7 @@ impl From<User> for crate::User {
             badges: Some(value.badges as i32),
             status: value.status.map(Into::into),
             profile: None,
+            privacy: None,
             flags: Some(value.flags as i32),
             privileged: value.privileged,
             bot: value.bot.map(Into::into),
@@ -1357,6 +1796,48 @@ impl From<UserProfile> for crate::UserProfile {
     }
 }
 
+impl From<crate::PrivacyLevel> for PrivacyLevel {
+    fn from(value: crate::PrivacyLevel) -> Self {
+        match value {
+            crate::PrivacyLevel::Everyone => PrivacyLevel::Everyone,
+            crate::PrivacyLevel::Mutual => PrivacyLevel::Mutual,
+            crate::PrivacyLevel::Friends => PrivacyLevel::Friends,
+            crate::PrivacyLevel::Nobody => PrivacyLevel::Nobody,
+        }
+    }
+}
+
+impl From<PrivacyLevel> for crate::PrivacyLevel {
+    fn from(value: PrivacyLevel) -> crate::PrivacyLevel {
+        match value {
+            PrivacyLevel::Everyone => crate::PrivacyLevel::Everyone,
+            PrivacyLevel::Mutual => crate::PrivacyLevel::Mutual,
+            PrivacyLevel::Friends => crate::PrivacyLevel::Friends,
+            PrivacyLevel::Nobody => crate::PrivacyLevel::Nobody,
+        }
+    }
+}
+
+impl From<crate::UserPrivacy> for UserPrivacy {
+    fn from(value: crate::UserPrivacy) -> Self {
+        UserPrivacy {
+            direct_messages: value.direct_messages.into(),
+            friend_requests: value.friend_requests.into(),
+            profile: value.profile.into(),
+        }
+    }
+}
+
+impl From<UserPrivacy> for crate::UserPrivacy {
+    fn from(value: UserPrivacy) -> crate::UserPrivacy {
+        crate::UserPrivacy {
+            direct_messages: value.direct_messages.into(),
+            friend_requests: value.friend_requests.into(),
+            profile: value.profile.into(),
+        }
+    }
+}
+
 impl From<crate::BotInformation> for BotInformation {
     fn from(value: crate::BotInformation) -> Self {
         BotInformation {
@@ -1377,6 +1858,7 @@ impl From<crate::FieldsMessage> for FieldsMessage {
     fn from(value: crate::FieldsMessage) -> Self {
         match value {
             crate::FieldsMessage::Pinned => FieldsMessage::Pinned,
+            crate::FieldsMessage::Embeds => FieldsMessage::Embeds,
         }
     }
 }
@@ -1384,6 +1866,7 @@ impl From<FieldsMessage> for crate::FieldsMessage {
     fn from(value: FieldsMessage) -> Self {
         match value {
             FieldsMessage::Pinned => crate::FieldsMessage::Pinned,
+            FieldsMessage::Embeds => crate::FieldsMessage::Embeds,
         }
     }
 }
@@ -2,7 +2,7 @@ use std::{collections::HashMap, hash::RandomState};
 
 use revolt_permissions::{
     ChannelPermission, ChannelType, Override, OverrideField, PermissionValue, ALLOW_IN_TIMEOUT,
-    DEFAULT_PERMISSION_DIRECT_MESSAGE,
+    DEFAULT_PERMISSION_DIRECT_MESSAGE, DEFAULT_PERMISSION_VIEW_ONLY,
 };
 
 use crate::{Channel, Database, Member, Server, User};
@@ -197,7 +197,12 @@ async fn calculate_members_permissions<'a>(
 ) -> HashMap<String, PermissionValue> {
     let mut resp = HashMap::new();
 
-    let (_, channel_role_permissions, channel_default_permissions) = match query
+    let (
+        channel_id,
+        channel_role_permissions,
+        channel_member_permissions,
+        channel_default_permissions,
+    ) = match query
         .channel
         .as_ref()
         .expect("A channel must be assigned to calculate channel permissions")
@@ -206,15 +211,22 @@ async fn calculate_members_permissions<'a>(
         Channel::TextChannel {
             id,
             role_permissions,
+            member_permissions,
             default_permissions,
             ..
         }
         | Channel::VoiceChannel {
             id,
             role_permissions,
+            member_permissions,
             default_permissions,
             ..
-        } => (id, role_permissions, default_permissions),
+        } => (
+            id,
+            role_permissions,
+            member_permissions,
+            default_permissions,
+        ),
         _ => panic!("Calculation of member permissions must be done on a server channel"),
     };
 
@@ -259,6 +271,8 @@ async fn calculate_members_permissions<'a>(
         query.members = Some(query.cached_members.as_ref().unwrap().to_vec())
     }
 
+    let category = query.server.category_of(&channel_id);
+
     let members: HashMap<&String, &Member, RandomState> = HashMap::from_iter(
         query
             .members
@@ -298,27 +312,35 @@ async fn calculate_members_permissions<'a>(
         // Get the user's server permissions
         let mut permission = calculate_server_permissions(&query.server, user, member);
 
+        // Apply the category's overrides before the channel's own
+        if let Some(category) = category {
+            if let Some(defaults) = category.default_permissions {
+                permission.apply(defaults.into());
+            }
+
+            for role_override in
+                ordered_role_overrides(&query.server, member, &category.role_permissions)
+            {
+                permission.apply(role_override)
+            }
+        }
+
         if let Some(defaults) = channel_default_permissions {
             permission.apply(defaults.into());
         }
 
-        // Get the applicable role overrides
-        let mut roles = channel_role_permissions
-            .iter()
-            .filter(|(id, _)| member.roles.contains(id))
-            .filter_map(|(id, permission)| {
-                query.server.roles.get(id).map(|role| {
-                    let v: Override = (*permission).into();
-                    (role.rank, v)
-                })
-            })
-            .collect::<Vec<(i64, Override)>>();
+        for role_override in
+            ordered_role_overrides(&query.server, member, &channel_role_permissions)
+        {
+            permission.apply(role_override)
+        }
 
-        roles.sort_by(|a, b| b.0.cmp(&a.0));
-        let overrides = roles.into_iter().map(|(_, v)| v);
+        if let Some(member_override) = channel_member_permissions.get(&user.id) {
+            permission.apply((*member_override).into());
+        }
 
-        for role_override in overrides {
-            permission.apply(role_override)
+        if member.pending {
+            permission.restrict(*DEFAULT_PERMISSION_VIEW_ONLY);
         }
 
         resp.insert(user.id.clone(), permission);
@@ -327,6 +349,27 @@ async fn calculate_members_permissions<'a>(
     resp
 }
 
+/// Get the applicable role overrides for a member, ordered from lowest to highest
+fn ordered_role_overrides(
+    server: &Server,
+    member: &Member,
+    role_permissions: &HashMap<String, OverrideField>,
+) -> Vec<Override> {
+    let mut roles = role_permissions
+        .iter()
+        .filter(|(id, _)| member.roles.contains(id))
+        .filter_map(|(id, permission)| {
+            server.roles.get(id).map(|role| {
+                let v: Override = (*permission).into();
+                (role.rank, v)
+            })
+        })
+        .collect::<Vec<(i64, Override)>>();
+
+    roles.sort_by_key(|(rank, _)| std::cmp::Reverse(*rank));
+    roles.into_iter().map(|(_, v)| v).collect()
+}
+
 /// Calculates a member's server permissions
 fn calculate_server_permissions(server: &Server, user: &User, member: &Member) -> PermissionValue {
     if user.privileged || server.owner == user.id {
@@ -356,5 +399,9 @@ fn calculate_server_permissions(server: &Server, user: &User, member: &Member) -
         permissions.restrict(*ALLOW_IN_TIMEOUT);
     }
 
+    if member.pending {
+        permissions.restrict(*DEFAULT_PERMISSION_VIEW_ONLY);
+    }
+
     permissions
 }
@@ -1,8 +1,9 @@
 use std::borrow::Cow;
 
 use revolt_permissions::{
-    calculate_user_permissions, ChannelType, Override, PermissionQuery, PermissionValue,
-    RelationshipStatus, DEFAULT_PERMISSION_DIRECT_MESSAGE,
+    calculate_user_permissions, ChannelType, Override, PermissionLayer, PermissionQuery,
+    PermissionValue, PrivacyLevel, RelationshipStatus, UserPrivacy,
+    DEFAULT_PERMISSION_DIRECT_MESSAGE,
 };
 
 use crate::{Channel, Database, Member, Server, User};
@@ -110,6 +111,28 @@ impl PermissionQuery for DatabasePermissionQuery<'_> {
         }
     }
 
+    /// Get the privacy settings of the currently selected user
+    async fn user_privacy(&mut self) -> UserPrivacy {
+        let privacy = self
+            .user
+            .as_ref()
+            .and_then(|user| user.privacy.clone())
+            .unwrap_or_default();
+
+        let level = |level: crate::PrivacyLevel| match level {
+            crate::PrivacyLevel::Everyone => PrivacyLevel::Everyone,
+            crate::PrivacyLevel::Mutual => PrivacyLevel::Mutual,
+            crate::PrivacyLevel::Friends => PrivacyLevel::Friends,
+            crate::PrivacyLevel::Nobody => PrivacyLevel::Nobody,
+        };
+
+        UserPrivacy {
+            direct_messages: level(privacy.direct_messages),
+            friend_requests: level(privacy.friend_requests),
+            profile: level(privacy.profile),
+        }
+    }
+
     // * For calculating server permission
 
     /// Is our perspective user the server's owner?
@@ -151,7 +174,7 @@ impl PermissionQuery for DatabasePermissionQuery<'_> {
     }
 
     /// Get the ordered role overrides (from lowest to highest) for this member in this server
-    async fn get_our_server_role_overrides(&mut self) -> Vec<Override> {
+    async fn get_our_server_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
         if let Some(server) = &self.server {
             let member_roles = self
                 .member
@@ -163,14 +186,14 @@ impl PermissionQuery for DatabasePermissionQuery<'_> {
                 .roles
                 .iter()
                 .filter(|(id, _)| member_roles.contains(id))
-                .map(|(_, role)| {
+                .map(|(id, role)| {
                     let v: Override = role.permissions.into();
-                    (role.rank, v)
+                    (id.clone(), role.rank, v)
                 })
-                .collect::<Vec<(i64, Override)>>();
+                .collect::<Vec<(String, i64, Override)>>();
 
-            roles.sort_by(|a, b| b.0.cmp(&a.0));
-            roles.into_iter().map(|(_, v)| v).collect()
+            roles.sort_by_key(|(_, rank, _)| std::cmp::Reverse(*rank));
+            roles
         } else {
             vec![]
         }
@@ -185,6 +208,15 @@ impl PermissionQuery for DatabasePermissionQuery<'_> {
         }
     }
 
+    /// Is our perspective user yet to pass member screening
+    async fn are_we_pending_screening(&mut self) -> bool {
+        if let Some(member) = &self.member {
+            member.pending
+        } else {
+            false
+        }
+    }
+
     // * For calculating channel permission
 
     /// Get the type of the channel
@@ -241,21 +273,73 @@ impl PermissionQuery for DatabasePermissionQuery<'_> {
         }
     }
 
-    /// Get the ordered role overrides (from lowest to highest) for this member in this channel
-    async fn get_our_channel_role_overrides(&mut self) -> Vec<Override> {
+    /// Get the default permissions of the category this channel belongs to
+    async fn get_default_category_permissions(&mut self) -> Override {
+        if let (Some(channel), Some(server)) = (&self.channel, &self.server) {
+            server
+                .category_of(channel.id())
+                .and_then(|category| category.default_permissions)
+                .unwrap_or_default()
+                .into()
+        } else {
+            Default::default()
+        }
+    }
+
+    /// Get the ordered role overrides (from lowest to highest) for this member in this channel's category
+    async fn get_our_category_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
+        if let (Some(channel), Some(server)) = (&self.channel, &self.server) {
+            if let Some(category) = server.category_of(channel.id()) {
+                let member_roles = self
+                    .member
+                    .as_ref()
+                    .map(|member| member.roles.clone())
+                    .unwrap_or_default();
+
+                let mut roles = category
+                    .role_permissions
+                    .iter()
+                    .filter(|(id, _)| member_roles.contains(id))
+                    .filter_map(|(id, permission)| {
+                        server.roles.get(id).map(|role| {
+                            let v: Override = (*permission).into();
+                            (id.clone(), role.rank, v)
+                        })
+                    })
+                    .collect::<Vec<(String, i64, Override)>>();
+
+                roles.sort_by_key(|(_, rank, _)| std::cmp::Reverse(*rank));
+                return roles;
+            }
+        }
+
+        vec![]
+    }
+
+    /// Get the ordered role overrides (from lowest to highest) for this member in this channel,
+    /// followed by any override for this specific member
+    async fn get_our_channel_role_overrides(&mut self) -> Vec<(PermissionLayer, Override)> {
         if let Some(channel) = &self.channel {
             match channel {
                 Cow::Borrowed(Channel::TextChannel {
-                    role_permissions, ..
+                    role_permissions,
+                    member_permissions,
+                    ..
                 })
                 | Cow::Owned(Channel::TextChannel {
-                    role_permissions, ..
+                    role_permissions,
+                    member_permissions,
+                    ..
                 })
                 | Cow::Borrowed(Channel::VoiceChannel {
-                    role_permissions, ..
+                    role_permissions,
+                    member_permissions,
+                    ..
                 })
                 | Cow::Owned(Channel::VoiceChannel {
-                    role_permissions, ..
+                    role_permissions,
+                    member_permissions,
+                    ..
                 }) => {
                     if let Some(server) = &self.server {
                         let member_roles = self
@@ -270,13 +354,28 @@ impl PermissionQuery for DatabasePermissionQuery<'_> {
                             .filter_map(|(id, permission)| {
                                 server.roles.get(id).map(|role| {
                                     let v: Override = (*permission).into();
-                                    (role.rank, v)
+                                    (id.clone(), role.rank, v)
                                 })
                             })
-                            .collect::<Vec<(i64, Override)>>();
+                            .collect::<Vec<(String, i64, Override)>>();
+
+                        roles.sort_by_key(|(_, rank, _)| std::cmp::Reverse(*rank));
+
+                        let mut overrides: Vec<(PermissionLayer, Override)> = roles
+                            .into_iter()
+                            .map(|(role_id, rank, v)| {
+                                (PermissionLayer::ChannelRole { role_id, rank }, v)
+                            })
+                            .collect();
+
+                        if let Some(member) = &self.member {
+                            if let Some(permission) = member_permissions.get(&member.id.user) {
+                                overrides
+                                    .push((PermissionLayer::MemberOverride, (*permission).into()));
+                            }
+                        }
 
-                        roles.sort_by(|a, b| b.0.cmp(&a.0));
-                        roles.into_iter().map(|(_, v)| v).collect()
+                        overrides
                     } else {
                         vec![]
                     }
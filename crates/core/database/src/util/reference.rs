@@ -10,7 +10,8 @@ use schemars::{
 };
 
 use crate::{
-    Bot, Channel, Database, Emoji, Invite, Member, Message, Server, ServerBan, User, Webhook,
+    Bot, Channel, Database, Emoji, Invite, Member, Message, Server, ServerBan, ServerTemplate,
+    Sticker, User, Webhook,
 };
 
 /// Reference to some object in the database
@@ -40,6 +41,11 @@ impl<'a> Reference<'a> {
         db.fetch_emoji(self.id).await
     }
 
+    /// Fetch sticker from Ref
+    pub async fn as_sticker(&self, db: &Database) -> Result<Sticker> {
+        db.fetch_sticker(self.id).await
+    }
+
     /// Fetch channel from Ref
     pub async fn as_channel(&self, db: &Database) -> Result<Channel> {
         db.fetch_channel(self.id).await
@@ -93,6 +99,11 @@ impl<'a> Reference<'a> {
         db.fetch_server(self.id).await
     }
 
+    /// Fetch server template from Ref
+    pub async fn as_server_template(&self, db: &Database) -> Result<ServerTemplate> {
+        db.fetch_server_template(self.id).await
+    }
+
     /// Fetch user from Ref
     pub async fn as_user(&self, db: &Database) -> Result<User> {
         db.fetch_user(self.id).await
@@ -1,10 +1,16 @@
-use std::io::{BufRead, Read, Seek, Write};
+use std::{
+    io::{BufRead, Cursor, Read, Seek, Write},
+    process::Command,
+};
 
 use aes_gcm::{
     aead::{AeadCore, AeadMutInPlace, OsRng},
     Aes256Gcm, Key, KeyInit, Nonce,
 };
-use image::{DynamicImage, ImageBuffer};
+use image::{
+    codecs::{avif::AvifEncoder, gif::GifDecoder, jpeg::JpegEncoder, webp::WebPDecoder},
+    AnimationDecoder, DynamicImage, ImageBuffer,
+};
 use revolt_config::{config, report_internal_error, FilesS3};
 use revolt_result::{create_error, Result};
 
@@ -16,6 +22,7 @@ use aws_sdk_s3::{
 use base64::prelude::*;
 use tempfile::NamedTempFile;
 use tiny_skia::Pixmap;
+use webp::{AnimEncoder, AnimFrame, WebPConfig};
 
 /// Size of the authentication tag in the buffer
 pub const AUTHENTICATION_TAG_SIZE_BYTES: usize = 16;
@@ -71,19 +78,7 @@ pub async fn fetch_from_s3(bucket_id: &str, path: &str, nonce: &str) -> Result<V
         return Ok(buf);
     }
 
-    // Recover nonce as bytes
-    let nonce = &BASE64_STANDARD.decode(nonce).unwrap()[..];
-    let nonce: &Nonce<typenum::consts::U12> = nonce.into();
-
-    // Decrypt the file
-    create_cipher(&config.files.encryption_key)
-        .decrypt_in_place(nonce, b"", &mut buf)
-        .map_err(|_| create_error!(InternalError))?;
-
-    // Remove the authentication tag bytes that were added during encryption
-    buf.truncate(buf.len() - AUTHENTICATION_TAG_SIZE_BYTES);
-
-    Ok(buf)
+    decrypt_buffer(&config.files.encryption_key, nonce, buf)
 }
 
 /// Encrypt and upload a file to S3 (returning its nonce/IV)
@@ -91,29 +86,70 @@ pub async fn upload_to_s3(bucket_id: &str, path: &str, buf: &[u8]) -> Result<Str
     let config = config().await;
     let client = create_client(config.files.s3);
 
-    // Generate a nonce
-    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    // Encrypt the file
+    let (nonce, buf) = encrypt_buffer(&config.files.encryption_key, buf)?;
 
-    // Extend the buffer for in-place encryption
-    let mut buf = [buf, &[0; AUTHENTICATION_TAG_SIZE_BYTES]].concat();
+    // Upload the file to remote
+    report_internal_error!(
+        client
+            .put_object()
+            .bucket(bucket_id)
+            .key(path)
+            .body(buf.into())
+            .send()
+            .await
+    )?;
 
-    // Encrypt the file in place
-    create_cipher(&config.files.encryption_key)
-        .encrypt_in_place(&nonce, b"", &mut buf)
-        .map_err(|_| create_error!(InternalError))?;
+    Ok(nonce)
+}
 
-    // Upload the file to remote
+/// Encrypt and upload a generated variant to S3 (storing its nonce/IV alongside it)
+pub async fn upload_variant_to_s3(bucket_id: &str, path: &str, buf: &[u8]) -> Result<()> {
+    let config = config().await;
+    let client = create_client(config.files.s3);
+
+    // Encrypt the file
+    let (nonce, buf) = encrypt_buffer(&config.files.encryption_key, buf)?;
+
+    // Upload the file to remote, variants have no database entry to hold the nonce
     report_internal_error!(
         client
             .put_object()
             .bucket(bucket_id)
             .key(path)
+            .metadata("nonce", nonce)
             .body(buf.into())
             .send()
             .await
     )?;
 
-    Ok(BASE64_STANDARD.encode(nonce))
+    Ok(())
+}
+
+/// Fetch a generated variant from S3 (and decrypt it) if it exists
+pub async fn fetch_variant_from_s3(bucket_id: &str, path: &str) -> Option<Vec<u8>> {
+    let config = config().await;
+    let client = create_client(config.files.s3);
+
+    // Send a request for the file, it is fine for it to not exist
+    let mut obj = client
+        .get_object()
+        .bucket(bucket_id)
+        .key(path)
+        .send()
+        .await
+        .ok()?;
+
+    // Recover the nonce
+    let nonce = obj.metadata().and_then(|m| m.get("nonce")).cloned()?;
+
+    // Read the file from remote
+    let mut buf = vec![];
+    while let Some(bytes) = obj.body.next().await {
+        buf.extend_from_slice(&bytes.ok()?);
+    }
+
+    decrypt_buffer(&config.files.encryption_key, &nonce, buf).ok()
 }
 
 /// Delete a file from S3 by path
@@ -133,6 +169,117 @@ pub async fn delete_from_s3(bucket_id: &str, path: &str) -> Result<()> {
     Ok(())
 }
 
+/// List generated variants under a given prefix in S3 along with when they were last modified (UNIX timestamp in seconds)
+pub async fn list_variants_in_s3(bucket_id: &str, prefix: &str) -> Result<Vec<(String, i64)>> {
+    let config = config().await;
+    let client = create_client(config.files.s3);
+
+    // Listings are capped at 1000 keys, keep going until we run out
+    let mut variants = vec![];
+    let mut continuation_token = None;
+    loop {
+        let objects = report_internal_error!(
+            client
+                .list_objects_v2()
+                .bucket(bucket_id)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+        )?;
+
+        variants.extend(objects.contents().iter().filter_map(|object| {
+            Some((
+                object.key()?.to_owned(),
+                object
+                    .last_modified()
+                    .map(|date| date.secs())
+                    .unwrap_or_default(),
+            ))
+        }));
+
+        continuation_token = objects.next_continuation_token().map(ToOwned::to_owned);
+        if continuation_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(variants)
+}
+
+/// Delete all generated variants under a given prefix from S3
+pub async fn delete_variants_from_s3(bucket_id: &str, prefix: &str) -> Result<()> {
+    let config = config().await;
+    let client = create_client(config.files.s3);
+
+    // Listings are capped at 1000 keys, keep going until we run out
+    let mut continuation_token = None;
+    loop {
+        let objects = report_internal_error!(
+            client
+                .list_objects_v2()
+                .bucket(bucket_id)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+        )?;
+
+        for object in objects.contents() {
+            if let Some(key) = object.key() {
+                report_internal_error!(
+                    client
+                        .delete_object()
+                        .bucket(bucket_id)
+                        .key(key)
+                        .send()
+                        .await
+                )?;
+            }
+        }
+
+        continuation_token = objects.next_continuation_token().map(ToOwned::to_owned);
+        if continuation_token.is_none() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Encrypt a buffer (returning its nonce/IV and the encrypted data)
+fn encrypt_buffer(key: &str, buf: &[u8]) -> Result<(String, Vec<u8>)> {
+    // Generate a nonce
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    // Extend the buffer for in-place encryption
+    let mut buf = [buf, &[0; AUTHENTICATION_TAG_SIZE_BYTES]].concat();
+
+    // Encrypt the file in place
+    create_cipher(key)
+        .encrypt_in_place(&nonce, b"", &mut buf)
+        .map_err(|_| create_error!(InternalError))?;
+
+    Ok((BASE64_STANDARD.encode(nonce), buf))
+}
+
+/// Decrypt a buffer using the given nonce/IV
+fn decrypt_buffer(key: &str, nonce: &str, mut buf: Vec<u8>) -> Result<Vec<u8>> {
+    // Recover nonce as bytes
+    let nonce = &report_internal_error!(BASE64_STANDARD.decode(nonce))?[..];
+    let nonce: &Nonce<typenum::consts::U12> = nonce.into();
+
+    // Decrypt the file
+    create_cipher(key)
+        .decrypt_in_place(nonce, b"", &mut buf)
+        .map_err(|_| create_error!(InternalError))?;
+
+    // Remove the authentication tag bytes that were added during encryption
+    buf.truncate(buf.len() - AUTHENTICATION_TAG_SIZE_BYTES);
+
+    Ok(buf)
+}
+
 /// Determine size of image at temp file
 pub fn image_size(f: &NamedTempFile) -> Option<(usize, usize)> {
     if let Ok(size) = imagesize::size(f.path())
@@ -184,6 +331,44 @@ pub fn video_size(f: &NamedTempFile) -> Option<(i64, i64)> {
     }
 }
 
+/// Determine duration of audio at temp file (in milliseconds)
+pub fn audio_duration(f: &NamedTempFile) -> Option<i64> {
+    ffprobe::ffprobe(f.path())
+        .inspect_err(|err| tracing::error!("Failed to ffprobe file! {err:?}"))
+        .ok()?
+        .format
+        .duration?
+        .parse::<f64>()
+        .ok()
+        .map(|seconds| (seconds * 1000.0) as i64)
+}
+
+/// Extract the first frame of video at temp file to use as a poster
+pub fn video_poster(f: &NamedTempFile) -> Option<DynamicImage> {
+    let output = Command::new("ffmpeg")
+        .args([
+            // Read original file
+            "-i",
+            f.path().to_str()?,
+            // Take only the first frame
+            "-frames:v",
+            "1",
+            // Write it out as PNG to stdout
+            "-f",
+            "image2pipe",
+            "-vcodec",
+            "png",
+            "-",
+        ])
+        .output()
+        .inspect_err(|err| tracing::error!("Failed to run ffmpeg! {err:?}"))
+        .ok()?;
+
+    image::load_from_memory_with_format(&output.stdout, image::ImageFormat::Png)
+        .inspect_err(|err| tracing::error!("Failed to read video poster! {err:?}"))
+        .ok()
+}
+
 /// Decode image from reader
 pub fn decode_image<R: Read + BufRead + Seek>(reader: &mut R, mime: &str) -> Result<DynamicImage> {
     match mime {
@@ -270,18 +455,79 @@ pub fn is_valid_image<R: Read + BufRead + Seek>(reader: &mut R, mime: &str) -> b
     }
 }
 
+/// Number of horizontal components used for placeholders
+const PLACEHOLDER_COMPONENTS_X: u32 = 4;
+
+/// Number of vertical components used for placeholders
+const PLACEHOLDER_COMPONENTS_Y: u32 = 3;
+
+/// Maximum side of the image used to compute placeholders
+const PLACEHOLDER_SAMPLE_SIZE: u32 = 64;
+
+/// Create compact blurhash placeholder from given image
+pub fn create_placeholder(image: &DynamicImage) -> Option<String> {
+    // Blurhash only captures low frequency detail,
+    // so we can work on a tiny version of the image
+    let image = image
+        .thumbnail(PLACEHOLDER_SAMPLE_SIZE, PLACEHOLDER_SAMPLE_SIZE)
+        .to_rgba8();
+
+    blurhash::encode(
+        PLACEHOLDER_COMPONENTS_X,
+        PLACEHOLDER_COMPONENTS_Y,
+        image.width(),
+        image.height(),
+        image.as_raw(),
+    )
+    .inspect_err(|err| tracing::error!("Failed to generate placeholder! {err:?}"))
+    .ok()
+}
+
+/// Image formats that previews can be encoded as
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PreviewFormat {
+    Avif,
+    Webp,
+    Jpeg,
+}
+
+impl PreviewFormat {
+    /// Mime type of this format
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            PreviewFormat::Avif => "image/avif",
+            PreviewFormat::Webp => "image/webp",
+            PreviewFormat::Jpeg => "image/jpeg",
+        }
+    }
+
+    /// File extension of this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PreviewFormat::Avif => "avif",
+            PreviewFormat::Webp => "webp",
+            PreviewFormat::Jpeg => "jpeg",
+        }
+    }
+}
+
+/// Resize image to fit within given bounds, preserving aspect ratio
+fn fit_within(image: DynamicImage, [w, h]: [usize; 2]) -> DynamicImage {
+    //.resize(width as u32, height as u32, image::imageops::FilterType::Gaussian)
+    // resize is about 2.5x slower,
+    // thumbnail doesn't have terrible quality
+    // so we use thumbnail
+    image.thumbnail(image.width().min(w as u32), image.height().min(h as u32))
+}
+
 /// Create thumbnail from given image
 pub async fn create_thumbnail(image: DynamicImage, tag: &str) -> Vec<u8> {
     // Load configuration
     let config = config().await;
-    let [w, h] = config.files.preview.get(tag).unwrap();
+    let size = config.files.preview.get(tag).unwrap();
 
     // Create thumbnail
-    //.resize(width as u32, height as u32, image::imageops::FilterType::Gaussian)
-    // resize is about 2.5x slower,
-    // thumbnail doesn't have terrible quality
-    // so we use thumbnail
-    let image = image.thumbnail(image.width().min(*w as u32), image.height().min(*h as u32));
+    let image = fit_within(image, *size);
 
     // Encode it into WEBP
     let encoder = webp::Encoder::from_image(&image).expect("Could not create encoder.");
@@ -291,3 +537,129 @@ pub async fn create_thumbnail(image: DynamicImage, tag: &str) -> Vec<u8> {
         encoder.encode_lossless().to_vec()
     }
 }
+
+/// Create preview from given image that fits within the given size
+pub async fn create_preview(
+    image: DynamicImage,
+    size: [usize; 2],
+    format: PreviewFormat,
+) -> Result<Vec<u8>> {
+    // Load configuration
+    let config = config().await;
+
+    // Create thumbnail
+    let image = fit_within(image, size);
+
+    // Encode it into the requested format
+    let mut buf = Vec::new();
+    match format {
+        PreviewFormat::Webp => {
+            let encoder = webp::Encoder::from_image(&image)
+                .map_err(|_| create_error!(ImageProcessingFailed))?;
+
+            buf = if config.files.webp_quality != 100.0 {
+                encoder.encode(config.files.webp_quality).to_vec()
+            } else {
+                encoder.encode_lossless().to_vec()
+            };
+        }
+        PreviewFormat::Avif => {
+            report_internal_error!(DynamicImage::ImageRgba8(image.to_rgba8()).write_with_encoder(
+                AvifEncoder::new_with_speed_quality(
+                    &mut buf,
+                    config.files.avif_speed,
+                    config.files.avif_quality
+                )
+            ))?;
+        }
+        PreviewFormat::Jpeg => {
+            // JPEG has no alpha channel
+            report_internal_error!(DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(
+                JpegEncoder::new_with_quality(&mut buf, config.files.jpeg_quality)
+            ))?;
+        }
+    }
+
+    Ok(buf)
+}
+
+/// Check whether GIF or WebP data has more than one frame
+pub fn is_animated(data: &[u8], mime: &str) -> bool {
+    match mime {
+        "image/gif" => GifDecoder::new(Cursor::new(data))
+            .map(|decoder| decoder.into_frames().take(2).count() > 1)
+            .unwrap_or_default(),
+        "image/webp" => WebPDecoder::new(Cursor::new(data))
+            .map(|decoder| decoder.has_animation())
+            .unwrap_or_default(),
+        _ => false,
+    }
+}
+
+/// Create animated WebP preview that fits within the given size from GIF or animated WebP data
+///
+/// Returns None if the given data is not animated
+pub async fn create_animated_preview(
+    data: &[u8],
+    mime: &str,
+    size: [usize; 2],
+) -> Result<Option<Vec<u8>>> {
+    // Load configuration
+    let config = config().await;
+
+    // Read all frames from the source
+    let frames = match mime {
+        "image/gif" => report_internal_error!(GifDecoder::new(Cursor::new(data)))?.into_frames(),
+        "image/webp" => {
+            let decoder = report_internal_error!(WebPDecoder::new(Cursor::new(data)))?;
+            if !decoder.has_animation() {
+                return Ok(None);
+            }
+
+            decoder.into_frames()
+        }
+        _ => return Ok(None),
+    };
+
+    // Resize each frame as we go to keep memory usage down
+    let mut timestamp = 0;
+    let mut resized = Vec::new();
+    for frame in frames {
+        let frame = report_internal_error!(frame)?;
+        let (numer, denom) = frame.delay().numer_denom_ms();
+        let image = fit_within(DynamicImage::ImageRgba8(frame.into_buffer()), size).to_rgba8();
+
+        resized.push((image, timestamp));
+        timestamp += (numer / denom.max(1)) as i32;
+    }
+
+    // Single frame images aren't animated
+    if resized.len() < 2 {
+        return Ok(None);
+    }
+
+    // Configure the encoder
+    let mut webp_config = WebPConfig::new().map_err(|_| create_error!(ImageProcessingFailed))?;
+    if config.files.webp_quality != 100.0 {
+        webp_config.quality = config.files.webp_quality;
+    } else {
+        webp_config.lossless = 1;
+    }
+
+    // Encode all the frames into WEBP
+    let (width, height) = resized[0].0.dimensions();
+    let mut encoder = AnimEncoder::new(width, height, &webp_config);
+    for (image, timestamp) in &resized {
+        encoder.add_frame(AnimFrame::from_rgba(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            *timestamp,
+        ));
+    }
+
+    encoder
+        .try_encode()
+        .map(|data| Some(data.to_vec()))
+        .map_err(|_| create_error!(ImageProcessingFailed))
+}
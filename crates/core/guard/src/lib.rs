@@ -0,0 +1,252 @@
+//! # Guard
+//!
+//! Checks for requests made to addresses supplied by users, such as link
+//! previews or push notification endpoints, so they can't be pointed at
+//! internal services.
+//!
+//! ## Features
+//! - `reqwest`: Provides [`PublicResolver`] to only connect to public addresses.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use url::{Host, Url};
+
+/// Check whether an address is publicly routable
+///
+/// Loopback, private, link-local, multicast and otherwise reserved ranges are rejected.
+pub fn is_ip_allowed(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_ipv4_allowed(ip),
+        IpAddr::V6(ip) => is_ipv6_allowed(ip),
+    }
+}
+
+/// Check whether an IPv4 address is publicly routable
+fn is_ipv4_allowed(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_multicast()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        // 0.0.0.0/8 ("this" network)
+        || a == 0
+        // 100.64.0.0/10 (carrier-grade NAT)
+        || (a == 100 && (b & 0b1100_0000) == 64)
+        // 192.0.0.0/24 (IETF protocol assignments)
+        || (a == 192 && b == 0 && c == 0)
+        // 198.18.0.0/15 (benchmarking)
+        || (a == 198 && (b & 0b1111_1110) == 18)
+        // 240.0.0.0/4 (reserved)
+        || a >= 240)
+}
+
+/// Check whether an IPv6 address is publicly routable
+///
+/// Addresses embedding an IPv4 address are checked against the IPv4 rules.
+fn is_ipv6_allowed(ip: Ipv6Addr) -> bool {
+    // IPv4-mapped addresses (::ffff:0:0/96)
+    if let Some(ip) = ip.to_ipv4_mapped() {
+        return is_ipv4_allowed(ip);
+    }
+
+    let segments = ip.segments();
+    let ipv4 = |hi: u16, lo: u16| Ipv4Addr::from(((hi as u32) << 16) | lo as u32);
+
+    // IPv4-compatible addresses (::a.b.c.d), this also covers :: and ::1
+    if segments[..6] == [0, 0, 0, 0, 0, 0] {
+        return is_ipv4_allowed(ipv4(segments[6], segments[7]));
+    }
+
+    // NAT64 addresses (64:ff9b::/96)
+    if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        return is_ipv4_allowed(ipv4(segments[6], segments[7]));
+    }
+
+    // 6to4 addresses (2002::/16)
+    if segments[0] == 0x2002 {
+        return is_ipv4_allowed(ipv4(segments[1], segments[2]));
+    }
+
+    // Teredo addresses (2001::/32) carry the server and the inverted client address
+    if segments[0] == 0x2001 && segments[1] == 0 {
+        return is_ipv4_allowed(ipv4(segments[2], segments[3]))
+            && is_ipv4_allowed(ipv4(!segments[6], !segments[7]));
+    }
+
+    !(ip.is_multicast()
+        // fc00::/7 (unique local)
+        || (segments[0] & 0xfe00) == 0xfc00
+        // fe80::/10 (link-local)
+        || (segments[0] & 0xffc0) == 0xfe80
+        // fec0::/10 (site-local)
+        || (segments[0] & 0xffc0) == 0xfec0
+        // 2001:db8::/32 (documentation)
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8))
+}
+
+/// Resolve a host name to the publicly routable addresses it points at
+///
+/// Fails if the host does not resolve to any public address.
+pub async fn lookup_public_host(host: &str) -> std::io::Result<Vec<SocketAddr>> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0))
+        .await?
+        .filter(|addr| is_ip_allowed(addr.ip()))
+        .collect();
+
+    if addrs.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("host {host} does not resolve to any public address"),
+        ));
+    }
+
+    Ok(addrs)
+}
+
+/// Check whether a URL is HTTP(S) and doesn't point at an internal IP address
+///
+/// Host names are not resolved, pair this with [`PublicResolver`] when connecting.
+pub fn is_url_allowed(url: &Url) -> bool {
+    if !matches!(url.scheme(), "http" | "https") {
+        return false;
+    }
+
+    match url.host() {
+        Some(Host::Ipv4(ip)) => is_ip_allowed(IpAddr::V4(ip)),
+        Some(Host::Ipv6(ip)) => is_ip_allowed(IpAddr::V6(ip)),
+        Some(Host::Domain(_)) => true,
+        None => false,
+    }
+}
+
+/// Check whether a URL points at a publicly routable HTTP(S) server
+///
+/// Host names are resolved, so this should be checked again right before
+/// connecting in case the records change.
+pub async fn is_url_public(url: &Url) -> bool {
+    if !is_url_allowed(url) {
+        return false;
+    }
+
+    match url.host() {
+        Some(Host::Domain(host)) => lookup_public_host(host).await.is_ok(),
+        _ => true,
+    }
+}
+
+/// DNS resolver which only ever hands out publicly routable addresses
+///
+/// Connections are made to the addresses checked here, so a host cannot
+/// pass validation and then rebind to an internal address.
+/// URLs with IP literals skip the resolver and must be checked separately.
+#[cfg(feature = "reqwest")]
+pub struct PublicResolver;
+
+#[cfg(feature = "reqwest")]
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(async move {
+            let addrs = lookup_public_host(name.as_str()).await?;
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use url::Url;
+
+    use super::{is_ip_allowed, is_url_allowed};
+
+    #[test]
+    fn ipv4_ranges() {
+        for (ip, allowed) in [
+            ("1.1.1.1", true),
+            ("8.8.8.8", true),
+            ("0.0.0.0", false),
+            ("0.1.2.3", false),
+            ("10.0.0.1", false),
+            ("100.64.0.1", false),
+            ("100.128.0.1", true),
+            ("127.0.0.1", false),
+            ("169.254.169.254", false),
+            ("172.16.0.1", false),
+            ("172.32.0.1", true),
+            ("192.0.0.8", false),
+            ("192.0.2.1", false),
+            ("192.168.1.1", false),
+            ("198.18.0.1", false),
+            ("224.0.0.1", false),
+            ("240.0.0.1", false),
+            ("255.255.255.255", false),
+        ] {
+            assert_eq!(
+                is_ip_allowed(ip.parse::<IpAddr>().unwrap()),
+                allowed,
+                "{ip}"
+            );
+        }
+    }
+
+    #[test]
+    fn ipv6_ranges() {
+        for (ip, allowed) in [
+            ("2606:4700:4700::1111", true),
+            ("::", false),
+            ("::1", false),
+            ("ff02::1", false),
+            ("fc00::1", false),
+            ("fd12:3456::1", false),
+            ("fe80::1", false),
+            ("fec0::1", false),
+            ("2001:db8::1", false),
+            // IPv4-mapped
+            ("::ffff:1.1.1.1", true),
+            ("::ffff:127.0.0.1", false),
+            ("::ffff:169.254.169.254", false),
+            // IPv4-compatible
+            ("::1.1.1.1", true),
+            ("::10.0.0.1", false),
+            ("::127.0.0.1", false),
+            // NAT64
+            ("64:ff9b::1.1.1.1", true),
+            ("64:ff9b::192.168.0.1", false),
+            // 6to4
+            ("2002:101:101::1", true),
+            ("2002:7f00:1::1", false),
+            ("2002:a9fe:a9fe::1", false),
+            // Teredo, client 1.1.1.1 and 10.0.0.1 respectively
+            ("2001:0:101:101::fefe:fefe", true),
+            ("2001:0:101:101::f5ff:fffe", false),
+            ("2001:0:a00:1::fefe:fefe", false),
+        ] {
+            assert_eq!(
+                is_ip_allowed(ip.parse::<IpAddr>().unwrap()),
+                allowed,
+                "{ip}"
+            );
+        }
+    }
+
+    #[test]
+    fn urls() {
+        for (url, allowed) in [
+            ("https://ntfy.sh/upAbCdEf?up=1", true),
+            ("http://1.1.1.1/", true),
+            ("http://127.0.0.1:8000/", false),
+            ("http://[::1]/", false),
+            ("http://[::ffff:10.0.0.1]/", false),
+            ("http://169.254.169.254/latest/meta-data/", false),
+            ("file:///etc/passwd", false),
+            ("ftp://1.1.1.1/", false),
+        ] {
+            assert_eq!(is_url_allowed(&Url::parse(url).unwrap()), allowed, "{url}");
+        }
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(feature = "validator")]
+use validator::Validate;
+
+auto_derived!(
+    /// Auto-moderation configuration for a server
+    pub struct AutoModConfig {
+        /// Rules evaluated against every message, in order
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        pub rules: Vec<AutoModRule>,
+        /// Id of the channel to post alerts in
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub alert_channel: Option<String>,
+        /// Roles whose members are never moderated
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        pub exempt_roles: Vec<String>,
+        /// Channels in which messages are never moderated
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        pub exempt_channels: Vec<String>,
+    }
+
+    /// Single auto-moderation rule
+    pub struct AutoModRule {
+        /// Name of the rule
+        pub name: String,
+        /// Condition that triggers this rule
+        pub trigger: AutoModTrigger,
+        /// Action taken when this rule is triggered
+        pub action: AutoModAction,
+    }
+
+    /// Condition that triggers an auto-moderation rule
+    #[serde(tag = "type")]
+    pub enum AutoModTrigger {
+        /// Message contains any of the given keywords, ignoring case
+        Keyword { keywords: Vec<String> },
+        /// Message matches any of the given regular expressions
+        Regex { patterns: Vec<String> },
+        /// Message mentions more users and roles than allowed
+        MentionSpam { limit: u32 },
+        /// Message contains a link
+        Links,
+        /// Message contains an invite to a server
+        Invites,
+        /// Message has an attachment of a blocked type
+        ///
+        /// Types may end in `/*` to match an entire family, such as `image/*`.
+        Attachments { content_types: Vec<String> },
+    }
+
+    /// Action taken when an auto-moderation rule is triggered
+    #[serde(tag = "type")]
+    pub enum AutoModAction {
+        /// Reject the message
+        Block,
+        /// Reject the message and post an alert
+        DeleteAndAlert,
+        /// Reject the message, post an alert and time out the author
+        Timeout {
+            /// Length of the timeout in seconds
+            duration: u32,
+        },
+    }
+
+    /// New auto-moderation configuration
+    #[cfg_attr(feature = "validator", derive(Validate))]
+    pub struct DataEditAutoMod {
+        /// Rules evaluated against every message, in order
+        #[cfg_attr(feature = "validator", validate(length(max = 25)))]
+        #[cfg_attr(feature = "serde", serde(default))]
+        pub rules: Vec<AutoModRule>,
+        /// Id of the channel to post alerts in
+        pub alert_channel: Option<String>,
+        /// Roles whose members are never moderated
+        #[cfg_attr(feature = "serde", serde(default))]
+        pub exempt_roles: Vec<String>,
+        /// Channels in which messages are never moderated
+        #[cfg_attr(feature = "serde", serde(default))]
+        pub exempt_channels: Vec<String>,
+    }
+
+    /// Message to check against auto-moderation rules
+    pub struct DataTestAutoMod {
+        /// Text content of the message
+        pub content: Option<String>,
+        /// Content types of the message's attachments
+        #[cfg_attr(feature = "serde", serde(default))]
+        pub content_types: Vec<String>,
+    }
+
+    /// Outcome of checking a message against auto-moderation rules
+    pub struct AutoModTestResult {
+        /// First rule the message triggers
+        pub rule: Option<AutoModRule>,
+    }
+);
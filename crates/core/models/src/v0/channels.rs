@@ -1,6 +1,6 @@
 use super::File;
 
-use revolt_permissions::{Override, OverrideField};
+use revolt_permissions::{Override, OverrideField, PermissionTraceStep};
 use std::collections::{HashMap, HashSet};
 
 #[cfg(feature = "rocket")]
@@ -100,6 +100,27 @@ auto_derived!(
                 )
             )]
             role_permissions: HashMap<String, OverrideField>,
+            /// Permissions assigned to individual members in this channel
+            #[cfg_attr(
+                feature = "serde",
+                serde(
+                    default = "HashMap::<String, OverrideField>::new",
+                    skip_serializing_if = "HashMap::<String, OverrideField>::is_empty"
+                )
+            )]
+            member_permissions: HashMap<String, OverrideField>,
+            /// Whether this channel's permissions are kept in sync with its category
+            #[cfg_attr(
+                feature = "serde",
+                serde(skip_serializing_if = "crate::if_false", default)
+            )]
+            permissions_synced: bool,
+            /// Minimum number of seconds members must wait between messages
+            #[cfg_attr(
+                feature = "serde",
+                serde(skip_serializing_if = "crate::if_zero_u32", default)
+            )]
+            slowmode: u32,
 
             /// Whether this channel is marked as not safe for work
             #[cfg_attr(
@@ -137,6 +158,21 @@ auto_derived!(
                 )
             )]
             role_permissions: HashMap<String, OverrideField>,
+            /// Permissions assigned to individual members in this channel
+            #[cfg_attr(
+                feature = "serde",
+                serde(
+                    default = "HashMap::<String, OverrideField>::new",
+                    skip_serializing_if = "HashMap::<String, OverrideField>::is_empty"
+                )
+            )]
+            member_permissions: HashMap<String, OverrideField>,
+            /// Whether this channel's permissions are kept in sync with its category
+            #[cfg_attr(
+                feature = "serde",
+                serde(skip_serializing_if = "crate::if_false", default)
+            )]
+            permissions_synced: bool,
 
             /// Whether this channel is marked as not safe for work
             #[cfg_attr(
@@ -167,8 +203,14 @@ auto_derived!(
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub role_permissions: Option<HashMap<String, OverrideField>>,
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub member_permissions: Option<HashMap<String, OverrideField>>,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub default_permissions: Option<OverrideField>,
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub permissions_synced: Option<bool>,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub slowmode: Option<u32>,
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub last_message_id: Option<String>,
     }
 
@@ -205,6 +247,17 @@ auto_derived!(
         /// Whether this channel is archived
         pub archived: Option<bool>,
 
+        /// Whether this channel's permissions should follow its category
+        ///
+        /// Enabling this copies the category's permissions onto the channel.
+        pub permissions_synced: Option<bool>,
+
+        /// Minimum number of seconds members must wait between messages
+        ///
+        /// Only applies to text channels, set to `0` to disable.
+        #[cfg_attr(feature = "validator", validate(range(max = 21600)))]
+        pub slowmode: Option<u32>,
+
         /// Fields to remove from channel
         #[cfg_attr(feature = "serde", serde(default))]
         pub remove: Vec<FieldsChannel>,
@@ -281,6 +334,14 @@ auto_derived!(
         pub permissions: Override,
     }
 
+    /// Breakdown of a member's effective permissions in a channel
+    pub struct PermissionExplanation {
+        /// Effective permissions
+        pub permissions: u64,
+        /// Each layer applied, in order
+        pub trace: Vec<PermissionTraceStep>,
+    }
+
     /// Options when deleting a channel
     #[cfg_attr(feature = "rocket", derive(FromForm))]
     pub struct OptionsChannelDelete {
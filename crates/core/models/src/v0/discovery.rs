@@ -0,0 +1,171 @@
+use super::File;
+
+#[cfg(feature = "validator")]
+use validator::Validate;
+
+#[cfg(feature = "rocket")]
+use rocket::{FromForm, FromFormField};
+
+auto_derived!(
+    /// Discovery listing for a server
+    pub struct DiscoveryListing {
+        /// Id of the server being listed
+        #[cfg_attr(feature = "serde", serde(rename = "_id"))]
+        pub id: String,
+        /// Category this server is listed under
+        pub category: DiscoveryCategory,
+        /// Tags describing this server
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        pub tags: Vec<String>,
+        /// Primary language of this server
+        pub language: String,
+        /// Review state of this listing
+        pub state: DiscoveryListingState,
+        /// Id of the user who applied for this listing
+        pub applicant_id: String,
+        /// Id of the user who last reviewed this listing
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub reviewer_id: Option<String>,
+        /// Reason given when this listing was rejected
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub rejection_reason: Option<String>,
+    }
+
+    /// Category of a discovery listing
+    #[cfg_attr(feature = "rocket", derive(FromFormField))]
+    pub enum DiscoveryCategory {
+        Gaming,
+        Music,
+        Art,
+        Technology,
+        Education,
+        Science,
+        Entertainment,
+        Community,
+        Other,
+    }
+
+    /// Review state of a discovery listing
+    pub enum DiscoveryListingState {
+        /// Waiting to be reviewed
+        Pending,
+        /// Visible in the directory
+        Approved,
+        /// Refused by a reviewer
+        Rejected,
+    }
+
+    /// Server as shown in the discovery directory
+    pub struct DiscoveryServer {
+        /// Server Id
+        #[cfg_attr(feature = "serde", serde(rename = "_id"))]
+        pub id: String,
+        /// Server name
+        pub name: String,
+        /// Server description
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub description: Option<String>,
+        /// Icon attachment
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub icon: Option<File>,
+        /// Banner attachment
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub banner: Option<File>,
+        /// Whether this server is flagged as not safe for work
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::if_false", default)
+        )]
+        pub nsfw: bool,
+        /// Category this server is listed under
+        pub category: DiscoveryCategory,
+        /// Tags describing this server
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        pub tags: Vec<String>,
+        /// Primary language of this server
+        pub language: String,
+        /// Number of members in this server
+        pub member_count: usize,
+    }
+
+    /// Sort used for the discovery directory
+    #[derive(Default)]
+    #[cfg_attr(feature = "rocket", derive(FromFormField))]
+    pub enum DiscoverySort {
+        /// Sort by the largest servers first
+        #[default]
+        Members,
+        /// Sort by the most recently active servers first
+        Activity,
+    }
+
+    /// Options for searching the server directory
+    #[derive(Default)]
+    #[cfg_attr(feature = "validator", derive(Validate))]
+    #[cfg_attr(feature = "rocket", derive(FromForm))]
+    pub struct OptionsDiscoverServers {
+        /// Text to search for in server names and descriptions
+        #[cfg_attr(feature = "validator", validate(length(min = 1, max = 64)))]
+        pub query: Option<String>,
+        /// Only include servers in this category
+        pub category: Option<DiscoveryCategory>,
+        /// Only include servers in this language
+        #[cfg_attr(feature = "validator", validate(length(min = 2, max = 16)))]
+        pub language: Option<String>,
+        /// Only include servers with this tag
+        #[cfg_attr(feature = "validator", validate(length(min = 1, max = 24)))]
+        pub tag: Option<String>,
+        /// Sort order of the results
+        pub sort: Option<DiscoverySort>,
+        /// Maximum number of servers to return
+        #[cfg_attr(feature = "validator", validate(range(min = 1, max = 100)))]
+        pub limit: Option<usize>,
+        /// Number of servers to skip
+        pub offset: Option<usize>,
+    }
+
+    /// Options for searching the bot directory
+    #[derive(Default)]
+    #[cfg_attr(feature = "validator", derive(Validate))]
+    #[cfg_attr(feature = "rocket", derive(FromForm))]
+    pub struct OptionsDiscoverBots {
+        /// Text to search for in bot names and descriptions
+        #[cfg_attr(feature = "validator", validate(length(min = 1, max = 64)))]
+        pub query: Option<String>,
+        /// Maximum number of bots to return
+        #[cfg_attr(feature = "validator", validate(range(min = 1, max = 100)))]
+        pub limit: Option<usize>,
+        /// Number of bots to skip
+        pub offset: Option<usize>,
+    }
+
+    /// Application to list a server in the discovery directory
+    #[cfg_attr(feature = "validator", derive(Validate))]
+    pub struct DataDiscoveryApplication {
+        /// Category to list this server under
+        pub category: DiscoveryCategory,
+        /// Tags describing this server
+        #[cfg_attr(feature = "validator", validate(length(max = 5)))]
+        #[cfg_attr(feature = "serde", serde(default))]
+        pub tags: Vec<String>,
+        /// Primary language of this server
+        #[cfg_attr(feature = "validator", validate(length(min = 2, max = 16)))]
+        pub language: String,
+    }
+
+    /// Review of a discovery listing
+    #[cfg_attr(feature = "validator", derive(Validate))]
+    pub struct DataReviewDiscoveryListing {
+        /// Whether to approve this listing
+        pub approve: bool,
+        /// Reason for rejecting this listing
+        #[cfg_attr(feature = "validator", validate(length(min = 1, max = 1024)))]
+        pub reason: Option<String>,
+    }
+);
@@ -19,6 +19,9 @@ auto_derived!(
         pub height: usize,
         /// Positioning and size
         pub size: ImageSize,
+        /// Blurhash placeholder to show while the image loads
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        pub placeholder: Option<String>,
     }
 
     /// Video
@@ -29,6 +32,9 @@ auto_derived!(
         pub width: usize,
         /// Height of the video
         pub height: usize,
+        /// Blurhash placeholder of the first frame to show while the video loads
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        pub placeholder: Option<String>,
     }
 
     /// Type of remote Twitch content
@@ -122,6 +128,9 @@ auto_derived!(
         /// Site name
         #[serde(skip_serializing_if = "Option::is_none")]
         pub site_name: Option<String>,
+        /// Name of the author of the content
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub author_name: Option<String>,
         /// URL to site icon
         #[serde(skip_serializing_if = "Option::is_none")]
         pub icon_url: Option<String>,
@@ -188,6 +197,10 @@ impl WebsiteMetadata {
             s.truncate(32);
         }
 
+        if let Some(s) = self.author_name.as_mut() {
+            s.truncate(100);
+        }
+
         if let Some(s) = self.icon_url.as_mut() {
             s.truncate(256);
         }
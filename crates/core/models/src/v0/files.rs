@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 auto_derived!(
     /// File
     pub struct File {
@@ -35,6 +37,28 @@ auto_derived!(
         /// Id of the object this file is associated with
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub object_id: Option<String>,
+
+        /// Signature required to fetch this file
+        ///
+        /// Present on attachments from channels which are not publicly viewable
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "Option::is_none", default)
+        )]
+        pub signature: Option<FileSignature>,
+    }
+
+    /// Signed URL parameters for a file
+    ///
+    /// Each field should be appended to the file URL as a query parameter of the same name:
+    /// `/{tag}/{id}?expires={expires}&viewer={viewer}&signature={signature}`
+    pub struct FileSignature {
+        /// UNIX timestamp (in seconds) after which the signature is no longer valid
+        pub expires: u64,
+        /// Id of the user this signature was issued to
+        pub viewer: String,
+        /// URL-safe base64 encoded HMAC-SHA256 signature
+        pub signature: String,
     }
 
     /// Metadata associated with a file
@@ -47,10 +71,57 @@ auto_derived!(
         /// File contains textual data and should be displayed as such
         Text,
         /// File is an image with specific dimensions
-        Image { width: usize, height: usize },
+        Image {
+            width: usize,
+            height: usize,
+            /// Blurhash placeholder to show while the image loads
+            #[cfg_attr(
+                feature = "serde",
+                serde(skip_serializing_if = "Option::is_none", default)
+            )]
+            placeholder: Option<String>,
+            /// Whether the image has more than one frame
+            #[cfg_attr(
+                feature = "serde",
+                serde(skip_serializing_if = "crate::if_false", default)
+            )]
+            animated: bool,
+        },
         /// File is a video with specific dimensions
-        Video { width: usize, height: usize },
+        Video {
+            width: usize,
+            height: usize,
+            /// Blurhash placeholder of the first frame to show while the video loads
+            #[cfg_attr(
+                feature = "serde",
+                serde(skip_serializing_if = "Option::is_none", default)
+            )]
+            placeholder: Option<String>,
+        },
         /// File is audio
-        Audio,
+        Audio {
+            /// Duration of the audio (in milliseconds)
+            #[cfg_attr(
+                feature = "serde",
+                serde(skip_serializing_if = "Option::is_none", default)
+            )]
+            duration: Option<usize>,
+            /// Downsampled waveform of a voice message, one amplitude (0-255) per sample
+            #[cfg_attr(
+                feature = "serde",
+                serde(skip_serializing_if = "Option::is_none", default)
+            )]
+            waveform: Option<Vec<u8>>,
+        },
+    }
+
+    /// Storage used by the current user
+    pub struct StorageUsage {
+        /// Total number of bytes used
+        pub total: usize,
+        /// Maximum number of bytes that can be used
+        pub quota: usize,
+        /// Number of bytes used per tag
+        pub tags: HashMap<String, usize>,
     }
 );
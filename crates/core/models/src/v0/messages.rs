@@ -38,6 +38,9 @@ auto_derived_partial!(
         /// Message content
         #[serde(skip_serializing_if = "Option::is_none")]
         pub content: Option<String>,
+        /// Id of the sticker sent instead of content
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub sticker: Option<String>,
         /// System message
         #[serde(skip_serializing_if = "Option::is_none")]
         pub system: Option<SystemMessage>,
@@ -269,6 +272,9 @@ auto_derived!(
         pub masquerade: Option<Masquerade>,
         /// Information about how this message should be interacted with
         pub interactions: Option<Interactions>,
+        /// Id of a sticker to send instead of content
+        #[cfg_attr(feature = "validator", validate(length(min = 26, max = 26)))]
+        pub sticker: Option<String>,
 
         /// Bitfield of message flags
         ///
@@ -364,6 +370,15 @@ auto_derived!(
         pub remove_all: Option<bool>,
     }
 
+    /// Options for suppressing embeds
+    #[cfg_attr(feature = "rocket", derive(FromForm))]
+    pub struct OptionsSuppressEmbeds {
+        /// Index of a single embed to remove
+        ///
+        /// All generated embeds are removed and further generation is suppressed if not given.
+        pub index: Option<usize>,
+    }
+
     /// Message flag bitfield
     #[repr(u32)]
     pub enum MessageFlags {
@@ -374,11 +389,16 @@ auto_derived!(
         /// Message will mention all users who are online and can see the channel.
         /// This cannot be true if MentionsEveryone is true
         MentionsOnline = 3,
+        /// Message is a voice message and consists of a single audio attachment
+        VoiceMessage = 4,
+        /// Message will not have embeds generated for links in its content
+        SuppressEmbeds = 5,
     }
 
     /// Optional fields on message
     pub enum FieldsMessage {
         Pinned,
+        Embeds,
     }
 );
 
@@ -472,6 +492,8 @@ impl PushNotification {
 
         let body = if let Some(ref sys) = msg.system {
             sys.clone().into()
+        } else if msg.flags & (1 << MessageFlags::VoiceMessage as u32) != 0 {
+            "Sent a voice message".to_string()
         } else if let Some(ref text) = msg.content {
             text.clone()
         } else if let Some(text) = msg.embeds.as_ref().and_then(|embeds| match embeds.first() {
@@ -1,33 +1,47 @@
+mod automod;
 mod bots;
 mod channel_invites;
 mod channel_unreads;
 mod channel_webhooks;
 mod channels;
+mod discovery;
 mod embeds;
 mod emojis;
 mod files;
 mod messages;
 mod policy_changes;
+mod push;
+mod reaction_roles;
 mod safety_reports;
 mod server_bans;
 mod server_members;
+mod server_templates;
 mod servers;
+mod stickers;
+mod user_favourites;
 mod user_settings;
 mod users;
 
+pub use automod::*;
 pub use bots::*;
 pub use channel_invites::*;
 pub use channel_unreads::*;
 pub use channel_webhooks::*;
 pub use channels::*;
+pub use discovery::*;
 pub use embeds::*;
 pub use emojis::*;
 pub use files::*;
 pub use messages::*;
 pub use policy_changes::*;
+pub use push::*;
+pub use reaction_roles::*;
 pub use safety_reports::*;
 pub use server_bans::*;
 pub use server_members::*;
+pub use server_templates::*;
 pub use servers::*;
+pub use stickers::*;
+pub use user_favourites::*;
 pub use user_settings::*;
 pub use users::*;
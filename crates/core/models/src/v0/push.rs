@@ -0,0 +1,41 @@
+//! End-to-end encrypted push notifications
+//!
+//! Mobile sessions (`apn` and `fcm` subscriptions) may opt in to encrypted
+//! notifications by setting the `p256dh` field of their push subscription to
+//! `<public key>.<auth secret>`, both encoded as unpadded base64url:
+//!
+//! - `public key` is an uncompressed P-256 public key (65 bytes)
+//! - `auth secret` is 16 random bytes kept on the device
+//!
+//! The notification is serialised as JSON (`{ "type": ..., "data": ... }`,
+//! where `type` is one of `MessageNotification`, `FRAccepted`, `FRReceived`
+//! or `Generic`; `MessageNotification` carries a [`super::PushNotification`])
+//! and encrypted to the device key using `aes128gcm` as described in
+//! RFC 8291, the same scheme used for Web Push.
+//!
+//! The result is delivered as an [`EncryptedPushNotification`]:
+//!
+//! - **APNs:** under the `encrypted` key, next to an `aps` alert using the
+//!   `push.encrypted` loc-key and `mutable-content` so that a notification
+//!   service extension can decrypt and replace it.
+//! - **FCM:** as a high priority message with the data fields `type` (always
+//!   `push.encrypted`), `v` and `ciphertext`, all as strings, and an Android
+//!   notification using the `push_encrypted` body string resource which is
+//!   shown if the application isn't running to display it itself.
+//!
+//! Both services cap payloads at 4KB. Message notifications that don't fit
+//! are trimmed to a short preview; if the notification still doesn't fit,
+//! only the placeholder alert is sent (without `encrypted`, `v` or
+//! `ciphertext`) and the application should fetch the message itself.
+//!
+//! Badge updates are not encrypted.
+
+auto_derived!(
+    /// Encrypted push notification
+    pub struct EncryptedPushNotification {
+        /// Format version, currently always `1`
+        pub v: u8,
+        /// Encrypted notification as unpadded base64url
+        pub ciphertext: String,
+    }
+);
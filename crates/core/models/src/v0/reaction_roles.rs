@@ -0,0 +1,19 @@
+auto_derived!(
+    /// Binding of an emoji reaction on a message to a self-assignable role
+    pub struct ReactionRole {
+        /// Id of the message the reaction is placed on
+        pub message_id: String,
+        /// Id of the channel the message was sent in
+        pub channel_id: String,
+        /// Emoji Id or unicode emoji
+        pub emoji: String,
+        /// Id of the role granted by reacting
+        pub role_id: String,
+    }
+
+    /// Role to bind to a reaction
+    pub struct DataSetReactionRole {
+        /// Id of a self-assignable role
+        pub role_id: String,
+    }
+);
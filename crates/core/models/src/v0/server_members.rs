@@ -57,6 +57,12 @@ auto_derived_partial!(
         /// Timestamp this member is timed out until
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub timeout: Option<Timestamp>,
+        /// Whether this member has yet to accept the server's screening
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::if_false", default)
+        )]
+        pub pending: bool,
     },
     "PartialMember"
 );
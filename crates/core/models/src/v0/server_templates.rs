@@ -0,0 +1,148 @@
+use super::{Category, Role, SystemMessageChannels};
+
+use revolt_permissions::OverrideField;
+use std::collections::HashMap;
+
+#[cfg(feature = "validator")]
+use validator::Validate;
+
+auto_derived!(
+    /// Server template
+    pub struct ServerTemplate {
+        /// Template code
+        #[cfg_attr(feature = "serde", serde(rename = "_id"))]
+        pub code: String,
+        /// Id of the server this template was created from
+        pub source_server_id: String,
+        /// Id of user who created this template
+        pub creator_id: String,
+        /// Name of the template
+        pub name: String,
+        /// Description of the template
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub description: Option<String>,
+        /// Captured structure of the server
+        pub layout: ServerLayout,
+    }
+
+    /// Structure of a server without any members or messages
+    pub struct ServerLayout {
+        /// Default set of server and channel permissions
+        pub default_permissions: i64,
+        /// Whether the server is flagged as not safe for work
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::if_false", default)
+        )]
+        pub nsfw: bool,
+        /// Roles for the server
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                default = "HashMap::<String, Role>::new",
+                skip_serializing_if = "HashMap::<String, Role>::is_empty"
+            )
+        )]
+        pub roles: HashMap<String, Role>,
+        /// Categories for the server
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        pub categories: Vec<Category>,
+        /// Channels within the server
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        pub channels: Vec<TemplateChannel>,
+        /// Configuration for sending system event messages
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub system_messages: Option<SystemMessageChannels>,
+        /// Emojis for the server
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        pub emojis: Vec<TemplateEmoji>,
+    }
+
+    /// Channel captured in a server template
+    pub struct TemplateChannel {
+        /// Id of the channel in the source server
+        pub id: String,
+        /// Type of channel
+        #[cfg_attr(feature = "serde", serde(rename = "type"))]
+        pub channel_type: TemplateChannelType,
+        /// Display name of the channel
+        pub name: String,
+        /// Channel description
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub description: Option<String>,
+        /// Whether this channel is marked as not safe for work
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::if_false", default)
+        )]
+        pub nsfw: bool,
+        /// Default permissions assigned to users in this channel
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub default_permissions: Option<OverrideField>,
+        /// Permissions assigned based on role to this channel
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                default = "HashMap::<String, OverrideField>::new",
+                skip_serializing_if = "HashMap::<String, OverrideField>::is_empty"
+            )
+        )]
+        pub role_permissions: HashMap<String, OverrideField>,
+        /// Whether this channel's permissions are synced with its category
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::if_false", default)
+        )]
+        pub permissions_synced: bool,
+    }
+
+    /// Type of channel captured in a server template
+    pub enum TemplateChannelType {
+        Text,
+        Voice,
+    }
+
+    /// Emoji captured in a server template
+    pub struct TemplateEmoji {
+        /// Id of the emoji in the source server
+        pub id: String,
+        /// Emoji name
+        pub name: String,
+        /// Whether the emoji is animated
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::if_false", default)
+        )]
+        pub animated: bool,
+        /// Whether the emoji is marked as nsfw
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::if_false", default)
+        )]
+        pub nsfw: bool,
+    }
+
+    /// Information about new server template to create
+    #[derive(Default)]
+    #[cfg_attr(feature = "validator", derive(Validate))]
+    pub struct DataCreateServerTemplate {
+        /// Template name
+        #[cfg_attr(feature = "validator", validate(length(min = 1, max = 32)))]
+        pub name: String,
+        /// Template description
+        #[cfg_attr(feature = "validator", validate(length(min = 0, max = 1024)))]
+        pub description: Option<String>,
+        /// Whether to include the server's emojis
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub include_emojis: Option<bool>,
+    }
+);
@@ -33,6 +33,9 @@ auto_derived_partial!(
         /// Configuration for sending system event messages
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
         pub system_messages: Option<SystemMessageChannels>,
+        /// Rules and questions new members must accept before participating
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub screening: Option<MemberScreening>,
 
         /// Roles for this server
         #[cfg_attr(
@@ -103,6 +106,15 @@ auto_derived_partial!(
         /// Ranking of this role
         #[cfg_attr(feature = "serde", serde(default))]
         pub rank: i64,
+        /// Whether members may assign this role to themselves
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::if_false", default)
+        )]
+        pub self_assignable: bool,
+        /// Exclusive group of self-assignable roles, members may only hold one role per group
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub group: Option<String>,
     },
     "PartialRole"
 );
@@ -113,6 +125,7 @@ auto_derived!(
         Description,
         Categories,
         SystemMessages,
+        Screening,
         Icon,
         Banner,
     }
@@ -120,6 +133,7 @@ auto_derived!(
     /// Optional fields on server object
     pub enum FieldsRole {
         Colour,
+        Group,
     }
 
     /// Channel category
@@ -133,6 +147,19 @@ auto_derived!(
         pub title: String,
         /// Channels in this category
         pub channels: Vec<String>,
+
+        /// Default permissions assigned to users in this category
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub default_permissions: Option<OverrideField>,
+        /// Permissions assigned based on role to this category
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                default = "HashMap::<String, OverrideField>::new",
+                skip_serializing_if = "HashMap::<String, OverrideField>::is_empty"
+            )
+        )]
+        pub role_permissions: HashMap<String, OverrideField>,
     }
 
     /// System message channel assignments
@@ -151,6 +178,21 @@ auto_derived!(
         pub user_banned: Option<String>,
     }
 
+    /// Onboarding gate new members must pass before participating
+    #[cfg_attr(feature = "validator", derive(Validate))]
+    pub struct MemberScreening {
+        /// Rules members must accept
+        #[cfg_attr(feature = "validator", validate(length(min = 1, max = 2000)))]
+        pub rules: String,
+        /// Statements members must individually agree to
+        #[cfg_attr(feature = "validator", validate(length(max = 5)))]
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Vec::is_empty")
+        )]
+        pub questions: Vec<String>,
+    }
+
     /// Information about new server to create
     #[derive(Default)]
     #[cfg_attr(feature = "validator", derive(Validate))]
@@ -234,6 +276,9 @@ auto_derived!(
         pub categories: Option<Vec<Category>>,
         /// System message configuration
         pub system_messages: Option<SystemMessageChannels>,
+        /// Member screening configuration
+        #[cfg_attr(feature = "validator", validate)]
+        pub screening: Option<MemberScreening>,
 
         /// Bitfield of server flags
         #[cfg_attr(feature = "validator", serde(skip_serializing_if = "Option::is_none"))]
@@ -271,6 +316,11 @@ auto_derived!(
         ///
         /// **Removed** - no effect, use the edit server role positions route
         pub rank: Option<i64>,
+        /// Whether members may assign this role to themselves
+        pub self_assignable: Option<bool>,
+        /// Exclusive group of self-assignable roles
+        #[cfg_attr(feature = "validator", validate(length(min = 1, max = 32)))]
+        pub group: Option<String>,
         /// Fields to remove from role object
         #[cfg_attr(feature = "serde", serde(default))]
         pub remove: Vec<FieldsRole>,
@@ -293,4 +343,11 @@ auto_derived!(
     pub struct DataEditRoleRanks {
         pub ranks: Vec<String>,
     }
+
+    /// Acceptance of a server's member screening
+    pub struct DataAcceptScreening {
+        /// Agreement to each screening question, in order
+        #[cfg_attr(feature = "serde", serde(default))]
+        pub answers: Vec<bool>,
+    }
 );
@@ -0,0 +1,89 @@
+#[cfg(feature = "validator")]
+use validator::Validate;
+
+auto_derived_partial!(
+    /// Sticker
+    pub struct Sticker {
+        /// Unique Id
+        #[cfg_attr(feature = "serde", serde(rename = "_id"))]
+        pub id: String,
+        /// What owns this sticker
+        pub parent: StickerParent,
+        /// Uploader user id
+        pub creator_id: String,
+        /// Sticker name
+        pub name: String,
+        /// Sticker description
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub description: Option<String>,
+        /// Emoji this sticker is related to
+        ///
+        /// Either a unicode emoji or the id of a custom emoji
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub emoji: Option<String>,
+        /// Whether the sticker is animated
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::if_false", default)
+        )]
+        pub animated: bool,
+        /// Whether the sticker is marked as nsfw
+        #[cfg_attr(
+            feature = "serde",
+            serde(skip_serializing_if = "crate::if_false", default)
+        )]
+        pub nsfw: bool,
+    },
+    "PartialSticker"
+);
+
+auto_derived!(
+    /// Parent Id of the sticker
+    #[serde(tag = "type")]
+    pub enum StickerParent {
+        Server { id: String },
+        Detached,
+    }
+
+    /// Optional fields on sticker object
+    pub enum FieldsSticker {
+        Description,
+        Emoji,
+    }
+
+    /// Create a new sticker
+    #[cfg_attr(feature = "validator", derive(Validate))]
+    pub struct DataCreateSticker {
+        /// Sticker name
+        #[validate(length(min = 1, max = 32))]
+        pub name: String,
+        /// Sticker description
+        #[validate(length(min = 0, max = 128))]
+        pub description: Option<String>,
+        /// Emoji this sticker is related to
+        #[validate(length(min = 1, max = 128))]
+        pub emoji: Option<String>,
+        /// Whether the sticker is mature
+        #[serde(default)]
+        pub nsfw: bool,
+    }
+
+    /// Changes to a sticker
+    #[cfg_attr(feature = "validator", derive(Validate))]
+    pub struct DataEditSticker {
+        /// Sticker name
+        #[validate(length(min = 1, max = 32))]
+        pub name: Option<String>,
+        /// Sticker description
+        #[validate(length(min = 0, max = 128))]
+        pub description: Option<String>,
+        /// Emoji this sticker is related to
+        #[validate(length(min = 1, max = 128))]
+        pub emoji: Option<String>,
+        /// Whether the sticker is mature
+        pub nsfw: Option<bool>,
+        /// Fields to remove from sticker
+        #[cfg_attr(feature = "serde", serde(default))]
+        pub remove: Vec<FieldsSticker>,
+    }
+);
@@ -0,0 +1,50 @@
+#[cfg(feature = "validator")]
+use validator::Validate;
+
+auto_derived!(
+    /// Saved favourite
+    pub struct Favourite {
+        /// Unique Id
+        #[cfg_attr(feature = "serde", serde(rename = "_id"))]
+        pub id: String,
+        /// What was favourited
+        pub item: FavouriteItem,
+    }
+
+    /// Item that can be saved as a favourite
+    #[serde(tag = "type")]
+    pub enum FavouriteItem {
+        /// GIF found through the GIF search or an embed
+        Gif {
+            /// URL of the GIF
+            url: String,
+            /// Provider the GIF was found through
+            #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+            provider: Option<String>,
+        },
+        /// Uploaded attachment
+        Attachment {
+            /// Id of the attachment
+            attachment_id: String,
+        },
+        /// Custom emoji
+        Emoji {
+            /// Id of the emoji
+            emoji_id: String,
+        },
+    }
+
+    /// New favourite
+    pub struct DataAddFavourite {
+        /// Item to save
+        pub item: FavouriteItem,
+    }
+
+    /// New order of favourites
+    #[cfg_attr(feature = "validator", derive(Validate))]
+    pub struct DataReorderFavourites {
+        /// Ids of every favourite in the new order
+        #[cfg_attr(feature = "validator", validate(length(max = 1000)))]
+        pub ids: Vec<String>,
+    }
+);
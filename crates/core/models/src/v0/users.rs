@@ -162,6 +162,30 @@ auto_derived!(
         pub background: Option<File>,
     }
 
+    /// Who a user allows to interact with them in a given way
+    pub enum PrivacyLevel {
+        /// Anyone on the platform
+        Everyone,
+        /// Users who share a server or group with them
+        Mutual,
+        /// Only their friends
+        Friends,
+        /// No one at all
+        Nobody,
+    }
+
+    /// User's privacy settings
+    pub struct UserPrivacy {
+        /// Who may send direct messages to this user
+        pub direct_messages: PrivacyLevel,
+        /// Who may send friend requests to this user
+        ///
+        /// Only `Everyone`, `Mutual` and `Nobody` are accepted.
+        pub friend_requests: PrivacyLevel,
+        /// Who may view this user's profile
+        pub profile: PrivacyLevel,
+    }
+
     /// User badge bitfield
     #[repr(u32)]
     pub enum UserBadges {
@@ -215,6 +239,19 @@ auto_derived!(
         pub background: Option<String>,
     }
 
+    /// New user privacy settings
+    pub struct DataUserPrivacy {
+        /// Who may send direct messages to this user
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub direct_messages: Option<PrivacyLevel>,
+        /// Who may send friend requests to this user
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub friend_requests: Option<PrivacyLevel>,
+        /// Who may view this user's profile
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub profile: Option<PrivacyLevel>,
+    }
+
     /// New user information
     #[cfg_attr(feature = "validator", derive(Validate))]
     pub struct DataEditUser {
@@ -236,6 +273,11 @@ auto_derived!(
         /// This is applied as a partial.
         #[cfg_attr(feature = "validator", validate)]
         pub profile: Option<DataUserProfile>,
+        /// New user privacy settings
+        ///
+        /// This is applied as a partial.
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        pub privacy: Option<DataUserPrivacy>,
 
         /// Bitfield of user badges
         #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
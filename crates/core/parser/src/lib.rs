@@ -1,4 +1,4 @@
-use std::collections::{HashSet, VecDeque};
+use std::{collections::{HashSet, VecDeque}, ops::Range};
 
 use logos::Logos;
 
@@ -19,7 +19,9 @@ pub enum MessageToken<'a> {
     #[token("@everyone")]
     MentionEveryone,
     #[token("@online")]
-    MentionOnline
+    MentionOnline,
+    #[regex("<https?://[^>\\s]+>", |lex| lex.span())]
+    SuppressedLink(Range<usize>)
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -27,7 +29,9 @@ pub struct MessageResults {
     pub user_mentions: HashSet<String>,
     pub role_mentions: HashSet<String>,
     pub mentions_everyone: bool,
-    pub mentions_online: bool
+    pub mentions_online: bool,
+    /// Byte ranges of links wrapped in angle brackets, including the brackets
+    pub suppressed_links: Vec<Range<usize>>
 }
 
 struct MessageParserIterator<'a, I> {
@@ -87,6 +91,7 @@ pub fn parse_message(text: &str) -> MessageResults {
             MessageToken::RoleMention(id) => { results.role_mentions.insert(id.to_string()); },
             MessageToken::MentionEveryone => results.mentions_everyone = true,
             MessageToken::MentionOnline => results.mentions_online = true,
+            MessageToken::SuppressedLink(span) => results.suppressed_links.push(span),
         };
     };
 
@@ -246,6 +251,24 @@ mod tests {
         assert_eq!(output[2], MessageToken::CodeblockMarker(1));
     }
 
+    #[test]
+    fn test_suppressed_link() {
+        let output = parse_message_iter("look at <https://revolt.chat/> and https://example.com").collect::<Vec<_>>();
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0], MessageToken::SuppressedLink(8..30));
+    }
+
+    #[test]
+    fn test_suppressed_links_are_collected() {
+        let text = "<https://revolt.chat> <http://example.com/a?b=c> `<https://example.org>` \\<https://example.net> https://revolt.chat";
+        let output = parse_message(text);
+
+        assert_eq!(output.suppressed_links.len(), 2);
+        assert_eq!(&text[output.suppressed_links[0].clone()], "<https://revolt.chat>");
+        assert_eq!(&text[output.suppressed_links[1].clone()], "<http://example.com/a?b=c>");
+    }
+
     #[test]
     fn test_escape_mention() {
         let output = parse_message_iter("i wont ping \\@everyone").collect::<Vec<_>>();
@@ -1,7 +1,8 @@
 use crate::{
-    ChannelPermission, ChannelType, PermissionQuery, PermissionValue, RelationshipStatus,
-    UserPermission, ALLOW_IN_TIMEOUT, DEFAULT_PERMISSION_DIRECT_MESSAGE,
-    DEFAULT_PERMISSION_SAVED_MESSAGES, DEFAULT_PERMISSION_VIEW_ONLY,
+    ChannelPermission, ChannelType, PermissionLayer, PermissionQuery, PermissionTraceStep,
+    PermissionTracer, PermissionValue, PrivacyLevel, RelationshipStatus, UserPermission,
+    ALLOW_IN_TIMEOUT, DEFAULT_PERMISSION_DIRECT_MESSAGE, DEFAULT_PERMISSION_SAVED_MESSAGES,
+    DEFAULT_PERMISSION_VIEW_ONLY,
 };
 
 /// Calculate permissions against a user
@@ -16,65 +17,161 @@ pub async fn calculate_user_permissions<P: PermissionQuery>(query: &mut P) -> Pe
 
     let mut permissions = 0_u64;
     match query.user_relationship().await {
-        RelationshipStatus::Friend => return u64::MAX.into(),
+        RelationshipStatus::Friend => {
+            let privacy = query.user_privacy().await;
+            let mut permissions: PermissionValue = u64::MAX.into();
+
+            if privacy.direct_messages == PrivacyLevel::Nobody {
+                permissions.revoke(UserPermission::SendMessage as u64);
+            }
+
+            if privacy.profile == PrivacyLevel::Nobody {
+                permissions.revoke(UserPermission::ViewProfile as u64);
+            }
+
+            return permissions;
+        }
         RelationshipStatus::Blocked | RelationshipStatus::BlockedOther => {
             return (UserPermission::Access as u64).into()
         }
         RelationshipStatus::Incoming | RelationshipStatus::Outgoing => {
-            permissions = UserPermission::Access as u64;
+            // Pending requests can always be answered
+            permissions = UserPermission::Access as u64 | UserPermission::SendFriendRequest as u64;
         }
         _ => {}
     }
 
-    if query.have_mutual_connection().await {
-        permissions = UserPermission::Access as u64 + UserPermission::ViewProfile as u64;
+    let privacy = query.user_privacy().await;
+    let mutual = query.have_mutual_connection().await;
 
-        if query.user_is_bot().await || query.are_we_a_bot().await {
-            permissions += UserPermission::SendMessage as u64;
-        }
+    if mutual {
+        permissions |= UserPermission::Access as u64;
+    }
+
+    if privacy.profile.permits(mutual) {
+        permissions |= UserPermission::Access as u64 | UserPermission::ViewProfile as u64;
+    }
+
+    // Bots may message anyone they share a connection with,
+    // unless the user has turned off direct messages entirely
+    let bot_exempt = mutual
+        && privacy.direct_messages != PrivacyLevel::Nobody
+        && (query.user_is_bot().await || query.are_we_a_bot().await);
 
-        permissions.into()
-    } else {
-        permissions.into()
+    if bot_exempt || privacy.direct_messages.permits(mutual) {
+        permissions |= UserPermission::Access as u64 | UserPermission::SendMessage as u64;
     }
 
-    // TODO: add boolean switch for permission for users to globally message a user
-    // maybe an enum?
-    // PrivacyLevel { Private, Friends, Mutual, Public, Global }
+    if privacy.friend_requests.permits(mutual) {
+        permissions |= UserPermission::SendFriendRequest as u64;
+    }
 
-    // TODO: add boolean switch for permission for users to mutually DM a user
+    permissions.into()
 }
 
 /// Calculate permissions against a server
 pub async fn calculate_server_permissions<P: PermissionQuery>(query: &mut P) -> PermissionValue {
-    if query.are_we_privileged().await || query.are_we_server_owner().await {
-        return ChannelPermission::GrantAllSafe.into();
+    server_permissions(query, &mut PermissionTracer::disabled()).await
+}
+
+/// Calculate permissions against a channel
+pub async fn calculate_channel_permissions<P: PermissionQuery>(query: &mut P) -> PermissionValue {
+    channel_permissions(query, &mut PermissionTracer::disabled()).await
+}
+
+/// Calculate permissions against a server, recording each layer applied
+pub async fn explain_server_permissions<P: PermissionQuery>(
+    query: &mut P,
+) -> (PermissionValue, Vec<PermissionTraceStep>) {
+    let mut tracer = PermissionTracer::enabled();
+    let permissions = server_permissions(query, &mut tracer).await;
+    (permissions, tracer.into_steps())
+}
+
+/// Calculate permissions against a channel, recording each layer applied
+pub async fn explain_channel_permissions<P: PermissionQuery>(
+    query: &mut P,
+) -> (PermissionValue, Vec<PermissionTraceStep>) {
+    let mut tracer = PermissionTracer::enabled();
+    let permissions = channel_permissions(query, &mut tracer).await;
+    (permissions, tracer.into_steps())
+}
+
+async fn server_permissions<P: PermissionQuery>(
+    query: &mut P,
+    tracer: &mut PermissionTracer,
+) -> PermissionValue {
+    if query.are_we_privileged().await {
+        let permissions = ChannelPermission::GrantAllSafe.into();
+        tracer.record(PermissionLayer::Privileged, None, permissions);
+        return permissions;
+    }
+
+    if query.are_we_server_owner().await {
+        let permissions = ChannelPermission::GrantAllSafe.into();
+        tracer.record(PermissionLayer::ServerOwner, None, permissions);
+        return permissions;
     }
 
     if !query.are_we_a_member().await {
-        return 0_u64.into();
+        let permissions = 0_u64.into();
+        tracer.record(PermissionLayer::NotMember, None, permissions);
+        return permissions;
     }
 
+    let mut permissions = server_role_permissions(query, tracer).await;
+    restrict_member(query, &mut permissions, tracer).await;
+    permissions
+}
+
+/// Apply the server's default permissions and our role overrides
+async fn server_role_permissions<P: PermissionQuery>(
+    query: &mut P,
+    tracer: &mut PermissionTracer,
+) -> PermissionValue {
     let mut permissions: PermissionValue = query.get_default_server_permissions().await.into();
+    tracer.record(PermissionLayer::ServerDefault, None, permissions);
 
-    for role_override in query.get_our_server_role_overrides().await {
-        permissions.apply(role_override);
+    for (role_id, rank, role_override) in query.get_our_server_role_overrides().await {
+        permissions.apply(role_override.clone());
+        tracer.record(
+            PermissionLayer::ServerRole { role_id, rank },
+            Some(&role_override),
+            permissions,
+        );
     }
 
+    permissions
+}
+
+/// Restrict the permissions of members who are timed out or pending screening
+async fn restrict_member<P: PermissionQuery>(
+    query: &mut P,
+    permissions: &mut PermissionValue,
+    tracer: &mut PermissionTracer,
+) {
     if query.are_we_timed_out().await {
         permissions.restrict(*ALLOW_IN_TIMEOUT);
+        tracer.record(PermissionLayer::Timeout, None, *permissions);
     }
 
-    permissions
+    if query.are_we_pending_screening().await {
+        permissions.restrict(*DEFAULT_PERMISSION_VIEW_ONLY);
+        tracer.record(PermissionLayer::Screening, None, *permissions);
+    }
 }
 
-/// Calculate permissions against a channel
-pub async fn calculate_channel_permissions<P: PermissionQuery>(query: &mut P) -> PermissionValue {
+async fn channel_permissions<P: PermissionQuery>(
+    query: &mut P,
+    tracer: &mut PermissionTracer,
+) -> PermissionValue {
     if query.are_we_privileged().await {
-        return ChannelPermission::GrantAllSafe.into();
+        let permissions = ChannelPermission::GrantAllSafe.into();
+        tracer.record(PermissionLayer::Privileged, None, permissions);
+        return permissions;
     }
 
-    match query.get_channel_type().await {
+    let permissions: PermissionValue = match query.get_channel_type().await {
         ChannelType::SavedMessages => {
             if query.do_we_own_the_channel().await {
                 DEFAULT_PERMISSION_SAVED_MESSAGES.into()
@@ -111,28 +208,60 @@ pub async fn calculate_channel_permissions<P: PermissionQuery>(query: &mut P) ->
             query.set_server_from_channel().await;
 
             if query.are_we_server_owner().await {
-                ChannelPermission::GrantAllSafe.into()
+                let permissions = ChannelPermission::GrantAllSafe.into();
+                tracer.record(PermissionLayer::ServerOwner, None, permissions);
+                return permissions;
             } else if query.are_we_a_member().await {
-                let mut permissions = calculate_server_permissions(query).await;
-                permissions.apply(query.get_default_channel_permissions().await);
+                let mut permissions = server_role_permissions(query, tracer).await;
 
-                for role_override in query.get_our_channel_role_overrides().await {
-                    permissions.apply(role_override);
+                let category_default = query.get_default_category_permissions().await;
+                permissions.apply(category_default.clone());
+                tracer.record(
+                    PermissionLayer::CategoryDefault,
+                    Some(&category_default),
+                    permissions,
+                );
+
+                for (role_id, rank, role_override) in query.get_our_category_role_overrides().await
+                {
+                    permissions.apply(role_override.clone());
+                    tracer.record(
+                        PermissionLayer::CategoryRole { role_id, rank },
+                        Some(&role_override),
+                        permissions,
+                    );
                 }
 
-                if query.are_we_timed_out().await {
-                    permissions.restrict(*ALLOW_IN_TIMEOUT);
+                let channel_default = query.get_default_channel_permissions().await;
+                permissions.apply(channel_default.clone());
+                tracer.record(
+                    PermissionLayer::ChannelDefault,
+                    Some(&channel_default),
+                    permissions,
+                );
+
+                for (layer, channel_override) in query.get_our_channel_role_overrides().await {
+                    permissions.apply(channel_override.clone());
+                    tracer.record(layer, Some(&channel_override), permissions);
                 }
 
+                restrict_member(query, &mut permissions, tracer).await;
+
                 if !permissions.has_channel_permission(ChannelPermission::ViewChannel) {
                     permissions.revoke_all();
+                    tracer.record(PermissionLayer::MissingViewChannel, None, permissions);
                 }
 
-                permissions
+                return permissions;
             } else {
-                0_u64.into()
+                let permissions = 0_u64.into();
+                tracer.record(PermissionLayer::NotMember, None, permissions);
+                return permissions;
             }
         }
         ChannelType::Unknown => 0_u64.into(),
-    }
+    };
+
+    tracer.record(PermissionLayer::Base, None, permissions);
+    permissions
 }
@@ -0,0 +1,108 @@
+#[cfg(feature = "schemas")]
+use schemars::JsonSchema;
+
+use super::{Override, PermissionValue};
+
+/// Layer of the permission calculation
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemas", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum PermissionLayer {
+    /// User is privileged and receives all permissions
+    Privileged,
+    /// User owns the server and receives all permissions
+    ServerOwner,
+    /// User is not a member of the server
+    NotMember,
+    /// Fixed permissions for direct messages, groups and saved messages
+    Base,
+    /// Default server permissions
+    ServerDefault,
+    /// Role override from the server
+    ServerRole {
+        /// Id of the role
+        role_id: String,
+        /// Rank of the role
+        rank: i64,
+    },
+    /// Default permissions of the channel's category
+    CategoryDefault,
+    /// Role override from the channel's category
+    CategoryRole {
+        /// Id of the role
+        role_id: String,
+        /// Rank of the role
+        rank: i64,
+    },
+    /// Default channel permissions
+    ChannelDefault,
+    /// Role override from the channel
+    ChannelRole {
+        /// Id of the role
+        role_id: String,
+        /// Rank of the role
+        rank: i64,
+    },
+    /// Override for this specific member from the channel
+    MemberOverride,
+    /// Member is timed out and restricted to a limited set of permissions
+    Timeout,
+    /// Member has not yet passed screening and is restricted to viewing
+    Screening,
+    /// Member cannot view the channel so all permissions are revoked
+    MissingViewChannel,
+}
+
+/// Single step of a permission calculation
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemas", derive(JsonSchema))]
+pub struct PermissionTraceStep {
+    /// Layer which was applied
+    pub layer: PermissionLayer,
+    /// Override applied by this layer, if any
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "override", skip_serializing_if = "Option::is_none")
+    )]
+    pub applied: Option<Override>,
+    /// Resulting permissions after this layer
+    pub permissions: u64,
+}
+
+/// Records the steps of a permission calculation
+pub(crate) struct PermissionTracer(Option<Vec<PermissionTraceStep>>);
+
+impl PermissionTracer {
+    /// Tracer which discards every step
+    pub fn disabled() -> Self {
+        Self(None)
+    }
+
+    /// Tracer which keeps every step
+    pub fn enabled() -> Self {
+        Self(Some(vec![]))
+    }
+
+    /// Record a layer and the resulting permissions
+    pub fn record(
+        &mut self,
+        layer: PermissionLayer,
+        applied: Option<&Override>,
+        permissions: PermissionValue,
+    ) {
+        if let Some(steps) = &mut self.0 {
+            steps.push(PermissionTraceStep {
+                layer,
+                applied: applied.cloned(),
+                permissions: permissions.into(),
+            });
+        }
+    }
+
+    /// Take the recorded steps
+    pub fn into_steps(self) -> Vec<PermissionTraceStep> {
+        self.0.unwrap_or_default()
+    }
+}
@@ -11,6 +11,51 @@ pub enum RelationshipStatus {
     BlockedOther,
 }
 
+/// Who a user allows to interact with them in a given way
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum PrivacyLevel {
+    /// Anyone on the platform
+    Everyone,
+    /// Users who share a server or group with them
+    Mutual,
+    /// Only their friends
+    Friends,
+    /// No one at all
+    Nobody,
+}
+
+impl PrivacyLevel {
+    /// Whether a user who is not a friend passes this level
+    pub fn permits(&self, mutual: bool) -> bool {
+        match self {
+            PrivacyLevel::Everyone => true,
+            PrivacyLevel::Mutual => mutual,
+            PrivacyLevel::Friends | PrivacyLevel::Nobody => false,
+        }
+    }
+}
+
+/// Privacy settings of a user
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct UserPrivacy {
+    /// Who may send them direct messages
+    pub direct_messages: PrivacyLevel,
+    /// Who may send them friend requests
+    pub friend_requests: PrivacyLevel,
+    /// Who may view their profile
+    pub profile: PrivacyLevel,
+}
+
+impl Default for UserPrivacy {
+    fn default() -> Self {
+        Self {
+            direct_messages: PrivacyLevel::Friends,
+            friend_requests: PrivacyLevel::Everyone,
+            profile: PrivacyLevel::Mutual,
+        }
+    }
+}
+
 /// User permission definitions
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -21,6 +66,7 @@ pub enum UserPermission {
     ViewProfile = 1 << 1,
     SendMessage = 1 << 2,
     Invite = 1 << 3,
+    SendFriendRequest = 1 << 4,
 }
 
 impl fmt::Display for UserPermission {
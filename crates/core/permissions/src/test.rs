@@ -1,7 +1,8 @@
 use crate::{
-    calculate_channel_permissions, calculate_user_permissions, ChannelPermission, ChannelType,
-    Override, PermissionQuery, RelationshipStatus, DEFAULT_PERMISSION_DIRECT_MESSAGE,
-    DEFAULT_PERMISSION_SERVER, DEFAULT_PERMISSION_VIEW_ONLY,
+    calculate_channel_permissions, calculate_user_permissions, explain_channel_permissions,
+    ChannelPermission, ChannelType, Override, PermissionLayer, PermissionQuery, PermissionValue,
+    PrivacyLevel, RelationshipStatus, UserPermission, UserPrivacy,
+    DEFAULT_PERMISSION_DIRECT_MESSAGE, DEFAULT_PERMISSION_SERVER, DEFAULT_PERMISSION_VIEW_ONLY,
 };
 
 #[async_std::test]
@@ -44,6 +45,10 @@ async fn validate_user_permissions() {
             false
         }
 
+        async fn user_privacy(&mut self) -> UserPrivacy {
+            UserPrivacy::default()
+        }
+
         async fn are_we_server_owner(&mut self) -> bool {
             unreachable!()
         }
@@ -56,7 +61,7 @@ async fn validate_user_permissions() {
             unreachable!()
         }
 
-        async fn get_our_server_role_overrides(&mut self) -> Vec<Override> {
+        async fn get_our_server_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
             unreachable!()
         }
 
@@ -64,6 +69,10 @@ async fn validate_user_permissions() {
             unreachable!()
         }
 
+        async fn are_we_pending_screening(&mut self) -> bool {
+            unreachable!()
+        }
+
         async fn get_channel_type(&mut self) -> ChannelType {
             ChannelType::DirectMessage
         }
@@ -72,7 +81,15 @@ async fn validate_user_permissions() {
             unreachable!()
         }
 
-        async fn get_our_channel_role_overrides(&mut self) -> Vec<Override> {
+        async fn get_default_category_permissions(&mut self) -> Override {
+            unreachable!()
+        }
+
+        async fn get_our_category_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
+            unreachable!()
+        }
+
+        async fn get_our_channel_role_overrides(&mut self) -> Vec<(PermissionLayer, Override)> {
             unreachable!()
         }
 
@@ -133,6 +150,10 @@ async fn validate_group_permissions() {
             unreachable!()
         }
 
+        async fn user_privacy(&mut self) -> UserPrivacy {
+            unreachable!()
+        }
+
         async fn are_we_server_owner(&mut self) -> bool {
             unreachable!()
         }
@@ -145,7 +166,7 @@ async fn validate_group_permissions() {
             unreachable!()
         }
 
-        async fn get_our_server_role_overrides(&mut self) -> Vec<Override> {
+        async fn get_our_server_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
             unreachable!()
         }
 
@@ -153,6 +174,10 @@ async fn validate_group_permissions() {
             unreachable!()
         }
 
+        async fn are_we_pending_screening(&mut self) -> bool {
+            unreachable!()
+        }
+
         async fn get_channel_type(&mut self) -> ChannelType {
             ChannelType::Group
         }
@@ -164,7 +189,15 @@ async fn validate_group_permissions() {
             }
         }
 
-        async fn get_our_channel_role_overrides(&mut self) -> Vec<Override> {
+        async fn get_default_category_permissions(&mut self) -> Override {
+            unreachable!()
+        }
+
+        async fn get_our_category_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
+            unreachable!()
+        }
+
+        async fn get_our_channel_role_overrides(&mut self) -> Vec<(PermissionLayer, Override)> {
             unreachable!()
         }
 
@@ -229,6 +262,10 @@ async fn validate_server_permissions() {
             unreachable!()
         }
 
+        async fn user_privacy(&mut self) -> UserPrivacy {
+            unreachable!()
+        }
+
         async fn are_we_server_owner(&mut self) -> bool {
             false
         }
@@ -243,17 +280,25 @@ async fn validate_server_permissions() {
                 | ChannelPermission::ReadMessageHistory as u64
         }
 
-        async fn get_our_server_role_overrides(&mut self) -> Vec<Override> {
-            vec![Override {
-                allow: ChannelPermission::UploadFiles as u64 | ChannelPermission::React as u64,
-                deny: ChannelPermission::ReadMessageHistory as u64,
-            }]
+        async fn get_our_server_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
+            vec![(
+                "01ROLE".to_string(),
+                0,
+                Override {
+                    allow: ChannelPermission::UploadFiles as u64 | ChannelPermission::React as u64,
+                    deny: ChannelPermission::ReadMessageHistory as u64,
+                },
+            )]
         }
 
         async fn are_we_timed_out(&mut self) -> bool {
             false
         }
 
+        async fn are_we_pending_screening(&mut self) -> bool {
+            false
+        }
+
         async fn get_channel_type(&mut self) -> ChannelType {
             ChannelType::ServerChannel
         }
@@ -265,11 +310,25 @@ async fn validate_server_permissions() {
             }
         }
 
-        async fn get_our_channel_role_overrides(&mut self) -> Vec<Override> {
-            vec![Override {
-                allow: 0,
-                deny: ChannelPermission::React as u64,
-            }]
+        async fn get_default_category_permissions(&mut self) -> Override {
+            Override { allow: 0, deny: 0 }
+        }
+
+        async fn get_our_category_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
+            vec![]
+        }
+
+        async fn get_our_channel_role_overrides(&mut self) -> Vec<(PermissionLayer, Override)> {
+            vec![(
+                PermissionLayer::ChannelRole {
+                    role_id: "01ROLE".to_string(),
+                    rank: 0,
+                },
+                Override {
+                    allow: 0,
+                    deny: ChannelPermission::React as u64,
+                },
+            )]
         }
 
         async fn do_we_own_the_channel(&mut self) -> bool {
@@ -300,6 +359,119 @@ async fn validate_timed_out_member() {
     let value: u64 = perms.into();
     assert_eq!(value, *DEFAULT_PERMISSION_VIEW_ONLY);
 
+    // Timeout is only applied once
+    let (_, trace) = explain_channel_permissions(&mut query).await;
+    assert_eq!(
+        trace
+            .iter()
+            .filter(|step| step.layer == PermissionLayer::Timeout)
+            .count(),
+        1
+    );
+
+    #[async_trait]
+    impl PermissionQuery for Scenario {
+        async fn are_we_privileged(&mut self) -> bool {
+            false
+        }
+
+        async fn are_we_a_bot(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn are_the_users_same(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn user_relationship(&mut self) -> RelationshipStatus {
+            unreachable!()
+        }
+
+        async fn user_is_bot(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn have_mutual_connection(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn user_privacy(&mut self) -> UserPrivacy {
+            unreachable!()
+        }
+
+        async fn are_we_server_owner(&mut self) -> bool {
+            false
+        }
+
+        async fn are_we_a_member(&mut self) -> bool {
+            true
+        }
+
+        async fn get_default_server_permissions(&mut self) -> u64 {
+            *DEFAULT_PERMISSION_SERVER
+        }
+
+        async fn get_our_server_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
+            vec![]
+        }
+
+        async fn are_we_timed_out(&mut self) -> bool {
+            true
+        }
+
+        async fn are_we_pending_screening(&mut self) -> bool {
+            false
+        }
+
+        async fn get_channel_type(&mut self) -> ChannelType {
+            ChannelType::ServerChannel
+        }
+
+        async fn get_default_channel_permissions(&mut self) -> Override {
+            Override { allow: 0, deny: 0 }
+        }
+
+        async fn get_default_category_permissions(&mut self) -> Override {
+            Override { allow: 0, deny: 0 }
+        }
+
+        async fn get_our_category_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
+            vec![]
+        }
+
+        async fn get_our_channel_role_overrides(&mut self) -> Vec<(PermissionLayer, Override)> {
+            vec![]
+        }
+
+        async fn do_we_own_the_channel(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn are_we_part_of_the_channel(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn set_recipient_as_user(&mut self) {
+            unreachable!()
+        }
+
+        async fn set_server_from_channel(&mut self) {
+            // no-op
+        }
+    }
+}
+
+#[async_std::test]
+async fn validate_pending_member() {
+    /// Scenario in which we have joined a server but not yet passed its screening,
+    /// even though a channel override would otherwise let us send messages
+    struct Scenario {}
+    let mut query = Scenario {};
+
+    let perms = calculate_channel_permissions(&mut query).await;
+    let value: u64 = perms.into();
+    assert_eq!(value, *DEFAULT_PERMISSION_VIEW_ONLY);
+
     #[async_trait]
     impl PermissionQuery for Scenario {
         async fn are_we_privileged(&mut self) -> bool {
@@ -326,6 +498,10 @@ async fn validate_timed_out_member() {
             unreachable!()
         }
 
+        async fn user_privacy(&mut self) -> UserPrivacy {
+            unreachable!()
+        }
+
         async fn are_we_server_owner(&mut self) -> bool {
             false
         }
@@ -338,11 +514,15 @@ async fn validate_timed_out_member() {
             *DEFAULT_PERMISSION_SERVER
         }
 
-        async fn get_our_server_role_overrides(&mut self) -> Vec<Override> {
+        async fn get_our_server_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
             vec![]
         }
 
         async fn are_we_timed_out(&mut self) -> bool {
+            false
+        }
+
+        async fn are_we_pending_screening(&mut self) -> bool {
             true
         }
 
@@ -354,10 +534,347 @@ async fn validate_timed_out_member() {
             Override { allow: 0, deny: 0 }
         }
 
-        async fn get_our_channel_role_overrides(&mut self) -> Vec<Override> {
+        async fn get_default_category_permissions(&mut self) -> Override {
+            Override { allow: 0, deny: 0 }
+        }
+
+        async fn get_our_category_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
             vec![]
         }
 
+        async fn get_our_channel_role_overrides(&mut self) -> Vec<(PermissionLayer, Override)> {
+            vec![(
+                PermissionLayer::ChannelRole {
+                    role_id: "01ROLE".to_string(),
+                    rank: 0,
+                },
+                Override {
+                    allow: ChannelPermission::SendMessage as u64,
+                    deny: 0,
+                },
+            )]
+        }
+
+        async fn do_we_own_the_channel(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn are_we_part_of_the_channel(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn set_recipient_as_user(&mut self) {
+            unreachable!()
+        }
+
+        async fn set_server_from_channel(&mut self) {
+            // no-op
+        }
+    }
+}
+
+#[async_std::test]
+async fn validate_privacy_settings() {
+    /// Scenario in which we have a DM channel open with another user
+    /// who has customised who may interact with them
+    struct Scenario {
+        friends: bool,
+        mutual: bool,
+        privacy: UserPrivacy,
+    }
+
+    // Stranger who accepts DMs from mutuals but hides their profile and friend requests
+    let mut query = Scenario {
+        friends: false,
+        mutual: true,
+        privacy: UserPrivacy {
+            direct_messages: PrivacyLevel::Mutual,
+            friend_requests: PrivacyLevel::Nobody,
+            profile: PrivacyLevel::Nobody,
+        },
+    };
+
+    let perms = calculate_user_permissions(&mut query).await;
+    let value: u64 = perms.into();
+    assert_eq!(
+        value,
+        UserPermission::Access as u64 | UserPermission::SendMessage as u64
+    );
+
+    let perms = calculate_channel_permissions(&mut query).await;
+    let value: u64 = perms.into();
+    assert_eq!(value, *DEFAULT_PERMISSION_DIRECT_MESSAGE);
+
+    // Stranger without a mutual connection using the default settings
+    let mut query = Scenario {
+        friends: false,
+        mutual: false,
+        privacy: UserPrivacy::default(),
+    };
+
+    let perms = calculate_user_permissions(&mut query).await;
+    let value: u64 = perms.into();
+    assert_eq!(value, UserPermission::SendFriendRequest as u64);
+
+    // Stranger without a mutual connection who accepts DMs from everyone
+    query.privacy.direct_messages = PrivacyLevel::Everyone;
+
+    let perms = calculate_user_permissions(&mut query).await;
+    assert!(perms.has_user_permission(UserPermission::SendMessage));
+    assert!(!perms.has_user_permission(UserPermission::ViewProfile));
+
+    // Friend who has turned off direct messages entirely
+    let mut query = Scenario {
+        friends: true,
+        mutual: true,
+        privacy: UserPrivacy {
+            direct_messages: PrivacyLevel::Nobody,
+            ..Default::default()
+        },
+    };
+
+    let perms = calculate_user_permissions(&mut query).await;
+    assert!(perms.has_user_permission(UserPermission::ViewProfile));
+    assert!(!perms.has_user_permission(UserPermission::SendMessage));
+
+    let perms = calculate_channel_permissions(&mut query).await;
+    let value: u64 = perms.into();
+    assert_eq!(value, *DEFAULT_PERMISSION_VIEW_ONLY);
+
+    #[async_trait]
+    impl PermissionQuery for Scenario {
+        async fn are_we_privileged(&mut self) -> bool {
+            false
+        }
+
+        async fn are_we_a_bot(&mut self) -> bool {
+            false
+        }
+
+        async fn are_the_users_same(&mut self) -> bool {
+            false
+        }
+
+        async fn user_relationship(&mut self) -> RelationshipStatus {
+            if self.friends {
+                RelationshipStatus::Friend
+            } else {
+                RelationshipStatus::None
+            }
+        }
+
+        async fn user_is_bot(&mut self) -> bool {
+            false
+        }
+
+        async fn have_mutual_connection(&mut self) -> bool {
+            self.mutual
+        }
+
+        async fn user_privacy(&mut self) -> UserPrivacy {
+            self.privacy
+        }
+
+        async fn are_we_server_owner(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn are_we_a_member(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn get_default_server_permissions(&mut self) -> u64 {
+            unreachable!()
+        }
+
+        async fn get_our_server_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
+            unreachable!()
+        }
+
+        async fn are_we_timed_out(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn are_we_pending_screening(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn get_channel_type(&mut self) -> ChannelType {
+            ChannelType::DirectMessage
+        }
+
+        async fn get_default_channel_permissions(&mut self) -> Override {
+            unreachable!()
+        }
+
+        async fn get_default_category_permissions(&mut self) -> Override {
+            unreachable!()
+        }
+
+        async fn get_our_category_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
+            unreachable!()
+        }
+
+        async fn get_our_channel_role_overrides(&mut self) -> Vec<(PermissionLayer, Override)> {
+            unreachable!()
+        }
+
+        async fn do_we_own_the_channel(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn are_we_part_of_the_channel(&mut self) -> bool {
+            true
+        }
+
+        async fn set_recipient_as_user(&mut self) {
+            // no-op
+        }
+
+        async fn set_server_from_channel(&mut self) {
+            unreachable!()
+        }
+    }
+}
+
+#[async_std::test]
+async fn validate_category_permissions() {
+    /// Scenario in which we are in a server channel inside a category where:
+    /// - the server grants viewing, reading history and sending messages by default
+    /// - the category denies sending messages by default
+    /// - but our role is allowed to send messages and react in the category
+    /// - and the channel itself denies reacting by default
+    /// - while we are specifically denied uploading files in the channel
+    struct Scenario {}
+    let mut query = Scenario {};
+
+    let perms = calculate_channel_permissions(&mut query).await;
+    let value: u64 = perms.into();
+    assert_eq!(
+        value,
+        ChannelPermission::ViewChannel as u64
+            | ChannelPermission::SendMessage as u64
+            | ChannelPermission::ReadMessageHistory as u64
+    );
+
+    // Explaining the calculation yields the same result and every layer in order
+    let (explained, trace) = explain_channel_permissions(&mut query).await;
+    assert_eq!(explained, perms);
+    assert_eq!(
+        trace
+            .iter()
+            .map(|step| step.layer.clone())
+            .collect::<Vec<PermissionLayer>>(),
+        vec![
+            PermissionLayer::ServerDefault,
+            PermissionLayer::CategoryDefault,
+            PermissionLayer::CategoryRole {
+                role_id: "01ROLE".to_string(),
+                rank: 0,
+            },
+            PermissionLayer::ChannelDefault,
+            PermissionLayer::MemberOverride,
+        ]
+    );
+    assert!(!PermissionValue::from(trace[1].permissions)
+        .has_channel_permission(ChannelPermission::SendMessage));
+    assert_eq!(trace.last().unwrap().permissions, value);
+
+    #[async_trait]
+    impl PermissionQuery for Scenario {
+        async fn are_we_privileged(&mut self) -> bool {
+            false
+        }
+
+        async fn are_we_a_bot(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn are_the_users_same(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn user_relationship(&mut self) -> RelationshipStatus {
+            unreachable!()
+        }
+
+        async fn user_is_bot(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn have_mutual_connection(&mut self) -> bool {
+            unreachable!()
+        }
+
+        async fn user_privacy(&mut self) -> UserPrivacy {
+            unreachable!()
+        }
+
+        async fn are_we_server_owner(&mut self) -> bool {
+            false
+        }
+
+        async fn are_we_a_member(&mut self) -> bool {
+            true
+        }
+
+        async fn get_default_server_permissions(&mut self) -> u64 {
+            ChannelPermission::ViewChannel as u64
+                | ChannelPermission::SendMessage as u64
+                | ChannelPermission::ReadMessageHistory as u64
+        }
+
+        async fn get_our_server_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
+            vec![]
+        }
+
+        async fn are_we_timed_out(&mut self) -> bool {
+            false
+        }
+
+        async fn are_we_pending_screening(&mut self) -> bool {
+            false
+        }
+
+        async fn get_channel_type(&mut self) -> ChannelType {
+            ChannelType::ServerChannel
+        }
+
+        async fn get_default_channel_permissions(&mut self) -> Override {
+            Override {
+                allow: 0,
+                deny: ChannelPermission::React as u64,
+            }
+        }
+
+        async fn get_default_category_permissions(&mut self) -> Override {
+            Override {
+                allow: 0,
+                deny: ChannelPermission::SendMessage as u64,
+            }
+        }
+
+        async fn get_our_category_role_overrides(&mut self) -> Vec<(String, i64, Override)> {
+            vec![(
+                "01ROLE".to_string(),
+                0,
+                Override {
+                    allow: ChannelPermission::SendMessage as u64 | ChannelPermission::React as u64,
+                    deny: 0,
+                },
+            )]
+        }
+
+        async fn get_our_channel_role_overrides(&mut self) -> Vec<(PermissionLayer, Override)> {
+            vec![(
+                PermissionLayer::MemberOverride,
+                Override {
+                    allow: 0,
+                    deny: ChannelPermission::UploadFiles as u64,
+                },
+            )]
+        }
+
         async fn do_we_own_the_channel(&mut self) -> bool {
             unreachable!()
         }
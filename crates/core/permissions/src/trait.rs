@@ -1,4 +1,4 @@
-use crate::{ChannelType, Override, RelationshipStatus};
+use crate::{ChannelType, Override, PermissionLayer, RelationshipStatus, UserPrivacy};
 
 #[async_trait]
 pub trait PermissionQuery {
@@ -22,6 +22,9 @@ pub trait PermissionQuery {
     /// Do we have a mutual connection with the currently selected user?
     async fn have_mutual_connection(&mut self) -> bool;
 
+    /// Get the privacy settings of the currently selected user
+    async fn user_privacy(&mut self) -> UserPrivacy;
+
     // * For calculating server permission
 
     /// Is our perspective user the server's owner?
@@ -34,11 +37,15 @@ pub trait PermissionQuery {
     async fn get_default_server_permissions(&mut self) -> u64;
 
     /// Get the ordered role overrides (from lowest to highest) for this member in this server
-    async fn get_our_server_role_overrides(&mut self) -> Vec<Override>;
+    /// as (role id, rank, override)
+    async fn get_our_server_role_overrides(&mut self) -> Vec<(String, i64, Override)>;
 
     /// Is our perspective user timed out on this server?
     async fn are_we_timed_out(&mut self) -> bool;
 
+    /// Is our perspective user yet to pass this server's member screening?
+    async fn are_we_pending_screening(&mut self) -> bool;
+
     // * For calculating channel permission
 
     /// Get the type of the channel
@@ -48,8 +55,16 @@ pub trait PermissionQuery {
     /// Group channel defaults should be mapped to an allow-only override
     async fn get_default_channel_permissions(&mut self) -> Override;
 
-    /// Get the ordered role overrides (from lowest to highest) for this member in this channel
-    async fn get_our_channel_role_overrides(&mut self) -> Vec<Override>;
+    /// Get the default permissions of the category this channel belongs to
+    async fn get_default_category_permissions(&mut self) -> Override;
+
+    /// Get the ordered role overrides (from lowest to highest) for this member in this channel's category
+    /// as (role id, rank, override)
+    async fn get_our_category_role_overrides(&mut self) -> Vec<(String, i64, Override)>;
+
+    /// Get the ordered role overrides (from lowest to highest) for this member in this channel,
+    /// followed by any override for this specific member, as (layer, override)
+    async fn get_our_channel_role_overrides(&mut self) -> Vec<(PermissionLayer, Override)>;
 
     /// Do we own this group or saved messages channel if it is one of those?
     async fn do_we_own_the_channel(&mut self) -> bool;
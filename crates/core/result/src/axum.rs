@@ -20,6 +20,8 @@ impl IntoResponse for Error {
             ErrorType::BlockedByOther => StatusCode::FORBIDDEN,
             ErrorType::NotFriends => StatusCode::FORBIDDEN,
             ErrorType::TooManyPendingFriendRequests { .. } => StatusCode::BAD_REQUEST,
+            ErrorType::TooManyFavourites { .. } => StatusCode::BAD_REQUEST,
+            ErrorType::FavouritesChanged => StatusCode::CONFLICT,
 
             ErrorType::UnknownChannel => StatusCode::NOT_FOUND,
             ErrorType::UnknownMessage => StatusCode::NOT_FOUND,
@@ -36,6 +38,8 @@ impl IntoResponse for Error {
             ErrorType::NotInGroup => StatusCode::NOT_FOUND,
             ErrorType::AlreadyPinned => StatusCode::BAD_REQUEST,
             ErrorType::NotPinned => StatusCode::BAD_REQUEST,
+            ErrorType::InSlowmode { .. } => StatusCode::TOO_MANY_REQUESTS,
+            ErrorType::BlockedByAutoMod { .. } => StatusCode::FORBIDDEN,
 
             ErrorType::UnknownServer => StatusCode::NOT_FOUND,
             ErrorType::InvalidRole => StatusCode::NOT_FOUND,
@@ -46,6 +50,8 @@ impl IntoResponse for Error {
             ErrorType::TooManyServers { .. } => StatusCode::BAD_REQUEST,
             ErrorType::TooManyEmbeds { .. } => StatusCode::BAD_REQUEST,
             ErrorType::TooManyEmoji { .. } => StatusCode::BAD_REQUEST,
+            ErrorType::TooManyStickers { .. } => StatusCode::BAD_REQUEST,
+            ErrorType::TooManyTemplates { .. } => StatusCode::BAD_REQUEST,
             ErrorType::TooManyChannels { .. } => StatusCode::BAD_REQUEST,
             ErrorType::TooManyRoles { .. } => StatusCode::BAD_REQUEST,
 
@@ -81,6 +87,7 @@ impl IntoResponse for Error {
             ErrorType::ProxyError => StatusCode::BAD_REQUEST,
             ErrorType::FileTooSmall => StatusCode::UNPROCESSABLE_ENTITY,
             ErrorType::FileTooLarge { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorType::StorageQuotaExceeded { .. } => StatusCode::UNPROCESSABLE_ENTITY,
             ErrorType::FileTypeNotAllowed => StatusCode::BAD_REQUEST,
             ErrorType::ImageProcessingFailed => StatusCode::INTERNAL_SERVER_ERROR,
             ErrorType::NoEmbedData => StatusCode::BAD_REQUEST,
@@ -72,6 +72,10 @@ pub enum ErrorType {
     TooManyPendingFriendRequests {
         max: usize,
     },
+    TooManyFavourites {
+        max: usize,
+    },
+    FavouritesChanged,
 
     // ? Channel related errors
     UnknownChannel,
@@ -101,6 +105,12 @@ pub enum ErrorType {
     NotInGroup,
     AlreadyPinned,
     NotPinned,
+    InSlowmode {
+        retry_after: u64,
+    },
+    BlockedByAutoMod {
+        rule: String,
+    },
 
     // ? Server related errors
     UnknownServer,
@@ -112,6 +122,12 @@ pub enum ErrorType {
     TooManyEmoji {
         max: usize,
     },
+    TooManyStickers {
+        max: usize,
+    },
+    TooManyTemplates {
+        max: usize,
+    },
     TooManyRoles {
         max: usize,
     },
@@ -164,6 +180,9 @@ pub enum ErrorType {
     FileTooLarge {
         max: usize,
     },
+    StorageQuotaExceeded {
+        max: usize,
+    },
     FileTypeNotAllowed,
     ImageProcessingFailed,
     NoEmbedData,
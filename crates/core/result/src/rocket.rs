@@ -26,6 +26,8 @@ impl<'r> Responder<'r, 'static> for Error {
             ErrorType::BlockedByOther => Status::Forbidden,
             ErrorType::NotFriends => Status::Forbidden,
             ErrorType::TooManyPendingFriendRequests { .. } => Status::BadRequest,
+            ErrorType::TooManyFavourites { .. } => Status::BadRequest,
+            ErrorType::FavouritesChanged => Status::Conflict,
 
             ErrorType::UnknownChannel => Status::NotFound,
             ErrorType::UnknownMessage => Status::NotFound,
@@ -42,6 +44,8 @@ impl<'r> Responder<'r, 'static> for Error {
             ErrorType::NotInGroup => Status::NotFound,
             ErrorType::AlreadyPinned => Status::BadRequest,
             ErrorType::NotPinned => Status::BadRequest,
+            ErrorType::InSlowmode { .. } => Status::TooManyRequests,
+            ErrorType::BlockedByAutoMod { .. } => Status::Forbidden,
             ErrorType::InvalidFlagValue => Status::BadRequest,
 
             ErrorType::UnknownServer => Status::NotFound,
@@ -53,6 +57,8 @@ impl<'r> Responder<'r, 'static> for Error {
             ErrorType::TooManyServers { .. } => Status::BadRequest,
             ErrorType::TooManyEmbeds { .. } => Status::BadRequest,
             ErrorType::TooManyEmoji { .. } => Status::BadRequest,
+            ErrorType::TooManyStickers { .. } => Status::BadRequest,
+            ErrorType::TooManyTemplates { .. } => Status::BadRequest,
             ErrorType::TooManyChannels { .. } => Status::BadRequest,
             ErrorType::TooManyRoles { .. } => Status::BadRequest,
 
@@ -87,6 +93,7 @@ impl<'r> Responder<'r, 'static> for Error {
             ErrorType::ProxyError => Status::BadRequest,
             ErrorType::FileTooSmall => Status::UnprocessableEntity,
             ErrorType::FileTooLarge { .. } => Status::UnprocessableEntity,
+            ErrorType::StorageQuotaExceeded { .. } => Status::UnprocessableEntity,
             ErrorType::FileTypeNotAllowed => Status::BadRequest,
             ErrorType::ImageProcessingFailed => Status::InternalServerError,
             ErrorType::NoEmbedData => Status::BadRequest,
@@ -1,7 +1,9 @@
 use revolt_config::configure;
 use revolt_database::DatabaseInfo;
 use revolt_result::Result;
-use tasks::{file_deletion, prune_dangling_files, prune_members};
+use tasks::{
+    backfill_placeholders, discovery_stats, file_deletion, prune_dangling_files, prune_members,
+};
 use tokio::try_join;
 
 pub mod tasks;
@@ -12,6 +14,8 @@ async fn main() -> Result<()> {
 
     let db = DatabaseInfo::Auto.connect().await.expect("database");
     try_join!(
+        backfill_placeholders::task(db.clone()),
+        discovery_stats::task(db.clone()),
         file_deletion::task(db.clone()),
         prune_dangling_files::task(db.clone()),
         prune_members::task(db.clone())
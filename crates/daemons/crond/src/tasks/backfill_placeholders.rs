@@ -0,0 +1,76 @@
+use std::io::Cursor;
+
+use log::{info, warn};
+use revolt_database::Database;
+use revolt_files::{create_placeholder, decode_image, fetch_from_s3};
+use revolt_result::Result;
+
+/// Number of hashes to process per batch
+const BATCH_SIZE: i64 = 100;
+
+pub async fn task(db: Database) -> Result<()> {
+    // Hashes which fail to produce a placeholder are skipped over,
+    // progress is otherwise kept in the database so this can be
+    // safely interrupted and resumed on the next start up
+    let mut after: Option<String> = None;
+    let mut filled = 0;
+
+    loop {
+        // Give up on this run rather than taking down other tasks
+        let hashes = match db
+            .fetch_attachment_hashes_without_placeholder(after.as_deref(), BATCH_SIZE)
+            .await
+        {
+            Ok(hashes) => hashes,
+            Err(err) => {
+                warn!("Failed to fetch hashes without placeholders: {err:?}");
+                break;
+            }
+        };
+
+        let Some(last) = hashes.last() else {
+            break;
+        };
+
+        after = Some(last.id.clone());
+
+        for hash in hashes {
+            // Videos are skipped as posters require ffmpeg,
+            // new uploads will have placeholders generated by Autumn.
+            let placeholder = match fetch_from_s3(&hash.bucket_id, &hash.path, &hash.iv)
+                .await
+                .and_then(|data| decode_image(&mut Cursor::new(data), &hash.content_type))
+            {
+                Ok(image) => create_placeholder(&image),
+                Err(err) => {
+                    warn!("Failed to generate placeholder for {}: {err:?}", hash.id);
+                    None
+                }
+            };
+
+            let Some(placeholder) = placeholder else {
+                continue;
+            };
+
+            if let Err(err) = db
+                .set_attachment_hash_placeholder(&hash.id, &placeholder)
+                .await
+            {
+                warn!("Failed to save placeholder for {}: {err:?}", hash.id);
+                continue;
+            }
+
+            if let Err(err) = db.set_attachments_placeholder(&hash.id, &placeholder).await {
+                warn!("Failed to update attachments for {}: {err:?}", hash.id);
+            }
+
+            filled += 1;
+        }
+    }
+
+    if filled > 0 {
+        info!("Backfilled placeholders for {filled} images");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use log::warn;
+use revolt_database::{Database, DiscoveryListing, DiscoveryListingState};
+use revolt_result::Result;
+use tokio::time::sleep;
+
+/// Refresh the member counts and activity used to search and sort the server directory
+pub async fn task(db: Database) -> Result<()> {
+    loop {
+        match db
+            .fetch_discovery_listings_by_state(&DiscoveryListingState::Approved)
+            .await
+        {
+            Ok(listings) => {
+                for listing in listings {
+                    let result = match db.fetch_server(&listing.id).await {
+                        Ok(server) => DiscoveryListing::refresh_stats(&db, &server).await,
+                        Err(err) => Err(err),
+                    };
+
+                    if let Err(s) = result {
+                        revolt_config::capture_error(&s);
+                        warn!(
+                            "Failed to refresh discovery listing {}: {:?}",
+                            listing.id, &s
+                        );
+                    }
+                }
+            }
+            Err(s) => {
+                revolt_config::capture_error(&s);
+                warn!("Failed to fetch discovery listings: {:?}", &s);
+            }
+        }
+
+        sleep(Duration::from_secs(600)).await;
+    }
+}
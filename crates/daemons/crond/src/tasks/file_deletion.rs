@@ -1,8 +1,9 @@
 use std::time::Duration;
 
-use log::info;
-use revolt_database::Database;
-use revolt_files::delete_from_s3;
+use log::{error, info};
+use revolt_config::config;
+use revolt_database::{Database, EmojiParent, FileUsedFor, FileUsedForType, StorageUsage};
+use revolt_files::{delete_from_s3, delete_variants_from_s3};
 use revolt_result::Result;
 use tokio::time::sleep;
 
@@ -11,6 +12,22 @@ pub async fn task(db: Database) -> Result<()> {
         let files = db.fetch_deleted_attachments().await?;
 
         for file in files {
+            // Delete any generated previews stored for this file
+            if config().await.files.store_preview_variants {
+                let file_hash = db
+                    .fetch_attachment_hash(file.hash.as_ref().expect("no `hash` present"))
+                    .await?;
+
+                // Leave the file in place to be retried on the next pass
+                if let Err(err) =
+                    delete_variants_from_s3(&file_hash.bucket_id, &format!("previews/{}/", file.id))
+                        .await
+                {
+                    error!("Failed to delete previews of file {}: {err:?}", file.id);
+                    continue;
+                }
+            }
+
             let count = db
                 .count_file_hash_references(file.hash.as_ref().expect("no `hash` present"))
                 .await?;
@@ -29,6 +46,32 @@ pub async fn task(db: Database) -> Result<()> {
                 info!("Deleted file hash {}", file_hash.id);
             }
 
+            // Release the file from its owners' storage usage
+            if let Some(uploader_id) = &file.uploader_id {
+                StorageUsage::release(&db, uploader_id, &file).await?;
+            }
+
+            if let Some(FileUsedFor {
+                object_type: FileUsedForType::ServerIcon | FileUsedForType::ServerBanner,
+                id,
+            }) = &file.used_for
+            {
+                StorageUsage::release(&db, id, &file).await?;
+            }
+
+            // Emojis are charged to their server until they are detached from it
+            if let Some(FileUsedFor {
+                object_type: FileUsedForType::Emoji,
+                id,
+            }) = &file.used_for
+            {
+                if let Ok(emoji) = db.fetch_emoji(id).await {
+                    if let EmojiParent::Server { id } = &emoji.parent {
+                        StorageUsage::release(&db, id, &file).await?;
+                    }
+                }
+            }
+
             // Delete the file
             db.delete_attachment(&file.id).await?;
             info!("Deleted file {}", file.id);
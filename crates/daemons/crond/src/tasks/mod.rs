@@ -1,3 +1,5 @@
+pub mod backfill_placeholders;
+pub mod discovery_stats;
 pub mod file_deletion;
 pub mod prune_dangling_files;
 pub mod prune_members;
@@ -122,6 +122,7 @@ impl AsyncConsumer for AckConsumer {
                     session_id: session.id.clone(),
                     token: session.subscription.as_ref().unwrap().auth.clone(),
                     extras: Default::default(),
+                    attempts: 0,
                 };
                 let raw_service_payload = serde_json::to_string(&service_payload);
 
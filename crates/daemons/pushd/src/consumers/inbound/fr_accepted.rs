@@ -78,8 +78,14 @@ impl FRAcceptedConsumer {
                         user_id: session.user_id,
                         session_id: session.id,
                         extras: HashMap::new(),
+                        attempts: 0,
                     };
 
+                    // Web Push keys, or the device key of a mobile session using encrypted notifications
+                    if !sub.p256dh.is_empty() {
+                        sendable.extras.insert("p256dh".to_string(), sub.p256dh);
+                    }
+
                     let args: BasicPublishArguments;
 
                     if sub.endpoint == "apn" {
@@ -94,6 +100,12 @@ impl FRAcceptedConsumer {
                             config.pushd.fcm.queue.as_str(),
                         )
                         .finish();
+                    } else if sub.endpoint == "unifiedpush" {
+                        args = BasicPublishArguments::new(
+                            config.pushd.exchange.as_str(),
+                            config.pushd.unifiedpush.queue.as_str(),
+                        )
+                        .finish();
                     } else {
                         // web push (vapid)
                         args = BasicPublishArguments::new(
@@ -101,7 +113,6 @@ impl FRAcceptedConsumer {
                             config.pushd.vapid.queue.as_str(),
                         )
                         .finish();
-                        sendable.extras.insert("p265dh".to_string(), sub.p256dh);
                         sendable
                             .extras
                             .insert("endpoint".to_string(), sub.endpoint.clone());
@@ -84,8 +84,14 @@ impl GenericConsumer {
                         user_id: session.user_id,
                         session_id: session.id,
                         extras: HashMap::new(),
+                        attempts: 0,
                     };
 
+                    // Web Push keys, or the device key of a mobile session using encrypted notifications
+                    if !sub.p256dh.is_empty() {
+                        sendable.extras.insert("p256dh".to_string(), sub.p256dh);
+                    }
+
                     let args: BasicPublishArguments;
 
                     if sub.endpoint == "apn" {
@@ -100,6 +106,12 @@ impl GenericConsumer {
                             config.pushd.fcm.queue.as_str(),
                         )
                         .finish();
+                    } else if sub.endpoint == "unifiedpush" {
+                        args = BasicPublishArguments::new(
+                            config.pushd.exchange.as_str(),
+                            config.pushd.unifiedpush.queue.as_str(),
+                        )
+                        .finish();
                     } else {
                         // web push (vapid)
                         args = BasicPublishArguments::new(
@@ -107,7 +119,6 @@ impl GenericConsumer {
                             config.pushd.vapid.queue.as_str(),
                         )
                         .finish();
-                        sendable.extras.insert("p265dh".to_string(), sub.p256dh);
                         sendable
                             .extras
                             .insert("endpoint".to_string(), sub.endpoint.clone());
@@ -49,10 +49,15 @@ impl<'a> PayloadLike for MessagePayload<'a> {
 
 // region: consumer
 
+use super::{
+    delivery::{DeliveryOutcome, DeliveryReceipts, Recipient},
+    encryption::{device_key, encrypt},
+};
+
 pub struct ApnsOutboundConsumer {
-    #[allow(dead_code)]
     db: Database,
     client: Client,
+    receipts: DeliveryReceipts,
 }
 
 impl ApnsOutboundConsumer {
@@ -88,6 +93,40 @@ impl ApnsOutboundConsumer {
         }
         None
     }
+
+    /// Classify a failed APNs delivery
+    fn classify(err: Error) -> DeliveryOutcome {
+        match &err {
+            Error::ResponseError(Response {
+                error: Some(ErrorBody { reason, .. }),
+                ..
+            }) => match reason {
+                ErrorReason::BadDeviceToken
+                | ErrorReason::Unregistered
+                | ErrorReason::DeviceTokenNotForTopic => DeliveryOutcome::Expired(err.to_string()),
+                ErrorReason::TooManyRequests
+                | ErrorReason::IdleTimeout
+                | ErrorReason::ExpiredProviderToken
+                | ErrorReason::TooManyProviderTokenUpdates
+                | ErrorReason::InternalServerError
+                | ErrorReason::ServiceUnavailable
+                | ErrorReason::Shutdown => DeliveryOutcome::Transient(err.to_string()),
+                ErrorReason::BadCertificate
+                | ErrorReason::BadCertificateEnvironment
+                | ErrorReason::InvalidProviderToken
+                | ErrorReason::MissingProviderToken
+                | ErrorReason::Forbidden => DeliveryOutcome::Unauthorized(err.to_string()),
+                _ => DeliveryOutcome::Rejected(err.to_string()),
+            },
+            Error::ResponseError(Response { code, .. }) if *code >= 500 => {
+                DeliveryOutcome::Transient(err.to_string())
+            }
+            Error::ConnectionError(_) | Error::ClientError(_) | Error::RequestTimeout(_) => {
+                DeliveryOutcome::Transient(err.to_string())
+            }
+            _ => DeliveryOutcome::Rejected(err.to_string()),
+        }
+    }
 }
 
 impl ApnsOutboundConsumer {
@@ -121,12 +160,16 @@ impl ApnsOutboundConsumer {
         )
         .expect("could not create APN client");
 
-        Ok(ApnsOutboundConsumer { db, client })
+        Ok(ApnsOutboundConsumer {
+            db,
+            client,
+            receipts: DeliveryReceipts::new("apn", &config.pushd.apn.queue),
+        })
     }
 
     async fn consume_event(
         &mut self,
-        _channel: &AmqpChannel,
+        channel: &AmqpChannel,
         _deliver: Deliver,
         _basic_properties: BasicProperties,
         content: Vec<u8>,
@@ -145,215 +188,245 @@ impl ApnsOutboundConsumer {
 
         let resp: Result<Response, Error>;
 
-        match payload.notification {
-            PayloadKind::FRReceived(alert) => {
-                let loc_args = vec![Cow::from(
-                    alert
-                        .from_user
-                        .display_name
-                        .or(Some(format!(
-                            "{}#{}",
-                            alert.from_user.username, alert.from_user.discriminator
-                        )))
-                        .clone()
-                        .ok_or_else(|| anyhow!("missing name"))?,
-                )];
-
-                let apn_payload = Payload {
-                    aps: APS {
-                        alert: Some(APSAlert::Default(DefaultAlert {
-                            title: None,
-                            subtitle: None,
-                            body: None,
-                            title_loc_key: None,
-                            title_loc_args: None,
-                            action_loc_key: None,
-                            loc_key: Some("push.fr.received"),
-                            loc_args: Some(loc_args),
-                            launch_image: None,
-                        })),
-                        badge: self.get_badge_count(&payload.user_id).await,
-                        sound: Some(APSSound::Sound("default")),
-                        thread_id: None,
-                        content_available: None,
-                        category: None,
-                        mutable_content: Some(1),
-                        url_args: None,
-                    },
-                    device_token: &payload.token,
-                    options: payload_options.clone(),
-                    data: BTreeMap::new(),
-                };
-
-                debug!(
-                    "Sending friend request received for user: {:}",
-                    &payload.user_id
-                );
-                resp = self.client.send(apn_payload).await;
+        if let Some(key) = device_key(&payload) {
+            // Only a placeholder alert is visible to Apple, the notification service extension
+            // decrypts the notification and replaces it on device
+            let mut data = BTreeMap::new();
+            if let Some(encrypted) = encrypt(&key, &payload.notification)? {
+                data.insert("encrypted", serde_json::to_value(encrypted)?);
             }
 
-            PayloadKind::FRAccepted(alert) => {
-                let loc_args = vec![Cow::from(
-                    alert
-                        .accepted_user
-                        .display_name
-                        .or(Some(format!(
-                            "{}#{}",
-                            alert.accepted_user.username, alert.accepted_user.discriminator
-                        )))
-                        .clone()
-                        .ok_or_else(|| anyhow!("missing name"))?,
-                )];
-
-                let apn_payload = Payload {
-                    aps: APS {
-                        alert: Some(APSAlert::Default(DefaultAlert {
-                            title: None,
-                            subtitle: None,
-                            body: None,
-                            title_loc_key: None,
-                            title_loc_args: None,
-                            action_loc_key: None,
-                            loc_key: Some("push.fr.accepted"),
-                            loc_args: Some(loc_args),
-                            launch_image: None,
-                        })),
-                        badge: self.get_badge_count(&payload.user_id).await,
-                        sound: Some(APSSound::Sound("default")),
-                        thread_id: None,
-                        content_available: None,
-                        category: None,
-                        mutable_content: Some(1),
-                        url_args: None,
-                    },
-                    device_token: &payload.token,
-                    options: payload_options.clone(),
-                    data: BTreeMap::new(),
-                };
-
-                debug!(
-                    "Sending friend request accept for user: {:}",
-                    &payload.user_id
-                );
-                resp = self.client.send(apn_payload).await;
-            }
-            PayloadKind::Generic(alert) => {
-                let apn_payload = Payload {
-                    aps: APS {
-                        alert: Some(APSAlert::Default(DefaultAlert {
-                            title: Some(&alert.title),
-                            subtitle: None,
-                            body: Some(&alert.body),
-                            title_loc_key: None,
-                            title_loc_args: None,
-                            action_loc_key: None,
-                            loc_key: None,
-                            loc_args: None,
-                            launch_image: None,
-                        })),
-                        badge: self.get_badge_count(&payload.user_id).await,
-                        sound: Some(APSSound::Sound("default")),
-                        thread_id: None,
-                        content_available: None,
-                        category: None,
-                        mutable_content: Some(1),
-                        url_args: None,
-                    },
-                    device_token: &payload.token,
-                    options: payload_options.clone(),
-                    data: BTreeMap::new(),
-                };
-
-                debug!(
-                    "Sending generic notification for user: {:}",
-                    &payload.user_id
-                );
-                resp = self.client.send(apn_payload).await;
-            }
+            let apn_payload = Payload {
+                aps: APS {
+                    alert: Some(APSAlert::Default(DefaultAlert {
+                        title: None,
+                        subtitle: None,
+                        body: None,
+                        title_loc_key: None,
+                        title_loc_args: None,
+                        action_loc_key: None,
+                        loc_key: Some("push.encrypted"),
+                        loc_args: None,
+                        launch_image: None,
+                    })),
+                    badge: self.get_badge_count(&payload.user_id).await,
+                    sound: Some(APSSound::Sound("default")),
+                    thread_id: None,
+                    content_available: None,
+                    category: None,
+                    mutable_content: Some(1),
+                    url_args: None,
+                },
+                device_token: &payload.token,
+                options: payload_options.clone(),
+                data,
+            };
+
+            debug!(
+                "Sending encrypted notification for user: {:}",
+                &payload.user_id
+            );
+            resp = self.client.send(apn_payload).await;
+        } else {
+            match payload.notification {
+                PayloadKind::FRReceived(alert) => {
+                    let loc_args = vec![Cow::from(
+                        alert
+                            .from_user
+                            .display_name
+                            .or(Some(format!(
+                                "{}#{}",
+                                alert.from_user.username, alert.from_user.discriminator
+                            )))
+                            .clone()
+                            .ok_or_else(|| anyhow!("missing name"))?,
+                    )];
+
+                    let apn_payload = Payload {
+                        aps: APS {
+                            alert: Some(APSAlert::Default(DefaultAlert {
+                                title: None,
+                                subtitle: None,
+                                body: None,
+                                title_loc_key: None,
+                                title_loc_args: None,
+                                action_loc_key: None,
+                                loc_key: Some("push.fr.received"),
+                                loc_args: Some(loc_args),
+                                launch_image: None,
+                            })),
+                            badge: self.get_badge_count(&payload.user_id).await,
+                            sound: Some(APSSound::Sound("default")),
+                            thread_id: None,
+                            content_available: None,
+                            category: None,
+                            mutable_content: Some(1),
+                            url_args: None,
+                        },
+                        device_token: &payload.token,
+                        options: payload_options.clone(),
+                        data: BTreeMap::new(),
+                    };
+
+                    debug!(
+                        "Sending friend request received for user: {:}",
+                        &payload.user_id
+                    );
+                    resp = self.client.send(apn_payload).await;
+                }
 
-            PayloadKind::MessageNotification(alert) => {
-                let title = self.format_title(&alert);
-                let apn_payload = MessagePayload {
-                    aps: APS {
-                        alert: Some(APSAlert::Default(DefaultAlert {
-                            title: Some(&title),
-                            subtitle: None,
-                            body: Some(&alert.body),
-                            title_loc_key: None,
-                            title_loc_args: None,
-                            action_loc_key: None,
-                            loc_key: None,
-                            loc_args: None,
-                            launch_image: None,
-                        })),
-                        badge: self.get_badge_count(&payload.user_id).await,
-                        sound: Some(APSSound::Sound("default")),
-                        thread_id: Some(alert.channel.id()),
-                        content_available: None,
-                        category: None,
-                        mutable_content: Some(1),
-                        url_args: None,
-                    },
-                    device_token: &payload.token,
-                    options: payload_options.clone(),
-                    message: &alert.message,
-                    url: &alert.url,
-                    author_avatar: &alert.icon,
-                    author_display_name: &alert.author,
-                    channel_name: alert.channel.name().unwrap_or(&title),
-                };
-
-                debug!(
-                    "Sending message notification for user: {:}",
-                    &payload.user_id
-                );
-                resp = self.client.send(apn_payload).await;
-            }
-            PayloadKind::BadgeUpdate(badge) => {
-                let apn_payload = Payload {
-                    aps: APS {
-                        badge: Some(badge as u32),
-                        ..Default::default()
-                    },
-                    device_token: &payload.token,
-                    options: payload_options.clone(),
-                    data: BTreeMap::new(),
-                };
-
-                debug!("Sending badge update for user: {:}", &payload.user_id);
-                resp = self.client.send(apn_payload).await;
-            }
-        }
+                PayloadKind::FRAccepted(alert) => {
+                    let loc_args = vec![Cow::from(
+                        alert
+                            .accepted_user
+                            .display_name
+                            .or(Some(format!(
+                                "{}#{}",
+                                alert.accepted_user.username, alert.accepted_user.discriminator
+                            )))
+                            .clone()
+                            .ok_or_else(|| anyhow!("missing name"))?,
+                    )];
+
+                    let apn_payload = Payload {
+                        aps: APS {
+                            alert: Some(APSAlert::Default(DefaultAlert {
+                                title: None,
+                                subtitle: None,
+                                body: None,
+                                title_loc_key: None,
+                                title_loc_args: None,
+                                action_loc_key: None,
+                                loc_key: Some("push.fr.accepted"),
+                                loc_args: Some(loc_args),
+                                launch_image: None,
+                            })),
+                            badge: self.get_badge_count(&payload.user_id).await,
+                            sound: Some(APSSound::Sound("default")),
+                            thread_id: None,
+                            content_available: None,
+                            category: None,
+                            mutable_content: Some(1),
+                            url_args: None,
+                        },
+                        device_token: &payload.token,
+                        options: payload_options.clone(),
+                        data: BTreeMap::new(),
+                    };
+
+                    debug!(
+                        "Sending friend request accept for user: {:}",
+                        &payload.user_id
+                    );
+                    resp = self.client.send(apn_payload).await;
+                }
+                PayloadKind::Generic(alert) => {
+                    let apn_payload = Payload {
+                        aps: APS {
+                            alert: Some(APSAlert::Default(DefaultAlert {
+                                title: Some(&alert.title),
+                                subtitle: None,
+                                body: Some(&alert.body),
+                                title_loc_key: None,
+                                title_loc_args: None,
+                                action_loc_key: None,
+                                loc_key: None,
+                                loc_args: None,
+                                launch_image: None,
+                            })),
+                            badge: self.get_badge_count(&payload.user_id).await,
+                            sound: Some(APSSound::Sound("default")),
+                            thread_id: None,
+                            content_available: None,
+                            category: None,
+                            mutable_content: Some(1),
+                            url_args: None,
+                        },
+                        device_token: &payload.token,
+                        options: payload_options.clone(),
+                        data: BTreeMap::new(),
+                    };
+
+                    debug!(
+                        "Sending generic notification for user: {:}",
+                        &payload.user_id
+                    );
+                    resp = self.client.send(apn_payload).await;
+                }
 
-        if let Err(err) = resp {
-            match err {
-                Error::ResponseError(Response {
-                    error:
-                        Some(ErrorBody {
-                            reason: ErrorReason::BadDeviceToken | ErrorReason::Unregistered,
-                            ..
-                        }),
-                    ..
-                }) => {
-                    info!(
-                        "Removing APNS subscription id {:} (user: {:}) due to invalid token",
-                        &payload.session_id, &payload.user_id
+                PayloadKind::MessageNotification(alert) => {
+                    let title = self.format_title(&alert);
+                    let apn_payload = MessagePayload {
+                        aps: APS {
+                            alert: Some(APSAlert::Default(DefaultAlert {
+                                title: Some(&title),
+                                subtitle: None,
+                                body: Some(&alert.body),
+                                title_loc_key: None,
+                                title_loc_args: None,
+                                action_loc_key: None,
+                                loc_key: None,
+                                loc_args: None,
+                                launch_image: None,
+                            })),
+                            badge: self.get_badge_count(&payload.user_id).await,
+                            sound: Some(APSSound::Sound("default")),
+                            thread_id: Some(alert.channel.id()),
+                            content_available: None,
+                            category: None,
+                            mutable_content: Some(1),
+                            url_args: None,
+                        },
+                        device_token: &payload.token,
+                        options: payload_options.clone(),
+                        message: &alert.message,
+                        url: &alert.url,
+                        author_avatar: &alert.icon,
+                        author_display_name: &alert.author,
+                        channel_name: alert.channel.name().unwrap_or(&title),
+                    };
+
+                    debug!(
+                        "Sending message notification for user: {:}",
+                        &payload.user_id
                     );
-                    if let Err(err) = self
-                        .db
-                        .remove_push_subscription_by_session_id(&payload.session_id)
-                        .await
-                    {
-                        revolt_config::capture_error(&err);
-                    }
+                    resp = self.client.send(apn_payload).await;
                 }
-                err => {
-                    revolt_config::capture_error(&err);
+                PayloadKind::BadgeUpdate(badge) => {
+                    let apn_payload = Payload {
+                        aps: APS {
+                            badge: Some(badge as u32),
+                            ..Default::default()
+                        },
+                        device_token: &payload.token,
+                        options: payload_options.clone(),
+                        data: BTreeMap::new(),
+                    };
+
+                    debug!("Sending badge update for user: {:}", &payload.user_id);
+                    resp = self.client.send(apn_payload).await;
                 }
             }
         }
 
-        Ok(())
+        let outcome = match resp {
+            Ok(_) => DeliveryOutcome::Delivered,
+            Err(err) => Self::classify(err),
+        };
+
+        self.receipts
+            .record(
+                &self.db,
+                channel,
+                Recipient {
+                    user_id: &payload.user_id,
+                    session_id: &payload.session_id,
+                    attempts: payload.attempts,
+                },
+                &content,
+                outcome,
+            )
+            .await
     }
 }
 
@@ -376,3 +449,49 @@ impl AsyncConsumer for ApnsOutboundConsumer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use revolt_a2::{Error, ErrorBody, ErrorReason, Response};
+
+    use super::ApnsOutboundConsumer;
+
+    fn response(code: u16, reason: Option<ErrorReason>) -> Error {
+        Error::ResponseError(Response {
+            error: reason.map(|reason| ErrorBody {
+                reason,
+                timestamp: None,
+            }),
+            apns_id: None,
+            code,
+        })
+    }
+
+    #[test]
+    fn classify() {
+        for (err, expected) in [
+            (response(410, Some(ErrorReason::Unregistered)), "expired"),
+            (response(400, Some(ErrorReason::BadDeviceToken)), "expired"),
+            (response(429, Some(ErrorReason::TooManyRequests)), "retried"),
+            (
+                response(403, Some(ErrorReason::ExpiredProviderToken)),
+                "retried",
+            ),
+            (
+                response(403, Some(ErrorReason::InvalidProviderToken)),
+                "unauthorized",
+            ),
+            (
+                response(413, Some(ErrorReason::PayloadTooLarge)),
+                "rejected",
+            ),
+            (response(503, None), "retried"),
+            (response(400, None), "rejected"),
+            (Error::RequestTimeout(20), "retried"),
+        ] {
+            let description = err.to_string();
+            let outcome = ApnsOutboundConsumer::classify(err);
+            assert_eq!(outcome.stat(), expected, "{description}");
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use amqprs::{
+    channel::{BasicPublishArguments, Channel as AmqpChannel},
+    BasicProperties,
+};
+use anyhow::Result;
+use redis_kiss::{get_connection, AsyncCommands};
+use revolt_database::Database;
+
+/// Outcome of handing a notification over to a push provider
+#[derive(Debug)]
+pub enum DeliveryOutcome {
+    /// Provider accepted the notification
+    Delivered,
+    /// Delivery failed but may succeed if attempted again later
+    Transient(String),
+    /// Subscription is gone and will never accept notifications again
+    Expired(String),
+    /// Provider refused this notification, retrying will not help
+    Rejected(String),
+    /// Provider refused our own credentials, someone needs to look at the configuration
+    ///
+    /// The subscription is kept and the notification is retried like a transient failure.
+    Unauthorized(String),
+}
+
+impl DeliveryOutcome {
+    /// Key used when recording statistics for this outcome
+    pub fn stat(&self) -> &'static str {
+        match self {
+            DeliveryOutcome::Delivered => "delivered",
+            DeliveryOutcome::Transient(_) => "retried",
+            DeliveryOutcome::Expired(_) => "expired",
+            DeliveryOutcome::Rejected(_) => "rejected",
+            DeliveryOutcome::Unauthorized(_) => "unauthorized",
+        }
+    }
+}
+
+/// Session a notification was addressed to
+pub struct Recipient<'a> {
+    pub user_id: &'a str,
+    pub session_id: &'a str,
+    /// Number of delivery attempts made before this one
+    pub attempts: u32,
+}
+
+/// Handles the result of a delivery attempt for one push provider
+///
+/// Expired subscriptions are removed from their session, transient failures are parked
+/// in a delayed retry queue until the attempt limit is reached, and every outcome is
+/// counted in Redis under `pushd:delivery:<provider>`.
+pub struct DeliveryReceipts {
+    provider: &'static str,
+    queue: String,
+}
+
+impl DeliveryReceipts {
+    pub fn new(provider: &'static str, queue: &str) -> DeliveryReceipts {
+        DeliveryReceipts {
+            provider,
+            queue: queue.to_string(),
+        }
+    }
+
+    /// Act on the outcome of a delivery attempt
+    ///
+    /// `content` is the raw payload as it was received, used to requeue transient failures.
+    pub async fn record(
+        &self,
+        db: &Database,
+        channel: &AmqpChannel,
+        recipient: Recipient<'_>,
+        content: &str,
+        outcome: DeliveryOutcome,
+    ) -> Result<()> {
+        let mut stat = outcome.stat();
+
+        match outcome {
+            DeliveryOutcome::Delivered => {
+                debug!(
+                    "Delivered {} notification to session {}",
+                    self.provider, recipient.session_id
+                );
+            }
+            DeliveryOutcome::Expired(reason) => {
+                info!(
+                    "Removing {} subscription for session {} (user: {}): {reason}",
+                    self.provider, recipient.session_id, recipient.user_id
+                );
+
+                if let Err(err) = db
+                    .remove_push_subscription_by_session_id(recipient.session_id)
+                    .await
+                {
+                    revolt_config::capture_error(&err);
+                }
+            }
+            DeliveryOutcome::Rejected(reason) => {
+                warn!(
+                    "{} rejected notification for session {}: {reason}",
+                    self.provider, recipient.session_id
+                );
+            }
+            DeliveryOutcome::Transient(reason) => {
+                if !self.retry(channel, &recipient, content, &reason).await? {
+                    stat = "dropped";
+                }
+            }
+            DeliveryOutcome::Unauthorized(reason) => {
+                error!(
+                    "{} refused our credentials for session {}: {reason}",
+                    self.provider, recipient.session_id
+                );
+
+                revolt_config::capture_message(
+                    &format!("{} refused our credentials: {reason}", self.provider),
+                    revolt_config::Level::Error,
+                );
+
+                if !self.retry(channel, &recipient, content, &reason).await? {
+                    stat = "dropped";
+                }
+            }
+        }
+
+        self.count(stat).await;
+        Ok(())
+    }
+
+    /// Park a notification in the delayed retry queue
+    ///
+    /// Returns false if the attempt limit was reached and the notification was dropped.
+    async fn retry(
+        &self,
+        channel: &AmqpChannel,
+        recipient: &Recipient<'_>,
+        content: &str,
+        reason: &str,
+    ) -> Result<bool> {
+        let config = revolt_config::config().await;
+        let attempts = recipient.attempts + 1;
+
+        if attempts >= config.pushd.max_delivery_attempts {
+            warn!(
+                "Giving up on {} notification for session {} after {attempts} attempts: {reason}",
+                self.provider, recipient.session_id
+            );
+
+            return Ok(false);
+        }
+
+        debug!(
+            "Retrying {} notification for session {} (attempt {attempts}): {reason}",
+            self.provider, recipient.session_id
+        );
+
+        let mut retry: serde_json::Value = serde_json::from_str(content)?;
+        retry["attempts"] = attempts.into();
+
+        // Publish straight to the delay queue, it dead-letters back onto the outbound queue
+        channel
+            .basic_publish(
+                BasicProperties::default()
+                    .with_content_type("application/json")
+                    .with_persistence(true)
+                    .finish(),
+                serde_json::to_vec(&retry)?,
+                BasicPublishArguments::new("", &config.pushd.get_retry_queue(&self.queue)),
+            )
+            .await?;
+
+        Ok(true)
+    }
+
+    /// Increment a delivery statistic for this provider
+    async fn count(&self, stat: &str) {
+        if let Ok(mut conn) = get_connection().await {
+            let _: Option<i64> = conn
+                .hincr(format!("pushd:delivery:{}", self.provider), stat, 1)
+                .await
+                .ok();
+        }
+    }
+}
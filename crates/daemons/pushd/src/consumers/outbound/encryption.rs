@@ -0,0 +1,204 @@
+use anyhow::Result;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use revolt_database::events::rabbit::{DeviceKey, PayloadKind, PayloadToService};
+use revolt_models::v0::{EncryptedPushNotification, PushNotification};
+
+/// Current version of the encrypted notification format
+const VERSION: u8 = 1;
+
+/// Longest encoded ciphertext we will send
+///
+/// APNs and FCM both cap payloads at 4KB, this leaves room for everything else.
+const MAX_CIPHERTEXT_LENGTH: usize = 3072;
+
+/// Number of characters of message content kept when trimming a notification
+const TRIMMED_CONTENT_LENGTH: usize = 256;
+
+/// Find the device key of a session that opted in to encrypted notifications
+///
+/// Badge updates carry nothing sensitive and are always sent as-is.
+pub fn device_key(payload: &PayloadToService) -> Option<DeviceKey> {
+    if matches!(payload.notification, PayloadKind::BadgeUpdate(_)) {
+        return None;
+    }
+
+    payload
+        .extras
+        .get("p256dh")
+        .and_then(|key| DeviceKey::parse(key))
+}
+
+/// Encrypt a notification to a device key
+///
+/// Message notifications which don't fit are trimmed down to a short preview,
+/// if the notification still doesn't fit `None` is returned and the caller
+/// should only send the placeholder alert.
+///
+/// See `revolt_models::v0::EncryptedPushNotification` for the format.
+pub fn encrypt(
+    key: &DeviceKey,
+    notification: &PayloadKind,
+) -> Result<Option<EncryptedPushNotification>> {
+    let encrypted = seal(key, notification)?;
+    if encrypted.ciphertext.len() <= MAX_CIPHERTEXT_LENGTH {
+        return Ok(Some(encrypted));
+    }
+
+    let PayloadKind::MessageNotification(notification) = notification else {
+        return Ok(None);
+    };
+
+    let encrypted = seal(key, &PayloadKind::MessageNotification(trim(notification)))?;
+    Ok((encrypted.ciphertext.len() <= MAX_CIPHERTEXT_LENGTH).then_some(encrypted))
+}
+
+/// Serialise and encrypt a notification
+fn seal(key: &DeviceKey, notification: &PayloadKind) -> Result<EncryptedPushNotification> {
+    let plaintext = serde_json::to_vec(notification)?;
+    let ciphertext = ece::encrypt(&key.public_key, &key.auth_secret, &plaintext)?;
+
+    Ok(EncryptedPushNotification {
+        v: VERSION,
+        ciphertext: URL_SAFE_NO_PAD.encode(ciphertext),
+    })
+}
+
+/// Strip a message notification down to what is needed to display it
+fn trim(notification: &PushNotification) -> PushNotification {
+    let mut notification = notification.clone();
+    truncate(&mut notification.body);
+
+    let message = &mut notification.message;
+    if let Some(content) = &mut message.content {
+        truncate(content);
+    }
+
+    message.user = None;
+    message.member = None;
+    message.embeds = None;
+    message.attachments = None;
+
+    notification
+}
+
+/// Truncate text to at most `TRIMMED_CONTENT_LENGTH` characters
+fn truncate(text: &mut String) {
+    if let Some((index, _)) = text.char_indices().nth(TRIMMED_CONTENT_LENGTH) {
+        text.truncate(index);
+        text.push('…');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revolt_database::events::rabbit::GenericPayload;
+    use serde_json::json;
+
+    /// Generate a device key, returning the private half for decryption
+    fn device() -> (DeviceKey, Box<dyn ece::crypto::LocalKeyPair>) {
+        let (key_pair, auth_secret) = ece::generate_keypair_and_auth_secret().unwrap();
+        let value = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(key_pair.pub_as_raw().unwrap()),
+            URL_SAFE_NO_PAD.encode(auth_secret)
+        );
+
+        (DeviceKey::parse(&value).unwrap(), key_pair)
+    }
+
+    fn decrypt(
+        key: &DeviceKey,
+        key_pair: &dyn ece::crypto::LocalKeyPair,
+        encrypted: &EncryptedPushNotification,
+    ) -> serde_json::Value {
+        let ciphertext = URL_SAFE_NO_PAD.decode(&encrypted.ciphertext).unwrap();
+        let plaintext = ece::decrypt(
+            &key_pair.raw_components().unwrap(),
+            &key.auth_secret,
+            &ciphertext,
+        )
+        .unwrap();
+
+        serde_json::from_slice(&plaintext).unwrap()
+    }
+
+    fn generic(body: String) -> PayloadKind {
+        PayloadKind::Generic(GenericPayload {
+            title: "Title".to_string(),
+            body,
+            icon: None,
+            user: Default::default(),
+        })
+    }
+
+    fn message(content: String) -> PayloadKind {
+        PayloadKind::MessageNotification(
+            serde_json::from_value(json!({
+                "author": "user",
+                "icon": "https://example.com/avatar.png",
+                "body": content,
+                "tag": "01CHANNEL",
+                "timestamp": 0,
+                "url": "https://example.com/channel/01CHANNEL/01MESSAGE",
+                "message": {
+                    "_id": "01MESSAGE",
+                    "channel": "01CHANNEL",
+                    "author": "01USER",
+                    "content": content
+                },
+                "channel": {
+                    "channel_type": "DirectMessage",
+                    "_id": "01CHANNEL",
+                    "active": true,
+                    "recipients": ["01USER", "01OTHER"]
+                }
+            }))
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn round_trip() {
+        let (key, key_pair) = device();
+        let notification = generic("Hello!".to_string());
+
+        let encrypted = encrypt(&key, &notification).unwrap().unwrap();
+        assert_eq!(encrypted.v, VERSION);
+        assert_eq!(
+            decrypt(&key, key_pair.as_ref(), &encrypted),
+            serde_json::to_value(&notification).unwrap()
+        );
+    }
+
+    #[test]
+    fn oversized() {
+        let (key, key_pair) = device();
+
+        // Message notifications are trimmed to fit
+        let encrypted = encrypt(&key, &message("a".repeat(2000))).unwrap().unwrap();
+        assert!(encrypted.ciphertext.len() <= MAX_CIPHERTEXT_LENGTH);
+
+        let decrypted = decrypt(&key, key_pair.as_ref(), &encrypted);
+        let body = decrypted["data"]["body"].as_str().unwrap();
+        assert_eq!(body.chars().count(), TRIMMED_CONTENT_LENGTH + 1);
+        assert_eq!(decrypted["data"]["message"]["content"], body);
+
+        // Anything else falls back to the placeholder
+        assert!(encrypt(&key, &generic("a".repeat(4000))).unwrap().is_none());
+    }
+
+    #[test]
+    fn device_keys() {
+        let (key, _) = device();
+        let auth_secret = URL_SAFE_NO_PAD.encode(&key.auth_secret);
+
+        // Not a point on the curve
+        let public_key = format!("BA{}", "A".repeat(85));
+        assert!(DeviceKey::parse(&format!("{public_key}.{auth_secret}")).is_none());
+
+        // Missing auth secret
+        let public_key = URL_SAFE_NO_PAD.encode(&key.public_key);
+        assert!(DeviceKey::parse(&public_key).is_none());
+    }
+}
@@ -5,18 +5,100 @@ use amqprs::{channel::Channel as AmqpChannel, consumer::AsyncConsumer, BasicProp
 use anyhow::{anyhow, bail, Result};
 use async_trait::async_trait;
 use fcm_v1::{
-    android::AndroidConfig,
+    android::{AndroidConfig, AndroidMessagePriority, AndroidNotification},
     auth::{Authenticator, ServiceAccountKey},
     message::{Message, Notification},
     Client, Error as FcmError,
 };
 use revolt_database::{events::rabbit::*, Database};
 use revolt_models::v0::{Channel, PushNotification};
+use serde::Deserialize;
 use serde_json::Value;
 
+use super::{
+    delivery::{DeliveryOutcome, DeliveryReceipts, Recipient},
+    encryption::{device_key, encrypt},
+};
+
+/// Error response returned by the FCM v1 API
+#[derive(Deserialize)]
+struct FcmErrorResponse {
+    error: FcmErrorStatus,
+}
+
+/// Status of a failed FCM request
+#[derive(Deserialize)]
+struct FcmErrorStatus {
+    /// HTTP status code
+    code: u16,
+    #[serde(default)]
+    details: Vec<FcmErrorDetail>,
+}
+
+/// Additional error information attached to a failed FCM request
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FcmErrorDetail {
+    /// Present on `google.firebase.fcm.v1.FcmError` details
+    error_code: Option<FcmErrorCode>,
+    /// Present on `google.rpc.BadRequest` details
+    #[serde(default)]
+    field_violations: Vec<FcmFieldViolation>,
+}
+
+/// Invalid field of a rejected FCM request
+#[derive(Deserialize)]
+struct FcmFieldViolation {
+    field: String,
+}
+
+/// FCM specific error codes
+///
+/// https://firebase.google.com/docs/reference/fcm/rest/v1/ErrorCode
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum FcmErrorCode {
+    InvalidArgument,
+    Unregistered,
+    SenderIdMismatch,
+    QuotaExceeded,
+    Unavailable,
+    Internal,
+    ThirdPartyAuthError,
+    #[serde(other)]
+    Unspecified,
+}
+
+impl FcmErrorResponse {
+    /// Parse the response body out of an FCM error,
+    /// fcm_v1 formats these as `error code <status> (<reason>): <body>`
+    fn parse(message: &str) -> Option<FcmErrorResponse> {
+        let (_, body) = message.split_once("): ")?;
+        serde_json::from_str(body).ok()
+    }
+
+    /// FCM error code, if one was given
+    fn error_code(&self) -> Option<FcmErrorCode> {
+        self.error
+            .details
+            .iter()
+            .find_map(|detail| detail.error_code)
+    }
+
+    /// Whether the request was rejected because of the registration token
+    fn is_token_invalid(&self) -> bool {
+        self.error
+            .details
+            .iter()
+            .flat_map(|detail| &detail.field_violations)
+            .any(|violation| violation.field == "message.token")
+    }
+}
+
 pub struct FcmOutboundConsumer {
     db: Database,
     client: Client,
+    receipts: DeliveryReceipts,
 }
 
 impl FcmOutboundConsumer {
@@ -36,6 +118,92 @@ impl FcmOutboundConsumer {
             _ => "Unknown".to_string(),
         }
     }
+
+    /// Build a message carrying an encrypted notification
+    ///
+    /// A placeholder alert is attached so that something is shown if the
+    /// application isn't running to decrypt the notification itself.
+    fn encrypted_message(key: &DeviceKey, payload: &PayloadToService) -> Result<Message> {
+        let mut data = HashMap::new();
+        data.insert(
+            "type".to_string(),
+            Value::String("push.encrypted".to_string()),
+        );
+
+        if let Some(encrypted) = encrypt(key, &payload.notification)? {
+            data.insert("v".to_string(), Value::String(encrypted.v.to_string()));
+            data.insert(
+                "ciphertext".to_string(),
+                Value::String(encrypted.ciphertext),
+            );
+        }
+
+        Ok(Message {
+            token: Some(payload.token.clone()),
+            data: Some(data),
+            android: Some(AndroidConfig {
+                priority: Some(AndroidMessagePriority::High),
+                notification: Some(AndroidNotification {
+                    body_loc_key: Some("push_encrypted".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    /// Classify a failed FCM delivery
+    fn classify(err: FcmError) -> DeliveryOutcome {
+        let FcmError::FCM(ref message) = err else {
+            return match err {
+                // Failing to authenticate ourselves says nothing about the device token
+                FcmError::Auth => DeliveryOutcome::Unauthorized(err.to_string()),
+                FcmError::Timeout => DeliveryOutcome::Transient(err.to_string()),
+                err => DeliveryOutcome::Rejected(err.to_string()),
+            };
+        };
+
+        // Proxies in front of FCM may not answer with a JSON body
+        let response = FcmErrorResponse::parse(message);
+        let status = match &response {
+            Some(response) => response.error.code,
+            None => message
+                .strip_prefix("error code ")
+                .and_then(|rest| rest.get(..3))
+                .and_then(|code| code.parse().ok())
+                .unwrap_or_default(),
+        };
+
+        match response.as_ref().and_then(FcmErrorResponse::error_code) {
+            Some(FcmErrorCode::Unregistered | FcmErrorCode::SenderIdMismatch) => {
+                DeliveryOutcome::Expired(err.to_string())
+            }
+            Some(FcmErrorCode::InvalidArgument)
+                if response
+                    .as_ref()
+                    .is_some_and(FcmErrorResponse::is_token_invalid) =>
+            {
+                DeliveryOutcome::Expired(err.to_string())
+            }
+            Some(
+                FcmErrorCode::QuotaExceeded | FcmErrorCode::Unavailable | FcmErrorCode::Internal,
+            ) => DeliveryOutcome::Transient(err.to_string()),
+            Some(FcmErrorCode::ThirdPartyAuthError) => {
+                DeliveryOutcome::Unauthorized(err.to_string())
+            }
+            Some(FcmErrorCode::InvalidArgument | FcmErrorCode::Unspecified) => {
+                DeliveryOutcome::Rejected(err.to_string())
+            }
+            // Fall back on the HTTP status if FCM didn't tell us anything more specific
+            None => match status {
+                404 => DeliveryOutcome::Expired(err.to_string()),
+                401 | 403 => DeliveryOutcome::Unauthorized(err.to_string()),
+                429 | 500.. => DeliveryOutcome::Transient(err.to_string()),
+                _ => DeliveryOutcome::Rejected(err.to_string()),
+            },
+        }
+    }
 }
 
 impl FcmOutboundConsumer {
@@ -63,12 +231,13 @@ impl FcmOutboundConsumer {
                 false,
                 Duration::from_secs(5),
             ),
+            receipts: DeliveryReceipts::new("fcm", &config.pushd.fcm.queue),
         })
     }
 
     async fn consume_event(
         &mut self,
-        _channel: &AmqpChannel,
+        channel: &AmqpChannel,
         _deliver: Deliver,
         _basic_properties: BasicProperties,
         content: Vec<u8>,
@@ -79,119 +248,126 @@ impl FcmOutboundConsumer {
         #[allow(clippy::needless_late_init)]
         let resp: Result<Message, FcmError>;
 
-        match payload.notification {
-            PayloadKind::FRReceived(alert) => {
-                let name = alert
-                    .from_user
-                    .display_name
-                    .or(Some(format!(
-                        "{}#{}",
-                        alert.from_user.username, alert.from_user.discriminator
-                    )))
-                    .clone()
-                    .ok_or_else(|| anyhow!("missing name"))?;
-
-                let mut data = HashMap::new();
-                data.insert(
-                    "type".to_string(),
-                    Value::String("push.fr.receive".to_string()),
-                );
-                data.insert("id".to_string(), Value::String(alert.from_user.id));
-                data.insert("username".to_string(), Value::String(name));
-
-                let msg = Message {
-                    token: Some(payload.token),
-                    data: Some(data),
-                    ..Default::default()
-                };
+        if let Some(key) = device_key(&payload) {
+            resp = self
+                .client
+                .send(&Self::encrypted_message(&key, &payload)?)
+                .await;
+        } else {
+            match payload.notification {
+                PayloadKind::FRReceived(alert) => {
+                    let name = alert
+                        .from_user
+                        .display_name
+                        .or(Some(format!(
+                            "{}#{}",
+                            alert.from_user.username, alert.from_user.discriminator
+                        )))
+                        .clone()
+                        .ok_or_else(|| anyhow!("missing name"))?;
 
-                resp = self.client.send(&msg).await;
-            }
+                    let mut data = HashMap::new();
+                    data.insert(
+                        "type".to_string(),
+                        Value::String("push.fr.receive".to_string()),
+                    );
+                    data.insert("id".to_string(), Value::String(alert.from_user.id));
+                    data.insert("username".to_string(), Value::String(name));
 
-            PayloadKind::FRAccepted(alert) => {
-                let name = alert
-                    .accepted_user
-                    .display_name
-                    .or(Some(format!(
-                        "{}#{}",
-                        alert.accepted_user.username, alert.accepted_user.discriminator
-                    )))
-                    .clone()
-                    .ok_or_else(|| anyhow!("missing name"))?;
-
-                let mut data: HashMap<String, Value> = HashMap::new();
-                data.insert(
-                    "type".to_string(),
-                    Value::String("push.fr.accept".to_string()),
-                );
-                data.insert("id".to_string(), Value::String(alert.accepted_user.id));
-                data.insert("username".to_string(), Value::String(name));
-
-                let msg = Message {
-                    token: Some(payload.token),
-                    data: Some(data),
-                    ..Default::default()
-                };
+                    let msg = Message {
+                        token: Some(payload.token),
+                        data: Some(data),
+                        ..Default::default()
+                    };
 
-                resp = self.client.send(&msg).await;
-            }
-            PayloadKind::Generic(alert) => {
-                let msg = Message {
-                    token: Some(payload.token),
-                    notification: Some(Notification {
-                        title: Some(alert.title),
-                        body: Some(alert.body),
-                        image: alert.icon,
-                    }),
-                    ..Default::default()
-                };
+                    resp = self.client.send(&msg).await;
+                }
 
-                resp = self.client.send(&msg).await;
-            }
+                PayloadKind::FRAccepted(alert) => {
+                    let name = alert
+                        .accepted_user
+                        .display_name
+                        .or(Some(format!(
+                            "{}#{}",
+                            alert.accepted_user.username, alert.accepted_user.discriminator
+                        )))
+                        .clone()
+                        .ok_or_else(|| anyhow!("missing name"))?;
+
+                    let mut data: HashMap<String, Value> = HashMap::new();
+                    data.insert(
+                        "type".to_string(),
+                        Value::String("push.fr.accept".to_string()),
+                    );
+                    data.insert("id".to_string(), Value::String(alert.accepted_user.id));
+                    data.insert("username".to_string(), Value::String(name));
 
-            PayloadKind::MessageNotification(alert) => {
-                let title = self.format_title(&alert);
-
-                let msg = Message {
-                    token: Some(payload.token),
-                    notification: Some(Notification {
-                        title: Some(title),
-                        body: Some(alert.body),
-                        image: Some(alert.icon),
-                    }),
-                    android: Some(AndroidConfig {
-                        collapse_key: Some(alert.tag),
+                    let msg = Message {
+                        token: Some(payload.token),
+                        data: Some(data),
                         ..Default::default()
-                    }),
-                    ..Default::default()
-                };
+                    };
 
-                resp = self.client.send(&msg).await;
-            }
+                    resp = self.client.send(&msg).await;
+                }
+                PayloadKind::Generic(alert) => {
+                    let msg = Message {
+                        token: Some(payload.token),
+                        notification: Some(Notification {
+                            title: Some(alert.title),
+                            body: Some(alert.body),
+                            image: alert.icon,
+                        }),
+                        ..Default::default()
+                    };
 
-            PayloadKind::BadgeUpdate(_) => {
-                bail!("FCM cannot handle badge updates and they should not be sent here.");
-            }
-        }
+                    resp = self.client.send(&msg).await;
+                }
+
+                PayloadKind::MessageNotification(alert) => {
+                    let title = self.format_title(&alert);
+
+                    let msg = Message {
+                        token: Some(payload.token),
+                        notification: Some(Notification {
+                            title: Some(title),
+                            body: Some(alert.body),
+                            image: Some(alert.icon),
+                        }),
+                        android: Some(AndroidConfig {
+                            collapse_key: Some(alert.tag),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    };
 
-        if let Err(err) = resp {
-            match err {
-                FcmError::Auth => {
-                    if let Err(err) = self
-                        .db
-                        .remove_push_subscription_by_session_id(&payload.session_id)
-                        .await
-                    {
-                        revolt_config::capture_error(&err);
-                    }
+                    resp = self.client.send(&msg).await;
                 }
-                err => {
-                    revolt_config::capture_error(&err);
+
+                PayloadKind::BadgeUpdate(_) => {
+                    bail!("FCM cannot handle badge updates and they should not be sent here.");
                 }
             }
         }
 
-        Ok(())
+        let outcome = match resp {
+            Ok(_) => DeliveryOutcome::Delivered,
+            Err(err) => Self::classify(err),
+        };
+
+        self.receipts
+            .record(
+                &self.db,
+                channel,
+                Recipient {
+                    user_id: &payload.user_id,
+                    session_id: &payload.session_id,
+                    attempts: payload.attempts,
+                },
+                &content,
+                outcome,
+            )
+            .await
     }
 }
 
@@ -214,3 +390,70 @@ impl AsyncConsumer for FcmOutboundConsumer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fcm_v1::Error as FcmError;
+
+    use super::FcmOutboundConsumer;
+
+    fn response(status: &str, body: &str) -> FcmError {
+        FcmError::FCM(format!("error code {status}: {body}"))
+    }
+
+    #[test]
+    fn classify() {
+        for (err, expected) in [
+            (
+                response(
+                    "404 (Not Found)",
+                    r#"{"error":{"code":404,"message":"Requested entity was not found.","status":"NOT_FOUND","details":[{"@type":"type.googleapis.com/google.firebase.fcm.v1.FcmError","errorCode":"UNREGISTERED"}]}}"#,
+                ),
+                "expired",
+            ),
+            (
+                response(
+                    "400 (Bad Request)",
+                    r#"{"error":{"code":400,"message":"The registration token is not a valid FCM registration token","status":"INVALID_ARGUMENT","details":[{"@type":"type.googleapis.com/google.firebase.fcm.v1.FcmError","errorCode":"INVALID_ARGUMENT"},{"@type":"type.googleapis.com/google.rpc.BadRequest","fieldViolations":[{"field":"message.token","description":"Invalid registration token"}]}]}}"#,
+                ),
+                "expired",
+            ),
+            (
+                response(
+                    "400 (Bad Request)",
+                    r#"{"error":{"code":400,"message":"Invalid value","status":"INVALID_ARGUMENT","details":[{"@type":"type.googleapis.com/google.firebase.fcm.v1.FcmError","errorCode":"INVALID_ARGUMENT"},{"@type":"type.googleapis.com/google.rpc.BadRequest","fieldViolations":[{"field":"message.android.ttl"}]}]}}"#,
+                ),
+                "rejected",
+            ),
+            (
+                response(
+                    "429 (Too Many Requests)",
+                    r#"{"error":{"code":429,"status":"RESOURCE_EXHAUSTED","details":[{"@type":"type.googleapis.com/google.firebase.fcm.v1.FcmError","errorCode":"QUOTA_EXCEEDED"}]}}"#,
+                ),
+                "retried",
+            ),
+            (
+                response(
+                    "503 (Service Unavailable)",
+                    r#"{"error":{"code":503,"status":"UNAVAILABLE"}}"#,
+                ),
+                "retried",
+            ),
+            (
+                response(
+                    "401 (Unauthorized)",
+                    r#"{"error":{"code":401,"details":[{"@type":"type.googleapis.com/google.firebase.fcm.v1.FcmError","errorCode":"THIRD_PARTY_AUTH_ERROR"}]}}"#,
+                ),
+                "unauthorized",
+            ),
+            (response("502 (Bad Gateway)", "<html>"), "retried"),
+            (response("400 (Bad Request)", "<html>"), "rejected"),
+            (FcmError::Auth, "unauthorized"),
+            (FcmError::Timeout, "retried"),
+            (FcmError::Deserialization, "rejected"),
+        ] {
+            let outcome = FcmOutboundConsumer::classify(err.clone());
+            assert_eq!(outcome.stat(), expected, "{err:?}");
+        }
+    }
+}
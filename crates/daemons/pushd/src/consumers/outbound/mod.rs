@@ -1,3 +1,7 @@
 pub mod apn;
+pub mod delivery;
+pub mod encryption;
 pub mod fcm;
+pub mod payload;
+pub mod unifiedpush;
 pub mod vapid;
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, bail, Result};
+use revolt_database::events::rabbit::PayloadKind;
+
+/// Encode a notification as the JSON body handed to web-style push providers (Vapid, UnifiedPush)
+///
+/// Friend request notifications are reduced to a `body` string, everything else is passed through as-is.
+pub fn encode_json_body(notification: PayloadKind, provider: &str) -> Result<String> {
+    match notification {
+        PayloadKind::FRReceived(alert) => {
+            let name = alert
+                .from_user
+                .display_name
+                .or(Some(format!(
+                    "{}#{}",
+                    alert.from_user.username, alert.from_user.discriminator
+                )))
+                .clone()
+                .ok_or_else(|| anyhow!("missing name"))?;
+
+            let mut body = HashMap::new();
+            body.insert("body", format!("{} sent you a friend request", name));
+
+            Ok(serde_json::to_string(&body)?)
+        }
+        PayloadKind::FRAccepted(alert) => {
+            let name = alert
+                .accepted_user
+                .display_name
+                .or(Some(format!(
+                    "{}#{}",
+                    alert.accepted_user.username, alert.accepted_user.discriminator
+                )))
+                .clone()
+                .ok_or_else(|| anyhow!("missing name"))?;
+
+            let mut body = HashMap::new();
+            body.insert("body", format!("{} accepted your friend request", name));
+
+            Ok(serde_json::to_string(&body)?)
+        }
+        PayloadKind::Generic(alert) => Ok(serde_json::to_string(&alert)?),
+        PayloadKind::MessageNotification(alert) => Ok(serde_json::to_string(&alert)?),
+        PayloadKind::BadgeUpdate(_) => {
+            bail!("{provider} cannot handle badge updates and they should not be sent here.");
+        }
+    }
+}
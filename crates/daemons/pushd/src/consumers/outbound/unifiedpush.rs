@@ -0,0 +1,129 @@
+use std::{sync::Arc, time::Duration};
+
+use amqprs::{channel::Channel as AmqpChannel, consumer::AsyncConsumer, BasicProperties, Deliver};
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use reqwest::{header::CONTENT_TYPE, redirect::Policy, Client, StatusCode, Url};
+use revolt_database::{events::rabbit::*, Database};
+use revolt_guard::{is_url_allowed, PublicResolver};
+
+use super::{
+    delivery::{DeliveryOutcome, DeliveryReceipts, Recipient},
+    payload::encode_json_body,
+};
+
+/// Delivers notifications to UnifiedPush distributors
+///
+/// The session's push token is the distributor endpoint, notifications are POSTed to it as JSON.
+/// Endpoints are checked again before every delivery and only ever connected to on public addresses.
+pub struct UnifiedPushOutboundConsumer {
+    db: Database,
+    client: Client,
+    receipts: DeliveryReceipts,
+}
+
+impl UnifiedPushOutboundConsumer {
+    pub async fn new(db: Database) -> Result<UnifiedPushOutboundConsumer> {
+        let config = revolt_config::config().await;
+
+        if !config.pushd.unifiedpush.enabled {
+            bail!("UnifiedPush is disabled");
+        }
+
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.pushd.unifiedpush.timeout))
+            .redirect(Policy::none())
+            .dns_resolver(Arc::new(PublicResolver))
+            .build()?;
+
+        Ok(UnifiedPushOutboundConsumer {
+            db,
+            client,
+            receipts: DeliveryReceipts::new("unifiedpush", &config.pushd.unifiedpush.queue),
+        })
+    }
+
+    /// Classify a distributor's response
+    fn classify(status: StatusCode) -> DeliveryOutcome {
+        match status {
+            status if status.is_success() => DeliveryOutcome::Delivered,
+            // Application was unregistered from the distributor
+            StatusCode::NOT_FOUND | StatusCode::GONE => {
+                DeliveryOutcome::Expired(status.to_string())
+            }
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::REQUEST_TIMEOUT => {
+                DeliveryOutcome::Transient(status.to_string())
+            }
+            status if status.is_server_error() => DeliveryOutcome::Transient(status.to_string()),
+            status => DeliveryOutcome::Rejected(status.to_string()),
+        }
+    }
+
+    async fn consume_event(
+        &mut self,
+        channel: &AmqpChannel,
+        _deliver: Deliver,
+        _basic_properties: BasicProperties,
+        content: Vec<u8>,
+    ) -> Result<()> {
+        let content = String::from_utf8(content)?;
+        let payload: PayloadToService = serde_json::from_str(content.as_str())?;
+
+        let body = encode_json_body(payload.notification, "UnifiedPush")?;
+
+        let outcome = match Url::parse(&payload.token) {
+            // Endpoint could never be requested in the first place
+            Err(err) => DeliveryOutcome::Expired(err.to_string()),
+            Ok(endpoint) if !is_url_allowed(&endpoint) => {
+                DeliveryOutcome::Expired(format!("{endpoint} is not a public address"))
+            }
+            Ok(endpoint) => match self
+                .client
+                .post(endpoint)
+                .header(CONTENT_TYPE, "application/json")
+                .body(body)
+                .send()
+                .await
+            {
+                Ok(response) => Self::classify(response.status()),
+                Err(err) if err.is_builder() => DeliveryOutcome::Expired(err.to_string()),
+                Err(err) => DeliveryOutcome::Transient(err.to_string()),
+            },
+        };
+
+        self.receipts
+            .record(
+                &self.db,
+                channel,
+                Recipient {
+                    user_id: &payload.user_id,
+                    session_id: &payload.session_id,
+                    attempts: payload.attempts,
+                },
+                &content,
+                outcome,
+            )
+            .await
+    }
+}
+
+#[allow(unused_variables)]
+#[async_trait]
+impl AsyncConsumer for UnifiedPushOutboundConsumer {
+    async fn consume(
+        &mut self,
+        channel: &AmqpChannel,
+        deliver: Deliver,
+        basic_properties: BasicProperties,
+        content: Vec<u8>,
+    ) {
+        if let Err(err) = self
+            .consume_event(channel, deliver, basic_properties, content)
+            .await
+        {
+            revolt_config::capture_anyhow(&err);
+            eprintln!("Failed to process UnifiedPush event: {err:?}");
+        }
+    }
+}
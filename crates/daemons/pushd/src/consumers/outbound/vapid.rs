@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use amqprs::{channel::Channel as AmqpChannel, consumer::AsyncConsumer, BasicProperties, Deliver};
 
 use anyhow::{anyhow, bail, Result};
@@ -14,10 +12,16 @@ use web_push::{
     WebPushClient, WebPushError, WebPushMessageBuilder,
 };
 
+use super::{
+    delivery::{DeliveryOutcome, DeliveryReceipts, Recipient},
+    payload::encode_json_body,
+};
+
 pub struct VapidOutboundConsumer {
     db: Database,
     client: IsahcWebPushClient,
     pkey: Vec<u8>,
+    receipts: DeliveryReceipts,
 }
 
 impl VapidOutboundConsumer {
@@ -36,12 +40,34 @@ impl VapidOutboundConsumer {
             db,
             client: IsahcWebPushClient::new().unwrap(),
             pkey: web_push_private_key,
+            receipts: DeliveryReceipts::new("vapid", &config.pushd.vapid.queue),
         })
     }
 
+    /// Classify a failed web push delivery
+    fn classify(err: WebPushError) -> DeliveryOutcome {
+        match err {
+            // Our VAPID signature was refused, the subscription itself may be fine
+            WebPushError::Unauthorized => DeliveryOutcome::Unauthorized(err.to_string()),
+            // Subscription was revoked by the browser or is no longer usable
+            WebPushError::EndpointNotValid
+            | WebPushError::EndpointNotFound
+            | WebPushError::InvalidUri
+            | WebPushError::MissingCryptoKeys
+            | WebPushError::InvalidCryptoKeys => DeliveryOutcome::Expired(err.to_string()),
+            // Connection problems, provider outages and unexpected statuses (e.g. rate limits)
+            WebPushError::ServerError(_)
+            | WebPushError::Unspecified
+            | WebPushError::IoError
+            | WebPushError::TlsError
+            | WebPushError::Other(_) => DeliveryOutcome::Transient(err.to_string()),
+            err => DeliveryOutcome::Rejected(err.to_string()),
+        }
+    }
+
     async fn consume_event(
         &mut self,
-        _channel: &AmqpChannel,
+        channel: &AmqpChannel,
         _deliver: Deliver,
         _basic_properties: BasicProperties,
         content: Vec<u8>,
@@ -65,80 +91,34 @@ impl VapidOutboundConsumer {
             },
         };
 
-        #[allow(clippy::needless_late_init)]
-        let payload_body: String;
-
-        match payload.notification {
-            PayloadKind::FRReceived(alert) => {
-                let name = alert
-                    .from_user
-                    .display_name
-                    .or(Some(format!(
-                        "{}#{}",
-                        alert.from_user.username, alert.from_user.discriminator
-                    )))
-                    .clone()
-                    .ok_or_else(|| anyhow!("missing name"))?;
-
-                let mut body = HashMap::new();
-                body.insert("body", format!("{} sent you a friend request", name));
-
-                payload_body = serde_json::to_string(&body)?;
-            }
-            PayloadKind::FRAccepted(alert) => {
-                let name = alert
-                    .accepted_user
-                    .display_name
-                    .or(Some(format!(
-                        "{}#{}",
-                        alert.accepted_user.username, alert.accepted_user.discriminator
-                    )))
-                    .clone()
-                    .ok_or_else(|| anyhow!("missing name"))?;
-
-                let mut body = HashMap::new();
-                body.insert("body", format!("{} accepted your friend request", name));
-
-                payload_body = serde_json::to_string(&body)?;
-            }
-            PayloadKind::Generic(alert) => {
-                payload_body = serde_json::to_string(&alert)?;
-            }
-            PayloadKind::MessageNotification(alert) => {
-                payload_body = serde_json::to_string(&alert)?;
-            }
-            PayloadKind::BadgeUpdate(_) => {
-                bail!("Vapid cannot handle badge updates and they should not be sent here.");
-            }
-        }
+        let payload_body = encode_json_body(payload.notification, "Vapid")?;
 
-        match VapidSignatureBuilder::from_pem(std::io::Cursor::new(&self.pkey), &subscription) {
-            Ok(sig_builder) => match sig_builder.build() {
-                Ok(signature) => {
-                    let mut builder = WebPushMessageBuilder::new(&subscription);
-                    builder.set_vapid_signature(signature);
-
-                    builder.set_payload(ContentEncoding::AesGcm, payload_body.as_bytes());
-
-                    match builder.build() {
-                        Ok(msg) => {
-                            if let Err(err) = self.client.send(msg).await {
-                                if err == WebPushError::Unauthorized {
-                                    self.db
-                                        .remove_push_subscription_by_session_id(&payload.session_id)
-                                        .await?;
-                                }
-                            }
-
-                            Ok(())
-                        }
-                        Err(err) => Err(err.into()),
-                    }
-                }
-                Err(err) => Err(err.into()),
-            },
-            Err(err) => Err(err.into()),
-        }
+        let signature =
+            VapidSignatureBuilder::from_pem(std::io::Cursor::new(&self.pkey), &subscription)?
+                .build()?;
+
+        let mut builder = WebPushMessageBuilder::new(&subscription);
+        builder.set_vapid_signature(signature);
+        builder.set_payload(ContentEncoding::AesGcm, payload_body.as_bytes());
+
+        let outcome = match self.client.send(builder.build()?).await {
+            Ok(()) => DeliveryOutcome::Delivered,
+            Err(err) => Self::classify(err),
+        };
+
+        self.receipts
+            .record(
+                &self.db,
+                channel,
+                Recipient {
+                    user_id: &payload.user_id,
+                    session_id: &payload.session_id,
+                    attempts: payload.attempts,
+                },
+                &content,
+                outcome,
+            )
+            .await
     }
 }
 
@@ -161,3 +141,35 @@ impl AsyncConsumer for VapidOutboundConsumer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use web_push::WebPushError;
+
+    use super::VapidOutboundConsumer;
+
+    #[test]
+    fn classify() {
+        for (err, expected) in [
+            (WebPushError::EndpointNotValid, "expired"),
+            (WebPushError::EndpointNotFound, "expired"),
+            (WebPushError::InvalidCryptoKeys, "expired"),
+            (WebPushError::Unauthorized, "unauthorized"),
+            (
+                WebPushError::ServerError(Some(Duration::from_secs(30))),
+                "retried",
+            ),
+            (
+                WebPushError::Other("429 Too Many Requests".to_string()),
+                "retried",
+            ),
+            (WebPushError::PayloadTooLarge, "rejected"),
+            (WebPushError::BadRequest(None), "rejected"),
+        ] {
+            let outcome = VapidOutboundConsumer::classify(err.clone());
+            assert_eq!(outcome.stat(), expected, "{err:?}");
+        }
+    }
+}
@@ -8,7 +8,7 @@ use amqprs::{
     },
     connection::{Connection, OpenConnectionArguments},
     consumer::AsyncConsumer,
-    FieldTable,
+    FieldTable, FieldValue,
 };
 use revolt_config::{config, Settings};
 use tokio::sync::Notify;
@@ -19,7 +19,10 @@ use consumers::{
         ack::AckConsumer, fr_accepted::FRAcceptedConsumer, fr_received::FRReceivedConsumer,
         generic::GenericConsumer, mass_mention::MassMessageConsumer, message::MessageConsumer,
     },
-    outbound::{apn::ApnsOutboundConsumer, fcm::FcmOutboundConsumer, vapid::VapidOutboundConsumer},
+    outbound::{
+        apn::ApnsOutboundConsumer, fcm::FcmOutboundConsumer,
+        unifiedpush::UnifiedPushOutboundConsumer, vapid::VapidOutboundConsumer,
+    },
 };
 
 #[tokio::main(flavor = "multi_thread", worker_threads = 2)]
@@ -115,11 +118,9 @@ async fn main() {
 
     if !config.pushd.apn.pkcs8.is_empty() {
         connections.push(
-            make_queue_and_consume(
+            make_outbound_queue_and_consume(
                 &config,
                 &config.pushd.apn.queue,
-                &config.pushd.apn.queue,
-                None,
                 ApnsOutboundConsumer::new(db.clone()).await.unwrap(),
             )
             .await,
@@ -142,11 +143,9 @@ async fn main() {
 
     if !config.pushd.fcm.auth_uri.is_empty() {
         connections.push(
-            make_queue_and_consume(
+            make_outbound_queue_and_consume(
                 &config,
                 &config.pushd.fcm.queue,
-                &config.pushd.fcm.queue,
-                None,
                 FcmOutboundConsumer::new(db.clone()).await.unwrap(),
             )
             .await,
@@ -155,17 +154,26 @@ async fn main() {
 
     if !config.pushd.vapid.public_key.is_empty() {
         connections.push(
-            make_queue_and_consume(
+            make_outbound_queue_and_consume(
                 &config,
                 &config.pushd.vapid.queue,
-                &config.pushd.vapid.queue,
-                None,
                 VapidOutboundConsumer::new(db.clone()).await.unwrap(),
             )
             .await,
         )
     }
 
+    if config.pushd.unifiedpush.enabled {
+        connections.push(
+            make_outbound_queue_and_consume(
+                &config,
+                &config.pushd.unifiedpush.queue,
+                UnifiedPushOutboundConsumer::new(db.clone()).await.unwrap(),
+            )
+            .await,
+        )
+    }
+
     let guard = Notify::new();
     guard.notified().await;
 
@@ -175,6 +183,44 @@ async fn main() {
     }
 }
 
+/// Consume an outbound (provider) queue, alongside the delay queue its transient failures are retried from
+///
+/// Messages in the delay queue expire after `retry_delay` and are dead-lettered straight back onto
+/// this deployment's outbound queue through the default exchange, so prod and test don't both receive them.
+async fn make_outbound_queue_and_consume<F>(
+    config: &Settings,
+    queue_name: &str,
+    consumer: F,
+) -> (Channel, Connection)
+where
+    F: AsyncConsumer + Send + 'static,
+{
+    let (channel, connection) =
+        make_queue_and_consume(config, queue_name, queue_name, None, consumer).await;
+
+    let mut table = FieldTable::new();
+    table.insert(
+        "x-message-ttl".try_into().unwrap(),
+        FieldValue::I(config.pushd.retry_delay as i32),
+    );
+    table.insert("x-dead-letter-exchange".try_into().unwrap(), "".into());
+    table.insert(
+        "x-dead-letter-routing-key".try_into().unwrap(),
+        config.pushd.get_queue_name(queue_name).into(),
+    );
+
+    let mut args = QueueDeclareArguments::new(&config.pushd.get_retry_queue(queue_name));
+    args.durable(true);
+    args.arguments(table);
+
+    _ = channel
+        .queue_declare(args.finish())
+        .await
+        .expect("Failed to declare pushd retry queue");
+
+    (channel, connection)
+}
+
 async fn make_queue_and_consume<F>(
     config: &Settings,
     queue_name: &str,
@@ -29,20 +29,37 @@ pub async fn edit(
 
     let mut channel = target.as_channel(db).await?;
     let mut query = DatabasePermissionQuery::new(db, &user).channel(&channel);
-    calculate_channel_permissions(&mut query)
-        .await
-        .throw_if_lacking_channel_permission(ChannelPermission::ManageChannel)?;
+    let permissions = calculate_channel_permissions(&mut query).await;
+
+    permissions.throw_if_lacking_channel_permission(ChannelPermission::ManageChannel)?;
 
     if data.name.is_none()
         && data.description.is_none()
         && data.icon.is_none()
         && data.nsfw.is_none()
         && data.owner.is_none()
+        && data.permissions_synced.is_none()
+        && data.slowmode.is_none()
         && data.remove.is_empty()
     {
         return Ok(Json(channel.into()));
     }
 
+    // Syncing replaces the channel's permissions with those of its category
+    let category = if data.permissions_synced == Some(true) {
+        permissions.throw_if_lacking_channel_permission(ChannelPermission::ManagePermissions)?;
+
+        Some(
+            query
+                .server_ref()
+                .as_ref()
+                .and_then(|server| server.category_of(channel.id()).cloned())
+                .ok_or_else(|| create_error!(InvalidOperation))?,
+        )
+    } else {
+        None
+    };
+
     let mut partial: PartialChannel = Default::default();
 
     // Transfer group ownership
@@ -150,6 +167,23 @@ pub async fn edit(
                 partial.nsfw = Some(new_nsfw);
             }
 
+            if data.permissions_synced == Some(false) {
+                if let Channel::Group { .. } = &channel {
+                    return Err(create_error!(InvalidOperation));
+                }
+
+                partial.permissions_synced = Some(false);
+            }
+
+            if let Some(new_slowmode) = data.slowmode {
+                if let Channel::TextChannel { slowmode, .. } = &mut channel {
+                    *slowmode = new_slowmode;
+                    partial.slowmode = Some(new_slowmode);
+                } else {
+                    return Err(create_error!(InvalidOperation));
+                }
+            }
+
             // Send out mutation system messages.
             if let Channel::Group { .. } = &channel {
                 if let Some(name) = &partial.name {
@@ -208,16 +242,22 @@ pub async fn edit(
                 }
             }
 
-            channel
-                .update(
-                    db,
-                    partial,
-                    data.remove.into_iter().map(|f| f.into()).collect(),
-                )
-                .await?;
+            if partial != PartialChannel::default() || !data.remove.is_empty() {
+                channel
+                    .update(
+                        db,
+                        partial,
+                        data.remove.into_iter().map(|f| f.into()).collect(),
+                    )
+                    .await?;
+            }
         }
         _ => return Err(create_error!(InvalidOperation)),
     };
 
+    if let Some(category) = category {
+        channel.sync_permissions(db, &category).await?;
+    }
+
     Ok(Json(channel.into()))
 }
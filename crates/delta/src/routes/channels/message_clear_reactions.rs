@@ -30,6 +30,7 @@ pub async fn clear_reactions(
     let mut message = msg.as_message_in_channel(db, channel.id()).await?;
 
     // Clear reactions
+    let reactions = message.reactions.clone();
     message
         .update(
             db,
@@ -37,8 +38,26 @@ pub async fn clear_reactions(
                 reactions: Some(Default::default()),
                 ..Default::default()
             },
-            vec![]
+            vec![],
         )
+        .await?;
+
+    // Revoke roles bound to any of the cleared reactions
+    for reaction_role in db
+        .fetch_reaction_roles_by_message(&message.id)
         .await
-        .map(|_| EmptyResponse)
+        .unwrap_or_default()
+    {
+        if let Some(user_ids) = reactions.get(&reaction_role.id.emoji) {
+            for user_id in user_ids {
+                if let Err(err) = reaction_role.revoke(db, user_id).await {
+                    log::error!(
+                        "Failed to revoke reaction role {reaction_role:?} from {user_id}: {err:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(EmptyResponse)
 }
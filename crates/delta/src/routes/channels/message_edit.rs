@@ -2,10 +2,10 @@ use iso8601_timestamp::Timestamp;
 use revolt_database::{
     tasks,
     util::{permissions::DatabasePermissionQuery, reference::Reference},
-    Database, Message, PartialMessage, User,
+    AutoModConfig, Database, Message, PartialMessage, User,
 };
 use revolt_models::v0::{self, Embed};
-use revolt_permissions::{calculate_channel_permissions, ChannelPermission};
+use revolt_permissions::{calculate_channel_permissions, ChannelPermission, PermissionQuery};
 use revolt_result::{create_error, Result};
 use rocket::{serde::json::Json, State};
 use validator::Validate;
@@ -47,6 +47,28 @@ pub async fn edit(
         return Err(create_error!(CannotEditMessage));
     }
 
+    // Run the new content past the server's auto-moderation rules
+    if edit.content.is_some() {
+        // Make sure we have server member (edge case if server owner)
+        query.are_we_a_member().await;
+
+        if let (Some(server), Some(member)) = (query.server_ref(), query.member_ref()) {
+            if let Some(config) = AutoModConfig::fetch_compiled(db, &server.id).await? {
+                config
+                    .check_message(
+                        db,
+                        server,
+                        &user.id,
+                        Some(member),
+                        channel.id(),
+                        edit.content.as_deref(),
+                        &[],
+                    )
+                    .await?;
+            }
+        }
+    }
+
     message.edited = Some(Timestamp::now_utc());
     let mut partial = PartialMessage {
         edited: message.edited,
@@ -85,7 +107,9 @@ pub async fn edit(
     message.update(db, partial, vec![]).await?;
 
     // Queue up a task for processing embeds if the we have sufficient permissions
-    if permissions.has_channel_permission(ChannelPermission::SendEmbeds) {
+    if permissions.has_channel_permission(ChannelPermission::SendEmbeds)
+        && !message.has_suppressed_embeds()
+    {
         if let Some(content) = edit.content {
             tasks::process_embeds::queue(
                 message.channel.to_string(),
@@ -96,5 +120,7 @@ pub async fn edit(
         }
     }
 
-    Ok(Json(message.into_model(None, None)))
+    let mut message = message.into_model(None, None);
+    Message::sign_attachments(db, &channel, std::slice::from_mut(&mut message), &user.id).await?;
+    Ok(Json(message))
 }
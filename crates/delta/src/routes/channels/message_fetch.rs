@@ -1,6 +1,6 @@
 use revolt_database::{
     util::{permissions::DatabasePermissionQuery, reference::Reference},
-    Database, User,
+    Database, Message, User,
 };
 use revolt_models::v0;
 use revolt_permissions::{calculate_channel_permissions, ChannelPermission};
@@ -29,5 +29,7 @@ pub async fn fetch(
         return Err(create_error!(NotFound));
     }
 
-    Ok(Json(message.into_model(None, None)))
+    let mut message = message.into_model(None, None);
+    Message::sign_attachments(db, &channel, std::slice::from_mut(&mut message), &user.id).await?;
+    Ok(Json(message))
 }
@@ -113,6 +113,7 @@ mod test {
                 embeds: None,
                 masquerade: None,
                 interactions: None,
+                sticker: None,
                 flags: None,
             },
             v0::MessageAuthor::User(&user.clone().into(&harness.db, Some(&user)).await),
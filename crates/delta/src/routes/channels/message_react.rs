@@ -1,6 +1,6 @@
 use revolt_database::{
     util::{permissions::DatabasePermissionQuery, reference::Reference},
-    Database, User,
+    Database, ReactionRoleCompositeKey, User,
 };
 use revolt_permissions::{calculate_channel_permissions, ChannelPermission};
 use revolt_result::Result;
@@ -29,8 +29,20 @@ pub async fn react_message(
     let message = msg.as_message_in_channel(db, channel.id()).await?;
 
     // Add the reaction
-    message
-        .add_reaction(db, &user, emoji.id)
+    message.add_reaction(db, &user, emoji.id).await?;
+
+    // Grant any role bound to this reaction, the reaction itself stands either way
+    if let Ok(reaction_role) = db
+        .fetch_reaction_role(&ReactionRoleCompositeKey {
+            message: message.id,
+            emoji: emoji.id.to_string(),
+        })
         .await
-        .map(|_| EmptyResponse)
+    {
+        if let Err(err) = reaction_role.grant(db, &user.id).await {
+            log::error!("Failed to grant reaction role {reaction_role:?}: {err:?}");
+        }
+    }
+
+    Ok(EmptyResponse)
 }
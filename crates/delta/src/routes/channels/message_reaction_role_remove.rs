@@ -0,0 +1,35 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, ReactionRoleCompositeKey, User,
+};
+use revolt_permissions::{calculate_channel_permissions, ChannelPermission};
+use revolt_result::Result;
+use rocket::State;
+use rocket_empty::EmptyResponse;
+
+/// # Remove Reaction Role
+///
+/// Unbind a reaction on a message from its role, roles already given out are kept.
+#[openapi(tag = "Interactions")]
+#[delete("/<target>/messages/<msg>/reaction_roles/<emoji>")]
+pub async fn remove_reaction_role(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    msg: Reference<'_>,
+    emoji: Reference<'_>,
+) -> Result<EmptyResponse> {
+    let channel = target.as_channel(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).channel(&channel);
+    calculate_channel_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManageRole)?;
+
+    let message = msg.as_message_in_channel(db, channel.id()).await?;
+    db.delete_reaction_role(&ReactionRoleCompositeKey {
+        message: message.id,
+        emoji: emoji.id.to_string(),
+    })
+    .await
+    .map(|_| EmptyResponse)
+}
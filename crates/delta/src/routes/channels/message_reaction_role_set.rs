@@ -0,0 +1,349 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, Emoji, ReactionRole, ReactionRoleCompositeKey, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_channel_permissions, ChannelPermission};
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+
+/// # Set Reaction Role
+///
+/// Bind a reaction on a message to a self-assignable role, members reacting with the emoji are given the role.
+///
+/// Channel must be part of a server.
+#[openapi(tag = "Interactions")]
+#[put("/<target>/messages/<msg>/reaction_roles/<emoji>", data = "<data>")]
+pub async fn set_reaction_role(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    msg: Reference<'_>,
+    emoji: Reference<'_>,
+    data: Json<v0::DataSetReactionRole>,
+) -> Result<Json<v0::ReactionRole>> {
+    let data = data.into_inner();
+    let channel = target.as_channel(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).channel(&channel);
+    calculate_channel_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManageRole)?;
+
+    let server = query
+        .server_ref()
+        .as_ref()
+        .ok_or_else(|| create_error!(InvalidOperation))?;
+
+    let role = server
+        .roles
+        .get(&data.role_id)
+        .ok_or_else(|| create_error!(NotFound))?;
+
+    // Only self-assignable roles may be handed out through reactions
+    if !role.self_assignable {
+        return Err(create_error!(InvalidOperation));
+    }
+
+    // Prevent us from binding roles above us
+    if server.owner != user.id && role.rank <= query.get_member_rank().unwrap_or(i64::MIN) {
+        return Err(create_error!(NotElevated));
+    }
+
+    let message = msg.as_message_in_channel(db, channel.id()).await?;
+    if !Emoji::can_use(db, emoji.id).await? {
+        return Err(create_error!(InvalidOperation));
+    }
+
+    let reaction_role = ReactionRole {
+        id: ReactionRoleCompositeKey {
+            message: message.id,
+            emoji: emoji.id.to_string(),
+        },
+        server_id: server.id.clone(),
+        channel_id: channel.id().to_string(),
+        role_id: data.role_id,
+    };
+
+    db.upsert_reaction_role(&reaction_role).await?;
+    Ok(Json(reaction_role.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{Member, PartialRole, ReactionRole, ReactionRoleCompositeKey};
+    use revolt_models::v0;
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn react_for_role() {
+        let harness = TestHarness::new().await;
+        let (_, owner_session, owner) = harness.new_user().await;
+        let (_, session, user) = harness.new_user().await;
+
+        let (server, channels) = harness.new_server(&owner).await;
+        let (channel, _, message) = harness.new_message(&owner, &server, channels.clone()).await;
+        Member::create(&harness.db, &server, &user, Some(channels))
+            .await
+            .expect("Failed to create member");
+
+        let (role_id, mut role) = harness.new_role(&server, 1, None).await;
+
+        // Roles must be flagged before they can be bound
+        let response = harness
+            .client
+            .put(format!(
+                "/channels/{}/messages/{}/reaction_roles/%F0%9F%91%8D",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new(
+                "x-session-token",
+                owner_session.token.to_string(),
+            ))
+            .header(ContentType::JSON)
+            .body(json!({ "role_id": role_id }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        drop(response);
+
+        role.update(
+            &harness.db,
+            &server.id,
+            &role_id,
+            PartialRole {
+                self_assignable: Some(true),
+                ..Default::default()
+            },
+            vec![],
+        )
+        .await
+        .unwrap();
+
+        let response = harness
+            .client
+            .put(format!(
+                "/channels/{}/messages/{}/reaction_roles/%F0%9F%91%8D",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new(
+                "x-session-token",
+                owner_session.token.to_string(),
+            ))
+            .header(ContentType::JSON)
+            .body(json!({ "role_id": role_id }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let reaction_role: v0::ReactionRole = response.into_json().await.expect("`ReactionRole`");
+        assert_eq!(reaction_role.role_id, role_id);
+
+        let response = harness
+            .client
+            .put(format!(
+                "/channels/{}/messages/{}/reactions/%F0%9F%91%8D",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+        drop(response);
+
+        let member = harness.db.fetch_member(&server.id, &user.id).await.unwrap();
+        assert_eq!(member.roles, vec![role_id.clone()]);
+
+        let response = harness
+            .client
+            .delete(format!(
+                "/channels/{}/messages/{}/reactions/%F0%9F%91%8D",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+        drop(response);
+
+        let member = harness.db.fetch_member(&server.id, &user.id).await.unwrap();
+        assert!(member.roles.is_empty());
+    }
+
+    #[rocket::async_test]
+    async fn bindings_removed_with_role_and_message() {
+        let harness = TestHarness::new().await;
+        let (_, _, owner) = harness.new_user().await;
+
+        let (server, channels) = harness.new_server(&owner).await;
+        let (channel, _, message) = harness.new_message(&owner, &server, channels).await;
+
+        let (first_role_id, first_role) = harness.new_role(&server, 1, None).await;
+        let (second_role_id, _) = harness.new_role(&server, 2, None).await;
+
+        for (emoji, role_id) in [("1", &first_role_id), ("2", &second_role_id)] {
+            harness
+                .db
+                .upsert_reaction_role(&ReactionRole {
+                    id: ReactionRoleCompositeKey {
+                        message: message.id.clone(),
+                        emoji: emoji.to_string(),
+                    },
+                    server_id: server.id.clone(),
+                    channel_id: channel.id().to_string(),
+                    role_id: role_id.clone(),
+                })
+                .await
+                .unwrap();
+        }
+
+        // Deleting a role removes the bindings granting it
+        first_role
+            .delete(&harness.db, &server.id, &first_role_id)
+            .await
+            .unwrap();
+
+        let reaction_roles = harness
+            .db
+            .fetch_reaction_roles_by_message(&message.id)
+            .await
+            .unwrap();
+
+        assert_eq!(reaction_roles.len(), 1);
+        assert_eq!(reaction_roles[0].role_id, second_role_id);
+
+        // Deleting the message removes the rest
+        let message_id = message.id.clone();
+        message.delete(&harness.db).await.unwrap();
+
+        assert!(harness
+            .db
+            .fetch_reaction_roles_by_message(&message_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[rocket::async_test]
+    async fn wiping_reactions_revokes_roles() {
+        let harness = TestHarness::new().await;
+        let (_, owner_session, owner) = harness.new_user().await;
+        let (_, first_session, first_user) = harness.new_user().await;
+        let (_, second_session, second_user) = harness.new_user().await;
+
+        let (server, channels) = harness.new_server(&owner).await;
+        let (channel, _, message) = harness.new_message(&owner, &server, channels.clone()).await;
+        for user in [&first_user, &second_user] {
+            Member::create(&harness.db, &server, user, Some(channels.clone()))
+                .await
+                .expect("Failed to create member");
+        }
+
+        let (role_id, mut role) = harness.new_role(&server, 1, None).await;
+        role.update(
+            &harness.db,
+            &server.id,
+            &role_id,
+            PartialRole {
+                self_assignable: Some(true),
+                ..Default::default()
+            },
+            vec![],
+        )
+        .await
+        .unwrap();
+
+        harness
+            .db
+            .upsert_reaction_role(&ReactionRole {
+                id: ReactionRoleCompositeKey {
+                    message: message.id.clone(),
+                    emoji: "👍".to_string(),
+                },
+                server_id: server.id.clone(),
+                channel_id: channel.id().to_string(),
+                role_id: role_id.clone(),
+            })
+            .await
+            .unwrap();
+
+        let (harness, channel, message, server) = (&harness, &channel, &message, &server);
+        let (first_session, second_session) = (&first_session, &second_session);
+        let (first_user, second_user) = (&first_user, &second_user);
+
+        let react_all = || async move {
+            for session in [first_session, second_session] {
+                let response = harness
+                    .client
+                    .put(format!(
+                        "/channels/{}/messages/{}/reactions/%F0%9F%91%8D",
+                        channel.id(),
+                        message.id
+                    ))
+                    .header(Header::new("x-session-token", session.token.to_string()))
+                    .dispatch()
+                    .await;
+
+                assert_eq!(response.status(), Status::NoContent);
+            }
+        };
+
+        let assert_roles = |expected: Vec<String>| async move {
+            for user in [first_user, second_user] {
+                let member = harness.db.fetch_member(&server.id, &user.id).await.unwrap();
+                assert_eq!(member.roles, expected);
+            }
+        };
+
+        // Removing all of a reaction revokes the role from everyone who reacted
+        react_all().await;
+        assert_roles(vec![role_id.clone()]).await;
+
+        let response = harness
+            .client
+            .delete(format!(
+                "/channels/{}/messages/{}/reactions/%F0%9F%91%8D?remove_all=true",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new(
+                "x-session-token",
+                owner_session.token.to_string(),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+        drop(response);
+        assert_roles(vec![]).await;
+
+        // So does clearing every reaction on the message
+        react_all().await;
+        assert_roles(vec![role_id.clone()]).await;
+
+        let response = harness
+            .client
+            .delete(format!(
+                "/channels/{}/messages/{}/reactions",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new(
+                "x-session-token",
+                owner_session.token.to_string(),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+        drop(response);
+        assert_roles(vec![]).await;
+    }
+}
@@ -0,0 +1,35 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_channel_permissions, ChannelPermission};
+use revolt_result::Result;
+use rocket::{serde::json::Json, State};
+
+/// # Fetch Reaction Roles
+///
+/// Fetch all reaction role bindings on a message.
+#[openapi(tag = "Interactions")]
+#[get("/<target>/messages/<msg>/reaction_roles")]
+pub async fn fetch_reaction_roles(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    msg: Reference<'_>,
+) -> Result<Json<Vec<v0::ReactionRole>>> {
+    let channel = target.as_channel(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).channel(&channel);
+    calculate_channel_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ViewChannel)?;
+
+    let message = msg.as_message_in_channel(db, channel.id()).await?;
+    Ok(Json(
+        db.fetch_reaction_roles_by_message(&message.id)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    ))
+}
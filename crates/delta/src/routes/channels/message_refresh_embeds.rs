@@ -0,0 +1,263 @@
+use revolt_database::{
+    tasks,
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, FieldsMessage, PartialMessage, User,
+};
+use revolt_models::v0::Embed;
+use revolt_permissions::{calculate_channel_permissions, ChannelPermission, PermissionValue};
+use revolt_result::{create_error, Result};
+use rocket::State;
+use rocket_empty::EmptyResponse;
+
+/// # Refresh Message Embeds
+///
+/// Discard generated embeds on a message and generate them again.
+///
+/// Requires `ManageMessages` permission, the author of the message must also be allowed to send embeds.
+#[openapi(tag = "Messaging")]
+#[post("/<target>/messages/<msg>/embeds/refresh")]
+pub async fn refresh_embeds(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    msg: Reference<'_>,
+) -> Result<EmptyResponse> {
+    let channel = target.as_channel(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).channel(&channel);
+    calculate_channel_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManageMessages)?;
+
+    // Fetch relevant message
+    let mut message = msg.as_message_in_channel(db, channel.id()).await?;
+    if message.has_suppressed_embeds() {
+        return Err(create_error!(InvalidOperation));
+    }
+
+    let Some(content) = message.content.clone() else {
+        return Err(create_error!(InvalidOperation));
+    };
+
+    // Moderators can't generate embeds the author couldn't have
+    let author_permissions: PermissionValue = if message.webhook.is_some() {
+        db.fetch_webhook(&message.author).await?.permissions.into()
+    } else {
+        let author = db.fetch_user(&message.author).await?;
+        let mut query = DatabasePermissionQuery::new(db, &author).channel(&channel);
+        calculate_channel_permissions(&mut query).await
+    };
+
+    author_permissions.throw_if_lacking_channel_permission(ChannelPermission::SendEmbeds)?;
+
+    // Clear any auto generated embeds
+    let embeds: Vec<Embed> = message
+        .embeds
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|embed| matches!(embed, Embed::Text(_)))
+        .collect();
+
+    if embeds.is_empty() {
+        message
+            .update(db, PartialMessage::default(), vec![FieldsMessage::Embeds])
+            .await?;
+    } else {
+        message
+            .update(
+                db,
+                PartialMessage {
+                    embeds: Some(embeds),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await?;
+    }
+
+    tasks::process_embeds::queue(message.channel, message.id, content).await;
+    Ok(EmptyResponse)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{
+        util::{idempotency::IdempotencyKey, reference::Reference},
+        Member, Message, PartialChannel, PartialMessage, Server, User,
+    };
+    use revolt_models::v0::{self, Embed, MessageFlags, WebsiteMetadata};
+    use revolt_permissions::{ChannelPermission, OverrideField};
+    use rocket::http::{Header, Status};
+
+    #[rocket::async_test]
+    async fn refresh_embeds() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (_, other_session, other_user) = harness.new_user().await;
+
+        let (server, channels) = Server::create(
+            &harness.db,
+            v0::DataCreateServer {
+                name: "Test Server".to_string(),
+                ..Default::default()
+            },
+            &user,
+            true,
+        )
+        .await
+        .expect("`Server`");
+
+        Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+        Member::create(&harness.db, &server, &other_user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+
+        let channel = channels.first().expect("channel");
+
+        let send = |user: User, flags: Option<u32>| {
+            let db = harness.db.clone();
+            let channel = channel.clone();
+
+            async move {
+                Message::create_from_api(
+                    &db,
+                    None,
+                    channel,
+                    v0::DataMessageSend {
+                        content: Some("https://revolt.chat".to_string()),
+                        nonce: None,
+                        attachments: None,
+                        replies: None,
+                        embeds: None,
+                        masquerade: None,
+                        interactions: None,
+                        sticker: None,
+                        flags,
+                    },
+                    v0::MessageAuthor::User(&user.clone().into(&db, Some(&user)).await),
+                    Some(user.clone().into(&db, Some(&user)).await),
+                    None,
+                    user.limits().await,
+                    IdempotencyKey::unchecked_from_string("0".to_string()),
+                    false,
+                    false,
+                )
+                .await
+                .expect("Failed to create message")
+            }
+        };
+
+        let mut message = send(user.clone(), None).await;
+        message
+            .update(
+                &harness.db,
+                PartialMessage {
+                    embeds: Some(vec![Embed::Website(WebsiteMetadata {
+                        url: None,
+                        original_url: None,
+                        special: None,
+                        title: Some("Stale".to_string()),
+                        description: None,
+                        image: None,
+                        video: None,
+                        site_name: None,
+                        author_name: None,
+                        icon_url: None,
+                        colour: None,
+                    })]),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        // Only moderators may refresh embeds
+        let response = harness
+            .client
+            .post(format!(
+                "/channels/{}/messages/{}/embeds/refresh",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new(
+                "x-session-token",
+                other_session.token.to_string(),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = harness
+            .client
+            .post(format!(
+                "/channels/{}/messages/{}/embeds/refresh",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+
+        let message = Reference::from_unchecked(&message.id)
+            .as_message(&harness.db)
+            .await
+            .unwrap();
+
+        assert!(message.embeds.is_none());
+
+        // Suppressed messages are left alone
+        let message = send(user.clone(), Some(1 << MessageFlags::SuppressEmbeds as u32)).await;
+        assert!(message.has_suppressed_embeds());
+
+        let response = harness
+            .client
+            .post(format!(
+                "/channels/{}/messages/{}/embeds/refresh",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+
+        // Authors who may not send embeds can't have them generated
+        let message = send(other_user.clone(), None).await;
+        let mut channel = channel.clone();
+        channel
+            .update(
+                &harness.db,
+                PartialChannel {
+                    default_permissions: Some(OverrideField {
+                        a: 0,
+                        d: ChannelPermission::SendEmbeds as i64,
+                    }),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let response = harness
+            .client
+            .post(format!(
+                "/channels/{}/messages/{}/embeds/refresh",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+}
@@ -3,7 +3,7 @@ use revolt_database::util::permissions::DatabasePermissionQuery;
 use revolt_database::{
     util::idempotency::IdempotencyKey, util::reference::Reference, Database, User,
 };
-use revolt_database::{Interactions, Message, AMQP};
+use revolt_database::{AutoModConfig, Interactions, Message, AMQP};
 use revolt_models::v0;
 use revolt_permissions::PermissionQuery;
 use revolt_permissions::{calculate_channel_permissions, ChannelPermission};
@@ -81,6 +81,31 @@ pub async fn message_send(
     // Make sure we have server member (edge case if server owner)
     query.are_we_a_member().await;
 
+    // Run the message past the server's auto-moderation rules
+    if let (Some(server), Some(member)) = (query.server_ref(), query.member_ref()) {
+        if let Some(config) = AutoModConfig::fetch_compiled(db, &server.id).await? {
+            config
+                .check_message(
+                    db,
+                    server,
+                    &user.id,
+                    Some(member),
+                    channel.id(),
+                    data.content.as_deref(),
+                    data.attachments.as_deref().unwrap_or_default(),
+                )
+                .await?;
+        }
+    }
+
+    // Enforce slowmode unless we can moderate this channel
+    let slowmode = !permissions.has_channel_permission(ChannelPermission::ManageMessages)
+        && !permissions.has_channel_permission(ChannelPermission::ManageChannel);
+
+    if slowmode {
+        channel.consume_slowmode(&user.id).await?;
+    }
+
     // Create model user / members
     let model_user = user
         .clone()
@@ -92,23 +117,36 @@ pub async fn message_send(
         .as_ref()
         .map(|member| member.clone().into_owned().into());
 
-    Ok(Json(
-        Message::create_from_api(
-            db,
-            Some(amqp),
-            channel,
-            data,
-            v0::MessageAuthor::User(&author),
-            Some(model_user.clone()),
-            model_member.clone(),
-            user.limits().await,
-            idempotency,
-            permissions.has_channel_permission(ChannelPermission::SendEmbeds),
-            allow_mentions,
-        )
-        .await?
-        .into_model(Some(model_user), model_member),
-    ))
+    let message = match Message::create_from_api(
+        db,
+        Some(amqp),
+        channel.clone(),
+        data,
+        v0::MessageAuthor::User(&author),
+        Some(model_user.clone()),
+        model_member.clone(),
+        user.limits().await,
+        idempotency,
+        permissions.has_channel_permission(ChannelPermission::SendEmbeds),
+        allow_mentions,
+    )
+    .await
+    {
+        Ok(message) => message,
+        Err(error) => {
+            // A rejected message should not use up the slowmode cooldown
+            if slowmode {
+                channel.release_slowmode(&user.id).await;
+            }
+
+            return Err(error);
+        }
+    };
+
+    let mut message = message.into_model(Some(model_user), model_member);
+
+    Message::sign_attachments(db, &channel, std::slice::from_mut(&mut message), &user.id).await?;
+    Ok(Json(message))
 }
 
 #[cfg(test)]
@@ -119,6 +157,7 @@ mod test {
     use revolt_database::{
         util::{idempotency::IdempotencyKey, reference::Reference},
         Channel, Member, Message, MessageFlagsValue, PartialChannel, PartialMember, Role, Server,
+        Sticker, StickerParent,
     };
     use revolt_models::v0::{self, DataCreateServerChannel, MessageFlags};
     use revolt_permissions::{ChannelPermission, OverrideField};
@@ -163,6 +202,8 @@ mod test {
             colour: None,
             hoist: false,
             rank: 5,
+            self_assignable: false,
+            group: None,
         };
 
         let role_id = role
@@ -188,10 +229,13 @@ mod test {
             active: None,
             permissions: None,
             role_permissions: Some(overrides),
+            member_permissions: None,
             default_permissions: Some(OverrideField {
                 a: 0,
                 d: ChannelPermission::ViewChannel as i64,
             }),
+            permissions_synced: None,
+            slowmode: None,
             last_message_id: None,
         };
         locked_channel
@@ -220,6 +264,7 @@ mod test {
                 embeds: None,
                 masquerade: None,
                 interactions: None,
+                sticker: None,
                 flags: None,
             },
             v0::MessageAuthor::User(&user.clone().into(&harness.db, Some(&user)).await),
@@ -260,6 +305,7 @@ mod test {
                 embeds: None,
                 masquerade: None,
                 interactions: None,
+                sticker: None,
                 flags: None,
             },
             v0::MessageAuthor::User(&user.clone().into(&harness.db, Some(&user)).await),
@@ -287,6 +333,7 @@ mod test {
             avatar: None,
             timeout: None,
             roles: Some(second_member_roles),
+            pending: None,
         };
         second_member
             .update(&harness.db, partial, vec![])
@@ -306,6 +353,7 @@ mod test {
                 embeds: None,
                 masquerade: None,
                 interactions: None,
+                sticker: None,
                 flags: None,
             },
             v0::MessageAuthor::User(&user.clone().into(&harness.db, Some(&user)).await),
@@ -352,6 +400,7 @@ mod test {
                 embeds: None,
                 masquerade: None,
                 interactions: None,
+                sticker: None,
                 flags: None,
             },
             v0::MessageAuthor::User(&user.clone().into(&harness.db, Some(&user)).await),
@@ -403,6 +452,7 @@ mod test {
                 embeds: None,
                 masquerade: None,
                 interactions: None,
+                sticker: None,
                 flags: None,
             },
             v0::MessageAuthor::User(&user.clone().into(&harness.db, Some(&user)).await),
@@ -440,6 +490,7 @@ mod test {
                 embeds: None,
                 masquerade: None,
                 interactions: None,
+                sticker: None,
                 flags: None,
             },
             v0::MessageAuthor::User(&user.clone().into(&harness.db, Some(&user)).await),
@@ -471,6 +522,7 @@ mod test {
                 embeds: None,
                 masquerade: None,
                 interactions: None,
+                sticker: None,
                 flags: None,
             },
             v0::MessageAuthor::User(&user.clone().into(&harness.db, Some(&user)).await),
@@ -521,6 +573,7 @@ mod test {
                 embeds: None,
                 masquerade: None,
                 interactions: None,
+                sticker: None,
                 flags: None,
             },
             v0::MessageAuthor::User(
@@ -566,6 +619,7 @@ mod test {
                 embeds: None,
                 masquerade: None,
                 interactions: None,
+                sticker: None,
                 flags: None,
             },
             v0::MessageAuthor::User(
@@ -613,6 +667,7 @@ mod test {
                     nickname: None,
                     roles: Some(vec![role_id.clone()]),
                     timeout: None,
+                    pending: None,
                 },
                 vec![],
             )
@@ -633,6 +688,7 @@ mod test {
                 embeds: None,
                 masquerade: None,
                 interactions: None,
+                sticker: None,
                 flags: None,
             },
             v0::MessageAuthor::User(
@@ -673,4 +729,190 @@ mod test {
             "Message has no role mentions"
         );
     }
+
+    #[rocket::async_test]
+    async fn voice_message_requires_audio_attachment() {
+        let harness = TestHarness::new().await;
+        let (_, _, user) = harness.new_user().await;
+        let (_, channels) = harness.new_server(&user).await;
+
+        let error = Message::create_from_api(
+            &harness.db,
+            Some(&harness.amqp),
+            channels[0].clone(),
+            v0::DataMessageSend {
+                content: Some("Not a voice message".to_string()),
+                nonce: None,
+                attachments: None,
+                replies: None,
+                embeds: None,
+                masquerade: None,
+                interactions: None,
+                sticker: None,
+                flags: Some(1 << MessageFlags::VoiceMessage as u32),
+            },
+            v0::MessageAuthor::User(&user.clone().into(&harness.db, Some(&user)).await),
+            Some(user.clone().into(&harness.db, Some(&user)).await),
+            None,
+            user.limits().await,
+            IdempotencyKey::unchecked_from_string("0".to_string()),
+            false,
+            true,
+        )
+        .await
+        .expect_err("Voice message without an attachment was created");
+
+        assert!(matches!(error.error_type, ErrorType::InvalidFlagValue));
+    }
+
+    #[rocket::async_test]
+    async fn voice_message_requires_processed_recording() {
+        use rocket::http::{ContentType, Header, Status};
+
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (_, channels) = harness.new_server(&user).await;
+
+        // Audio uploaded as a plain attachment has no waveform
+        let attachment = harness.new_upload("attachments", "audio/mpeg").await;
+
+        let response = harness
+            .client
+            .post(format!("/channels/{}/messages", channels[0].id()))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "attachments": [attachment],
+                    "flags": 1 << MessageFlags::VoiceMessage as u32
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[rocket::async_test]
+    async fn slowmode() {
+        use rocket::http::{ContentType, Header, Status};
+
+        let harness = TestHarness::new().await;
+        let (_, owner_session, owner) = harness.new_user().await;
+        let (_, session, user) = harness.new_user().await;
+        let (server, channels) = harness.new_server(&owner).await;
+        Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+
+        let response = harness
+            .client
+            .patch(format!("/channels/{}", channels[0].id()))
+            .header(Header::new(
+                "x-session-token",
+                owner_session.token.to_string(),
+            ))
+            .header(ContentType::JSON)
+            .body(json!({ "slowmode": 30 }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        drop(response);
+
+        for (token, content, expected) in [
+            // Rejected messages do not use up the cooldown
+            (&session.token, "", Status::UnprocessableEntity),
+            (&session.token, "Hello", Status::Ok),
+            (&session.token, "Hello", Status::TooManyRequests),
+            // Moderators bypass slowmode
+            (&owner_session.token, "Hello", Status::Ok),
+            (&owner_session.token, "Hello", Status::Ok),
+        ] {
+            let response = harness
+                .client
+                .post(format!("/channels/{}/messages", channels[0].id()))
+                .header(Header::new("x-session-token", token.to_string()))
+                .header(ContentType::JSON)
+                .body(json!({ "content": content }).to_string())
+                .dispatch()
+                .await;
+
+            assert_eq!(response.status(), expected);
+        }
+    }
+
+    #[rocket::async_test]
+    async fn sticker() {
+        use rocket::http::{ContentType, Header, Status};
+
+        let harness = TestHarness::new().await;
+        let (_, _, owner) = harness.new_user().await;
+        let (_, session, user) = harness.new_user().await;
+        let (sticker_server, sticker_channels) = harness.new_server(&owner).await;
+        let (_, channels) = harness.new_server(&user).await;
+        let sticker = harness.new_sticker(&sticker_server, &owner).await;
+
+        let send = |channel: &str, body: serde_json::Value| {
+            harness
+                .client
+                .post(format!("/channels/{channel}/messages"))
+                .header(Header::new("x-session-token", session.token.to_string()))
+                .header(ContentType::JSON)
+                .body(body.to_string())
+                .dispatch()
+        };
+
+        // Stickers from other servers can't be used without being a member
+        let response = send(channels[0].id(), json!({ "sticker": sticker.id })).await;
+        assert_eq!(response.status(), Status::NotFound);
+        drop(response);
+
+        Member::create(
+            &harness.db,
+            &sticker_server,
+            &user,
+            Some(sticker_channels.clone()),
+        )
+        .await
+        .expect("Failed to create member");
+
+        let response = send(channels[0].id(), json!({ "sticker": sticker.id })).await;
+        assert_eq!(response.status(), Status::Ok);
+        let message: v0::Message = response.into_json().await.expect("`Message`");
+        assert_eq!(message.sticker, Some(sticker.id.clone()));
+        assert_eq!(message.content, None);
+
+        // Stickers are sent on their own
+        let response = send(
+            channels[0].id(),
+            json!({ "sticker": sticker.id, "content": "Hello" }),
+        )
+        .await;
+        assert_eq!(response.status(), Status::BadRequest);
+        drop(response);
+
+        // NSFW stickers can only be sent in NSFW channels
+        let nsfw_sticker = Sticker {
+            id: ulid::Ulid::new().to_string(),
+            parent: StickerParent::Server {
+                id: sticker_server.id.clone(),
+            },
+            creator_id: owner.id.clone(),
+            name: "nsfw".to_string(),
+            description: None,
+            emoji: None,
+            animated: false,
+            nsfw: true,
+        };
+
+        nsfw_sticker
+            .create(&harness.db)
+            .await
+            .expect("Failed to create sticker");
+
+        let response = send(channels[0].id(), json!({ "sticker": nsfw_sticker.id })).await;
+        assert_eq!(response.status(), Status::BadRequest);
+    }
 }
@@ -0,0 +1,242 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, FieldsMessage, MessageFlagsValue, PartialMessage, User,
+};
+use revolt_models::v0::{self, Embed, MessageFlags};
+use revolt_permissions::{calculate_channel_permissions, ChannelPermission};
+use revolt_result::{create_error, Result};
+use rocket::State;
+use rocket_empty::EmptyResponse;
+
+/// # Suppress Message Embeds
+///
+/// Remove a generated embed from a message, or all of them.
+///
+/// Removing all embeds also prevents new ones from being generated for the message.
+///
+/// Requires `ManageMessages` if the message is not your own.
+#[openapi(tag = "Messaging")]
+#[delete("/<target>/messages/<msg>/embeds?<options..>")]
+pub async fn suppress_embeds(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    msg: Reference<'_>,
+    options: v0::OptionsSuppressEmbeds,
+) -> Result<EmptyResponse> {
+    let channel = target.as_channel(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).channel(&channel);
+    let permissions = calculate_channel_permissions(&mut query).await;
+
+    permissions.throw_if_lacking_channel_permission(ChannelPermission::ViewChannel)?;
+
+    // Fetch relevant message
+    let mut message = msg.as_message_in_channel(db, channel.id()).await?;
+    if message.author != user.id {
+        permissions.throw_if_lacking_channel_permission(ChannelPermission::ManageMessages)?;
+    }
+
+    let mut embeds = message.embeds.clone().unwrap_or_default();
+    let mut partial = PartialMessage::default();
+
+    if let Some(index) = options.index {
+        // Only generated embeds can be removed, sent embeds are part of the message
+        if !matches!(embeds.get(index), Some(embed) if !matches!(embed, Embed::Text(_))) {
+            return Err(create_error!(InvalidOperation));
+        }
+
+        embeds.remove(index);
+    } else {
+        embeds.retain(|embed| matches!(embed, Embed::Text(_)));
+
+        let mut flags = MessageFlagsValue(message.flags.unwrap_or_default());
+        flags.set(MessageFlags::SuppressEmbeds, true);
+        partial.flags = Some(flags.0);
+    }
+
+    let remove = if embeds.is_empty() {
+        vec![FieldsMessage::Embeds]
+    } else {
+        partial.embeds = Some(embeds);
+        vec![]
+    };
+
+    message
+        .update(db, partial, remove)
+        .await
+        .map(|_| EmptyResponse)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{
+        util::{idempotency::IdempotencyKey, reference::Reference},
+        Member, Message, PartialMessage, Server,
+    };
+    use revolt_models::v0::{self, Embed, MessageFlags, WebsiteMetadata};
+    use rocket::http::{Header, Status};
+
+    fn website_embed(title: &str) -> Embed {
+        Embed::Website(WebsiteMetadata {
+            url: None,
+            original_url: None,
+            special: None,
+            title: Some(title.to_string()),
+            description: None,
+            image: None,
+            video: None,
+            site_name: None,
+            author_name: None,
+            icon_url: None,
+            colour: None,
+        })
+    }
+
+    #[rocket::async_test]
+    async fn suppress_embeds() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (_, other_session, other_user) = harness.new_user().await;
+
+        let (server, channels) = Server::create(
+            &harness.db,
+            v0::DataCreateServer {
+                name: "Test Server".to_string(),
+                ..Default::default()
+            },
+            &user,
+            true,
+        )
+        .await
+        .expect("`Server`");
+
+        Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+        Member::create(&harness.db, &server, &other_user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+
+        let channel = channels.first().expect("channel");
+
+        let mut message = Message::create_from_api(
+            &harness.db,
+            None,
+            channel.clone(),
+            v0::DataMessageSend {
+                content: Some("https://revolt.chat https://example.com".to_string()),
+                nonce: None,
+                attachments: None,
+                replies: None,
+                embeds: None,
+                masquerade: None,
+                interactions: None,
+                sticker: None,
+                flags: None,
+            },
+            v0::MessageAuthor::User(&user.clone().into(&harness.db, Some(&user)).await),
+            Some(user.clone().into(&harness.db, Some(&user)).await),
+            None,
+            user.limits().await,
+            IdempotencyKey::unchecked_from_string("0".to_string()),
+            false,
+            false,
+        )
+        .await
+        .expect("Failed to create message");
+
+        message
+            .update(
+                &harness.db,
+                PartialMessage {
+                    embeds: Some(vec![website_embed("first"), website_embed("second")]),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        // Other members cannot suppress embeds without permission
+        let response = harness
+            .client
+            .delete(format!(
+                "/channels/{}/messages/{}/embeds?index=0",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new(
+                "x-session-token",
+                other_session.token.to_string(),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+
+        // Indices must point at a generated embed
+        let response = harness
+            .client
+            .delete(format!(
+                "/channels/{}/messages/{}/embeds?index=2",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+
+        // Remove a single embed
+        let response = harness
+            .client
+            .delete(format!(
+                "/channels/{}/messages/{}/embeds?index=0",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+
+        let message = Reference::from_unchecked(&message.id)
+            .as_message(&harness.db)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            message.embeds.as_deref(),
+            Some([Embed::Website(WebsiteMetadata { title: Some(title), .. })]) if title == "second"
+        ));
+        assert!(!message.has_suppressed_embeds());
+
+        // Remove all embeds
+        let response = harness
+            .client
+            .delete(format!(
+                "/channels/{}/messages/{}/embeds",
+                channel.id(),
+                message.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+
+        let message = Reference::from_unchecked(&message.id)
+            .as_message(&harness.db)
+            .await
+            .unwrap();
+
+        assert!(message.embeds.is_none());
+        assert!(message.has_suppressed_embeds());
+        assert!(message
+            .flags
+            .is_some_and(|flags| flags & (1 << MessageFlags::SuppressEmbeds as u32) != 0));
+    }
+}
@@ -1,6 +1,6 @@
 use revolt_database::{
     util::{permissions::DatabasePermissionQuery, reference::Reference},
-    Database, User,
+    Database, ReactionRoleCompositeKey, User,
 };
 use revolt_models::v0;
 use revolt_permissions::{calculate_channel_permissions, ChannelPermission};
@@ -39,16 +39,34 @@ pub async fn unreact_message(
     let message = msg.as_message_in_channel(db, channel.id()).await?;
 
     // Check if we should wipe all of this reaction
-    if remove_all {
-        return message
-            .clear_reaction(db, emoji.id)
-            .await
-            .map(|_| EmptyResponse);
-    }
+    let user_ids = if remove_all {
+        let user_ids = message.reactions.get(emoji.id).cloned().unwrap_or_default();
+
+        message.clear_reaction(db, emoji.id).await?;
+        user_ids.into_iter().collect()
+    } else {
+        // Remove the reaction
+        let user_id = options.user_id.as_ref().unwrap_or(&user.id);
+        message.remove_reaction(db, user_id, emoji.id).await?;
+        vec![user_id.clone()]
+    };
 
-    // Remove the reaction
-    message
-        .remove_reaction(db, options.user_id.as_ref().unwrap_or(&user.id), emoji.id)
+    // Revoke any role bound to this reaction
+    if let Ok(reaction_role) = db
+        .fetch_reaction_role(&ReactionRoleCompositeKey {
+            message: message.id,
+            emoji: emoji.id.to_string(),
+        })
         .await
-        .map(|_| EmptyResponse)
+    {
+        for user_id in user_ids {
+            if let Err(err) = reaction_role.revoke(db, &user_id).await {
+                log::error!(
+                    "Failed to revoke reaction role {reaction_role:?} from {user_id}: {err:?}"
+                );
+            }
+        }
+    }
+
+    Ok(EmptyResponse)
 }
@@ -18,12 +18,20 @@ mod message_fetch;
 mod message_pin;
 mod message_query;
 mod message_react;
+mod message_reaction_role_remove;
+mod message_reaction_role_set;
+mod message_reaction_roles_fetch;
+mod message_refresh_embeds;
 mod message_search;
 mod message_send;
+mod message_suppress_embeds;
 mod message_unpin;
 mod message_unreact;
+mod permissions_explain;
+mod permissions_remove_member;
 mod permissions_set;
 mod permissions_set_default;
+mod permissions_set_member;
 mod voice_join;
 mod webhook_create;
 mod webhook_fetch_all;
@@ -45,15 +53,23 @@ pub fn routes() -> (Vec<Route>, OpenApi) {
         message_bulk_delete::bulk_delete_messages,
         message_delete::delete,
         message_unpin::message_unpin,
+        message_suppress_embeds::suppress_embeds,
+        message_refresh_embeds::refresh_embeds,
         group_create::create_group,
         group_add_member::add_member,
         group_remove_member::remove_member,
         voice_join::call,
+        permissions_explain::explain_member_permissions,
         permissions_set::set_role_permissions,
         permissions_set_default::set_default_channel_permissions,
+        permissions_set_member::set_member_permissions,
+        permissions_remove_member::remove_member_permissions,
         message_react::react_message,
         message_unreact::unreact_message,
         message_clear_reactions::clear_reactions,
+        message_reaction_roles_fetch::fetch_reaction_roles,
+        message_reaction_role_set::set_reaction_role,
+        message_reaction_role_remove::remove_reaction_role,
         webhook_create::create_webhook,
         webhook_fetch_all::fetch_webhooks,
     ]
@@ -0,0 +1,120 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{
+    calculate_channel_permissions, explain_channel_permissions, ChannelPermission,
+};
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+
+/// # Explain Member Permissions
+///
+/// Calculates the effective permissions of a member in this channel,
+/// along with each layer applied to reach them.
+///
+/// Channel must be a `TextChannel` or `VoiceChannel`.
+#[openapi(tag = "Channel Permissions")]
+#[get("/<target>/permissions/members/<member_id>")]
+pub async fn explain_member_permissions(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    member_id: Reference<'_>,
+) -> Result<Json<v0::PermissionExplanation>> {
+    let channel = target.as_channel(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).channel(&channel);
+    calculate_channel_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManagePermissions)?;
+
+    let server = query
+        .server_ref()
+        .as_ref()
+        .ok_or_else(|| create_error!(InvalidOperation))?;
+
+    let member = member_id.as_member(db, &server.id).await?;
+    let target_user = db.fetch_user(&member.id.user).await?;
+
+    let mut query = DatabasePermissionQuery::new(db, &target_user)
+        .channel(&channel)
+        .server(server)
+        .member(&member);
+
+    let (permissions, trace) = explain_channel_permissions(&mut query).await;
+
+    Ok(Json(v0::PermissionExplanation {
+        permissions: permissions.into(),
+        trace,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{Member, PartialChannel};
+    use revolt_models::v0;
+    use revolt_permissions::{ChannelPermission, OverrideField, PermissionLayer};
+    use rocket::http::{Header, Status};
+
+    #[rocket::async_test]
+    async fn explain_member_permissions() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (_, _, other_user) = harness.new_user().await;
+
+        let (server, channels) = harness.new_server(&user).await;
+        Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+        Member::create(&harness.db, &server, &other_user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+
+        let mut channel = channels[0].clone();
+        channel
+            .update(
+                &harness.db,
+                PartialChannel {
+                    default_permissions: Some(OverrideField {
+                        a: 0,
+                        d: ChannelPermission::SendMessage as i64,
+                    }),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let response = harness
+            .client
+            .get(format!(
+                "/channels/{}/permissions/members/{}",
+                channel.id(),
+                other_user.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let explanation: v0::PermissionExplanation =
+            response.into_json().await.expect("`PermissionExplanation`");
+
+        assert_eq!(explanation.trace[0].layer, PermissionLayer::ServerDefault);
+        assert!(explanation
+            .trace
+            .iter()
+            .any(|step| step.layer == PermissionLayer::ChannelDefault));
+        assert_eq!(
+            explanation.trace.last().unwrap().permissions,
+            explanation.permissions
+        );
+        assert_eq!(
+            explanation.permissions & ChannelPermission::SendMessage as u64,
+            0
+        );
+    }
+}
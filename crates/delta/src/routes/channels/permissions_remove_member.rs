@@ -0,0 +1,137 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Channel, Database, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_channel_permissions, ChannelPermission, Override};
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+
+/// # Remove Member Permission
+///
+/// Removes the permission override for the specified member in this channel.
+///
+/// Channel must be a `TextChannel` or `VoiceChannel`.
+#[openapi(tag = "Channel Permissions")]
+#[delete("/<target>/permissions/members/<member_id>")]
+pub async fn remove_member_permissions(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    member_id: Reference<'_>,
+) -> Result<Json<v0::Channel>> {
+    let mut channel = target.as_channel(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).channel(&channel);
+    let permissions = calculate_channel_permissions(&mut query).await;
+
+    permissions.throw_if_lacking_channel_permission(ChannelPermission::ManagePermissions)?;
+
+    let current_value: Override = match &channel {
+        Channel::TextChannel {
+            member_permissions, ..
+        }
+        | Channel::VoiceChannel {
+            member_permissions, ..
+        } => member_permissions
+            .get(member_id.id)
+            .map(|value| (*value).into())
+            .ok_or_else(|| create_error!(NotFound))?,
+        _ => return Err(create_error!(InvalidOperation)),
+    };
+
+    let server = query
+        .server_ref()
+        .as_ref()
+        .ok_or_else(|| create_error!(InvalidOperation))?;
+
+    // Prevent us from editing members above us, if they are still in the server
+    if let Ok(member) = member_id.as_member(db, &server.id).await {
+        if server.owner != user.id
+            && member.get_ranking(server) <= query.get_member_rank().unwrap_or(i64::MIN)
+        {
+            return Err(create_error!(NotElevated));
+        }
+    }
+
+    // Lifting a deny requires holding the permissions it denied
+    permissions
+        .throw_permission_override(current_value, &Override::default())
+        .await?;
+
+    channel.remove_member_permission(db, member_id.id).await?;
+
+    Ok(Json(channel.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{Channel, Member, RemovalIntention};
+    use revolt_permissions::{ChannelPermission, OverrideField};
+    use rocket::http::{Header, Status};
+
+    fn has_override(channel: &Channel, user_id: &str) -> bool {
+        matches!(
+            channel,
+            Channel::TextChannel { member_permissions, .. }
+                if member_permissions.contains_key(user_id)
+        )
+    }
+
+    #[rocket::async_test]
+    async fn remove_member_permissions() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (_, _, other_user) = harness.new_user().await;
+
+        let (server, channels) = harness.new_server(&user).await;
+        Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+        let (member, _) = Member::create(&harness.db, &server, &other_user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+
+        let override_field = OverrideField {
+            a: ChannelPermission::ViewChannel as i64,
+            d: 0,
+        };
+
+        let mut channel = channels[0].clone();
+        channel
+            .set_member_permission(&harness.db, &other_user.id, override_field)
+            .await
+            .unwrap();
+
+        let response = harness
+            .client
+            .delete(format!(
+                "/channels/{}/permissions/members/{}",
+                channel.id(),
+                other_user.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        drop(response);
+
+        let mut channel = harness.db.fetch_channel(channel.id()).await.unwrap();
+        assert!(!has_override(&channel, &other_user.id));
+
+        // Overrides are dropped when the member leaves
+        channel
+            .set_member_permission(&harness.db, &other_user.id, override_field)
+            .await
+            .unwrap();
+
+        member
+            .remove(&harness.db, &server, RemovalIntention::Leave, true)
+            .await
+            .unwrap();
+
+        let channel = harness.db.fetch_channel(channel.id()).await.unwrap();
+        assert!(!has_override(&channel, &other_user.id));
+    }
+}
@@ -63,6 +63,7 @@ pub async fn set_default_channel_permissions(
                         db,
                         PartialChannel {
                             default_permissions: Some(field.into()),
+                            permissions_synced: Some(false),
                             ..Default::default()
                         },
                         vec![],
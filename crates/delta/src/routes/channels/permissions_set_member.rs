@@ -0,0 +1,151 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Channel, Database, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_channel_permissions, ChannelPermission, Override};
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+
+/// # Set Member Permission
+///
+/// Sets permissions for the specified member in this channel.
+///
+/// Channel must be a `TextChannel` or `VoiceChannel`.
+#[openapi(tag = "Channel Permissions")]
+#[put("/<target>/permissions/members/<member_id>", data = "<data>")]
+pub async fn set_member_permissions(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    member_id: Reference<'_>,
+    data: Json<v0::DataSetRolePermissions>,
+) -> Result<Json<v0::Channel>> {
+    let mut channel = target.as_channel(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).channel(&channel);
+    let permissions = calculate_channel_permissions(&mut query).await;
+
+    permissions.throw_if_lacking_channel_permission(ChannelPermission::ManagePermissions)?;
+
+    let current_value: Option<Override> = match &channel {
+        Channel::TextChannel {
+            member_permissions, ..
+        }
+        | Channel::VoiceChannel {
+            member_permissions, ..
+        } => member_permissions
+            .get(member_id.id)
+            .map(|value| (*value).into()),
+        _ => return Err(create_error!(InvalidOperation)),
+    };
+
+    let server = query
+        .server_ref()
+        .as_ref()
+        .ok_or_else(|| create_error!(InvalidOperation))?;
+
+    let member = member_id.as_member(db, &server.id).await?;
+
+    // Prevent us from editing members above us
+    if server.owner != user.id
+        && member.get_ranking(server) <= query.get_member_rank().unwrap_or(i64::MIN)
+    {
+        return Err(create_error!(NotElevated));
+    }
+
+    permissions
+        .throw_permission_override(current_value, &data.permissions)
+        .await?;
+
+    channel
+        .set_member_permission(db, &member.id.user, data.permissions.clone().into())
+        .await?;
+
+    Ok(Json(channel.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{
+        util::permissions::DatabasePermissionQuery, Channel, Member, PartialChannel,
+    };
+    use revolt_permissions::{calculate_channel_permissions, ChannelPermission, OverrideField};
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn set_member_permissions() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (_, _, other_user) = harness.new_user().await;
+
+        let (server, channels) = harness.new_server(&user).await;
+        Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+        Member::create(&harness.db, &server, &other_user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+
+        let mut channel = channels[0].clone();
+        channel
+            .update(
+                &harness.db,
+                PartialChannel {
+                    default_permissions: Some(OverrideField {
+                        a: 0,
+                        d: ChannelPermission::ViewChannel as i64,
+                    }),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let mut query = DatabasePermissionQuery::new(&harness.db, &other_user)
+            .channel(&channel)
+            .server(&server);
+        assert!(!calculate_channel_permissions(&mut query)
+            .await
+            .has_channel_permission(ChannelPermission::ViewChannel));
+
+        let response = harness
+            .client
+            .put(format!(
+                "/channels/{}/permissions/members/{}",
+                channel.id(),
+                other_user.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "permissions": {
+                        "allow": ChannelPermission::ViewChannel as u64,
+                        "deny": 0
+                    }
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        drop(response);
+
+        let channel = harness.db.fetch_channel(channel.id()).await.unwrap();
+        assert!(matches!(
+            &channel,
+            Channel::TextChannel { member_permissions, .. }
+                if member_permissions.contains_key(&other_user.id)
+        ));
+
+        let mut query = DatabasePermissionQuery::new(&harness.db, &other_user)
+            .channel(&channel)
+            .server(&server);
+        assert!(calculate_channel_permissions(&mut query)
+            .await
+            .has_channel_permission(ChannelPermission::ViewChannel));
+    }
+}
@@ -28,7 +28,7 @@ pub async fn create_emoji(
     })?;
 
     // Validate we have permission to write into parent
-    match &data.parent {
+    let server_id = match &data.parent {
         v0::EmojiParent::Server { id } => {
             let server = db.fetch_server(id).await?;
 
@@ -45,12 +45,14 @@ pub async fn create_emoji(
                     max: config.features.limits.global.server_emoji,
                 }));
             }
+
+            server.id
         }
         v0::EmojiParent::Detached => return Err(create_error!(InvalidOperation)),
     };
 
     // Find the relevant attachment
-    let attachment = File::use_emoji(db, &id, &id, &user.id).await?;
+    let attachment = File::use_emoji(db, &id, &id, &server_id, &user.id).await?;
 
     // Create the emoji object
     let emoji = Emoji {
@@ -4,11 +4,13 @@ use rocket::Route;
 mod emoji_create;
 mod emoji_delete;
 mod emoji_fetch;
+mod sticker_fetch;
 
 pub fn routes() -> (Vec<Route>, OpenApi) {
     openapi_get_routes_spec![
         emoji_create::create_emoji,
         emoji_delete::delete_emoji,
-        emoji_fetch::fetch_emoji
+        emoji_fetch::fetch_emoji,
+        sticker_fetch::fetch_sticker
     ]
 }
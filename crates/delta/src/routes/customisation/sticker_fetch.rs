@@ -0,0 +1,21 @@
+use revolt_database::{util::reference::Reference, Database};
+use revolt_models::v0;
+use revolt_result::Result;
+
+use rocket::{serde::json::Json, State};
+
+/// # Fetch Sticker
+///
+/// Fetch a sticker by its id.
+#[openapi(tag = "Server Customisation")]
+#[get("/sticker/<sticker_id>")]
+pub async fn fetch_sticker(
+    db: &State<Database>,
+    sticker_id: Reference<'_>,
+) -> Result<Json<v0::Sticker>> {
+    sticker_id
+        .as_sticker(db)
+        .await
+        .map(|sticker| sticker.into())
+        .map(Json)
+}
@@ -0,0 +1,161 @@
+use revolt_database::{util::reference::Reference, Database, User};
+use revolt_models::v0;
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+use validator::Validate;
+
+/// # Review Application
+///
+/// Approve or reject a server's discovery listing.
+#[openapi(tag = "Discovery")]
+#[post("/servers/<target>/review", data = "<data>")]
+pub async fn review(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    data: Json<v0::DataReviewDiscoveryListing>,
+) -> Result<Json<v0::DiscoveryListing>> {
+    if !user.privileged {
+        return Err(create_error!(NotPrivileged));
+    }
+
+    let data = data.into_inner();
+    data.validate().map_err(|error| {
+        create_error!(FailedValidation {
+            error: error.to_string()
+        })
+    })?;
+
+    let mut listing = db.fetch_discovery_listing(target.id).await?;
+    let mut server = target.as_server(db).await?;
+
+    listing
+        .review(db, &mut server, &user.id, data.approve, data.reason)
+        .await?;
+
+    Ok(Json(listing.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{
+        DiscoveryCategory, DiscoveryListing, DiscoveryListingState, PartialUser,
+    };
+    use revolt_models::v0;
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn reject_listing() {
+        let harness = TestHarness::new().await;
+        let (_, _, owner) = harness.new_user().await;
+        let (_, reviewer_session, reviewer) = harness.new_user().await;
+        let (_, other_session, _) = harness.new_user().await;
+
+        harness
+            .db
+            .update_user(
+                &reviewer.id,
+                &PartialUser {
+                    privileged: Some(true),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let tag = TestHarness::rand_string().to_lowercase();
+        let (mut server, _) = harness.new_server(&owner).await;
+        DiscoveryListing::apply(
+            &harness.db,
+            &mut server,
+            &owner.id,
+            DiscoveryCategory::Community,
+            vec![tag.clone()],
+            "en".to_string(),
+        )
+        .await
+        .unwrap();
+
+        // Only privileged users may see and review applications
+        let response = harness
+            .client
+            .get("/discovery/applications")
+            .header(Header::new(
+                "x-session-token",
+                other_session.token.to_string(),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+        drop(response);
+
+        let review = |token: String, body: serde_json::Value| {
+            harness
+                .client
+                .post(format!("/discovery/servers/{}/review", server.id))
+                .header(Header::new("x-session-token", token))
+                .header(ContentType::JSON)
+                .body(body.to_string())
+                .dispatch()
+        };
+
+        let response = review(other_session.token.to_string(), json!({ "approve": true })).await;
+        assert_eq!(response.status(), Status::Forbidden);
+        drop(response);
+
+        let response = harness
+            .client
+            .get("/discovery/applications")
+            .header(Header::new(
+                "x-session-token",
+                reviewer_session.token.to_string(),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let applications: Vec<v0::DiscoveryListing> =
+            response.into_json().await.expect("`Vec<DiscoveryListing>`");
+        assert!(applications.iter().any(|listing| listing.id == server.id));
+
+        let response = review(
+            reviewer_session.token.to_string(),
+            json!({ "approve": false, "reason": "Missing rules channel" }),
+        )
+        .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let listing: v0::DiscoveryListing = response.into_json().await.expect("`DiscoveryListing`");
+        assert_eq!(listing.state, v0::DiscoveryListingState::Rejected);
+        assert_eq!(
+            listing.rejection_reason,
+            Some("Missing rules channel".to_string())
+        );
+        assert_eq!(listing.reviewer_id, Some(reviewer.id.clone()));
+
+        // Rejected listings are no longer pending and stay out of the directory
+        let server = harness.db.fetch_server(&server.id).await.unwrap();
+        assert!(!server.discoverable);
+
+        let applications = harness
+            .db
+            .fetch_discovery_listings_by_state(&DiscoveryListingState::Pending)
+            .await
+            .unwrap();
+        assert!(!applications.iter().any(|listing| listing.id == server.id));
+
+        let response = harness
+            .client
+            .get(format!("/discovery/servers?tag={tag}"))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let results: Vec<v0::DiscoveryServer> =
+            response.into_json().await.expect("`Vec<DiscoveryServer>`");
+        assert!(results.is_empty());
+    }
+}
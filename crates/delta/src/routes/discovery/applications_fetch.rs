@@ -0,0 +1,23 @@
+use revolt_database::{Database, DiscoveryListingState, User};
+use revolt_models::v0;
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+
+/// # Fetch Applications
+///
+/// Fetch all discovery listings waiting to be reviewed.
+#[openapi(tag = "Discovery")]
+#[get("/applications")]
+pub async fn fetch_applications(
+    db: &State<Database>,
+    user: User,
+) -> Result<Json<Vec<v0::DiscoveryListing>>> {
+    if !user.privileged {
+        return Err(create_error!(NotPrivileged));
+    }
+
+    db.fetch_discovery_listings_by_state(&DiscoveryListingState::Pending)
+        .await
+        .map(|listings| listings.into_iter().map(Into::into).collect())
+        .map(Json)
+}
@@ -0,0 +1,130 @@
+use revolt_database::Database;
+use revolt_models::v0;
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+use validator::Validate;
+
+/// # Search Bots
+///
+/// Search the directory of discoverable bots.
+#[openapi(tag = "Discovery")]
+#[get("/bots?<options..>")]
+pub async fn search_bots(
+    db: &State<Database>,
+    options: v0::OptionsDiscoverBots,
+) -> Result<Json<Vec<v0::PublicBot>>> {
+    options.validate().map_err(|error| {
+        create_error!(FailedValidation {
+            error: error.to_string()
+        })
+    })?;
+
+    let bots = db
+        .fetch_discoverable_bots(
+            options.query.as_deref(),
+            options.offset.unwrap_or_default(),
+            options.limit.unwrap_or(50),
+        )
+        .await?;
+
+    let ids = bots
+        .iter()
+        .map(|bot| bot.id.clone())
+        .collect::<Vec<String>>();
+    let users = db.fetch_users(&ids).await?;
+
+    Ok(Json(
+        bots.into_iter()
+            .filter_map(|bot| {
+                users
+                    .iter()
+                    .find(|user| user.id == bot.id)
+                    .map(|user| bot.into_public_bot(user.clone()))
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{Bot, PartialBot};
+    use revolt_models::v0;
+    use rocket::http::Status;
+
+    #[rocket::async_test]
+    async fn search_bots() {
+        let harness = TestHarness::new().await;
+
+        // Unique prefix to keep other bots out of the results
+        let prefix = TestHarness::rand_string();
+
+        for (suffix, public, discoverable) in [
+            ("c", true, true),
+            ("a", true, true),
+            ("b", true, true),
+            ("d", true, false),
+            ("e", false, true),
+        ] {
+            let (_, _, owner) = harness.new_user().await;
+            Bot::create(
+                &harness.db,
+                format!("{prefix}{suffix}"),
+                &owner,
+                PartialBot {
+                    public: Some(public),
+                    discoverable: Some(discoverable),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("`Bot`");
+        }
+
+        let (harness, prefix) = (&harness, &prefix);
+        let search = |query: String| async move {
+            let response = harness
+                .client
+                .get(format!("/discovery/bots?query={prefix}{query}"))
+                .dispatch()
+                .await;
+
+            assert_eq!(response.status(), Status::Ok);
+            response
+                .into_json::<Vec<v0::PublicBot>>()
+                .await
+                .expect("`Vec<PublicBot>`")
+                .into_iter()
+                .map(|bot| bot.username)
+                .collect::<Vec<String>>()
+        };
+
+        // Only public, discoverable bots are listed, sorted by username
+        assert_eq!(
+            search(String::new()).await,
+            vec![
+                format!("{prefix}a"),
+                format!("{prefix}b"),
+                format!("{prefix}c")
+            ]
+        );
+
+        assert_eq!(
+            search("&limit=1&offset=1".to_string()).await,
+            vec![format!("{prefix}b")]
+        );
+
+        // Searches are case insensitive
+        let response = harness
+            .client
+            .get(format!("/discovery/bots?query={}A", prefix.to_uppercase()))
+            .dispatch()
+            .await;
+
+        let bots: Vec<v0::PublicBot> = response.into_json().await.expect("`Vec<PublicBot>`");
+        assert_eq!(
+            bots.into_iter().map(|bot| bot.username).collect::<Vec<_>>(),
+            vec![format!("{prefix}a")]
+        );
+    }
+}
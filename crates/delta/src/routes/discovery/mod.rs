@@ -0,0 +1,18 @@
+use revolt_rocket_okapi::revolt_okapi::openapi3::OpenApi;
+use rocket::Route;
+
+mod application_review;
+mod applications_fetch;
+mod bots_search;
+mod server_join;
+mod servers_search;
+
+pub fn routes() -> (Vec<Route>, OpenApi) {
+    openapi_get_routes_spec![
+        servers_search::search_servers,
+        bots_search::search_bots,
+        server_join::join,
+        applications_fetch::fetch_applications,
+        application_review::review
+    ]
+}
@@ -0,0 +1,169 @@
+use revolt_database::{util::reference::Reference, Database, DiscoveryListingState, Member, User};
+use revolt_models::v0;
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+
+/// # Join Server
+///
+/// Join a server listed in the discovery directory.
+#[openapi(tag = "Discovery")]
+#[post("/servers/<target>/join")]
+pub async fn join(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+) -> Result<Json<v0::InviteJoinResponse>> {
+    if user.bot.is_some() {
+        return Err(create_error!(IsBot));
+    }
+
+    let listing = db.fetch_discovery_listing(target.id).await?;
+    if listing.state != DiscoveryListingState::Approved {
+        return Err(create_error!(NotFound));
+    }
+
+    let server = target.as_server(db).await?;
+    if !server.discoverable {
+        return Err(create_error!(NotFound));
+    }
+
+    user.can_acquire_server(db).await?;
+
+    // Bans are enforced when creating the member
+    let (_, channels) = Member::create(db, &server, &user, None).await?;
+
+    Ok(Json(v0::InviteJoinResponse::Server {
+        channels: channels.into_iter().map(|c| c.into()).collect(),
+        server: server.into(),
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{Member, PartialUser, ServerBan};
+    use revolt_models::v0;
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn apply_review_and_join() {
+        let harness = TestHarness::new().await;
+        let (_, owner_session, owner) = harness.new_user().await;
+        let (_, reviewer_session, reviewer) = harness.new_user().await;
+        let (_, joiner_session, _) = harness.new_user().await;
+        let (_, banned_session, banned) = harness.new_user().await;
+
+        harness
+            .db
+            .update_user(
+                &reviewer.id,
+                &PartialUser {
+                    privileged: Some(true),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let (server, channels) = harness.new_server(&owner).await;
+        Member::create(&harness.db, &server, &owner, Some(channels))
+            .await
+            .expect("Failed to create member");
+        ServerBan::create(&harness.db, &server, &banned.id, None)
+            .await
+            .unwrap();
+
+        let response = harness
+            .client
+            .put(format!("/servers/{}/discovery", server.id))
+            .header(Header::new(
+                "x-session-token",
+                owner_session.token.to_string(),
+            ))
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "category": "Gaming",
+                    "tags": ["Speedrunning", "speedrunning"],
+                    "language": "EN"
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let listing: v0::DiscoveryListing = response.into_json().await.expect("`DiscoveryListing`");
+        assert_eq!(listing.state, v0::DiscoveryListingState::Pending);
+        assert_eq!(listing.tags, vec!["speedrunning".to_string()]);
+
+        // Pending listings cannot be joined
+        let response = harness
+            .client
+            .post(format!("/discovery/servers/{}/join", server.id))
+            .header(Header::new(
+                "x-session-token",
+                joiner_session.token.to_string(),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NotFound);
+        drop(response);
+
+        let response = harness
+            .client
+            .post(format!("/discovery/servers/{}/review", server.id))
+            .header(Header::new(
+                "x-session-token",
+                reviewer_session.token.to_string(),
+            ))
+            .header(ContentType::JSON)
+            .body(json!({ "approve": true }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        drop(response);
+
+        let response = harness
+            .client
+            .get("/discovery/servers?query=test&category=Gaming&language=en")
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let results: Vec<v0::DiscoveryServer> =
+            response.into_json().await.expect("`Vec<DiscoveryServer>`");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, server.id);
+        assert_eq!(results[0].member_count, 1);
+
+        let response = harness
+            .client
+            .post(format!("/discovery/servers/{}/join", server.id))
+            .header(Header::new(
+                "x-session-token",
+                joiner_session.token.to_string(),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        drop(response);
+
+        // Bans still apply when joining from discovery
+        let response = harness
+            .client
+            .post(format!("/discovery/servers/{}/join", server.id))
+            .header(Header::new(
+                "x-session-token",
+                banned_session.token.to_string(),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use revolt_database::{Database, DiscoveryListingQuery};
+use revolt_models::v0;
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+use validator::Validate;
+
+/// # Search Servers
+///
+/// Search the directory of discoverable servers.
+#[openapi(tag = "Discovery")]
+#[get("/servers?<options..>")]
+pub async fn search_servers(
+    db: &State<Database>,
+    options: v0::OptionsDiscoverServers,
+) -> Result<Json<Vec<v0::DiscoveryServer>>> {
+    options.validate().map_err(|error| {
+        create_error!(FailedValidation {
+            error: error.to_string()
+        })
+    })?;
+
+    let v0::OptionsDiscoverServers {
+        query,
+        category,
+        language,
+        tag,
+        sort,
+        limit,
+        offset,
+    } = options;
+
+    let listings = db
+        .fetch_approved_discovery_listings(&DiscoveryListingQuery {
+            text: query,
+            category: category.map(Into::into),
+            language: language.map(|language| language.to_lowercase()),
+            tag: tag.map(|tag| tag.to_lowercase()),
+            sort: sort.unwrap_or_default().into(),
+            offset: offset.unwrap_or_default(),
+            limit: limit.unwrap_or(50),
+        })
+        .await?;
+
+    let ids = listings
+        .iter()
+        .map(|listing| listing.id.clone())
+        .collect::<Vec<String>>();
+
+    let mut servers = db
+        .fetch_servers(&ids)
+        .await?
+        .into_iter()
+        .map(|server| (server.id.clone(), server))
+        .collect::<HashMap<_, _>>();
+
+    Ok(Json(
+        listings
+            .into_iter()
+            .filter_map(|listing| {
+                servers
+                    .remove(&listing.id)
+                    .filter(|server| server.discoverable)
+                    .map(|server| server.into_discovery_server(listing))
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{DiscoveryCategory, DiscoveryListing, DiscoveryListingStats};
+    use revolt_models::v0;
+    use rocket::http::Status;
+
+    #[rocket::async_test]
+    async fn search_filters_and_sorting() {
+        let harness = TestHarness::new().await;
+        let (_, _, owner) = harness.new_user().await;
+
+        // Unique tag to keep other listings out of the results
+        let tag = TestHarness::rand_string().to_lowercase();
+
+        let mut ids = vec![];
+        for (category, language, member_count, last_message_id) in [
+            (
+                DiscoveryCategory::Gaming,
+                "en",
+                5,
+                "01HZ0000000000000000000001",
+            ),
+            (
+                DiscoveryCategory::Music,
+                "de",
+                10,
+                "01HZ0000000000000000000002",
+            ),
+            (
+                DiscoveryCategory::Gaming,
+                "en",
+                1,
+                "01HZ0000000000000000000003",
+            ),
+        ] {
+            let (mut server, _) = harness.new_server(&owner).await;
+            let mut listing = DiscoveryListing::apply(
+                &harness.db,
+                &mut server,
+                &owner.id,
+                category,
+                vec![tag.clone()],
+                language.to_string(),
+            )
+            .await
+            .unwrap();
+
+            listing
+                .review(&harness.db, &mut server, &owner.id, true, None)
+                .await
+                .unwrap();
+
+            harness
+                .db
+                .update_discovery_listing_stats(
+                    &server.id,
+                    &DiscoveryListingStats {
+                        name: server.name.clone(),
+                        description: None,
+                        member_count,
+                        last_message_id: Some(last_message_id.to_string()),
+                    },
+                )
+                .await
+                .unwrap();
+
+            ids.push(server.id);
+        }
+
+        let (harness, tag) = (&harness, &tag);
+        let search = |query: String| async move {
+            let response = harness
+                .client
+                .get(format!("/discovery/servers?tag={tag}{query}"))
+                .dispatch()
+                .await;
+
+            assert_eq!(response.status(), Status::Ok);
+            response
+                .into_json::<Vec<v0::DiscoveryServer>>()
+                .await
+                .expect("`Vec<DiscoveryServer>`")
+                .into_iter()
+                .map(|server| server.id)
+                .collect::<Vec<String>>()
+        };
+
+        // Largest servers come first by default
+        assert_eq!(
+            search(String::new()).await,
+            vec![ids[1].clone(), ids[0].clone(), ids[2].clone()]
+        );
+
+        assert_eq!(
+            search("&sort=Activity".to_string()).await,
+            vec![ids[2].clone(), ids[1].clone(), ids[0].clone()]
+        );
+
+        assert_eq!(
+            search("&category=Gaming".to_string()).await,
+            vec![ids[0].clone(), ids[2].clone()]
+        );
+
+        // Languages are matched case insensitively
+        assert_eq!(
+            search("&language=DE".to_string()).await,
+            vec![ids[1].clone()]
+        );
+
+        assert_eq!(
+            search("&limit=1&offset=1".to_string()).await,
+            vec![ids[0].clone()]
+        );
+    }
+}
@@ -7,6 +7,7 @@ use rocket::{Build, Rocket};
 mod bots;
 mod channels;
 mod customisation;
+mod discovery;
 mod invites;
 mod onboard;
 mod policy;
@@ -15,6 +16,7 @@ mod root;
 mod safety;
 mod servers;
 mod sync;
+mod templates;
 mod users;
 mod webhooks;
 
@@ -31,6 +33,8 @@ pub fn mount(config: Settings, mut rocket: Rocket<Build>) -> Rocket<Build> {
             "/channels" => channels::routes(),
             "/servers" => servers::routes(),
             "/invites" => invites::routes(),
+            "/discovery" => discovery::routes(),
+            "/templates" => templates::routes(),
             "/custom" => customisation::routes(),
             "/safety" => safety::routes(),
             "/auth/account" => rocket_authifier::routes::account::routes(),
@@ -52,6 +56,8 @@ pub fn mount(config: Settings, mut rocket: Rocket<Build>) -> Rocket<Build> {
             "/channels" => channels::routes(),
             "/servers" => servers::routes(),
             "/invites" => invites::routes(),
+            "/discovery" => discovery::routes(),
+            "/templates" => templates::routes(),
             "/custom" => customisation::routes(),
             "/safety" => safety::routes(),
             "/auth/account" => rocket_authifier::routes::account::routes(),
@@ -74,6 +80,8 @@ pub fn mount(config: Settings, mut rocket: Rocket<Build>) -> Rocket<Build> {
             "/channels" => channels::routes(),
             "/servers" => servers::routes(),
             "/invites" => invites::routes(),
+            "/discovery" => discovery::routes(),
+            "/templates" => templates::routes(),
             "/custom" => customisation::routes(),
             "/safety" => safety::routes(),
             "/auth/account" => rocket_authifier::routes::account::routes(),
@@ -94,6 +102,8 @@ pub fn mount(config: Settings, mut rocket: Rocket<Build>) -> Rocket<Build> {
             "/channels" => channels::routes(),
             "/servers" => servers::routes(),
             "/invites" => invites::routes(),
+            "/discovery" => discovery::routes(),
+            "/templates" => templates::routes(),
             "/custom" => customisation::routes(),
             "/safety" => safety::routes(),
             "/auth/account" => rocket_authifier::routes::account::routes(),
@@ -161,7 +171,9 @@ fn custom_openapi_spec() -> OpenApi {
             "tags": [
               "Server Information",
               "Server Members",
-              "Server Permissions"
+              "Server Permissions",
+              "Server Templates",
+              "Auto Moderation"
             ]
           },
           {
@@ -170,6 +182,12 @@ fn custom_openapi_spec() -> OpenApi {
               "Invites"
             ]
           },
+          {
+            "name": "Discovery",
+            "tags": [
+              "Discovery"
+            ]
+          },
           {
             "name": "Customisation",
             "tags": [
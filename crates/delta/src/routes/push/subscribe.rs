@@ -2,15 +2,24 @@ use authifier::{
     models::{Session, WebPushSubscription},
     Authifier,
 };
-use revolt_result::{create_database_error, Result};
+use revolt_config::config;
+use revolt_database::events::rabbit::DeviceKey;
+use revolt_result::{create_database_error, create_error, Result};
 use rocket::{serde::json::Json, State};
 use rocket_empty::EmptyResponse;
+use url::Url;
 
 /// # Push Subscribe
 ///
 /// Create a new Web Push subscription.
 ///
 /// If an existing subscription exists on this session, it will be removed.
+///
+/// UnifiedPush distributors are registered by passing `unifiedpush` as the
+/// endpoint and the distributor's endpoint URL as `auth`.
+///
+/// Mobile (`apn` and `fcm`) subscriptions may opt in to end-to-end encrypted
+/// notifications by passing a device key as `p256dh`.
 #[openapi(tag = "Web Push")]
 #[post("/subscribe", data = "<data>")]
 pub async fn subscribe(
@@ -18,10 +27,131 @@ pub async fn subscribe(
     mut session: Session,
     data: Json<WebPushSubscription>,
 ) -> Result<EmptyResponse> {
-    session.subscription = Some(data.into_inner());
+    let data = data.into_inner();
+
+    if data.endpoint == "unifiedpush" {
+        if !config().await.pushd.unifiedpush.enabled {
+            return Err(create_error!(InvalidOperation));
+        }
+
+        // Distributors must be reachable on the public internet
+        let endpoint = Url::parse(&data.auth).map_err(|_| create_error!(InvalidProperty))?;
+        if !revolt_guard::is_url_public(&endpoint).await {
+            return Err(create_error!(InvalidProperty));
+        }
+    }
+
+    if matches!(data.endpoint.as_str(), "apn" | "fcm")
+        && !data.p256dh.is_empty()
+        && DeviceKey::parse(&data.p256dh).is_none()
+    {
+        return Err(create_error!(InvalidProperty));
+    }
+
+    session.subscription = Some(data);
     session
         .save(authifier)
         .await
         .map(|_| EmptyResponse)
         .map_err(|_| create_database_error!("save", "session"))
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn subscribe_unifiedpush() {
+        let harness = TestHarness::new().await;
+        let (_, session, _) = harness.new_user().await;
+
+        let subscribe = |auth: &str| {
+            harness
+                .client
+                .post("/push/subscribe")
+                .header(ContentType::JSON)
+                .header(Header::new("x-session-token", session.token.to_string()))
+                .body(
+                    json!({
+                        "endpoint": "unifiedpush",
+                        "p256dh": "",
+                        "auth": auth
+                    })
+                    .to_string(),
+                )
+                .dispatch()
+        };
+
+        // Public IP literals are accepted without a DNS lookup
+        assert_eq!(
+            subscribe("https://1.1.1.1/upAbCdEf?up=1").await.status(),
+            Status::NoContent
+        );
+
+        assert_eq!(subscribe("not a url").await.status(), Status::BadRequest);
+        assert_eq!(
+            subscribe("file:///etc/passwd").await.status(),
+            Status::BadRequest
+        );
+
+        // Internal addresses are rejected
+        for endpoint in [
+            "http://127.0.0.1:8000/",
+            "http://[::1]/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://localhost/",
+        ] {
+            assert_eq!(subscribe(endpoint).await.status(), Status::BadRequest);
+        }
+    }
+
+    #[rocket::async_test]
+    async fn subscribe_with_device_key() {
+        let harness = TestHarness::new().await;
+        let (_, session, _) = harness.new_user().await;
+
+        let subscribe = |p256dh: &str| {
+            harness
+                .client
+                .post("/push/subscribe")
+                .header(ContentType::JSON)
+                .header(Header::new("x-session-token", session.token.to_string()))
+                .body(
+                    json!({
+                        "endpoint": "fcm",
+                        "p256dh": p256dh,
+                        "auth": "fcm-registration-token"
+                    })
+                    .to_string(),
+                )
+                .dispatch()
+        };
+
+        // Plaintext notifications
+        assert_eq!(subscribe("").await.status(), Status::NoContent);
+
+        // Uncompressed P-256 public key and 16 byte auth secret
+        let public_key =
+            "BGsX0fLhLEJH-Lzm5WOkQPJ3A32BLeszoPShOUXYmMKWT-NC4v4af5uO5-tKfA-eFivOM1drMV7Oy7ZAaDe_UfU";
+        let auth_secret = "A".repeat(22);
+        assert_eq!(
+            subscribe(&format!("{public_key}.{auth_secret}"))
+                .await
+                .status(),
+            Status::NoContent
+        );
+
+        // Not a point on the curve
+        let invalid_key = format!("BA{}", "A".repeat(85));
+        assert_eq!(
+            subscribe(&format!("{invalid_key}.{auth_secret}"))
+                .await
+                .status(),
+            Status::BadRequest
+        );
+
+        // Missing auth secret
+        assert_eq!(subscribe(public_key).await.status(), Status::BadRequest);
+    }
+}
@@ -0,0 +1,38 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    AutoModConfig, Database, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_server_permissions, ChannelPermission};
+use revolt_result::Result;
+use rocket::{serde::json::Json, State};
+
+/// # Fetch Auto Moderation
+///
+/// Fetch this server's auto-moderation configuration.
+#[openapi(tag = "Auto Moderation")]
+#[get("/<target>/automod")]
+pub async fn fetch_automod(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+) -> Result<Json<v0::AutoModConfig>> {
+    let server = target.as_server(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+    calculate_server_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManageServer)?;
+
+    let config = db
+        .fetch_automod_config(&server.id)
+        .await
+        .unwrap_or_else(|_| AutoModConfig {
+            id: server.id.clone(),
+            rules: vec![],
+            alert_channel: None,
+            exempt_roles: vec![],
+            exempt_channels: vec![],
+        });
+
+    Ok(Json(config.into()))
+}
@@ -0,0 +1,293 @@
+use std::collections::HashSet;
+
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    AutoModConfig, AutoModTrigger, Database, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_server_permissions, ChannelPermission};
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+use validator::Validate;
+
+/// Longest timeout an auto-moderation rule may apply (28 days)
+const MAX_TIMEOUT: u32 = 28 * 24 * 60 * 60;
+
+/// # Set Auto Moderation
+///
+/// Replace this server's auto-moderation configuration.
+#[openapi(tag = "Auto Moderation")]
+#[put("/<target>/automod", data = "<data>")]
+pub async fn set_automod(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    data: Json<v0::DataEditAutoMod>,
+) -> Result<Json<v0::AutoModConfig>> {
+    let data = data.into_inner();
+    data.validate().map_err(|error| {
+        create_error!(FailedValidation {
+            error: error.to_string()
+        })
+    })?;
+
+    let server = target.as_server(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+    calculate_server_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManageServer)?;
+
+    // Validate rules
+    let mut names = HashSet::new();
+    for rule in &data.rules {
+        if rule.name.is_empty() || rule.name.len() > 32 || !names.insert(&rule.name) {
+            return Err(create_error!(InvalidProperty));
+        }
+
+        let valid = match &rule.trigger {
+            v0::AutoModTrigger::Keyword { keywords } => {
+                !keywords.is_empty()
+                    && keywords.len() <= 100
+                    && keywords
+                        .iter()
+                        .all(|keyword| !keyword.is_empty() && keyword.len() <= 64)
+            }
+            v0::AutoModTrigger::Regex { patterns } => {
+                !patterns.is_empty()
+                    && patterns.len() <= 10
+                    && patterns.iter().all(|pattern| {
+                        pattern.len() <= 256 && AutoModTrigger::compile_pattern(pattern).is_some()
+                    })
+            }
+            v0::AutoModTrigger::MentionSpam { limit } => *limit > 0,
+            v0::AutoModTrigger::Links | v0::AutoModTrigger::Invites => true,
+            v0::AutoModTrigger::Attachments { content_types } => {
+                !content_types.is_empty()
+                    && content_types.len() <= 20
+                    && content_types
+                        .iter()
+                        .all(|content_type| content_type.contains('/'))
+            }
+        };
+
+        if !valid {
+            return Err(create_error!(InvalidProperty));
+        }
+
+        if let v0::AutoModAction::Timeout { duration } = rule.action {
+            if duration == 0 || duration > MAX_TIMEOUT {
+                return Err(create_error!(InvalidProperty));
+            }
+        }
+    }
+
+    // Referenced channels and roles must belong to this server
+    if data
+        .alert_channel
+        .iter()
+        .chain(data.exempt_channels.iter())
+        .any(|id| !server.channels.contains(id))
+    {
+        return Err(create_error!(NotFound));
+    }
+
+    if data
+        .exempt_roles
+        .iter()
+        .any(|id| !server.roles.contains_key(id))
+    {
+        return Err(create_error!(InvalidRole));
+    }
+
+    let config = AutoModConfig {
+        id: server.id.clone(),
+        rules: data.rules.into_iter().map(Into::into).collect(),
+        alert_channel: data.alert_channel,
+        exempt_roles: data.exempt_roles,
+        exempt_channels: data.exempt_channels,
+    };
+
+    db.upsert_automod_config(&config).await?;
+    AutoModConfig::invalidate_cache(&server.id).await;
+    Ok(Json(config.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{AutoModAction, AutoModConfig, AutoModRule, AutoModTrigger, Member};
+    use revolt_models::v0;
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn configure_and_enforce() {
+        let harness = TestHarness::new().await;
+        let (_, owner_session, owner) = harness.new_user().await;
+        let (_, session, user) = harness.new_user().await;
+
+        let (server, channels) = harness.new_server(&owner).await;
+        Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+
+        // Invalid patterns are rejected
+        let response = harness
+            .client
+            .put(format!("/servers/{}/automod", server.id))
+            .header(Header::new(
+                "x-session-token",
+                owner_session.token.to_string(),
+            ))
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "rules": [{
+                        "name": "Broken",
+                        "trigger": { "type": "Regex", "patterns": ["("] },
+                        "action": { "type": "Block" }
+                    }]
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        drop(response);
+
+        let response = harness
+            .client
+            .put(format!("/servers/{}/automod", server.id))
+            .header(Header::new(
+                "x-session-token",
+                owner_session.token.to_string(),
+            ))
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "rules": [{
+                        "name": "No invites",
+                        "trigger": { "type": "Invites" },
+                        "action": { "type": "Timeout", "duration": 60 }
+                    }],
+                    "alert_channel": channels[0].id()
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        drop(response);
+
+        let response = harness
+            .client
+            .post(format!("/servers/{}/automod/test", server.id))
+            .header(Header::new(
+                "x-session-token",
+                owner_session.token.to_string(),
+            ))
+            .header(ContentType::JSON)
+            .body(json!({ "content": "join rvlt.gg/Testers" }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let result: v0::AutoModTestResult =
+            response.into_json().await.expect("`AutoModTestResult`");
+        assert_eq!(
+            result.rule.map(|rule| rule.name),
+            Some("No invites".to_string())
+        );
+
+        let response = harness
+            .client
+            .post(format!("/channels/{}/messages", channels[0].id()))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(json!({ "content": "join rvlt.gg/Testers" }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+        drop(response);
+
+        let member = harness.db.fetch_member(&server.id, &user.id).await.unwrap();
+        assert!(member.in_timeout());
+
+        // The server owner is never moderated
+        let response = harness
+            .client
+            .post(format!("/channels/{}/messages", channels[0].id()))
+            .header(Header::new(
+                "x-session-token",
+                owner_session.token.to_string(),
+            ))
+            .header(ContentType::JSON)
+            .body(json!({ "content": "join rvlt.gg/Testers" }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    #[rocket::async_test]
+    async fn enforce_on_edit() {
+        let harness = TestHarness::new().await;
+        let (_, _, owner) = harness.new_user().await;
+        let (_, session, user) = harness.new_user().await;
+
+        let (server, channels) = harness.new_server(&owner).await;
+        Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+
+        harness
+            .db
+            .upsert_automod_config(&AutoModConfig {
+                id: server.id.clone(),
+                rules: vec![AutoModRule {
+                    name: "No invites".to_string(),
+                    trigger: AutoModTrigger::Invites,
+                    action: AutoModAction::Block,
+                }],
+                alert_channel: None,
+                exempt_roles: vec![],
+                exempt_channels: vec![],
+            })
+            .await
+            .expect("Failed to set config");
+
+        let response = harness
+            .client
+            .post(format!("/channels/{}/messages", channels[0].id()))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(json!({ "content": "hello" }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let message: v0::Message = response.into_json().await.expect("`Message`");
+
+        // Blocked content cannot be edited into an existing message
+        let response = harness
+            .client
+            .patch(format!(
+                "/channels/{}/messages/{}",
+                channels[0].id(),
+                message.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(json!({ "content": "join rvlt.gg/Testers" }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+        drop(response);
+
+        let message = harness.db.fetch_message(&message.id).await.unwrap();
+        assert_eq!(message.content, Some("hello".to_string()));
+    }
+}
@@ -0,0 +1,44 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    AutoModConfig, AutoModContent, Database, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_server_permissions, ChannelPermission};
+use revolt_result::Result;
+use rocket::{serde::json::Json, State};
+
+/// # Test Auto Moderation
+///
+/// Check which auto-moderation rule, if any, a message would trigger without taking any action.
+///
+/// Exemptions are not taken into account.
+#[openapi(tag = "Auto Moderation")]
+#[post("/<target>/automod/test", data = "<data>")]
+pub async fn test_automod(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    data: Json<v0::DataTestAutoMod>,
+) -> Result<Json<v0::AutoModTestResult>> {
+    let data = data.into_inner();
+    let server = target.as_server(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+    calculate_server_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManageServer)?;
+
+    let rule = AutoModConfig::fetch_compiled(db, &server.id)
+        .await?
+        .and_then(|config| {
+            config
+                .evaluate(&AutoModContent {
+                    content: data.content.as_deref(),
+                    content_types: &data.content_types,
+                })
+                .cloned()
+        });
+
+    Ok(Json(v0::AutoModTestResult {
+        rule: rule.map(Into::into),
+    }))
+}
@@ -0,0 +1,67 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_server_permissions, ChannelPermission, Override};
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+
+/// # Set Category Role Permission
+///
+/// Sets permissions for the specified role in a category.
+///
+/// Channels synced to the category are updated to match.
+#[openapi(tag = "Server Permissions")]
+#[put(
+    "/<target>/categories/<category_id>/permissions/<role_id>",
+    data = "<data>",
+    rank = 2
+)]
+pub async fn set_category_role_permission(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    category_id: String,
+    role_id: String,
+    data: Json<v0::DataSetRolePermissions>,
+) -> Result<Json<v0::Server>> {
+    let data = data.into_inner();
+
+    let mut server = target.as_server(db).await?;
+    let current_value: Option<Override> = server
+        .categories
+        .as_ref()
+        .and_then(|categories| categories.iter().find(|c| c.id == category_id))
+        .ok_or_else(|| create_error!(NotFound))?
+        .role_permissions
+        .get(&role_id)
+        .map(|value| (*value).into());
+
+    let rank = server
+        .roles
+        .get(&role_id)
+        .map(|role| role.rank)
+        .ok_or_else(|| create_error!(NotFound))?;
+
+    let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+    let permissions = calculate_server_permissions(&mut query).await;
+
+    permissions.throw_if_lacking_channel_permission(ChannelPermission::ManagePermissions)?;
+
+    // Prevent us from editing roles above us
+    if rank <= query.get_member_rank().unwrap_or(i64::MIN) {
+        return Err(create_error!(NotElevated));
+    }
+
+    // Ensure we have access to grant these permissions forwards
+    permissions
+        .throw_permission_override(current_value, &data.permissions)
+        .await?;
+
+    server
+        .set_category_role_permission(db, &category_id, &role_id, data.permissions.into())
+        .await?;
+
+    Ok(Json(server.into()))
+}
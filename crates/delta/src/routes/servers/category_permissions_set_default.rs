@@ -0,0 +1,181 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, User,
+};
+use revolt_models::v0::{self, DataDefaultChannelPermissions};
+use revolt_permissions::{calculate_server_permissions, ChannelPermission, Override};
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+
+/// # Set Category Default Permission
+///
+/// Sets permissions for the default role in a category.
+///
+/// Channels synced to the category are updated to match.
+#[openapi(tag = "Server Permissions")]
+#[put(
+    "/<target>/categories/<category_id>/permissions/default",
+    data = "<data>",
+    rank = 1
+)]
+pub async fn set_category_default_permissions(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    category_id: String,
+    data: Json<v0::DataDefaultChannelPermissions>,
+) -> Result<Json<v0::Server>> {
+    let DataDefaultChannelPermissions::Field { permissions: field } = data.into_inner() else {
+        return Err(create_error!(InvalidOperation));
+    };
+
+    let mut server = target.as_server(db).await?;
+    let current_value: Option<Override> = server
+        .categories
+        .as_ref()
+        .and_then(|categories| categories.iter().find(|c| c.id == category_id))
+        .ok_or_else(|| create_error!(NotFound))?
+        .default_permissions
+        .map(Into::into);
+
+    let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+    let permissions = calculate_server_permissions(&mut query).await;
+
+    permissions.throw_if_lacking_channel_permission(ChannelPermission::ManagePermissions)?;
+
+    // Ensure we have access to grant these permissions forwards
+    permissions
+        .throw_permission_override(current_value, &field)
+        .await?;
+
+    server
+        .set_category_default_permissions(db, &category_id, field.into())
+        .await?;
+
+    Ok(Json(server.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{
+        util::permissions::DatabasePermissionQuery, Category, Channel, Member, PartialChannel,
+        PartialServer,
+    };
+    use revolt_permissions::{calculate_channel_permissions, ChannelPermission, OverrideField};
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn set_category_default_permissions() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (_, _, other_user) = harness.new_user().await;
+
+        let (mut server, channels) = harness.new_server(&user).await;
+        Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+        Member::create(&harness.db, &server, &other_user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+
+        let mut synced_channel = channels[0].clone();
+        let unsynced_channel = harness.new_channel(&server).await;
+        server = harness.db.fetch_server(&server.id).await.unwrap();
+
+        server
+            .update(
+                &harness.db,
+                PartialServer {
+                    categories: Some(vec![Category {
+                        id: "category".to_string(),
+                        title: "Category".to_string(),
+                        channels: vec![
+                            synced_channel.id().to_string(),
+                            unsynced_channel.id().to_string(),
+                        ],
+                        default_permissions: None,
+                        role_permissions: Default::default(),
+                    }]),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        synced_channel
+            .update(
+                &harness.db,
+                PartialChannel {
+                    permissions_synced: Some(true),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let response = harness
+            .client
+            .put(format!(
+                "/servers/{}/categories/category/permissions/default",
+                server.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "permissions": {
+                        "allow": 0,
+                        "deny": ChannelPermission::ViewChannel as u64
+                    }
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        drop(response);
+
+        let server = harness.db.fetch_server(&server.id).await.unwrap();
+        let expected = OverrideField {
+            a: 0,
+            d: ChannelPermission::ViewChannel as i64,
+        };
+
+        // Synced channel receives a copy of the category's permissions
+        let synced_channel = harness.db.fetch_channel(synced_channel.id()).await.unwrap();
+        assert!(matches!(
+            synced_channel,
+            Channel::TextChannel {
+                default_permissions: Some(permissions),
+                permissions_synced: true,
+                ..
+            } if permissions == expected
+        ));
+
+        // Other channels keep their own permissions but still inherit from the category
+        let unsynced_channel = harness
+            .db
+            .fetch_channel(unsynced_channel.id())
+            .await
+            .unwrap();
+        assert!(matches!(
+            unsynced_channel,
+            Channel::TextChannel {
+                default_permissions: None,
+                permissions_synced: false,
+                ..
+            }
+        ));
+
+        let mut query = DatabasePermissionQuery::new(&harness.db, &other_user)
+            .channel(&unsynced_channel)
+            .server(&server);
+        assert!(!calculate_channel_permissions(&mut query)
+            .await
+            .has_channel_permission(ChannelPermission::ViewChannel));
+    }
+}
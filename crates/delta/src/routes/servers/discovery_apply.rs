@@ -0,0 +1,60 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, DiscoveryListing, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_server_permissions, ChannelPermission};
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+use validator::Validate;
+
+/// # Apply for Discovery
+///
+/// Apply for this server to be listed in the discovery directory.
+///
+/// The server is hidden from discovery until the application has been reviewed.
+#[openapi(tag = "Discovery")]
+#[put("/<target>/discovery", data = "<data>")]
+pub async fn apply(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    data: Json<v0::DataDiscoveryApplication>,
+) -> Result<Json<v0::DiscoveryListing>> {
+    let data = data.into_inner();
+    data.validate().map_err(|error| {
+        create_error!(FailedValidation {
+            error: error.to_string()
+        })
+    })?;
+
+    let mut server = target.as_server(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+    calculate_server_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManageServer)?;
+
+    let mut tags: Vec<String> = vec![];
+    for tag in data.tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() || tag.chars().count() > 24 {
+            return Err(create_error!(InvalidProperty));
+        }
+
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    DiscoveryListing::apply(
+        db,
+        &mut server,
+        &user.id,
+        data.category.into(),
+        tags,
+        data.language.to_lowercase(),
+    )
+    .await
+    .map(Into::into)
+    .map(Json)
+}
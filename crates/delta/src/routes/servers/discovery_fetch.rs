@@ -0,0 +1,30 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_server_permissions, ChannelPermission};
+use revolt_result::Result;
+use rocket::{serde::json::Json, State};
+
+/// # Fetch Discovery Listing
+///
+/// Fetch this server's discovery listing and its review state.
+#[openapi(tag = "Discovery")]
+#[get("/<target>/discovery")]
+pub async fn fetch_listing(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+) -> Result<Json<v0::DiscoveryListing>> {
+    let server = target.as_server(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+    calculate_server_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManageServer)?;
+
+    db.fetch_discovery_listing(&server.id)
+        .await
+        .map(Into::into)
+        .map(Json)
+}
@@ -0,0 +1,129 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, User,
+};
+use revolt_permissions::{calculate_server_permissions, ChannelPermission};
+use revolt_result::Result;
+use rocket::State;
+use rocket_empty::EmptyResponse;
+
+/// # Withdraw from Discovery
+///
+/// Remove this server's discovery listing and hide it from the directory.
+#[openapi(tag = "Discovery")]
+#[delete("/<target>/discovery")]
+pub async fn withdraw(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+) -> Result<EmptyResponse> {
+    let mut server = target.as_server(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+    calculate_server_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManageServer)?;
+
+    db.fetch_discovery_listing(&server.id)
+        .await?
+        .delete(db, &mut server)
+        .await
+        .map(|_| EmptyResponse)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{DiscoveryCategory, DiscoveryListing, Member};
+    use revolt_models::v0;
+    use rocket::http::{Header, Status};
+
+    #[rocket::async_test]
+    async fn withdraw_listing() {
+        let harness = TestHarness::new().await;
+        let (_, session, owner) = harness.new_user().await;
+        let (_, member_session, member) = harness.new_user().await;
+
+        let (mut server, channels) = harness.new_server(&owner).await;
+        Member::create(&harness.db, &server, &member, Some(channels))
+            .await
+            .expect("Failed to create member");
+
+        let mut listing = DiscoveryListing::apply(
+            &harness.db,
+            &mut server,
+            &owner.id,
+            DiscoveryCategory::Technology,
+            vec![],
+            "en".to_string(),
+        )
+        .await
+        .unwrap();
+
+        listing
+            .review(&harness.db, &mut server, &owner.id, true, None)
+            .await
+            .unwrap();
+        assert!(server.discoverable);
+
+        let response = harness
+            .client
+            .get(format!("/servers/{}/discovery", server.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let fetched: v0::DiscoveryListing = response.into_json().await.expect("`DiscoveryListing`");
+        assert_eq!(fetched.state, v0::DiscoveryListingState::Approved);
+
+        // Members need ManageServer to withdraw the listing
+        let response = harness
+            .client
+            .delete(format!("/servers/{}/discovery", server.id))
+            .header(Header::new(
+                "x-session-token",
+                member_session.token.to_string(),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+        drop(response);
+
+        let response = harness
+            .client
+            .delete(format!("/servers/{}/discovery", server.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+        drop(response);
+
+        // Withdrawing hides the server from discovery
+        let server = harness.db.fetch_server(&server.id).await.unwrap();
+        assert!(!server.discoverable);
+
+        let response = harness
+            .client
+            .get(format!("/servers/{}/discovery", server.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NotFound);
+        drop(response);
+
+        let response = harness
+            .client
+            .post(format!("/discovery/servers/{}/join", server.id))
+            .header(Header::new(
+                "x-session-token",
+                member_session.token.to_string(),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
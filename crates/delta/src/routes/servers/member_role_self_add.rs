@@ -0,0 +1,179 @@
+use revolt_database::{util::reference::Reference, Database, User};
+use revolt_result::Result;
+use rocket::State;
+use rocket_empty::EmptyResponse;
+
+/// # Assign Role to Self
+///
+/// Give yourself a self-assignable role, replacing any role held from the same exclusive group.
+#[openapi(tag = "Server Members")]
+#[put("/<target>/members/@me/roles/<role_id>")]
+pub async fn add_self_role(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    role_id: String,
+) -> Result<EmptyResponse> {
+    let server = target.as_server(db).await?;
+    let mut member = db.fetch_member(&server.id, &user.id).await?;
+
+    member
+        .add_self_role(db, &server, &role_id)
+        .await
+        .map(|_| EmptyResponse)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{Member, MemberScreening, PartialRole, PartialServer};
+    use rocket::http::{Header, Status};
+
+    #[rocket::async_test]
+    async fn assign_self_roles() {
+        let harness = TestHarness::new().await;
+        let (_, _, owner) = harness.new_user().await;
+        let (_, session, user) = harness.new_user().await;
+
+        let (server, channels) = harness.new_server(&owner).await;
+        Member::create(&harness.db, &server, &user, Some(channels))
+            .await
+            .expect("Failed to create member");
+
+        let (locked_id, _) = harness.new_role(&server, 1, None).await;
+        let (he_id, mut he) = harness.new_role(&server, 2, None).await;
+        let (they_id, mut they) = harness.new_role(&server, 3, None).await;
+
+        for (id, role) in [(&he_id, &mut he), (&they_id, &mut they)] {
+            role.update(
+                &harness.db,
+                &server.id,
+                id,
+                PartialRole {
+                    self_assignable: Some(true),
+                    group: Some("pronouns".to_string()),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+        }
+
+        // Roles without the flag still require AssignRoles
+        let response = harness
+            .client
+            .put(format!(
+                "/servers/{}/members/@me/roles/{locked_id}",
+                server.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+        drop(response);
+
+        for id in [&he_id, &they_id] {
+            let response = harness
+                .client
+                .put(format!("/servers/{}/members/@me/roles/{id}", server.id))
+                .header(Header::new("x-session-token", session.token.to_string()))
+                .dispatch()
+                .await;
+
+            assert_eq!(response.status(), Status::NoContent);
+        }
+
+        // Roles in the same group are exclusive
+        let member = harness.db.fetch_member(&server.id, &user.id).await.unwrap();
+        assert_eq!(member.roles, vec![they_id.clone()]);
+
+        let response = harness
+            .client
+            .delete(format!(
+                "/servers/{}/members/@me/roles/{they_id}",
+                server.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+        drop(response);
+
+        let member = harness.db.fetch_member(&server.id, &user.id).await.unwrap();
+        assert!(member.roles.is_empty());
+    }
+
+    #[rocket::async_test]
+    async fn pending_members_cannot_assign_self_roles() {
+        let harness = TestHarness::new().await;
+        let (_, _, owner) = harness.new_user().await;
+        let (_, session, user) = harness.new_user().await;
+
+        let (mut server, channels) = harness.new_server(&owner).await;
+        server
+            .update(
+                &harness.db,
+                PartialServer {
+                    screening: Some(MemberScreening {
+                        rules: "Be nice".to_string(),
+                        questions: vec![],
+                    }),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let (member, _) = Member::create(&harness.db, &server, &user, Some(channels))
+            .await
+            .expect("Failed to create member");
+        assert!(member.pending);
+
+        let (role_id, mut role) = harness.new_role(&server, 1, None).await;
+        role.update(
+            &harness.db,
+            &server.id,
+            &role_id,
+            PartialRole {
+                self_assignable: Some(true),
+                ..Default::default()
+            },
+            vec![],
+        )
+        .await
+        .unwrap();
+
+        let response = harness
+            .client
+            .put(format!(
+                "/servers/{}/members/@me/roles/{role_id}",
+                server.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+        drop(response);
+
+        let response = harness
+            .client
+            .delete(format!(
+                "/servers/{}/members/@me/roles/{role_id}",
+                server.id
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+        drop(response);
+
+        let member = harness.db.fetch_member(&server.id, &user.id).await.unwrap();
+        assert!(member.roles.is_empty());
+    }
+}
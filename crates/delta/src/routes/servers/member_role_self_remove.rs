@@ -0,0 +1,24 @@
+use revolt_database::{util::reference::Reference, Database, User};
+use revolt_result::Result;
+use rocket::State;
+use rocket_empty::EmptyResponse;
+
+/// # Remove Role from Self
+///
+/// Remove a self-assignable role from yourself.
+#[openapi(tag = "Server Members")]
+#[delete("/<target>/members/@me/roles/<role_id>")]
+pub async fn remove_self_role(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    role_id: String,
+) -> Result<EmptyResponse> {
+    let server = target.as_server(db).await?;
+    let mut member = db.fetch_member(&server.id, &user.id).await?;
+
+    member
+        .remove_self_role(db, &server, &role_id)
+        .await
+        .map(|_| EmptyResponse)
+}
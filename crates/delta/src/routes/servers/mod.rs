@@ -1,10 +1,18 @@
 use revolt_rocket_okapi::revolt_okapi::openapi3::OpenApi;
 use rocket::Route;
 
+mod automod_fetch;
+mod automod_set;
+mod automod_test;
 mod ban_create;
 mod ban_list;
 mod ban_remove;
+mod category_permissions_set;
+mod category_permissions_set_default;
 mod channel_create;
+mod discovery_apply;
+mod discovery_fetch;
+mod discovery_withdraw;
 mod emoji_list;
 mod invites_fetch;
 mod member_edit;
@@ -12,6 +20,8 @@ mod member_experimental_query;
 mod member_fetch;
 mod member_fetch_all;
 mod member_remove;
+mod member_role_self_add;
+mod member_role_self_remove;
 mod permissions_set;
 mod permissions_set_default;
 mod roles_create;
@@ -19,11 +29,19 @@ mod roles_delete;
 mod roles_edit;
 mod roles_edit_positions;
 mod roles_fetch;
+mod screening_accept;
 mod server_ack;
 mod server_create;
 mod server_delete;
 mod server_edit;
 mod server_fetch;
+mod sticker_create;
+mod sticker_delete;
+mod sticker_edit;
+mod sticker_list;
+mod template_create;
+mod template_delete;
+mod template_list;
 
 pub fn routes() -> (Vec<Route>, OpenApi) {
     openapi_get_routes_spec![
@@ -37,7 +55,10 @@ pub fn routes() -> (Vec<Route>, OpenApi) {
         member_remove::kick,
         member_fetch::fetch,
         member_edit::edit,
+        member_role_self_add::add_self_role,
+        member_role_self_remove::remove_self_role,
         member_experimental_query::member_experimental_query,
+        screening_accept::accept_screening,
         ban_create::ban,
         ban_remove::unban,
         ban_list::list,
@@ -48,7 +69,22 @@ pub fn routes() -> (Vec<Route>, OpenApi) {
         roles_delete::delete,
         permissions_set::set_role_permission,
         permissions_set_default::set_default_server_permissions,
+        category_permissions_set::set_category_role_permission,
+        category_permissions_set_default::set_category_default_permissions,
         emoji_list::list_emoji,
-        roles_edit_positions::edit_role_ranks
+        sticker_list::list_stickers,
+        sticker_create::create_sticker,
+        sticker_edit::edit_sticker,
+        sticker_delete::delete_sticker,
+        roles_edit_positions::edit_role_ranks,
+        template_create::create_template,
+        template_list::list_templates,
+        template_delete::delete_template,
+        automod_fetch::fetch_automod,
+        automod_set::set_automod,
+        automod_test::test_automod,
+        discovery_fetch::fetch_listing,
+        discovery_apply::apply,
+        discovery_withdraw::withdraw
     ]
 }
@@ -47,6 +47,8 @@ pub async fn create(
         colour: None,
         hoist: false,
         permissions: Default::default(),
+        self_assignable: false,
+        group: None,
     };
 
     Ok(Json(v0::NewRoleResponse {
@@ -45,6 +45,8 @@ pub async fn edit(
             name,
             colour,
             hoist,
+            self_assignable,
+            group,
             remove,
             ..
         } = data;
@@ -53,6 +55,8 @@ pub async fn edit(
             name,
             colour,
             hoist,
+            self_assignable,
+            group,
             ..Default::default()
         };
 
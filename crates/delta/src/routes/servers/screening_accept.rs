@@ -0,0 +1,111 @@
+use revolt_database::{util::reference::Reference, Database, User};
+use revolt_models::v0;
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+use rocket_empty::EmptyResponse;
+
+/// # Accept Member Screening
+///
+/// Accept the server's rules and screening questions, lifting the view-only restriction placed on new members.
+#[openapi(tag = "Server Members")]
+#[post("/<target>/screening", data = "<data>")]
+pub async fn accept_screening(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    data: Json<v0::DataAcceptScreening>,
+) -> Result<EmptyResponse> {
+    let data = data.into_inner();
+    let server = target.as_server(db).await?;
+    let mut member = db.fetch_member(&server.id, &user.id).await?;
+
+    // Every question must be explicitly agreed to
+    let questions = server
+        .screening
+        .as_ref()
+        .map(|screening| screening.questions.len())
+        .unwrap_or_default();
+
+    if data.answers.len() != questions || data.answers.contains(&false) {
+        return Err(create_error!(InvalidProperty));
+    }
+
+    member
+        .accept_screening(db, &server)
+        .await
+        .map(|_| EmptyResponse)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{Member, MemberScreening, PartialServer};
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn accept_screening() {
+        let harness = TestHarness::new().await;
+        let (_, _, owner) = harness.new_user().await;
+        let (_, session, user) = harness.new_user().await;
+
+        let (mut server, channels) = harness.new_server(&owner).await;
+        server
+            .update(
+                &harness.db,
+                PartialServer {
+                    screening: Some(MemberScreening {
+                        rules: "Be nice".to_string(),
+                        questions: vec!["I have read the rules".to_string()],
+                    }),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let (member, _) = Member::create(&harness.db, &server, &user, Some(channels))
+            .await
+            .expect("Failed to create member");
+        assert!(member.pending);
+
+        let response = harness
+            .client
+            .post(format!("/servers/{}/screening", server.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(json!({ "answers": [false] }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+        drop(response);
+
+        let response = harness
+            .client
+            .post(format!("/servers/{}/screening", server.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(json!({ "answers": [true] }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+        drop(response);
+
+        let member = harness.db.fetch_member(&server.id, &user.id).await.unwrap();
+        assert!(!member.pending);
+
+        // Screening can only be accepted once
+        let response = harness
+            .client
+            .post(format!("/servers/{}/screening", server.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(json!({ "answers": [true] }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+}
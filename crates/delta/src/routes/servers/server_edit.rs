@@ -2,10 +2,10 @@ use std::collections::HashSet;
 
 use revolt_database::{
     util::{permissions::DatabasePermissionQuery, reference::Reference},
-    Database, File, PartialServer, User,
+    Category, Database, File, PartialServer, User,
 };
 use revolt_models::v0;
-use revolt_permissions::{calculate_server_permissions, ChannelPermission};
+use revolt_permissions::{calculate_server_permissions, ChannelPermission, Override};
 use revolt_result::{create_error, Result};
 use rocket::{serde::json::Json, State};
 use validator::Validate;
@@ -38,6 +38,7 @@ pub async fn edit(
         && data.icon.is_none()
         && data.banner.is_none()
         && data.system_messages.is_none()
+        && data.screening.is_none()
         && data.categories.is_none()
         // && data.nsfw.is_none()
         && data.flags.is_none()
@@ -51,6 +52,7 @@ pub async fn edit(
         || data.icon.is_some()
         || data.banner.is_some()
         || data.system_messages.is_some()
+        || data.screening.is_some()
         || data.analytics.is_some()
         || !data.remove.is_empty()
     {
@@ -76,6 +78,7 @@ pub async fn edit(
         banner,
         categories,
         system_messages,
+        screening,
         flags,
         // nsfw,
         discoverable,
@@ -88,6 +91,7 @@ pub async fn edit(
         description,
         categories: categories.map(|v| v.into_iter().map(Into::into).collect()),
         system_messages: system_messages.map(Into::into),
+        screening: screening.map(Into::into),
         flags,
         // nsfw,
         discoverable,
@@ -117,9 +121,19 @@ pub async fn edit(
         }
     }
 
+    if let Some(screening) = &partial.screening {
+        if screening
+            .questions
+            .iter()
+            .any(|question| question.is_empty() || question.len() > 256)
+        {
+            return Err(create_error!(InvalidProperty));
+        }
+    }
+
     if let Some(categories) = &mut partial.categories {
         let mut channel_ids = HashSet::new();
-        for category in categories {
+        for category in categories.iter_mut() {
             for channel in &category.channels {
                 if channel_ids.contains(channel) {
                     return Err(create_error!(InvalidOperation));
@@ -131,6 +145,64 @@ pub async fn edit(
             category
                 .channels
                 .retain(|item| server.channels.contains(item));
+
+            // Category permissions can only be changed through their own routes
+            let existing = server
+                .categories
+                .as_ref()
+                .and_then(|categories| categories.iter().find(|c| c.id == category.id));
+
+            category.default_permissions = existing.and_then(|c| c.default_permissions);
+            category.role_permissions = existing
+                .map(|c| c.role_permissions.clone())
+                .unwrap_or_default();
+        }
+
+        // Moving a channel into or out of a category with overrides changes who can
+        // access it, so we must be able to grant or remove those overrides ourselves
+        let overrides_of = |category: Option<&Category>| -> Vec<Override> {
+            category
+                .map(|category| {
+                    category
+                        .default_permissions
+                        .iter()
+                        .chain(category.role_permissions.values())
+                        .map(|value| (*value).into())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        for channel in &server.channels {
+            let previous = server.category_of(channel);
+            let next = categories
+                .iter()
+                .find(|category| category.channels.contains(channel));
+
+            if previous.map(|category| &category.id) == next.map(|category| &category.id) {
+                continue;
+            }
+
+            let removed = overrides_of(previous);
+            let added = overrides_of(next);
+            if removed.is_empty() && added.is_empty() {
+                continue;
+            }
+
+            permissions
+                .throw_if_lacking_channel_permission(ChannelPermission::ManagePermissions)?;
+
+            for value in removed {
+                permissions
+                    .throw_permission_override(value, &Override::default())
+                    .await?;
+            }
+
+            for value in added {
+                permissions
+                    .throw_permission_override(None::<Override>, &value)
+                    .await?;
+            }
         }
     }
 
@@ -152,3 +224,115 @@ pub async fn edit(
 
     Ok(Json(server.into()))
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{Category, Member, PartialMember, PartialRole, PartialServer};
+    use revolt_permissions::{ChannelPermission, OverrideField};
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn move_channel_between_categories() {
+        let harness = TestHarness::new().await;
+        let (_, _, owner) = harness.new_user().await;
+        let (_, session, user) = harness.new_user().await;
+
+        let (mut server, channels) = harness.new_server(&owner).await;
+        let (member, _) = Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+
+        let (role_id, _) = harness
+            .new_role(
+                &server,
+                1,
+                Some(OverrideField {
+                    a: ChannelPermission::ManageChannel as i64,
+                    d: 0,
+                }),
+            )
+            .await;
+
+        harness
+            .db
+            .update_member(
+                &member.id,
+                &PartialMember {
+                    roles: Some(vec![role_id.clone()]),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let channel_id = channels[0].id().to_string();
+        server
+            .update(
+                &harness.db,
+                PartialServer {
+                    categories: Some(vec![Category {
+                        id: "private".to_string(),
+                        title: "Private".to_string(),
+                        channels: vec![channel_id.clone()],
+                        default_permissions: Some(OverrideField {
+                            a: 0,
+                            d: ChannelPermission::ViewChannel as i64,
+                        }),
+                        role_permissions: Default::default(),
+                    }]),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let move_out = || {
+            harness
+                .client
+                .patch(format!("/servers/{}", server.id))
+                .header(Header::new("x-session-token", session.token.to_string()))
+                .header(ContentType::JSON)
+                .body(
+                    json!({
+                        "categories": [{
+                            "id": "private",
+                            "title": "Private",
+                            "channels": []
+                        }]
+                    })
+                    .to_string(),
+                )
+                .dispatch()
+        };
+
+        // Moving the channel out of the category would make it visible to everyone
+        assert_eq!(move_out().await.status(), Status::Forbidden);
+
+        let mut role = harness.db.fetch_server(&server.id).await.unwrap().roles[&role_id].clone();
+        role.update(
+            &harness.db,
+            &server.id,
+            &role_id,
+            PartialRole {
+                permissions: Some(OverrideField {
+                    a: (ChannelPermission::ManageChannel as u64
+                        | ChannelPermission::ManagePermissions as u64)
+                        as i64,
+                    d: 0,
+                }),
+                ..Default::default()
+            },
+            vec![],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(move_out().await.status(), Status::Ok);
+
+        let server = harness.db.fetch_server(&server.id).await.unwrap();
+        assert!(server.category_of(&channel_id).is_none());
+    }
+}
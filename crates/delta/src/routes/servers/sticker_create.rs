@@ -0,0 +1,223 @@
+use revolt_config::config;
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, Emoji, File, Metadata, Sticker, StickerParent, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_server_permissions, ChannelPermission};
+use revolt_result::{create_error, Result};
+use validator::Validate;
+
+use rocket::{serde::json::Json, State};
+
+/// # Create New Sticker
+///
+/// Create a sticker on a server by its Autumn upload id.
+#[openapi(tag = "Server Customisation")]
+#[put("/<target>/stickers/<id>", data = "<data>")]
+pub async fn create_sticker(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    id: String,
+    data: Json<v0::DataCreateSticker>,
+) -> Result<Json<v0::Sticker>> {
+    let config = config().await;
+
+    let data = data.into_inner();
+    data.validate().map_err(|error| {
+        create_error!(FailedValidation {
+            error: error.to_string()
+        })
+    })?;
+
+    let server = target.as_server(db).await?;
+
+    // Check for permission
+    let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+    calculate_server_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManageCustomisation)?;
+
+    // Check that we haven't hit the sticker limit
+    let stickers = db.fetch_stickers_by_parent_id(&server.id).await?;
+    if stickers.len() >= config.features.limits.global.server_stickers {
+        return Err(create_error!(TooManyStickers {
+            max: config.features.limits.global.server_stickers,
+        }));
+    }
+
+    // Validate the related emoji
+    if let Some(emoji) = &data.emoji {
+        if !Emoji::can_use(db, emoji).await? {
+            return Err(create_error!(InvalidOperation));
+        }
+    }
+
+    // Find the relevant attachment
+    let attachment = File::use_sticker(db, &id, &id, &user.id).await?;
+
+    // Create the sticker object
+    let sticker = Sticker {
+        id,
+        parent: StickerParent::Server { id: server.id },
+        creator_id: user.id,
+        name: data.name,
+        description: data.description,
+        emoji: data.emoji,
+        animated: matches!(attachment.metadata, Metadata::Image { animated: true, .. }),
+        nsfw: data.nsfw,
+    };
+
+    // Save sticker
+    sticker.create(db).await?;
+    Ok(Json(sticker.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{events::client::EventV1, FileUsedForType};
+    use revolt_models::v0;
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn create_sticker() {
+        let mut harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (server, _) = harness.new_server(&user).await;
+        let id = harness.new_upload("stickers", "image/gif").await;
+
+        let response = harness
+            .client
+            .put(format!("/servers/{}/stickers/{id}", server.id))
+            .header(ContentType::JSON)
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .body(
+                json!(v0::DataCreateSticker {
+                    name: "wave".to_string(),
+                    description: Some("Waving hello".to_string()),
+                    emoji: Some("👋".to_string()),
+                    nsfw: false,
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let sticker: v0::Sticker = response.into_json().await.expect("`Sticker`");
+        assert_eq!(sticker.id, id);
+        assert!(sticker.animated);
+        assert_eq!(
+            sticker.parent,
+            v0::StickerParent::Server {
+                id: server.id.clone()
+            }
+        );
+
+        // The upload is now claimed by the sticker
+        let file = harness.db.fetch_attachment("stickers", &id).await.unwrap();
+        assert!(matches!(
+            file.used_for.map(|used_for| used_for.object_type),
+            Some(FileUsedForType::Sticker)
+        ));
+
+        let event = harness
+            .wait_for_event(
+                &server.id,
+                |event| matches!(event, EventV1::StickerCreate(created) if created.id == id),
+            )
+            .await;
+
+        match event {
+            EventV1::StickerCreate(created) => assert_eq!(created, sticker),
+            _ => unreachable!(),
+        }
+    }
+
+    #[rocket::async_test]
+    async fn create_static_webp_sticker() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (server, _) = harness.new_server(&user).await;
+        let id = harness.new_upload("stickers", "image/webp").await;
+
+        let response = harness
+            .client
+            .put(format!("/servers/{}/stickers/{id}", server.id))
+            .header(ContentType::JSON)
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .body(
+                json!(v0::DataCreateSticker {
+                    name: "wave".to_string(),
+                    description: None,
+                    emoji: None,
+                    nsfw: false,
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        // Animation is taken from the decoded file rather than its type
+        assert_eq!(response.status(), Status::Ok);
+        let sticker: v0::Sticker = response.into_json().await.expect("`Sticker`");
+        assert!(!sticker.animated);
+    }
+
+    #[rocket::async_test]
+    async fn create_sticker_without_permission() {
+        let harness = TestHarness::new().await;
+        let (_, _, owner) = harness.new_user().await;
+        let (_, session, _) = harness.new_user().await;
+        let (server, _) = harness.new_server(&owner).await;
+        let id = harness.new_upload("stickers", "image/png").await;
+
+        let response = harness
+            .client
+            .put(format!("/servers/{}/stickers/{id}", server.id))
+            .header(ContentType::JSON)
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .body(
+                json!(v0::DataCreateSticker {
+                    name: "wave".to_string(),
+                    description: None,
+                    emoji: None,
+                    nsfw: false,
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    #[rocket::async_test]
+    async fn create_sticker_from_wrong_tag() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (server, _) = harness.new_server(&user).await;
+        let id = harness.new_upload("emojis", "image/png").await;
+
+        let response = harness
+            .client
+            .put(format!("/servers/{}/stickers/{id}", server.id))
+            .header(ContentType::JSON)
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .body(
+                json!(v0::DataCreateSticker {
+                    name: "wave".to_string(),
+                    description: None,
+                    emoji: None,
+                    nsfw: false,
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
@@ -0,0 +1,95 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, StickerParent, User,
+};
+use revolt_permissions::{calculate_server_permissions, ChannelPermission};
+use revolt_result::{create_error, Result};
+
+use rocket::State;
+use rocket_empty::EmptyResponse;
+
+/// # Delete Sticker
+///
+/// Delete a sticker from a server by its id.
+#[openapi(tag = "Server Customisation")]
+#[delete("/<target>/stickers/<sticker_id>")]
+pub async fn delete_sticker(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    sticker_id: Reference<'_>,
+) -> Result<EmptyResponse> {
+    let server = target.as_server(db).await?;
+    let sticker = sticker_id.as_sticker(db).await?;
+
+    // Ensure the sticker belongs to this server
+    if !matches!(&sticker.parent, StickerParent::Server { id } if id == &server.id) {
+        return Err(create_error!(NotFound));
+    }
+
+    // If we uploaded the sticker, then we have permission to delete it
+    if sticker.creator_id != user.id {
+        // Otherwise, validate we have permission to delete from the server
+        let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+        calculate_server_permissions(&mut query)
+            .await
+            .throw_if_lacking_channel_permission(ChannelPermission::ManageCustomisation)?;
+    }
+
+    // Delete the sticker
+    sticker.delete(db).await.map(|_| EmptyResponse)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{events::client::EventV1, StickerParent};
+    use rocket::http::{Header, Status};
+
+    #[rocket::async_test]
+    async fn delete_sticker() {
+        let mut harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (server, _) = harness.new_server(&user).await;
+        let sticker = harness.new_sticker(&server, &user).await;
+
+        let response = harness
+            .client
+            .delete(format!("/servers/{}/stickers/{}", server.id, sticker.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+        drop(response);
+
+        // Deleted stickers are detached so existing messages keep rendering
+        let stored = harness.db.fetch_sticker(&sticker.id).await.unwrap();
+        assert!(matches!(stored.parent, StickerParent::Detached));
+
+        harness
+            .wait_for_event(
+                &server.id,
+                |event| matches!(event, EventV1::StickerDelete { id } if id == &sticker.id),
+            )
+            .await;
+    }
+
+    #[rocket::async_test]
+    async fn delete_sticker_without_permission() {
+        let harness = TestHarness::new().await;
+        let (_, _, owner) = harness.new_user().await;
+        let (_, session, _) = harness.new_user().await;
+        let (server, _) = harness.new_server(&owner).await;
+        let sticker = harness.new_sticker(&server, &owner).await;
+
+        let response = harness
+            .client
+            .delete(format!("/servers/{}/stickers/{}", server.id, sticker.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+}
@@ -0,0 +1,155 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, Emoji, PartialSticker, StickerParent, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_server_permissions, ChannelPermission};
+use revolt_result::{create_error, Result};
+use validator::Validate;
+
+use rocket::{serde::json::Json, State};
+
+/// # Edit Sticker
+///
+/// Edit a sticker on a server by its id.
+#[openapi(tag = "Server Customisation")]
+#[patch("/<target>/stickers/<sticker_id>", data = "<data>")]
+pub async fn edit_sticker(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    sticker_id: Reference<'_>,
+    data: Json<v0::DataEditSticker>,
+) -> Result<Json<v0::Sticker>> {
+    let data = data.into_inner();
+    data.validate().map_err(|error| {
+        create_error!(FailedValidation {
+            error: error.to_string()
+        })
+    })?;
+
+    let server = target.as_server(db).await?;
+    let mut sticker = sticker_id.as_sticker(db).await?;
+
+    // Ensure the sticker belongs to this server
+    if !matches!(&sticker.parent, StickerParent::Server { id } if id == &server.id) {
+        return Err(create_error!(NotFound));
+    }
+
+    // Check for permission
+    let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+    calculate_server_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManageCustomisation)?;
+
+    // Validate the related emoji
+    if let Some(emoji) = &data.emoji {
+        if !Emoji::can_use(db, emoji).await? {
+            return Err(create_error!(InvalidOperation));
+        }
+    }
+
+    let v0::DataEditSticker {
+        name,
+        description,
+        emoji,
+        nsfw,
+        remove,
+    } = data;
+
+    let partial = PartialSticker {
+        name,
+        description,
+        emoji,
+        nsfw,
+        ..Default::default()
+    };
+
+    sticker
+        .update(
+            db,
+            partial,
+            remove.into_iter().map(|field| field.into()).collect(),
+        )
+        .await?;
+
+    Ok(Json(sticker.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::events::client::EventV1;
+    use revolt_models::v0;
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn edit_sticker() {
+        let mut harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (server, _) = harness.new_server(&user).await;
+        let sticker = harness.new_sticker(&server, &user).await;
+
+        let response = harness
+            .client
+            .patch(format!("/servers/{}/stickers/{}", server.id, sticker.id))
+            .header(ContentType::JSON)
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .body(
+                json!(v0::DataEditSticker {
+                    name: Some("renamed".to_string()),
+                    description: Some("A new description".to_string()),
+                    emoji: None,
+                    nsfw: None,
+                    remove: vec![v0::FieldsSticker::Emoji],
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let edited: v0::Sticker = response.into_json().await.expect("`Sticker`");
+        assert_eq!(edited.name, "renamed");
+        assert_eq!(edited.description.as_deref(), Some("A new description"));
+
+        let stored = harness.db.fetch_sticker(&sticker.id).await.unwrap();
+        assert_eq!(stored.name, "renamed");
+
+        harness
+            .wait_for_event(
+                &server.id,
+                |event| matches!(event, EventV1::StickerUpdate { id, .. } if id == &sticker.id),
+            )
+            .await;
+    }
+
+    #[rocket::async_test]
+    async fn edit_sticker_from_other_server() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (server, _) = harness.new_server(&user).await;
+        let (other_server, _) = harness.new_server(&user).await;
+        let sticker = harness.new_sticker(&other_server, &user).await;
+
+        let response = harness
+            .client
+            .patch(format!("/servers/{}/stickers/{}", server.id, sticker.id))
+            .header(ContentType::JSON)
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .body(
+                json!(v0::DataEditSticker {
+                    name: Some("renamed".to_string()),
+                    description: None,
+                    emoji: None,
+                    nsfw: None,
+                    remove: vec![],
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
@@ -0,0 +1,31 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, User,
+};
+use revolt_models::v0;
+use revolt_permissions::PermissionQuery;
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+
+/// # Fetch Server Stickers
+///
+/// Fetch all stickers on a server.
+#[openapi(tag = "Server Customisation")]
+#[get("/<target>/stickers")]
+pub async fn list_stickers(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+) -> Result<Json<Vec<v0::Sticker>>> {
+    let server = target.as_server(db).await?;
+    let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+    if !query.are_we_a_member().await {
+        return Err(create_error!(NotFound));
+    }
+
+    // Fetch all stickers from server if we can view it
+    db.fetch_stickers_by_parent_id(&server.id)
+        .await
+        .map(|v| v.into_iter().map(Into::into).collect())
+        .map(Json)
+}
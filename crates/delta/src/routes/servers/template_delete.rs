@@ -0,0 +1,162 @@
+use revolt_database::{
+    util::{permissions::DatabasePermissionQuery, reference::Reference},
+    Database, User,
+};
+use revolt_permissions::{calculate_server_permissions, ChannelPermission};
+use revolt_result::{create_error, Result};
+
+use rocket::State;
+use rocket_empty::EmptyResponse;
+
+/// # Delete Server Template
+///
+/// Delete a template created from a server by its code.
+#[openapi(tag = "Server Templates")]
+#[delete("/<target>/templates/<code>")]
+pub async fn delete_template(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    code: Reference<'_>,
+) -> Result<EmptyResponse> {
+    let server = target.as_server(db).await?;
+    let template = code.as_server_template(db).await?;
+
+    // Ensure the template was created from this server
+    if template.source_server_id != server.id {
+        return Err(create_error!(NotFound));
+    }
+
+    let mut query = DatabasePermissionQuery::new(db, &user).server(&server);
+    calculate_server_permissions(&mut query)
+        .await
+        .throw_if_lacking_channel_permission(ChannelPermission::ManageServer)?;
+
+    template.delete(db).await.map(|_| EmptyResponse)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{Member, ServerTemplate};
+    use revolt_models::v0;
+    use revolt_result::ErrorType;
+    use rocket::http::{Header, Status};
+
+    #[rocket::async_test]
+    async fn delete_template() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (_, other_session, other_user) = harness.new_user().await;
+
+        let (server, channels) = harness.new_server(&user).await;
+        Member::create(&harness.db, &server, &other_user, Some(channels))
+            .await
+            .expect("Failed to create member");
+
+        let template = ServerTemplate::create(
+            &harness.db,
+            &server,
+            &user,
+            v0::DataCreateServerTemplate {
+                name: "Template".to_string(),
+                description: None,
+                include_emojis: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        // Members without ManageServer can't delete templates
+        let response = harness
+            .client
+            .delete(format!(
+                "/servers/{}/templates/{}",
+                server.id, template.code
+            ))
+            .header(Header::new(
+                "x-session-token",
+                other_session.token.to_string(),
+            ))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+        drop(response);
+
+        let response = harness
+            .client
+            .get(format!("/servers/{}/templates", server.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let templates: Vec<v0::ServerTemplate> =
+            response.into_json().await.expect("`Vec<ServerTemplate>`");
+        assert_eq!(templates.len(), 1);
+
+        let response = harness
+            .client
+            .delete(format!(
+                "/servers/{}/templates/{}",
+                server.id, template.code
+            ))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+        drop(response);
+
+        assert!(harness
+            .db
+            .fetch_server_template(&template.code)
+            .await
+            .is_err());
+    }
+
+    #[rocket::async_test]
+    async fn template_limit_and_server_delete() {
+        let harness = TestHarness::new().await;
+        let (_, _, user) = harness.new_user().await;
+        let (server, _) = harness.new_server(&user).await;
+
+        let data = || v0::DataCreateServerTemplate {
+            name: "Template".to_string(),
+            description: None,
+            include_emojis: None,
+        };
+
+        let max = revolt_config::config()
+            .await
+            .features
+            .limits
+            .global
+            .server_templates;
+
+        for _ in 0..max {
+            ServerTemplate::create(&harness.db, &server, &user, data())
+                .await
+                .unwrap();
+        }
+
+        let error = ServerTemplate::create(&harness.db, &server, &user, data())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.error_type,
+            ErrorType::TooManyTemplates { .. }
+        ));
+
+        // Templates are removed alongside their source server
+        let server_id = server.id.clone();
+        server.delete(&harness.db).await.unwrap();
+        assert!(harness
+            .db
+            .fetch_server_templates_by_source(&server_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+use revolt_database::{
+    util::permissions::DatabasePermissionQuery, Database, EmojiParent, FileUsedForType, User,
+};
+use revolt_models::v0;
+use revolt_permissions::{calculate_channel_permissions, ChannelPermission};
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+
+/// # Add Favourite
+///
+/// Save a GIF, attachment or emoji to your favourites.
+///
+/// Favourites which are already saved are returned unchanged.
+#[openapi(tag = "Sync")]
+#[post("/favourites", data = "<data>")]
+pub async fn add(
+    db: &State<Database>,
+    user: User,
+    data: Json<v0::DataAddFavourite>,
+) -> Result<Json<v0::Favourite>> {
+    let item = data.into_inner().item;
+
+    // Make sure the favourite points at something real
+    match &item {
+        v0::FavouriteItem::Gif { url, provider } => {
+            if url.len() > 2048
+                || !(url.starts_with("https://") || url.starts_with("http://"))
+                || provider
+                    .as_ref()
+                    .is_some_and(|provider| provider.len() > 32)
+            {
+                return Err(create_error!(FailedValidation {
+                    error: "invalid gif".to_string()
+                }));
+            }
+        }
+        v0::FavouriteItem::Attachment { attachment_id } => {
+            let file = db
+                .fetch_attachment("attachments", attachment_id)
+                .await
+                .map_err(|_| create_error!(UnknownAttachment))?;
+
+            if file.deleted == Some(true) || file.reported == Some(true) {
+                return Err(create_error!(UnknownAttachment));
+            }
+
+            // Attachments uploaded by someone else must be visible to the user
+            if file.uploader_id.as_ref() != Some(&user.id) {
+                let message_id = file
+                    .used_for
+                    .filter(|used_for| matches!(used_for.object_type, FileUsedForType::Message))
+                    .map(|used_for| used_for.id)
+                    .or(file.message_id)
+                    .ok_or_else(|| create_error!(UnknownAttachment))?;
+
+                let message = db
+                    .fetch_message(&message_id)
+                    .await
+                    .map_err(|_| create_error!(UnknownAttachment))?;
+                let channel = db.fetch_channel(&message.channel).await?;
+
+                let mut query = DatabasePermissionQuery::new(db, &user).channel(&channel);
+                let permissions = calculate_channel_permissions(&mut query).await;
+                if !permissions.has_channel_permission(ChannelPermission::ViewChannel)
+                    || !permissions.has_channel_permission(ChannelPermission::ReadMessageHistory)
+                {
+                    return Err(create_error!(UnknownAttachment));
+                }
+            }
+        }
+        v0::FavouriteItem::Emoji { emoji_id } => {
+            let emoji = db.fetch_emoji(emoji_id).await?;
+            if matches!(emoji.parent, EmojiParent::Detached) {
+                return Err(create_error!(NotFound));
+            }
+        }
+    }
+
+    let mut favourites = db.fetch_user_favourites(&user.id).await?;
+    favourites
+        .add(db, item.into(), user.limits().await.favourites)
+        .await
+        .map(|favourite| Json(favourite.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::events::client::EventV1;
+    use revolt_models::v0;
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn add_favourite() {
+        let mut harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+
+        let add = |url: &str| {
+            harness
+                .client
+                .post("/sync/favourites")
+                .header(ContentType::JSON)
+                .header(Header::new("x-session-token", session.token.to_string()))
+                .body(
+                    json!(v0::DataAddFavourite {
+                        item: v0::FavouriteItem::Gif {
+                            url: url.to_string(),
+                            provider: Some("tenor".to_string()),
+                        },
+                    })
+                    .to_string(),
+                )
+                .dispatch()
+        };
+
+        let response = add("https://media.tenor.com/wave.gif").await;
+        assert_eq!(response.status(), Status::Ok);
+        let favourite: v0::Favourite = response.into_json().await.expect("`Favourite`");
+
+        // Saving the same GIF again is a no-op
+        let response = add("https://media.tenor.com/wave.gif").await;
+        assert_eq!(response.status(), Status::Ok);
+        let duplicate: v0::Favourite = response.into_json().await.expect("`Favourite`");
+        assert_eq!(favourite, duplicate);
+
+        // Only web links are accepted
+        assert_eq!(
+            add("javascript:alert(1)").await.status(),
+            Status::BadRequest
+        );
+
+        // Unknown emoji are rejected
+        let status = harness
+            .client
+            .post("/sync/favourites")
+            .header(ContentType::JSON)
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .body(
+                json!(v0::DataAddFavourite {
+                    item: v0::FavouriteItem::Emoji {
+                        emoji_id: "01HZXW0Q1Y0RZ7A5J3R4S8K9TB".to_string(),
+                    },
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await
+            .status();
+
+        assert_eq!(status, Status::NotFound);
+
+        // Attachments the user cannot see are rejected
+        let attachment_id = harness.new_upload("attachments", "image/png").await;
+        let status = harness
+            .client
+            .post("/sync/favourites")
+            .header(ContentType::JSON)
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .body(
+                json!(v0::DataAddFavourite {
+                    item: v0::FavouriteItem::Attachment { attachment_id },
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await
+            .status();
+
+        assert_eq!(status, Status::BadRequest);
+
+        let favourites = harness.db.fetch_user_favourites(&user.id).await.unwrap();
+        assert_eq!(favourites.favourites.len(), 1);
+
+        let event = harness
+            .wait_for_event(
+                &format!("{}!", user.id),
+                |event| matches!(event, EventV1::UserFavouritesUpdate { id, .. } if id == &user.id),
+            )
+            .await;
+
+        match event {
+            EventV1::UserFavouritesUpdate { favourites, .. } => {
+                assert_eq!(favourites, vec![favourite]);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[rocket::async_test]
+    async fn add_favourite_limit() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let max = user.limits().await.favourites;
+
+        for i in 0..=max {
+            let response = harness
+                .client
+                .post("/sync/favourites")
+                .header(ContentType::JSON)
+                .header(Header::new("x-session-token", session.token.to_string()))
+                .body(
+                    json!(v0::DataAddFavourite {
+                        item: v0::FavouriteItem::Gif {
+                            url: format!("https://media.tenor.com/{i}.gif"),
+                            provider: None,
+                        },
+                    })
+                    .to_string(),
+                )
+                .dispatch()
+                .await;
+
+            if i < max {
+                assert_eq!(response.status(), Status::Ok);
+            } else {
+                assert_eq!(response.status(), Status::BadRequest);
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use revolt_database::{Database, User};
+use revolt_models::v0;
+use revolt_result::Result;
+use rocket::serde::json::Json;
+use rocket::State;
+
+/// # Fetch Favourites
+///
+/// Fetch saved favourite GIFs, attachments and emoji in the order they were arranged.
+#[openapi(tag = "Sync")]
+#[get("/favourites")]
+pub async fn fetch(db: &State<Database>, user: User) -> Result<Json<Vec<v0::Favourite>>> {
+    db.fetch_user_favourites(&user.id).await.map(|favourites| {
+        Json(
+            favourites
+                .favourites
+                .into_iter()
+                .map(|favourite| favourite.into())
+                .collect(),
+        )
+    })
+}
@@ -1,10 +1,22 @@
 use revolt_rocket_okapi::revolt_okapi::openapi3::OpenApi;
 use rocket::Route;
 
+mod add_favourite;
+mod get_favourites;
 mod get_settings;
 mod get_unreads;
+mod remove_favourite;
+mod reorder_favourites;
 mod set_settings;
 
 pub fn routes() -> (Vec<Route>, OpenApi) {
-    openapi_get_routes_spec![get_settings::fetch, set_settings::set, get_unreads::unreads]
+    openapi_get_routes_spec![
+        get_settings::fetch,
+        set_settings::set,
+        get_unreads::unreads,
+        get_favourites::fetch,
+        add_favourite::add,
+        remove_favourite::remove,
+        reorder_favourites::reorder
+    ]
 }
@@ -0,0 +1,61 @@
+use revolt_database::{Database, User};
+use revolt_result::Result;
+use rocket::State;
+use rocket_empty::EmptyResponse;
+
+/// # Remove Favourite
+///
+/// Remove a saved favourite.
+#[openapi(tag = "Sync")]
+#[delete("/favourites/<id>")]
+pub async fn remove(db: &State<Database>, user: User, id: String) -> Result<EmptyResponse> {
+    let mut favourites = db.fetch_user_favourites(&user.id).await?;
+    favourites.remove(db, &id).await.map(|_| EmptyResponse)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::FavouriteItem;
+    use rocket::http::{Header, Status};
+
+    #[rocket::async_test]
+    async fn remove_favourite() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+
+        let mut favourites = harness.db.fetch_user_favourites(&user.id).await.unwrap();
+        let favourite = favourites
+            .add(
+                &harness.db,
+                FavouriteItem::Gif {
+                    url: "https://media.tenor.com/wave.gif".to_string(),
+                    provider: None,
+                },
+                10,
+            )
+            .await
+            .unwrap();
+
+        let response = harness
+            .client
+            .delete(format!("/sync/favourites/{}", favourite.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NoContent);
+
+        let favourites = harness.db.fetch_user_favourites(&user.id).await.unwrap();
+        assert!(favourites.favourites.is_empty());
+
+        let response = harness
+            .client
+            .delete(format!("/sync/favourites/{}", favourite.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
@@ -0,0 +1,159 @@
+use revolt_database::{Database, User};
+use revolt_models::v0;
+use revolt_result::{create_error, Result};
+use rocket::{serde::json::Json, State};
+use validator::Validate;
+
+/// # Reorder Favourites
+///
+/// Rearrange saved favourites, every favourite must be included exactly once.
+#[openapi(tag = "Sync")]
+#[put("/favourites/order", data = "<data>")]
+pub async fn reorder(
+    db: &State<Database>,
+    user: User,
+    data: Json<v0::DataReorderFavourites>,
+) -> Result<Json<Vec<v0::Favourite>>> {
+    let data = data.into_inner();
+    data.validate().map_err(|error| {
+        create_error!(FailedValidation {
+            error: error.to_string()
+        })
+    })?;
+
+    let mut favourites = db.fetch_user_favourites(&user.id).await?;
+    favourites.reorder(db, &data.ids).await?;
+
+    Ok(Json(
+        favourites
+            .favourites
+            .into_iter()
+            .map(|favourite| favourite.into())
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::FavouriteItem;
+    use revolt_models::v0;
+    use revolt_result::ErrorType;
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn reorder_favourites() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+
+        let mut favourites = harness.db.fetch_user_favourites(&user.id).await.unwrap();
+        let mut ids = vec![];
+        for i in 0..3 {
+            let favourite = favourites
+                .add(
+                    &harness.db,
+                    FavouriteItem::Gif {
+                        url: format!("https://media.tenor.com/{i}.gif"),
+                        provider: None,
+                    },
+                    10,
+                )
+                .await
+                .unwrap();
+
+            ids.push(favourite.id);
+        }
+
+        // Newest favourites come first
+        let stored = harness.db.fetch_user_favourites(&user.id).await.unwrap();
+        assert_eq!(
+            stored
+                .favourites
+                .iter()
+                .map(|favourite| favourite.id.clone())
+                .collect::<Vec<_>>(),
+            ids.iter().rev().cloned().collect::<Vec<_>>()
+        );
+
+        let response = harness
+            .client
+            .put("/sync/favourites/order")
+            .header(ContentType::JSON)
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .body(json!(v0::DataReorderFavourites { ids: ids.clone() }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let reordered: Vec<v0::Favourite> = response.into_json().await.expect("`Vec<Favourite>`");
+        assert_eq!(
+            reordered
+                .into_iter()
+                .map(|favourite| favourite.id)
+                .collect::<Vec<_>>(),
+            ids
+        );
+
+        // Orders must include every favourite exactly once
+        let response = harness
+            .client
+            .put("/sync/favourites/order")
+            .header(ContentType::JSON)
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .body(
+                json!(v0::DataReorderFavourites {
+                    ids: vec![ids[0].clone(), ids[0].clone(), ids[1].clone()]
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[rocket::async_test]
+    async fn reorder_stale_favourites() {
+        let harness = TestHarness::new().await;
+        let (_, _, user) = harness.new_user().await;
+
+        let mut favourites = harness.db.fetch_user_favourites(&user.id).await.unwrap();
+        let first = favourites
+            .add(
+                &harness.db,
+                FavouriteItem::Gif {
+                    url: "https://media.tenor.com/0.gif".to_string(),
+                    provider: None,
+                },
+                10,
+            )
+            .await
+            .unwrap();
+
+        // Another session saves a favourite after we fetched the list
+        let mut stale = harness.db.fetch_user_favourites(&user.id).await.unwrap();
+        favourites
+            .add(
+                &harness.db,
+                FavouriteItem::Gif {
+                    url: "https://media.tenor.com/1.gif".to_string(),
+                    provider: None,
+                },
+                10,
+            )
+            .await
+            .unwrap();
+
+        let error = stale
+            .reorder(&harness.db, &[first.id.clone()])
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error.error_type, ErrorType::FavouritesChanged));
+
+        // The other session's favourite was kept
+        let stored = harness.db.fetch_user_favourites(&user.id).await.unwrap();
+        assert_eq!(stored.favourites.len(), 2);
+    }
+}
@@ -0,0 +1,20 @@
+use revolt_database::{util::reference::Reference, Database};
+use revolt_models::v0;
+use revolt_result::Result;
+use rocket::{serde::json::Json, State};
+
+/// # Fetch Server Template
+///
+/// Preview a server template by its code.
+#[openapi(tag = "Server Templates")]
+#[get("/<target>")]
+pub async fn fetch(
+    db: &State<Database>,
+    target: Reference<'_>,
+) -> Result<Json<v0::ServerTemplate>> {
+    target
+        .as_server_template(db)
+        .await
+        .map(Into::into)
+        .map(Json)
+}
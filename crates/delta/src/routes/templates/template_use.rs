@@ -0,0 +1,263 @@
+use revolt_database::{util::reference::Reference, Database, Member, User};
+use revolt_models::v0;
+use revolt_result::{create_error, Result};
+
+use rocket::serde::json::Json;
+use rocket::State;
+use validator::Validate;
+
+/// # Create Server From Template
+///
+/// Create a new server using the layout of a server template.
+#[openapi(tag = "Server Templates")]
+#[post("/<target>", data = "<data>")]
+pub async fn use_template(
+    db: &State<Database>,
+    user: User,
+    target: Reference<'_>,
+    data: Json<v0::DataCreateServer>,
+) -> Result<Json<v0::CreateServerLegacyResponse>> {
+    if user.bot.is_some() {
+        return Err(create_error!(IsBot));
+    }
+
+    let data = data.into_inner();
+    data.validate().map_err(|error| {
+        create_error!(FailedValidation {
+            error: error.to_string()
+        })
+    })?;
+
+    let template = target.as_server_template(db).await?;
+    user.can_acquire_server(db).await?;
+
+    let (server, channels) = template.create_server(db, data, &user).await?;
+    let (_, channels) = Member::create(db, &server, &user, Some(channels)).await?;
+
+    Ok(Json(v0::CreateServerLegacyResponse {
+        server: server.into(),
+        channels: channels.into_iter().map(|channel| channel.into()).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{
+        Category, Channel, Emoji, EmojiParent, Member, PartialServer, SystemMessageChannels,
+    };
+    use revolt_models::v0;
+    use revolt_permissions::{ChannelPermission, OverrideField};
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn create_server_from_template() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+
+        let (mut server, channels) = harness.new_server(&user).await;
+        Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+
+        let (role_id, role) = harness.new_role(&server, 1, None).await;
+        let mut channel = channels[0].clone();
+        channel
+            .set_role_permission(
+                &harness.db,
+                &role_id,
+                OverrideField {
+                    a: 0,
+                    d: ChannelPermission::SendMessage as i64,
+                },
+            )
+            .await
+            .unwrap();
+
+        server = harness.db.fetch_server(&server.id).await.unwrap();
+        server
+            .update(
+                &harness.db,
+                PartialServer {
+                    categories: Some(vec![Category {
+                        id: "category".to_string(),
+                        title: "Category".to_string(),
+                        channels: vec![channel.id().to_string()],
+                        default_permissions: None,
+                        role_permissions: Default::default(),
+                    }]),
+                    system_messages: Some(SystemMessageChannels {
+                        user_joined: Some(channel.id().to_string()),
+                        user_left: None,
+                        user_kicked: None,
+                        user_banned: None,
+                    }),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .unwrap();
+
+        let response = harness
+            .client
+            .post(format!("/servers/{}/templates", server.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(json!({ "name": "Event" }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let template: v0::ServerTemplate = response.into_json().await.expect("`ServerTemplate`");
+        assert_eq!(template.layout.channels.len(), 1);
+
+        let response = harness
+            .client
+            .get(format!("/templates/{}", template.code))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        drop(response);
+
+        let response = harness
+            .client
+            .post(format!("/templates/{}", template.code))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(json!({ "name": "Event Copy" }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let created: v0::CreateServerLegacyResponse = response
+            .into_json()
+            .await
+            .expect("`CreateServerLegacyResponse`");
+
+        let new_server = harness.db.fetch_server(&created.server.id).await.unwrap();
+        assert_eq!(new_server.name, "Event Copy");
+        assert_eq!(new_server.channels.len(), 1);
+
+        // Roles are copied with new ids
+        let (new_role_id, new_role) = new_server.roles.iter().next().unwrap();
+        assert_ne!(new_role_id, &role_id);
+        assert_eq!(new_role.name, role.name);
+
+        // Channel overrides, categories and system messages point to the new objects
+        let new_channel_id = &new_server.channels[0];
+        let new_channel = harness.db.fetch_channel(new_channel_id).await.unwrap();
+        assert!(matches!(
+            &new_channel,
+            Channel::TextChannel { role_permissions, .. }
+                if role_permissions.contains_key(new_role_id)
+        ));
+
+        let categories = new_server.categories.unwrap();
+        assert_eq!(categories[0].channels, vec![new_channel_id.clone()]);
+        assert_eq!(
+            new_server.system_messages.unwrap().user_joined.as_ref(),
+            Some(new_channel_id)
+        );
+    }
+
+    #[rocket::async_test]
+    async fn copy_emojis_from_template() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+
+        let (server, channels) = harness.new_server(&user).await;
+        Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+
+        let mut emojis = vec![];
+        for name in ["kept", "reported"] {
+            let id = harness.new_upload("emojis", "image/png").await;
+            Emoji {
+                id: id.clone(),
+                parent: EmojiParent::Server {
+                    id: server.id.clone(),
+                },
+                creator_id: user.id.clone(),
+                name: name.to_string(),
+                animated: false,
+                nsfw: false,
+            }
+            .create(&harness.db)
+            .await
+            .unwrap();
+
+            emojis.push(id);
+        }
+
+        let response = harness
+            .client
+            .post(format!("/servers/{}/templates", server.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(json!({ "name": "Emojis", "include_emojis": true }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let template: v0::ServerTemplate = response.into_json().await.expect("`ServerTemplate`");
+        assert_eq!(template.layout.emojis.len(), 2);
+
+        // Files reported after the template was created are not copied
+        harness
+            .db
+            .mark_attachment_as_reported(&emojis[1])
+            .await
+            .unwrap();
+
+        let usage = harness.db.fetch_storage_usage(&user.id).await.unwrap();
+
+        let response = harness
+            .client
+            .post(format!("/templates/{}", template.code))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(json!({ "name": "Emojis Copy" }).to_string())
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+        let created: v0::CreateServerLegacyResponse = response
+            .into_json()
+            .await
+            .expect("`CreateServerLegacyResponse`");
+
+        let copied = harness
+            .db
+            .fetch_emoji_by_parent_id(&created.server.id)
+            .await
+            .unwrap();
+
+        assert_eq!(copied.len(), 1);
+        assert_eq!(copied[0].name, "kept");
+
+        // The copy is a fresh file charged to both the new owner and the new server
+        let file = harness
+            .db
+            .fetch_attachment("emojis", &copied[0].id)
+            .await
+            .unwrap();
+
+        assert_eq!(file.uploader_id.as_ref(), Some(&user.id));
+        assert_eq!(file.reported, None);
+        assert_eq!(file.deleted, None);
+
+        let new_usage = harness.db.fetch_storage_usage(&user.id).await.unwrap();
+        assert_eq!(new_usage.total, usage.total + file.size as i64);
+
+        let server_usage = harness
+            .db
+            .fetch_storage_usage(&created.server.id)
+            .await
+            .unwrap();
+
+        assert_eq!(server_usage.total, file.size as i64);
+    }
+}
@@ -1,6 +1,7 @@
-use revolt_database::util::reference::Reference;
-use revolt_database::{Database, User, AMQP};
+use revolt_database::util::{permissions::DatabasePermissionQuery, reference::Reference};
+use revolt_database::{Database, RelationshipStatus, User, AMQP};
 use revolt_models::v0;
+use revolt_permissions::{calculate_user_permissions, UserPermission};
 use revolt_result::{create_error, Result};
 use rocket::serde::json::Json;
 use rocket::State;
@@ -22,6 +23,14 @@ pub async fn add(
         return Err(create_error!(IsBot));
     }
 
+    // Only new requests are subject to the other user's privacy settings
+    if user.relationship_with(&target.id) == RelationshipStatus::None {
+        let mut query = DatabasePermissionQuery::new(db, &user).user(&target);
+        calculate_user_permissions(&mut query)
+            .await
+            .throw_if_lacking_user_permission(UserPermission::SendFriendRequest)?;
+    }
+
     user.add_friend(db, amqp, &mut target).await?;
     Ok(Json(target.into(db, &user).await))
 }
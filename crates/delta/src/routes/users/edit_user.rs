@@ -50,6 +50,7 @@ pub async fn edit(
     if data.display_name.is_none()
         && data.status.is_none()
         && data.profile.is_none()
+        && data.privacy.is_none()
         && data.avatar.is_none()
         && data.badges.is_none()
         && data.flags.is_none()
@@ -119,6 +120,29 @@ pub async fn edit(
         partial.profile = Some(new_profile);
     }
 
+    // 5. Apply new privacy settings
+    if let Some(privacy) = data.privacy {
+        // Friend requests can't be limited to existing friends
+        if privacy.friend_requests == Some(v0::PrivacyLevel::Friends) {
+            return Err(create_error!(InvalidProperty));
+        }
+
+        let mut new_privacy = user.privacy.take().unwrap_or_default();
+        if let Some(direct_messages) = privacy.direct_messages {
+            new_privacy.direct_messages = direct_messages.into();
+        }
+
+        if let Some(friend_requests) = privacy.friend_requests {
+            new_privacy.friend_requests = friend_requests.into();
+        }
+
+        if let Some(profile) = privacy.profile {
+            new_privacy.profile = profile.into();
+        }
+
+        partial.privacy = Some(new_privacy);
+    }
+
     user.update(
         db,
         partial,
@@ -0,0 +1,89 @@
+use revolt_database::User;
+use revolt_models::v0;
+use revolt_result::Result;
+use rocket::serde::json::Json;
+
+/// # Fetch Privacy Settings
+///
+/// Retrieve who may message you, send you friend requests and view your profile.
+#[openapi(tag = "User Information")]
+#[get("/@me/privacy")]
+pub async fn fetch_privacy(user: User) -> Result<Json<v0::UserPrivacy>> {
+    Ok(Json(user.privacy.unwrap_or_default().into()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_models::v0;
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn edit_and_fetch_privacy() {
+        let harness = TestHarness::new().await;
+        let (_, session, _) = harness.new_user().await;
+
+        let response = harness
+            .client
+            .get("/users/@me/privacy")
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let privacy: v0::UserPrivacy = response.into_json().await.expect("`UserPrivacy`");
+        assert_eq!(privacy.direct_messages, v0::PrivacyLevel::Friends);
+        assert_eq!(privacy.friend_requests, v0::PrivacyLevel::Everyone);
+        assert_eq!(privacy.profile, v0::PrivacyLevel::Mutual);
+
+        let response = harness
+            .client
+            .patch("/users/@me")
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "privacy": {
+                        "direct_messages": "Mutual",
+                        "friend_requests": "Nobody"
+                    }
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = harness
+            .client
+            .get("/users/@me/privacy")
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        let privacy: v0::UserPrivacy = response.into_json().await.expect("`UserPrivacy`");
+        assert_eq!(privacy.direct_messages, v0::PrivacyLevel::Mutual);
+        assert_eq!(privacy.friend_requests, v0::PrivacyLevel::Nobody);
+        assert_eq!(privacy.profile, v0::PrivacyLevel::Mutual);
+
+        let response = harness
+            .client
+            .patch("/users/@me")
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "privacy": {
+                        "friend_requests": "Friends"
+                    }
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+}
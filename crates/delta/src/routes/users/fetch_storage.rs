@@ -0,0 +1,57 @@
+use revolt_database::{Database, User};
+use revolt_models::v0;
+use revolt_result::Result;
+use rocket::{serde::json::Json, State};
+
+/// # Fetch Storage Usage
+///
+/// Retrieve how much storage your uploaded files are using.
+#[openapi(tag = "User Information")]
+#[get("/@me/storage")]
+pub async fn fetch_storage(db: &State<Database>, user: User) -> Result<Json<v0::StorageUsage>> {
+    let usage = db.fetch_storage_usage(&user.id).await?;
+    let limits = user.limits().await;
+
+    Ok(Json(v0::StorageUsage {
+        total: usage.total.max(0) as usize,
+        quota: limits.storage_quota,
+        tags: usage
+            .tags
+            .into_iter()
+            .map(|(tag, bytes)| (tag, bytes.max(0) as usize))
+            .collect(),
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_models::v0;
+    use rocket::http::{Header, Status};
+
+    #[rocket::async_test]
+    async fn fetch_storage() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+
+        harness
+            .db
+            .increment_storage_usage(&user.id, "attachments", 1024)
+            .await
+            .unwrap();
+
+        let response = harness
+            .client
+            .get("/users/@me/storage")
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let usage: v0::StorageUsage = response.into_json().await.expect("`StorageUsage`");
+        assert_eq!(usage.total, 1024);
+        assert_eq!(usage.tags.get("attachments"), Some(&1024));
+        assert_eq!(usage.quota, user.limits().await.storage_quota);
+    }
+}
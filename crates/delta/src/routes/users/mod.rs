@@ -6,8 +6,10 @@ mod block_user;
 mod change_username;
 mod edit_user;
 mod fetch_dms;
+mod fetch_privacy;
 mod fetch_profile;
 mod fetch_self;
+mod fetch_storage;
 mod fetch_user;
 mod fetch_user_flags;
 mod find_mutual;
@@ -27,6 +29,8 @@ pub fn routes() -> (Vec<Route>, OpenApi) {
         change_username::change_username,
         get_default_avatar::default_avatar,
         fetch_profile::profile,
+        fetch_privacy::fetch_privacy,
+        fetch_storage::fetch_storage,
         // Direct Messaging
         fetch_dms::direct_messages,
         open_dm::open_dm,
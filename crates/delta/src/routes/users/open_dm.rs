@@ -31,3 +31,60 @@ pub async fn open_dm(
         .map(Into::into)
         .map(Json)
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{Member, PartialUser, PrivacyLevel, UserPrivacy};
+    use rocket::http::{Header, Status};
+
+    #[rocket::async_test]
+    async fn open_dm_respects_privacy() {
+        let harness = TestHarness::new().await;
+        let (_, session, user) = harness.new_user().await;
+        let (_, _, mut other_user) = harness.new_user().await;
+
+        let (server, channels) = harness.new_server(&user).await;
+        Member::create(&harness.db, &server, &user, Some(channels.clone()))
+            .await
+            .expect("Failed to create member");
+        Member::create(&harness.db, &server, &other_user, Some(channels))
+            .await
+            .expect("Failed to create member");
+
+        // Only friends may send direct messages by default
+        let response = harness
+            .client
+            .get(format!("/users/{}/dm", other_user.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+        drop(response);
+
+        other_user
+            .update(
+                &harness.db,
+                PartialUser {
+                    privacy: Some(UserPrivacy {
+                        direct_messages: PrivacyLevel::Mutual,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .expect("Failed to update user");
+
+        let response = harness
+            .client
+            .get(format!("/users/{}/dm", other_user.id))
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+    }
+}
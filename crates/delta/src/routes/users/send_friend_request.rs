@@ -1,6 +1,9 @@
 // use revolt_database::util::reference::Reference;
-use revolt_database::{Database, User, AMQP};
+use revolt_database::{
+    util::permissions::DatabasePermissionQuery, Database, RelationshipStatus, User, AMQP,
+};
 use revolt_models::v0;
+use revolt_permissions::{calculate_user_permissions, UserPermission};
 use revolt_result::{create_error, Result};
 use rocket::serde::json::Json;
 use rocket::State;
@@ -8,6 +11,8 @@ use rocket::State;
 /// # Send Friend Request
 ///
 /// Send a friend request to another user.
+///
+/// Will fail if the other user does not accept friend requests from you.
 #[openapi(tag = "Relationships")]
 #[post("/friend", data = "<data>")]
 pub async fn send_friend_request(
@@ -23,9 +28,62 @@ pub async fn send_friend_request(
             return Err(create_error!(IsBot));
         }
 
+        // Only new requests are subject to the other user's privacy settings
+        if user.relationship_with(&target.id) == RelationshipStatus::None {
+            let mut query = DatabasePermissionQuery::new(db, &user).user(&target);
+            calculate_user_permissions(&mut query)
+                .await
+                .throw_if_lacking_user_permission(UserPermission::SendFriendRequest)?;
+        }
+
         user.add_friend(db, amqp, &mut target).await?;
         Ok(Json(target.into(db, &user).await))
     } else {
         Err(create_error!(InvalidProperty))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{rocket, util::test::TestHarness};
+    use revolt_database::{PartialUser, PrivacyLevel, UserPrivacy};
+    use rocket::http::{ContentType, Header, Status};
+
+    #[rocket::async_test]
+    async fn send_friend_request_respects_privacy() {
+        let harness = TestHarness::new().await;
+        let (_, session, _) = harness.new_user().await;
+        let (_, _, mut other_user) = harness.new_user().await;
+
+        other_user
+            .update(
+                &harness.db,
+                PartialUser {
+                    privacy: Some(UserPrivacy {
+                        friend_requests: PrivacyLevel::Nobody,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                vec![],
+            )
+            .await
+            .expect("Failed to update user");
+
+        let response = harness
+            .client
+            .post("/users/friend")
+            .header(Header::new("x-session-token", session.token.to_string()))
+            .header(ContentType::JSON)
+            .body(
+                json!({
+                    "username": format!("{}#{}", other_user.username, other_user.discriminator)
+                })
+                .to_string(),
+            )
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+}
@@ -1,7 +1,7 @@
 use revolt_config::config;
 use revolt_database::{
     util::{idempotency::IdempotencyKey, reference::Reference},
-    Database, Message, AMQP,
+    AutoModConfig, Channel, Database, Message, AMQP,
 };
 use revolt_models::v0;
 use revolt_permissions::{ChannelPermission, PermissionValue};
@@ -54,6 +54,24 @@ pub async fn webhook_execute(
 
     let channel = db.fetch_channel(&webhook.channel_id).await?;
 
+    // Run the message past the server's auto-moderation rules
+    if let Channel::TextChannel { server, .. } | Channel::VoiceChannel { server, .. } = &channel {
+        if let Some(config) = AutoModConfig::fetch_compiled(db, server).await? {
+            let server = db.fetch_server(server).await?;
+            config
+                .check_message(
+                    db,
+                    &server,
+                    &webhook.id,
+                    None,
+                    channel.id(),
+                    data.content.as_deref(),
+                    data.attachments.as_deref().unwrap_or_default(),
+                )
+                .await?;
+        }
+    }
+
     Ok(Json(
         Message::create_from_api(
             db,
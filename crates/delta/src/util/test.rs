@@ -6,7 +6,8 @@ use futures::StreamExt;
 use rand::Rng;
 use redis_kiss::redis::aio::PubSub;
 use revolt_database::{
-    events::client::EventV1, Channel, Database, Member, Message, Server, User, AMQP,
+    events::client::EventV1, Channel, Database, File, Member, Message, Metadata, Server, Sticker,
+    StickerParent, User, AMQP,
 };
 use revolt_database::{util::idempotency::IdempotencyKey, Role};
 use revolt_models::v0;
@@ -146,6 +147,8 @@ impl TestHarness {
             rank,
             colour: None,
             hoist: false,
+            self_assignable: false,
+            group: None,
         };
 
         let id = role
@@ -172,6 +175,69 @@ impl TestHarness {
         .expect("Failed to make test channel")
     }
 
+    #[allow(clippy::disallowed_methods)]
+    pub async fn new_upload(&self, tag: &str, content_type: &str) -> String {
+        let id = ulid::Ulid::new().to_string();
+
+        self.db
+            .insert_attachment(&File {
+                id: id.clone(),
+                tag: tag.to_string(),
+                filename: "upload".to_string(),
+                hash: None,
+                uploaded_at: None,
+                uploader_id: None,
+                used_for: None,
+                deleted: None,
+                reported: None,
+                metadata: if content_type.starts_with("audio/") {
+                    Metadata::Audio {
+                        duration: Some(1000),
+                        waveform: None,
+                    }
+                } else {
+                    Metadata::Image {
+                        width: 320,
+                        height: 320,
+                        placeholder: None,
+                        animated: content_type == "image/gif",
+                    }
+                },
+                content_type: content_type.to_string(),
+                size: 1024,
+                message_id: None,
+                user_id: None,
+                server_id: None,
+                object_id: None,
+            })
+            .await
+            .expect("Failed to create test upload");
+
+        id
+    }
+
+    pub async fn new_sticker(&self, server: &Server, user: &User) -> Sticker {
+        let sticker = Sticker {
+            id: ulid::Ulid::new().to_string(),
+            parent: StickerParent::Server {
+                id: server.id.clone(),
+            },
+            creator_id: user.id.clone(),
+            name: TestHarness::rand_string(),
+            description: None,
+            emoji: None,
+            animated: false,
+            nsfw: false,
+        };
+
+        sticker
+            .create(&self.db)
+            .await
+            .expect("Failed to create test sticker");
+
+        sticker
+    }
+
     pub async fn new_message(
         &self,
         user: &User,
@@ -194,6 +260,7 @@ impl TestHarness {
                 embeds: None,
                 masquerade: None,
                 interactions: None,
+                sticker: None,
                 flags: None,
             },
             v0::MessageAuthor::User(&user.clone().into(&self.db, Some(user)).await),
@@ -1,11 +1,11 @@
 use std::{
     io::{Cursor, Read},
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use axum::{
-    extract::{DefaultBodyLimit, Path, State},
-    http::{header, Method},
+    extract::{DefaultBodyLimit, Path, Query, State},
+    http::{header, HeaderMap, Method},
     response::{IntoResponse, Redirect, Response},
     routing::{get, post},
     Json, Router,
@@ -13,9 +13,12 @@ use axum::{
 use axum_typed_multipart::{FieldData, TryFromMultipart, TypedMultipart};
 use lazy_static::lazy_static;
 use revolt_config::{config, report_internal_error};
-use revolt_database::{iso8601_timestamp::Timestamp, Database, FileHash, Metadata, User};
+use revolt_database::{
+    iso8601_timestamp::Timestamp, Database, File, FileHash, Metadata, StorageUsage, User,
+};
 use revolt_files::{
-    create_thumbnail, decode_image, fetch_from_s3, upload_to_s3, AUTHENTICATION_TAG_SIZE_BYTES,
+    create_animated_preview, create_preview, decode_image, fetch_from_s3, fetch_variant_from_s3,
+    upload_to_s3, upload_variant_to_s3, PreviewFormat, AUTHENTICATION_TAG_SIZE_BYTES,
 };
 use revolt_result::{create_error, Error, Result};
 use serde::{Deserialize, Serialize};
@@ -25,7 +28,13 @@ use tokio::time::Instant;
 use tower_http::cors::{AllowHeaders, Any, CorsLayer};
 use utoipa::ToSchema;
 
-use crate::{exif::strip_metadata, metadata::generate_metadata, mime_type::determine_mime_type, AppState};
+use crate::{
+    exif::strip_metadata,
+    metadata::{generate_metadata, generate_placeholder},
+    mime_type::determine_mime_type,
+    voice::process_voice_message,
+    AppState,
+};
 
 /// Build the API router
 pub async fn router() -> Router<AppState> {
@@ -70,6 +79,25 @@ lazy_static! {
         .build();
 }
 
+lazy_static! {
+    /// Cache of generated previews keyed by file id and variant
+    static ref PREVIEW_CACHE: moka::future::Cache<String, (&'static str, Vec<u8>)> = moka::future::Cache::builder()
+        .weigher(|key: &String, (_, vec): &(&'static str, Vec<u8>)| -> u32 {
+            (key.len() + vec.len()).try_into().unwrap_or(u32::MAX)
+        })
+        .max_capacity(512 * 1024 * 1024) // Cache up to 512MiB in memory
+        .time_to_idle(Duration::from_secs(30 * 60)) // For up to 30 minutes since last use
+        .build();
+}
+
+lazy_static! {
+    /// Cache of whether files require a signature to be fetched, as working this out takes several lookups
+    static ref SIGNATURE_REQUIRED_CACHE: moka::future::Cache<String, bool> = moka::future::Cache::builder()
+        .max_capacity(100_000)
+        .time_to_live(Duration::from_secs(60)) // For up to a minute, in case the channel's visibility changes
+        .build();
+}
+
 /// Retrieve hash information and file data by given hash
 async fn retrieve_file_by_hash(hash: &FileHash) -> Result<Vec<u8>> {
     if let Some(data) = S3_CACHE.get(&hash.id).await {
@@ -119,6 +147,151 @@ pub enum Tag {
     icons,
     banners,
     emojis,
+    stickers,
+}
+
+/// Available formats to request previews in
+#[derive(Clone, Copy, Deserialize, Debug, ToSchema)]
+#[allow(non_camel_case_types)]
+pub enum Format {
+    avif,
+    webp,
+    jpeg,
+}
+
+impl From<Format> for PreviewFormat {
+    fn from(value: Format) -> Self {
+        match value {
+            Format::avif => PreviewFormat::Avif,
+            Format::webp => PreviewFormat::Webp,
+            Format::jpeg => PreviewFormat::Jpeg,
+        }
+    }
+}
+
+/// Options for generating a preview
+#[derive(Deserialize, Debug)]
+pub struct PreviewQuery {
+    /// Maximum width of the preview
+    width: Option<usize>,
+    /// Maximum height of the preview
+    height: Option<usize>,
+    /// Format to encode the preview as
+    format: Option<Format>,
+    /// Whether to preserve animation
+    #[serde(default)]
+    animated: bool,
+}
+
+/// Signed URL parameters, required for attachments in private channels
+#[derive(Deserialize, Debug)]
+pub struct SignatureQuery {
+    /// UNIX timestamp (in seconds) after which the signature is no longer valid
+    expires: Option<u64>,
+    /// Id of the user the signature was issued to
+    viewer: Option<String>,
+    /// Signature of the URL
+    signature: Option<String>,
+}
+
+impl SignatureQuery {
+    /// Query string to carry the signature over to another URL
+    fn to_query_string(&self) -> String {
+        match (&self.expires, &self.viewer, &self.signature) {
+            (Some(expires), Some(viewer), Some(signature)) => {
+                format!("?expires={expires}&viewer={viewer}&signature={signature}")
+            }
+            _ => String::new(),
+        }
+    }
+}
+
+/// Ensure the file may be served, requiring a valid signature if it was posted in a private channel
+///
+/// Returns the cache control header the file should be served with
+async fn check_signature(db: &Database, file: &File, query: &SignatureQuery) -> Result<String> {
+    if let (Some(expires), Some(viewer), Some(signature)) =
+        (query.expires, &query.viewer, &query.signature)
+    {
+        let config = config().await;
+        if !config.files.signing.key.is_empty()
+            && File::verify_url(
+                &config.files.signing.key,
+                &file.tag,
+                &file.id,
+                expires,
+                viewer,
+                signature,
+            )
+        {
+            // Shared caches must not hold onto the file past the signature expiring
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default();
+
+            return Ok(format!(
+                "private, max-age={}, must-revalidate",
+                expires.saturating_sub(now)
+            ));
+        }
+    }
+
+    let requires_signature = if let Some(hit) = SIGNATURE_REQUIRED_CACHE.get(&file.id).await {
+        hit
+    } else {
+        let requires_signature = file.requires_signature(db).await?;
+        SIGNATURE_REQUIRED_CACHE
+            .insert(file.id.to_owned(), requires_signature)
+            .await;
+        requires_signature
+    };
+
+    if requires_signature {
+        Err(create_error!(NotAuthenticated))
+    } else {
+        Ok(CACHE_CONTROL.to_owned())
+    }
+}
+
+/// Pick the best preview format the client has told us it accepts
+///
+/// Clients which don't tell us anything receive WebP as they always have.
+fn negotiate_format(headers: &HeaderMap) -> PreviewFormat {
+    let Some(accept) = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return PreviewFormat::Webp;
+    };
+
+    // Collect media ranges, skipping any the client explicitly refuses
+    let accepted: Vec<&str> = accept
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';').map(str::trim);
+            let media_range = parts.next()?;
+            let refused = parts.any(|param| {
+                param
+                    .strip_prefix("q=")
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .is_some_and(|q| q == 0.0)
+            });
+
+            (!refused).then_some(media_range)
+        })
+        .collect();
+
+    if accepted.contains(&"image/avif") {
+        PreviewFormat::Avif
+    } else if accepted
+        .iter()
+        .any(|range| matches!(*range, "image/webp" | "image/*" | "*/*"))
+    {
+        PreviewFormat::Webp
+    } else {
+        PreviewFormat::Jpeg
+    }
 }
 
 /// Request body for upload
@@ -130,6 +303,14 @@ pub struct UploadPayload {
     file: FieldData<NamedTempFile>,
 }
 
+/// Options for uploading a file
+#[derive(Deserialize, Debug)]
+pub struct UploadOptions {
+    /// Whether this file is a voice message
+    #[serde(default)]
+    voice: bool,
+}
+
 /// Successful upload response
 #[derive(Serialize, Debug, ToSchema)]
 pub struct UploadResponse {
@@ -149,6 +330,12 @@ pub struct UploadResponse {
 /// | icons | 2.5 MB | 40 MP or 10,000px | Image |
 /// | banners | 6 MB | 40 MP or 10,000px | Image |
 /// | emojis | 500 KB | 40 MP or 10,000px | Image |
+/// | stickers | 1 MB | 40 MP or 10,000px | Image |
+///
+/// Uploads count towards the user's total storage quota.
+///
+/// Attachments uploaded with `voice=true` are normalised to Opus (in an OGG container)
+/// and have their duration and waveform included in the metadata.
 #[utoipa::path(
     post,
     path = "/{tag}",
@@ -156,7 +343,8 @@ pub struct UploadResponse {
         (status = 200, description = "Upload was successful", body = UploadResponse)
     ),
     params(
-        ("tag" = Tag, Path, description = "Tag to upload to (e.g. attachments, icons, ...)")
+        ("tag" = Tag, Path, description = "Tag to upload to (e.g. attachments, icons, ...)"),
+        ("voice" = Option<bool>, Query, description = "Whether this attachment is a voice message")
     ),
     request_body(content_type = "multipart/form-data", content = UploadPayload),
     security(
@@ -168,6 +356,7 @@ async fn upload_file(
     State(db): State<Database>,
     user: User,
     Path(tag): Path<Tag>,
+    Query(options): Query<UploadOptions>,
     TypedMultipart(UploadPayload { mut file }): TypedMultipart<UploadPayload>,
 ) -> Result<Json<UploadResponse>> {
     // Fetch configuration
@@ -176,6 +365,11 @@ async fn upload_file(
     // Keep track of processing time
     let now = Instant::now();
 
+    // Only attachments can be voice messages
+    if options.voice && !matches!(tag, Tag::attachments) {
+        return Err(create_error!(InvalidOperation));
+    }
+
     // Extract the filename, or give it a generic name
     let filename = file.metadata.file_name.unwrap_or("unnamed-file".to_owned());
 
@@ -202,15 +396,30 @@ async fn upload_file(
         return Err(create_error!(FileTooLarge { max: size_limit }));
     }
 
+    // Bail out early if the user is out of storage,
+    // the file is charged against the quota once processed
+    let usage = db.fetch_storage_usage(&user.id).await?;
+    if usage.total.max(0) as usize + original_file_size > limits.storage_quota {
+        return Err(create_error!(StorageQuotaExceeded {
+            max: limits.storage_quota
+        }));
+    }
+
     // Generate sha256 hash
     let original_hash = {
         let mut hasher = sha2::Sha256::new();
+
+        // Voice messages are processed differently, keep them apart from regular uploads
+        if options.voice {
+            hasher.update(b"voice:");
+        }
+
         hasher.update(&buf);
         hasher.finalize()
     };
 
     // Generate an ID for this file
-    let id = if matches!(tag, Tag::emojis) {
+    let id = if matches!(tag, Tag::emojis | Tag::stickers) {
         ulid::Ulid::new().to_string()
     } else {
         nanoid::nanoid!(42)
@@ -219,6 +428,17 @@ async fn upload_file(
     // Determine the mime type for the file
     let mime_type = determine_mime_type(&mut file.contents, &buf, &filename);
 
+    // Voice messages are always stored as OGG
+    let filename = if options.voice {
+        let stem = filename
+            .rsplit_once('.')
+            .map_or(filename.as_str(), |(stem, _)| stem);
+
+        format!("{stem}.ogg")
+    } else {
+        filename
+    };
+
     // Check blocklist for mime type
     if config
         .files
@@ -244,13 +464,12 @@ async fn upload_file(
     {
         if !file_hash.iv.is_empty() {
             let tag: &'static str = tag.into();
-            db.insert_attachment(&file_hash.into_file(
-                id.clone(),
-                tag.to_owned(),
-                filename,
-                user.id,
-            ))
-            .await?;
+            let file = file_hash.into_file(id.clone(), tag.to_owned(), filename, user.id.clone());
+            StorageUsage::charge_within_quota(&db, &user.id, &file, limits.storage_quota).await?;
+            if let Err(err) = db.insert_attachment(&file).await {
+                StorageUsage::release(&db, &user.id, &file).await?;
+                return Err(err);
+            }
 
             return Ok(Json(UploadResponse { id }));
         }
@@ -260,8 +479,17 @@ async fn upload_file(
         false
     };
 
-    // Strip metadata
-    let (buf, metadata) = strip_metadata(file.contents, buf, metadata, mime_type).await?;
+    // Normalise voice messages or otherwise strip metadata
+    let (buf, metadata, mime_type) = if options.voice {
+        let (buf, metadata) = process_voice_message(&file.contents).await?;
+        (buf, metadata, "audio/ogg")
+    } else {
+        let (buf, metadata) = strip_metadata(file.contents, buf, metadata, mime_type).await?;
+        (buf, metadata, mime_type)
+    };
+
+    // Generate placeholder for previews
+    let metadata = generate_placeholder(&buf, metadata, mime_type);
 
     // Virus scan files if ClamAV is configured
     if matches!(metadata, Metadata::File)
@@ -300,24 +528,36 @@ async fn upload_file(
         size: new_file_size as isize,
     };
 
-    // Add attachment hash if it doesn't exist
-    if !file_hash_exists {
-        db.insert_attachment_hash(&file_hash).await?;
-    }
+    // Reserve storage for the file before storing anything
+    let tag: &'static str = tag.into();
+    let file = file_hash.into_file(id.clone(), tag.to_owned(), filename, user.id.clone());
+    StorageUsage::charge_within_quota(&db, &user.id, &file, limits.storage_quota).await?;
 
-    // Upload the file to S3 and commit nonce to database
-    let upload_start = Instant::now();
-    let nonce = upload_to_s3(&file_hash.bucket_id, &file_hash.id, &buf).await?;
-    db.set_attachment_hash_nonce(&file_hash.id, &nonce).await?;
+    let stored: Result<()> = async {
+        // Add attachment hash if it doesn't exist
+        if !file_hash_exists {
+            db.insert_attachment_hash(&file_hash).await?;
+        }
 
-    // Debug information
-    let time_to_upload = Instant::now() - upload_start;
-    tracing::info!("Took {time_to_upload:?} to upload {new_file_size} bytes to S3.");
+        // Upload the file to S3 and commit nonce to database
+        let upload_start = Instant::now();
+        let nonce = upload_to_s3(&file_hash.bucket_id, &file_hash.id, &buf).await?;
+        db.set_attachment_hash_nonce(&file_hash.id, &nonce).await?;
 
-    // Finally, create the file and return its ID
-    let tag: &'static str = tag.into();
-    db.insert_attachment(&file_hash.into_file(id.clone(), tag.to_owned(), filename, user.id))
-        .await?;
+        // Debug information
+        let time_to_upload = Instant::now() - upload_start;
+        tracing::info!("Took {time_to_upload:?} to upload {new_file_size} bytes to S3.");
+
+        // Finally, create the file
+        db.insert_attachment(&file).await
+    }
+    .await;
+
+    // Give the reserved storage back if the file didn't make it
+    if let Err(err) = stored {
+        StorageUsage::release(&db, &user.id, &file).await?;
+        return Err(err);
+    }
 
     Ok(Json(UploadResponse { id }))
 }
@@ -340,10 +580,18 @@ pub static CACHE_CONTROL: &str = "public, max-age=604800, must-revalidate";
 /// | icons | Up to 128px on any axis | ✅ |
 /// | banners | Up to 480px on any axis | ❌ |
 /// | emojis | Up to 128px on any axis | ❌ |
+/// | stickers | Up to 320px on any axis | ❌ <sup>§</sup> |
+///
+/// <sup>†</sup> aspect ratio will always be preserved, a smaller size may be picked using `width` and `height` from the sizes allowed by the server
+///
+/// <sup>‡</sup> to fetch animated variant, use `animated=true` or suffix `/{file_name}` or `/original` to the path
+///
+/// <sup>§</sup> animated stickers are always previewed as animated WebP
 ///
-/// <sup>†</sup> aspect ratio will always be preserved
+/// The preview will be encoded as AVIF, WebP or JPEG depending on the `Accept` header, unless `format` is given.
+/// Animated previews are always encoded as WebP.
 ///
-/// <sup>‡</sup> to fetch animated variant, suffix `/{file_name}` or `/original` to the path
+/// Attachments posted in private channels require the signature given alongside the file.
 #[utoipa::path(
     get,
     path = "/{tag}/{file_id}",
@@ -352,13 +600,24 @@ pub static CACHE_CONTROL: &str = "public, max-age=604800, must-revalidate";
     ),
     params(
         ("tag" = Tag, Path, description = "Tag to fetch from (e.g. attachments, icons, ...)"),
-        ("file_id" = String, Path, description = "File identifier")
+        ("file_id" = String, Path, description = "File identifier"),
+        ("width" = Option<usize>, Query, description = "Maximum width of the preview"),
+        ("height" = Option<usize>, Query, description = "Maximum height of the preview"),
+        ("format" = Option<Format>, Query, description = "Format to encode the preview as"),
+        ("animated" = Option<bool>, Query, description = "Whether to preserve animation"),
+        ("expires" = Option<u64>, Query, description = "Expiry of the signature (required for attachments in private channels)"),
+        ("viewer" = Option<String>, Query, description = "Viewer the signature was issued to (required for attachments in private channels)"),
+        ("signature" = Option<String>, Query, description = "Signature of the URL (required for attachments in private channels)")
     ),
 )]
 async fn fetch_preview(
     State(db): State<Database>,
     Path((tag, file_id)): Path<(Tag, String)>,
+    Query(query): Query<PreviewQuery>,
+    Query(signature): Query<SignatureQuery>,
+    headers: HeaderMap,
 ) -> Result<Response> {
+    let config = config().await;
     let tag_str: &'static str = tag.clone().into();
     let file = db.fetch_attachment(tag_str, &file_id).await?;
 
@@ -372,34 +631,123 @@ async fn fetch_preview(
         return Err(create_error!(NotFound));
     }
 
+    // Files in private channels must be signed
+    let cache_control = check_signature(&db, &file, &signature).await?;
+
+    // Only allow sizes from the allow list
+    for side in [query.width, query.height].into_iter().flatten() {
+        if !config.files.preview_sizes.contains(&side) {
+            return Err(create_error!(InvalidProperty));
+        }
+    }
+
     let hash = file.as_hash(&db).await?;
 
-    let is_animated = hash.content_type == "image/gif"; // TODO: extract this data from files
+    let is_animated = matches!(hash.metadata, Metadata::Image { animated: true, .. });
+    let wants_animation = (query.animated || matches!(tag, Tag::stickers)) && is_animated;
 
     // Only process image files and don't process GIFs if not avatar or icon
     if !matches!(hash.metadata, Metadata::Image { .. })
-        || (is_animated && !matches!(tag, Tag::avatars | Tag::icons))
+        || (hash.content_type == "image/gif"
+            && !wants_animation
+            && !matches!(tag, Tag::avatars | Tag::icons))
     {
         return Ok(
-            Redirect::permanent(&format!("/{tag_str}/{file_id}/{}", file.filename)).into_response(),
+            Redirect::permanent(&format!(
+                "/{tag_str}/{file_id}/{}{}",
+                file.filename,
+                signature.to_query_string()
+            ))
+            .into_response(),
         );
     }
 
-    // Original image data
-    let data = retrieve_file_by_hash(&hash).await?;
+    // Work out which variant we need to produce
+    let [max_w, max_h] = *config.files.preview.get(tag_str).unwrap();
+    let size = [
+        query.width.unwrap_or(max_w).min(max_w),
+        query.height.unwrap_or(max_h).min(max_h),
+    ];
+
+    let format = query
+        .format
+        .map(PreviewFormat::from)
+        .unwrap_or_else(|| negotiate_format(&headers));
+
+    // Animation can only be preserved by WebP
+    let format = if wants_animation && format != PreviewFormat::Jpeg {
+        PreviewFormat::Webp
+    } else {
+        format
+    };
 
-    // Read image and create thumbnail
-    let data = create_thumbnail(
-        decode_image(&mut Cursor::new(data), &file.content_type)?,
-        tag_str,
-    )
-    .await;
+    let animated = wants_animation && format == PreviewFormat::Webp;
+    let variant = format!(
+        "{}x{}{}.{}",
+        size[0],
+        size[1],
+        if animated { "-animated" } else { "" },
+        format.extension()
+    );
+
+    let cache_key = format!("{file_id}/{variant}");
+    let (content_type, data) = if let Some(hit) = PREVIEW_CACHE.get(&cache_key).await {
+        hit
+    } else {
+        let storage_path = format!("previews/{cache_key}");
+        let stored = if config.files.store_preview_variants {
+            fetch_variant_from_s3(&hash.bucket_id, &storage_path).await
+        } else {
+            None
+        };
+
+        let generated = if let Some(data) = stored {
+            data
+        } else {
+            // Original image data
+            let data = retrieve_file_by_hash(&hash).await?;
+
+            // Try to keep the animation if requested, otherwise create a still preview
+            let animated_preview = if animated {
+                create_animated_preview(&data, &hash.content_type, size).await?
+            } else {
+                None
+            };
+
+            let preview = if let Some(preview) = animated_preview {
+                preview
+            } else {
+                create_preview(
+                    decode_image(&mut Cursor::new(data), &file.content_type)?,
+                    size,
+                    format,
+                )
+                .await?
+            };
+
+            // Storing the variant is only an optimisation, still serve the preview on failure
+            if config.files.store_preview_variants {
+                if let Err(err) =
+                    upload_variant_to_s3(&hash.bucket_id, &storage_path, &preview).await
+                {
+                    tracing::error!("Failed to store preview {storage_path}: {err:?}");
+                }
+            }
+
+            preview
+        };
+
+        let entry = (format.mime_type(), generated);
+        PREVIEW_CACHE.insert(cache_key, entry.clone()).await;
+        entry
+    };
 
     Ok((
         [
-            (header::CONTENT_TYPE, "image/webp"),
+            (header::CONTENT_TYPE, content_type),
             (header::CONTENT_DISPOSITION, "inline"),
-            (header::CACHE_CONTROL, CACHE_CONTROL),
+            (header::CACHE_CONTROL, cache_control.as_str()),
+            (header::VARY, "Accept"),
         ],
         data,
     )
@@ -411,6 +759,8 @@ async fn fetch_preview(
 /// Content disposition header will be set to 'attachment' to prevent browser from rendering anything.
 ///
 /// Using `original` as the file name parameter will redirect you to the original file.
+///
+/// Attachments posted in private channels require the signature given alongside the file.
 #[utoipa::path(
     get,
     path = "/{tag}/{file_id}/{file_name}",
@@ -420,12 +770,16 @@ async fn fetch_preview(
     params(
         ("tag" = Tag, Path, description = "Tag to fetch from (e.g. attachments, icons, ...)"),
         ("file_id" = String, Path, description = "File identifier"),
-        ("file_name" = String, Path, description = "File name")
+        ("file_name" = String, Path, description = "File name"),
+        ("expires" = Option<u64>, Query, description = "Expiry of the signature (required for attachments in private channels)"),
+        ("viewer" = Option<String>, Query, description = "Viewer the signature was issued to (required for attachments in private channels)"),
+        ("signature" = Option<String>, Query, description = "Signature of the URL (required for attachments in private channels)")
     ),
 )]
 async fn fetch_file(
     State(db): State<Database>,
     Path((tag, file_id, file_name)): Path<(Tag, String, String)>,
+    Query(signature): Query<SignatureQuery>,
 ) -> Result<Response> {
     let tag: &'static str = tag.clone().into();
     let file = db.fetch_attachment(tag, &file_id).await?;
@@ -440,11 +794,19 @@ async fn fetch_file(
         return Err(create_error!(NotFound));
     }
 
+    // Files in private channels must be signed
+    let cache_control = check_signature(&db, &file, &signature).await?;
+
     // Ensure filename is correct
     if file_name != file.filename {
         if file_name == "original" {
             return Ok(
-                Redirect::permanent(&format!("/{tag}/{file_id}/{}", file.filename)).into_response(),
+                Redirect::permanent(&format!(
+                    "/{tag}/{file_id}/{}{}",
+                    file.filename,
+                    signature.to_query_string()
+                ))
+                .into_response(),
             );
         }
 
@@ -457,7 +819,7 @@ async fn fetch_file(
             [
                 (header::CONTENT_TYPE, hash.content_type),
                 (header::CONTENT_DISPOSITION, "attachment".to_owned()),
-                (header::CACHE_CONTROL, CACHE_CONTROL.to_owned()),
+                (header::CACHE_CONTROL, cache_control),
             ],
             data,
         )
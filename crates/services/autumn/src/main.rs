@@ -18,6 +18,7 @@ pub mod exif;
 pub mod metadata;
 pub mod mime_type;
 mod ratelimits;
+pub mod voice;
 
 #[derive(FromRef, Clone)]
 struct AppState {
@@ -49,6 +50,7 @@ async fn main() -> Result<(), std::io::Error> {
                 revolt_result::ErrorType,
                 api::RootResponse,
                 api::Tag,
+                api::Format,
                 api::UploadPayload,
                 api::UploadResponse
             )
@@ -1,7 +1,10 @@
-use std::io::Cursor;
+use std::io::{Cursor, Write};
 
 use revolt_database::Metadata;
-use revolt_files::{image_size, video_size};
+use revolt_files::{
+    audio_duration, create_placeholder, decode_image, image_size, is_animated, video_poster,
+    video_size,
+};
 use tempfile::NamedTempFile;
 
 /// Intersection of what infer can detect and what image-rs supports
@@ -26,6 +29,8 @@ pub fn generate_metadata(f: &NamedTempFile, mime_type: &str) -> Metadata {
             .map(|(width, height)| Metadata::Image {
                 width: width as isize,
                 height: height as isize,
+                placeholder: None,
+                animated: false,
             })
             .unwrap_or_default()
     } else if mime_type.starts_with("video/") {
@@ -33,10 +38,14 @@ pub fn generate_metadata(f: &NamedTempFile, mime_type: &str) -> Metadata {
             .map(|(width, height)| Metadata::Video {
                 width: width as isize,
                 height: height as isize,
+                placeholder: None,
             })
             .unwrap_or_default()
     } else if mime_type.starts_with("audio/") {
-        Metadata::Audio
+        Metadata::Audio {
+            duration: audio_duration(f).map(|duration| duration as isize),
+            waveform: None,
+        }
     } else if mime_type == "plain/text" {
         Metadata::Text
     } else {
@@ -74,3 +83,30 @@ pub fn validate_from_metadata(
 
     metadata
 }
+
+/// Compute a placeholder for image and video files from their processed data
+///
+/// Images are also checked for animation here.
+pub fn generate_placeholder(buf: &[u8], metadata: Metadata, mime_type: &str) -> Metadata {
+    match metadata {
+        Metadata::Image { width, height, .. } => Metadata::Image {
+            width,
+            height,
+            placeholder: decode_image(&mut Cursor::new(buf), mime_type)
+                .ok()
+                .and_then(|image| create_placeholder(&image)),
+            animated: is_animated(buf, mime_type),
+        },
+        Metadata::Video { width, height, .. } => Metadata::Video {
+            width,
+            height,
+            placeholder: NamedTempFile::new()
+                .and_then(|mut file| file.write_all(buf).map(|_| file))
+                .inspect_err(|err| tracing::error!("Failed to write video to disk! {err:?}"))
+                .ok()
+                .and_then(|file| video_poster(&file))
+                .and_then(|image| create_placeholder(&image)),
+        },
+        metadata => metadata,
+    }
+}
@@ -0,0 +1,164 @@
+use std::io::Read;
+
+use revolt_config::report_internal_error;
+use revolt_database::Metadata;
+use revolt_files::audio_duration;
+use revolt_result::{create_error, Result};
+use tempfile::NamedTempFile;
+use tokio::process::Command;
+
+/// Maximum number of samples kept in a voice message waveform
+pub const WAVEFORM_SAMPLES: usize = 64;
+
+/// Normalise a voice message to mono Opus (in an OGG container) and extract its duration and waveform
+pub async fn process_voice_message(file: &NamedTempFile) -> Result<(Vec<u8>, Metadata)> {
+    // Temporary output file
+    let mut out_file = report_internal_error!(NamedTempFile::new())?;
+    let out_path = out_file
+        .path()
+        .to_str()
+        .ok_or(create_error!(InternalError))?;
+
+    // Transcode the file with ffmpeg
+    let output = report_internal_error!(
+        Command::new("ffmpeg")
+            // Never wait for input on stdin
+            .arg("-nostdin")
+            .args([
+                // Overwrite the temporary file
+                "-y",
+                // Read original uploaded file
+                "-i",
+                file.path().to_str().ok_or(create_error!(InternalError))?,
+                // Strip any metadata and video streams
+                "-map_metadata",
+                "-1",
+                "-vn",
+                // Downmix to mono Opus suitable for speech
+                "-ac",
+                "1",
+                "-c:a",
+                "libopus",
+                "-b:a",
+                "32k",
+                "-application",
+                "voip",
+                // Save as OGG to new temporary file
+                "-f",
+                "ogg",
+                out_path,
+            ])
+            .output()
+            .await
+    )?;
+
+    // ffmpeg could not find any audio to transcode
+    if !output.status.success() {
+        return Err(create_error!(FileTypeNotAllowed));
+    }
+
+    // Decode the normalised audio to raw samples
+    let output = report_internal_error!(
+        Command::new("ffmpeg")
+            // Never wait for input on stdin
+            .arg("-nostdin")
+            // Read normalised file
+            .args(["-i", out_path])
+            // Decode to 8 kHz signed 16-bit PCM
+            .args(["-ac", "1", "-ar", "8000", "-f", "s16le"])
+            // Write to stdout
+            .arg("-")
+            .output()
+            .await
+    )?;
+
+    // Don't build a waveform from partially decoded audio
+    if !output.status.success() {
+        return Err(create_error!(FileTypeNotAllowed));
+    }
+
+    let metadata = Metadata::Audio {
+        duration: audio_duration(&out_file).map(|duration| duration as isize),
+        waveform: Some(downsample_waveform(&output.stdout)),
+    };
+
+    // Read the file from disk
+    let mut buf = Vec::<u8>::new();
+    report_internal_error!(out_file.read_to_end(&mut buf))?;
+
+    Ok((buf, metadata))
+}
+
+/// Reduce raw PCM samples to peak amplitudes scaled between 0 and 255
+fn downsample_waveform(pcm: &[u8]) -> Vec<u8> {
+    let samples: Vec<u16> = pcm
+        .chunks_exact(2)
+        .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]).unsigned_abs())
+        .collect();
+
+    if samples.is_empty() {
+        return vec![];
+    }
+
+    let peaks: Vec<u16> = samples
+        .chunks(samples.len().div_ceil(WAVEFORM_SAMPLES))
+        .map(|chunk| chunk.iter().copied().max().unwrap_or_default())
+        .collect();
+
+    let loudest = peaks.iter().copied().max().unwrap_or_default().max(1) as u32;
+    peaks
+        .into_iter()
+        .map(|peak| (peak as u32 * 255 / loudest) as u8)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{downsample_waveform, WAVEFORM_SAMPLES};
+
+    fn pcm(samples: &[i16]) -> Vec<u8> {
+        samples
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn empty_waveform() {
+        assert!(downsample_waveform(&[]).is_empty());
+
+        // A trailing half sample is ignored
+        assert!(downsample_waveform(&[1]).is_empty());
+    }
+
+    #[test]
+    fn short_waveform() {
+        // Fewer samples than the waveform holds are kept one to one
+        assert_eq!(
+            downsample_waveform(&pcm(&[100, -200, 50])),
+            vec![127, 255, 63]
+        );
+
+        // Silence does not divide by zero
+        assert_eq!(downsample_waveform(&pcm(&[0, 0])), vec![0, 0]);
+    }
+
+    #[test]
+    fn long_waveform() {
+        // Each peak is the loudest sample in its chunk
+        let samples: Vec<i16> = (0..WAVEFORM_SAMPLES as i16)
+            .flat_map(|chunk| (0..10).map(move |i| if i == 5 { -chunk * 100 } else { 0 }))
+            .collect();
+
+        let waveform = downsample_waveform(&pcm(&samples));
+        assert_eq!(waveform.len(), WAVEFORM_SAMPLES);
+        assert_eq!(waveform[0], 0);
+        assert_eq!(waveform[WAVEFORM_SAMPLES - 1], 255);
+        assert!(waveform.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // Lengths that don't divide evenly never exceed the sample count
+        let waveform = downsample_waveform(&pcm(&[i16::MIN; 1000]));
+        assert!(waveform.len() <= WAVEFORM_SAMPLES);
+        assert!(waveform.iter().all(|&peak| peak == 255));
+    }
+}
@@ -0,0 +1,209 @@
+//! Internal GIPHY API wrapper
+
+use std::{sync::Arc, time::Duration};
+
+use lru_time_cache::LruCache;
+use reqwest::Client;
+use revolt_coalesced::{CoalescionService, CoalescionServiceConfig};
+use serde::de::DeserializeOwned;
+use tokio::sync::RwLock;
+
+pub mod types;
+
+const GIPHY_API_BASE_URL: &str = "https://api.giphy.com/v1/gifs";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GiphyError {
+    HttpError,
+    InvalidResponse,
+}
+
+#[derive(Clone)]
+pub struct Giphy {
+    pub key: Arc<str>,
+    pub client: Client,
+    pub coalescion: CoalescionService<String>,
+    pub cache: Arc<RwLock<LruCache<String, Arc<types::PaginatedMediaResponse>>>>,
+
+    pub categories: Arc<RwLock<LruCache<String, Arc<types::CategoriesResponse>>>>,
+    pub featured: Arc<RwLock<LruCache<String, Arc<types::PaginatedMediaResponse>>>>,
+}
+
+/// Convert a locale such as `en_US` into the language code GIPHY expects
+fn language(locale: &str) -> &str {
+    locale.split(['_', '-']).next().unwrap_or("en")
+}
+
+impl Giphy {
+    pub fn new(key: &str) -> Self {
+        Self {
+            key: Arc::from(key),
+            client: Client::new(),
+            coalescion: CoalescionService::from_config(CoalescionServiceConfig {
+                max_concurrent: Some(100),
+                queue_requests: true,
+                max_queue: None,
+            }),
+
+            // 1 hour, 1k requests
+            cache: Arc::new(RwLock::new(LruCache::with_expiry_duration_and_capacity(
+                Duration::from_secs(60 * 60),
+                1000,
+            ))),
+
+            // 1 day, 1k requests
+            categories: Arc::new(RwLock::new(LruCache::with_expiry_duration_and_capacity(
+                Duration::from_secs(60 * 60 * 24),
+                1000,
+            ))),
+
+            // 1 hour, 1k requests, trending changes throughout the day
+            featured: Arc::new(RwLock::new(LruCache::with_expiry_duration_and_capacity(
+                Duration::from_secs(60 * 60),
+                1000,
+            ))),
+        }
+    }
+
+    pub async fn request<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[Option<(&str, &str)>],
+    ) -> Result<Arc<T>, GiphyError> {
+        let response = self
+            .client
+            .get(format!("{GIPHY_API_BASE_URL}{path}"))
+            .query(query)
+            .send()
+            .await
+            .inspect_err(|e| {
+                revolt_config::capture_error(e);
+            })
+            .map_err(|_| GiphyError::HttpError)?;
+
+        let text = response.text().await.map_err(|e| {
+            revolt_config::capture_error(&e);
+            GiphyError::HttpError
+        })?;
+
+        serde_json::from_str(&text).map(Arc::new).map_err(|e| {
+            revolt_config::capture_error(&e);
+            GiphyError::InvalidResponse
+        })
+    }
+
+    pub async fn search(
+        &self,
+        query: &str,
+        locale: &str,
+        limit: u32,
+        position: &str,
+    ) -> Result<Arc<types::PaginatedMediaResponse>, GiphyError> {
+        let unique_key = format!("s:{query}:{locale}:{limit}:{position}");
+
+        if self.cache.read().await.contains_key(&unique_key) {
+            if let Some(response) = self.cache.write().await.get(&unique_key) {
+                return Ok(response.clone());
+            }
+        }
+
+        let res = self
+            .coalescion
+            .execute(unique_key.clone(), || async move {
+                self.request::<types::PaginatedMediaResponse>(
+                    "/search",
+                    &[
+                        Some(("api_key", &self.key)),
+                        Some(("q", query)),
+                        Some(("lang", language(locale))),
+                        Some(("rating", "g")),
+                        Some(("limit", &limit.to_string())),
+                        (!position.is_empty()).then_some(("offset", position)),
+                    ],
+                )
+                .await
+            })
+            .await
+            .map_err(|_| GiphyError::HttpError)?;
+
+        if let Ok(resp) = &*res {
+            self.cache.write().await.insert(unique_key, resp.clone());
+        }
+
+        (*res).clone()
+    }
+
+    pub async fn categories(
+        &self,
+        locale: &str,
+    ) -> Result<Arc<types::CategoriesResponse>, GiphyError> {
+        let unique_key = format!("c-{locale}");
+
+        if self.categories.read().await.contains_key(&unique_key) {
+            if let Some(response) = self.categories.write().await.get(&unique_key) {
+                return Ok(response.clone());
+            }
+        }
+
+        let res = self
+            .coalescion
+            .execute(unique_key.clone(), || async move {
+                self.request::<types::CategoriesResponse>(
+                    "/categories",
+                    &[
+                        Some(("api_key", &self.key)),
+                        Some(("lang", language(locale))),
+                    ],
+                )
+                .await
+            })
+            .await
+            .map_err(|_| GiphyError::HttpError)?;
+
+        if let Ok(resp) = &*res {
+            self.categories
+                .write()
+                .await
+                .insert(unique_key, resp.clone());
+        }
+
+        (*res).clone()
+    }
+
+    pub async fn trending(
+        &self,
+        limit: u32,
+        position: &str,
+    ) -> Result<Arc<types::PaginatedMediaResponse>, GiphyError> {
+        let unique_key = format!("f-{limit}-{position}");
+
+        if self.featured.read().await.contains_key(&unique_key) {
+            if let Some(response) = self.featured.write().await.get(&unique_key) {
+                return Ok(response.clone());
+            }
+        }
+
+        let res = self
+            .coalescion
+            .execute(unique_key.clone(), || async move {
+                self.request::<types::PaginatedMediaResponse>(
+                    "/trending",
+                    &[
+                        Some(("api_key", &self.key)),
+                        Some(("rating", "g")),
+                        Some(("limit", &limit.to_string())),
+                        (!position.is_empty()).then_some(("offset", position)),
+                    ],
+                )
+                .await
+            })
+            .await
+            .map_err(|_| GiphyError::HttpError)?;
+
+        if let Ok(resp) = &*res {
+            self.featured.write().await.insert(unique_key, resp.clone());
+        }
+
+        (*res).clone()
+    }
+}
@@ -0,0 +1,83 @@
+//! GIPHY API models
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PaginatedMediaResponse {
+    pub data: Vec<MediaResponse>,
+    pub pagination: Pagination,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Pagination {
+    #[serde(default)]
+    pub total_count: u64,
+    pub count: u64,
+    pub offset: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MediaResponse {
+    pub id: String,
+    pub url: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub rating: String,
+    pub images: HashMap<String, ImageObject>,
+}
+
+/// A single rendition of a gif, GIPHY sends all numbers as strings
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ImageObject {
+    pub url: Option<String>,
+    pub width: Option<String>,
+    pub height: Option<String>,
+    pub mp4: Option<String>,
+    pub webp: Option<String>,
+}
+
+/// File format within a rendition
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rendition {
+    Gif,
+    Mp4,
+    Webp,
+}
+
+impl ImageObject {
+    /// Convert a format of this rendition into a normalised media object
+    pub fn as_media_object(&self, rendition: Rendition) -> Option<types::MediaObject> {
+        let url = match rendition {
+            Rendition::Gif => self.url.as_ref(),
+            Rendition::Mp4 => self.mp4.as_ref(),
+            Rendition::Webp => self.webp.as_ref(),
+        }?;
+
+        let dimensions = [&self.width, &self.height]
+            .into_iter()
+            .filter_map(|value| value.as_deref().and_then(|value| value.parse().ok()))
+            .collect();
+
+        Some(types::MediaObject {
+            url: url.clone(),
+            dimensions,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CategoriesResponse {
+    pub data: Vec<CategoryResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CategoryResponse {
+    pub name: String,
+    pub name_encoded: String,
+    pub gif: Option<MediaResponse>,
+}
@@ -12,8 +12,10 @@ use utoipa::{
 };
 use utoipa_scalar::{Scalar, Servable as ScalarServable};
 
-use crate::tenor::Tenor;
+use crate::providers::Providers;
 
+mod giphy;
+mod providers;
 mod ratelimits;
 mod routes;
 mod tenor;
@@ -22,7 +24,7 @@ mod types;
 #[derive(Clone, FromRef)]
 struct AppState {
     pub database: Database,
-    pub tenor: Tenor,
+    pub providers: Providers,
     pub ratelimit_storage: ratelimiter::RatelimitStorage,
 }
 
@@ -63,7 +65,7 @@ async fn main() -> Result<(), std::io::Error> {
         ),
         tags(
             (name = "Misc", description = "Misc routes for microservice."),
-            (name = "GIFs", description = "All routes for requesting GIFs from tenor.")
+            (name = "GIFs", description = "All routes for requesting GIFs from the configured providers.")
         ),
         components(
             schemas(
@@ -83,13 +85,13 @@ async fn main() -> Result<(), std::io::Error> {
         .await
         .expect("Unable to connect to database");
 
-    let tenor = tenor::Tenor::new(&config.api.security.tenor_key);
+    let providers = Providers::from_config(&config);
 
     let ratelimit_storage = ratelimiter::RatelimitStorage::new(ratelimits::GifboxRatelimits);
 
     let state = AppState {
         database,
-        tenor,
+        providers,
         ratelimit_storage,
     };
 
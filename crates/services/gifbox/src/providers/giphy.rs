@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use revolt_result::{create_error, Result};
+
+use crate::{giphy::Giphy, types};
+
+use super::GifProvider;
+
+#[async_trait]
+impl GifProvider for Giphy {
+    fn name(&self) -> &'static str {
+        "giphy"
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        locale: &str,
+        limit: u32,
+        _is_category: bool,
+        position: &str,
+    ) -> Result<types::PaginatedMediaResponse> {
+        Giphy::search(self, query, locale, limit, position)
+            .await
+            .map_err(|_| create_error!(InternalError))
+            .map(|results| results.as_ref().clone().into())
+    }
+
+    async fn featured(
+        &self,
+        _locale: &str,
+        limit: u32,
+        position: &str,
+    ) -> Result<types::PaginatedMediaResponse> {
+        // GIPHY trending is global
+        Giphy::trending(self, limit, position)
+            .await
+            .map_err(|_| create_error!(InternalError))
+            .map(|results| results.as_ref().clone().into())
+    }
+
+    async fn categories(&self, locale: &str) -> Result<Vec<types::CategoryResponse>> {
+        Giphy::categories(self, locale)
+            .await
+            .map_err(|_| create_error!(InternalError))
+            .map(|results| {
+                (*results)
+                    .clone()
+                    .data
+                    .into_iter()
+                    .map(|cat| cat.into())
+                    .collect()
+            })
+    }
+}
@@ -0,0 +1,101 @@
+//! Local provider for tests
+
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use async_trait::async_trait;
+use revolt_result::{create_error, Result};
+
+use crate::types;
+
+use super::GifProvider;
+
+/// Provider serving two pages of placeholder GIFs, or failing every request
+pub struct MockProvider {
+    pub name: &'static str,
+    pub fail: bool,
+    pub requests: AtomicUsize,
+}
+
+impl MockProvider {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            fail: false,
+            requests: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn failing(name: &'static str) -> Self {
+        Self {
+            fail: true,
+            ..Self::new(name)
+        }
+    }
+
+    fn page(&self, id: &str, position: &str) -> Result<types::PaginatedMediaResponse> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        if self.fail {
+            return Err(create_error!(InternalError));
+        }
+
+        let page = if position.is_empty() { "0" } else { position };
+
+        Ok(types::PaginatedMediaResponse {
+            results: vec![types::MediaResult {
+                id: format!("{id}-{page}"),
+                provider: self.name.to_owned(),
+                media_formats: HashMap::from([(
+                    "gif".to_owned(),
+                    types::MediaObject {
+                        url: format!("https://example.com/{}/{id}.gif", self.name),
+                        dimensions: vec![100, 100],
+                    },
+                )]),
+                url: format!("https://example.com/{}/{id}", self.name),
+            }],
+            next: (page == "0").then(|| "1".to_owned()),
+        })
+    }
+}
+
+#[async_trait]
+impl GifProvider for MockProvider {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        _locale: &str,
+        _limit: u32,
+        _is_category: bool,
+        position: &str,
+    ) -> Result<types::PaginatedMediaResponse> {
+        self.page(query, position)
+    }
+
+    async fn featured(
+        &self,
+        _locale: &str,
+        _limit: u32,
+        position: &str,
+    ) -> Result<types::PaginatedMediaResponse> {
+        self.page("featured", position)
+    }
+
+    async fn categories(&self, _locale: &str) -> Result<Vec<types::CategoryResponse>> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        if self.fail {
+            return Err(create_error!(InternalError));
+        }
+
+        Ok(vec![types::CategoryResponse {
+            title: format!("{} category", self.name),
+            image: format!("https://example.com/{}/category.gif", self.name),
+        }])
+    }
+}
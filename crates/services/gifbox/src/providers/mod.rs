@@ -0,0 +1,375 @@
+//! GIF providers results can be served from
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use revolt_config::Settings;
+use revolt_result::{create_error, Result};
+
+use crate::{giphy::Giphy, tenor::Tenor, types};
+
+mod giphy;
+#[cfg(test)]
+pub mod mock;
+mod tenor;
+
+/// Source of GIFs
+#[async_trait]
+pub trait GifProvider: Send + Sync {
+    /// Name of this provider, used to tag results and pagination positions
+    fn name(&self) -> &'static str;
+
+    /// Search for GIFs with a query
+    async fn search(
+        &self,
+        query: &str,
+        locale: &str,
+        limit: u32,
+        is_category: bool,
+        position: &str,
+    ) -> Result<types::PaginatedMediaResponse>;
+
+    /// Fetch currently trending GIFs
+    async fn featured(
+        &self,
+        locale: &str,
+        limit: u32,
+        position: &str,
+    ) -> Result<types::PaginatedMediaResponse>;
+
+    /// Fetch GIF categories
+    async fn categories(&self, locale: &str) -> Result<Vec<types::CategoryResponse>>;
+}
+
+/// 64-bit FNV-1a hash of a key
+///
+/// Unlike the standard library hashers, this is stable across Rust versions
+/// and processes, so the split between providers doesn't move around.
+fn fnv1a(key: &str) -> u64 {
+    key.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Weighted set of GIF providers
+#[derive(Clone)]
+pub struct Providers {
+    /// Enabled providers with their weights, heaviest first
+    providers: Arc<Vec<(Arc<dyn GifProvider>, u32)>>,
+}
+
+impl Providers {
+    /// Create a new set of providers, discarding any with no weight
+    pub fn new(mut providers: Vec<(Arc<dyn GifProvider>, u32)>) -> Self {
+        providers.retain(|(_, weight)| *weight > 0);
+        providers.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        Self {
+            providers: Arc::new(providers),
+        }
+    }
+
+    /// Create the providers enabled in configuration
+    pub fn from_config(config: &Settings) -> Self {
+        let mut providers: Vec<(Arc<dyn GifProvider>, u32)> = vec![];
+
+        for (name, weight) in &config.gifbox.providers {
+            match name.as_str() {
+                "tenor" => providers.push((
+                    Arc::new(Tenor::new(&config.api.security.tenor_key)),
+                    *weight,
+                )),
+                "giphy" => providers.push((
+                    Arc::new(Giphy::new(&config.api.security.giphy_key)),
+                    *weight,
+                )),
+                _ => tracing::warn!("Unknown GIF provider `{name}` in configuration"),
+            }
+        }
+
+        Self::new(providers)
+    }
+
+    /// Order in which providers should be tried for a given key
+    ///
+    /// The first provider is picked in proportion to its weight, the rest are
+    /// fallbacks. Picking by key rather than at random keeps results for the
+    /// same request consistent and cacheable.
+    fn order(&self, key: &str) -> Vec<&Arc<dyn GifProvider>> {
+        let total: u64 = self
+            .providers
+            .iter()
+            .map(|(_, weight)| *weight as u64)
+            .sum();
+
+        let mut providers: Vec<&Arc<dyn GifProvider>> = self
+            .providers
+            .iter()
+            .map(|(provider, _)| provider)
+            .collect();
+
+        if total > 0 {
+            let mut point = fnv1a(key) % total;
+            for (index, (_, weight)) in self.providers.iter().enumerate() {
+                if point < *weight as u64 {
+                    let primary = providers.remove(index);
+                    providers.insert(0, primary);
+                    break;
+                }
+
+                point -= *weight as u64;
+            }
+        }
+
+        providers
+    }
+
+    /// Find the provider a pagination position was handed out by
+    fn provider_for_position<'a>(
+        &self,
+        position: &'a str,
+    ) -> Option<(&Arc<dyn GifProvider>, &'a str)> {
+        let (name, position) = position.split_once(':')?;
+
+        self.providers
+            .iter()
+            .find(|(provider, _)| provider.name() == name)
+            .map(|(provider, _)| (provider, position))
+    }
+
+    /// Run a paginated request, continuing with the provider that handed out
+    /// the position or otherwise trying each provider in turn
+    async fn paginated<'a, F, Fut>(
+        &'a self,
+        key: &str,
+        position: &str,
+        request: F,
+    ) -> Result<types::PaginatedMediaResponse>
+    where
+        F: Fn(&'a Arc<dyn GifProvider>, String) -> Fut,
+        Fut: std::future::Future<Output = Result<types::PaginatedMediaResponse>>,
+    {
+        let tag = |provider: &Arc<dyn GifProvider>, mut response: types::PaginatedMediaResponse| {
+            response.next = response
+                .next
+                .map(|next| format!("{}:{next}", provider.name()));
+
+            response
+        };
+
+        if !position.is_empty() {
+            let (provider, position) = self
+                .provider_for_position(position)
+                .ok_or_else(|| create_error!(InvalidOperation))?;
+
+            return request(provider, position.to_owned())
+                .await
+                .map(|response| tag(provider, response));
+        }
+
+        let mut error = create_error!(InternalError);
+        for provider in self.order(key) {
+            match request(provider, String::new()).await {
+                Ok(response) => return Ok(tag(provider, response)),
+                Err(err) => error = err,
+            }
+        }
+
+        Err(error)
+    }
+
+    /// Search for GIFs with a query
+    pub async fn search(
+        &self,
+        query: &str,
+        locale: &str,
+        limit: u32,
+        is_category: bool,
+        position: &str,
+    ) -> Result<types::PaginatedMediaResponse> {
+        // Categories are searched with the provider they were listed from
+        let key = if is_category {
+            locale.to_owned()
+        } else {
+            format!("{query}:{locale}")
+        };
+
+        self.paginated(&key, position, |provider, position| async move {
+            provider
+                .search(query, locale, limit, is_category, &position)
+                .await
+        })
+        .await
+    }
+
+    /// Fetch currently trending GIFs
+    pub async fn featured(
+        &self,
+        locale: &str,
+        limit: u32,
+        position: &str,
+    ) -> Result<types::PaginatedMediaResponse> {
+        self.paginated(locale, position, |provider, position| async move {
+            provider.featured(locale, limit, &position).await
+        })
+        .await
+    }
+
+    /// Fetch GIF categories
+    pub async fn categories(&self, locale: &str) -> Result<Vec<types::CategoryResponse>> {
+        let mut error = create_error!(InternalError);
+        for provider in self.order(locale) {
+            match provider.categories(locale).await {
+                Ok(categories) => return Ok(categories),
+                Err(err) => error = err,
+            }
+        }
+
+        Err(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{atomic::Ordering, Arc},
+    };
+
+    use revolt_result::ErrorType;
+
+    use super::{fnv1a, mock::MockProvider, GifProvider, Providers};
+
+    fn registry(list: Vec<(Arc<MockProvider>, u32)>) -> Providers {
+        Providers::new(
+            list.into_iter()
+                .map(|(provider, weight)| (provider as Arc<dyn GifProvider>, weight))
+                .collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn spreads_requests_by_weight() {
+        let heavy = Arc::new(MockProvider::new("heavy"));
+        let light = Arc::new(MockProvider::new("light"));
+        let providers = registry(vec![(heavy.clone(), 3), (light.clone(), 1)]);
+
+        let mut served = HashMap::<String, usize>::new();
+        for i in 0..400 {
+            let response = providers
+                .search(&format!("query {i}"), "en_US", 1, false, "")
+                .await
+                .unwrap();
+
+            *served
+                .entry(response.results[0].provider.clone())
+                .or_default() += 1;
+        }
+
+        let heavy_count = served.get("heavy").copied().unwrap_or_default();
+        let light_count = served.get("light").copied().unwrap_or_default();
+
+        assert_eq!(heavy_count + light_count, 400);
+        assert!(heavy_count > light_count * 2);
+        assert!(light_count > 0);
+    }
+
+    #[test]
+    fn stable_hash() {
+        // Reference values for 64-bit FNV-1a
+        assert_eq!(fnv1a(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a("foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[tokio::test]
+    async fn same_query_same_provider() {
+        let providers = registry(vec![
+            (Arc::new(MockProvider::new("a")), 1),
+            (Arc::new(MockProvider::new("b")), 1),
+        ]);
+
+        let first = providers
+            .search("wave", "en_US", 1, false, "")
+            .await
+            .unwrap();
+        let second = providers
+            .search("wave", "en_US", 1, false, "")
+            .await
+            .unwrap();
+
+        assert_eq!(first, second);
+    }
+
+    #[tokio::test]
+    async fn ignores_zero_weight() {
+        let enabled = Arc::new(MockProvider::new("enabled"));
+        let disabled = Arc::new(MockProvider::new("disabled"));
+        let providers = registry(vec![(enabled.clone(), 1), (disabled.clone(), 0)]);
+
+        for i in 0..20 {
+            providers
+                .featured(&format!("locale {i}"), 1, "")
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(enabled.requests.load(Ordering::SeqCst), 20);
+        assert_eq!(disabled.requests.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn routes_pagination_to_provider() {
+        let a = Arc::new(MockProvider::new("a"));
+        let b = Arc::new(MockProvider::new("b"));
+        let providers = registry(vec![(a.clone(), 1), (b.clone(), 1)]);
+
+        let first = providers
+            .search("wave", "en_US", 1, false, "")
+            .await
+            .unwrap();
+        let provider = first.results[0].provider.clone();
+        let next = first.next.expect("next page");
+        assert_eq!(next, format!("{provider}:1"));
+
+        let second = providers
+            .search("wave", "en_US", 1, false, &next)
+            .await
+            .unwrap();
+
+        assert_eq!(second.results[0].provider, provider);
+        assert_eq!(second.results[0].id, "wave-1");
+        assert!(second.next.is_none());
+
+        // Positions from unknown providers are rejected
+        let error = providers
+            .search("wave", "en_US", 1, false, "unknown:1")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error.error_type, ErrorType::InvalidOperation));
+    }
+
+    #[tokio::test]
+    async fn falls_back_on_failure() {
+        let broken = Arc::new(MockProvider::failing("broken"));
+        let working = Arc::new(MockProvider::new("working"));
+        let providers = registry(vec![(broken.clone(), 100), (working.clone(), 1)]);
+
+        for i in 0..10 {
+            let response = providers
+                .search(&format!("query {i}"), "en_US", 1, false, "")
+                .await
+                .unwrap();
+
+            assert_eq!(response.results[0].provider, "working");
+        }
+
+        let categories = providers.categories("en_US").await.unwrap();
+        assert_eq!(categories[0].title, "working category");
+
+        // Everything failing surfaces the error
+        let providers = registry(vec![(broken, 1)]);
+        assert!(providers.featured("en_US", 1, "").await.is_err());
+    }
+}
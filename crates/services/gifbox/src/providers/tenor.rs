@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use revolt_result::{create_error, Result};
+
+use crate::{tenor::Tenor, types};
+
+use super::GifProvider;
+
+#[async_trait]
+impl GifProvider for Tenor {
+    fn name(&self) -> &'static str {
+        "tenor"
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        locale: &str,
+        limit: u32,
+        is_category: bool,
+        position: &str,
+    ) -> Result<types::PaginatedMediaResponse> {
+        Tenor::search(self, query, locale, limit, is_category, position)
+            .await
+            .map_err(|_| create_error!(InternalError))
+            .map(|results| results.as_ref().clone().into())
+    }
+
+    async fn featured(
+        &self,
+        locale: &str,
+        limit: u32,
+        position: &str,
+    ) -> Result<types::PaginatedMediaResponse> {
+        Tenor::featured(self, locale, limit, position)
+            .await
+            .map_err(|_| create_error!(InternalError))
+            .map(|results| results.as_ref().clone().into())
+    }
+
+    async fn categories(&self, locale: &str) -> Result<Vec<types::CategoryResponse>> {
+        Tenor::categories(self, locale)
+            .await
+            .map_err(|_| create_error!(InternalError))
+            .map(|results| {
+                (*results)
+                    .clone()
+                    .tags
+                    .into_iter()
+                    .map(|cat| cat.into())
+                    .collect()
+            })
+    }
+}
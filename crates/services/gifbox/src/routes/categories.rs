@@ -3,11 +3,11 @@ use axum::{
     Json,
 };
 use revolt_database::User;
-use revolt_result::{create_error, Result};
+use revolt_result::Result;
 use serde::Deserialize;
 use utoipa::IntoParams;
 
-use crate::{tenor, types};
+use crate::{providers::Providers, types};
 
 #[derive(Deserialize, IntoParams)]
 pub struct CategoriesQueryParams {
@@ -30,19 +30,7 @@ pub struct CategoriesQueryParams {
 pub async fn categories(
     _user: User,
     Query(params): Query<CategoriesQueryParams>,
-    State(tenor): State<tenor::Tenor>,
+    State(providers): State<Providers>,
 ) -> Result<Json<Vec<types::CategoryResponse>>> {
-    tenor
-        .categories(&params.locale)
-        .await
-        .map_err(|_| create_error!(InternalError))
-        .map(|results| {
-            (*results)
-                .clone()
-                .tags
-                .into_iter()
-                .map(|cat| cat.into())
-                .collect()
-        })
-        .map(Json)
+    providers.categories(&params.locale).await.map(Json)
 }
@@ -3,11 +3,11 @@ use axum::{
     Json,
 };
 use revolt_database::User;
-use revolt_result::{create_error, Result};
+use revolt_result::Result;
 use serde::Deserialize;
 use utoipa::IntoParams;
 
-use crate::{tenor, types};
+use crate::{providers::Providers, types};
 
 #[derive(Deserialize, IntoParams)]
 pub struct SearchQueryParams {
@@ -39,9 +39,9 @@ pub struct SearchQueryParams {
 pub async fn search(
     _user: User,
     Query(params): Query<SearchQueryParams>,
-    State(tenor): State<tenor::Tenor>,
+    State(providers): State<Providers>,
 ) -> Result<Json<types::PaginatedMediaResponse>> {
-    tenor
+    providers
         .search(
             &params.query,
             &params.locale,
@@ -50,7 +50,5 @@ pub async fn search(
             params.position.as_deref().unwrap_or_default(),
         )
         .await
-        .map_err(|_| create_error!(InternalError))
-        .map(|results| results.as_ref().clone().into())
         .map(Json)
 }
@@ -3,11 +3,11 @@ use axum::{
     Json,
 };
 use revolt_database::User;
-use revolt_result::{create_error, Result};
+use revolt_result::Result;
 use serde::Deserialize;
 use utoipa::IntoParams;
 
-use crate::{tenor, types};
+use crate::{providers::Providers, types};
 
 #[derive(Deserialize, IntoParams)]
 pub struct TrendingQueryParams {
@@ -34,16 +34,14 @@ pub struct TrendingQueryParams {
 pub async fn trending(
     _user: User,
     Query(params): Query<TrendingQueryParams>,
-    State(tenor): State<tenor::Tenor>,
+    State(providers): State<Providers>,
 ) -> Result<Json<types::PaginatedMediaResponse>> {
-    tenor
+    providers
         .featured(
             &params.locale,
             params.limit.unwrap_or(50),
             params.position.as_deref().unwrap_or_default(),
         )
         .await
-        .map_err(|_| create_error!(InternalError))
-        .map(|results| results.as_ref().clone().into())
         .map(Json)
 }
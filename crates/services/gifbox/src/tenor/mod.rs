@@ -15,6 +15,7 @@ const TENOR_API_BASE_URL: &str = "https://tenor.googleapis.com/v2";
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum TenorError {
     HttpError,
+    InvalidResponse,
 }
 
 #[derive(Clone)]
@@ -76,7 +77,10 @@ impl Tenor {
             TenorError::HttpError
         })?;
 
-        Ok(Arc::new(serde_json::from_str(&text).unwrap()))
+        serde_json::from_str(&text).map(Arc::new).map_err(|e| {
+            revolt_config::capture_error(&e);
+            TenorError::InvalidResponse
+        })
     }
 
     pub async fn search(
@@ -87,7 +91,7 @@ impl Tenor {
         is_category: bool,
         position: &str,
     ) -> Result<Arc<types::PaginatedMediaResponse>, TenorError> {
-        let unique_key = format!("s:{query}:{locale}:{is_category}:{position}");
+        let unique_key = format!("s:{query}:{locale}:{limit}:{is_category}:{position}");
 
         if self.cache.read().await.contains_key(&unique_key) {
             if let Some(response) = self.cache.write().await.get(&unique_key) {
@@ -106,13 +110,13 @@ impl Tenor {
                     Some(("locale", locale)),
                     Some(("contentfilter", "high")),
                     Some(("limit", &limit.to_string())),
-                    position.is_empty().then_some(("pos", position)),
+                    (!position.is_empty()).then_some(("pos", position)),
                     is_category.then_some(("component", "categories"))
                 ]
             ).await
         })
         .await
-        .unwrap();
+        .map_err(|_| TenorError::HttpError)?;
 
         if let Ok(resp) = &*res {
             self.cache.write().await.insert(unique_key, resp.clone());
@@ -147,7 +151,7 @@ impl Tenor {
                 ).await
             })
             .await
-            .unwrap();
+            .map_err(|_| TenorError::HttpError)?;
 
         if let Ok(resp) = &*res {
             self.categories
@@ -167,7 +171,7 @@ impl Tenor {
     ) -> Result<Arc<types::PaginatedMediaResponse>, TenorError> {
         let unique_key = format!("f-{locale}-{limit}-{position}");
 
-        if self.categories.read().await.contains_key(&unique_key) {
+        if self.featured.read().await.contains_key(&unique_key) {
             if let Some(response) = self.featured.write().await.get(&unique_key) {
                 return Ok(response.clone());
             }
@@ -183,12 +187,12 @@ impl Tenor {
                     Some(("locale", locale)),
                     Some(("contentfilter", "high")),
                     Some(("limit", &limit.to_string())),
-                    position.is_empty().then_some(("pos", position)),
+                    (!position.is_empty()).then_some(("pos", position)),
                 ]
             ).await
         })
         .await
-        .unwrap();
+        .map_err(|_| TenorError::HttpError)?;
 
         if let Ok(resp) = &*res {
             self.featured.write().await.insert(unique_key, resp.clone());
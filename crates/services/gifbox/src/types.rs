@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::tenor::types;
+use crate::{giphy::types as giphy, tenor::types};
 
 /// Successful root response
 #[derive(Serialize, Debug, ToSchema)]
@@ -25,11 +25,19 @@ pub struct PaginatedMediaResponse {
 /// Indivual gif result.
 #[derive(Serialize, Deserialize, ToSchema, Clone, Debug, PartialEq)]
 pub struct MediaResult {
-    /// Unique Tenor id.
+    /// Id of the gif, unique within its provider.
     pub id: String,
+    /// Name of the provider the gif is from.
+    pub provider: String,
     /// Mapping of each file format and url of the file.
+    ///
+    /// Formats are named after Tenor's (`gif`, `tinygif`, `mp4`, `webm`, ...),
+    /// not every provider offers every format.
+    ///
+    /// GIPHY does not offer WebM, so its results have no `webm` or `tinywebm`
+    /// formats, clients should fall back to `mp4` and `tinymp4` instead.
     pub media_formats: HashMap<String, MediaObject>,
-    /// Public Tenor web url for the gif.
+    /// Public web url for the gif.
     pub url: String,
 }
 
@@ -72,6 +80,7 @@ impl From<types::MediaResponse> for MediaResult {
     fn from(value: types::MediaResponse) -> Self {
         Self {
             id: value.id,
+            provider: "tenor".to_owned(),
             media_formats: value
                 .media_formats
                 .into_iter()
@@ -99,3 +108,62 @@ impl From<types::CategoryResponse> for CategoryResponse {
         }
     }
 }
+
+impl From<giphy::PaginatedMediaResponse> for PaginatedMediaResponse {
+    fn from(value: giphy::PaginatedMediaResponse) -> Self {
+        let next = value.pagination.offset + value.pagination.count;
+
+        Self {
+            results: value.data.into_iter().map(|result| result.into()).collect(),
+            next: (value.pagination.count > 0 && next < value.pagination.total_count)
+                .then(|| next.to_string()),
+        }
+    }
+}
+
+impl From<giphy::MediaResponse> for MediaResult {
+    fn from(value: giphy::MediaResponse) -> Self {
+        let mut media_formats = HashMap::new();
+
+        // Map renditions onto the equivalent Tenor formats, GIPHY has no WebM renditions
+        for (format, rendition, field) in [
+            ("gif", "original", giphy::Rendition::Gif),
+            ("mp4", "original", giphy::Rendition::Mp4),
+            ("webp", "original", giphy::Rendition::Webp),
+            ("tinygif", "fixed_width_small", giphy::Rendition::Gif),
+            ("tinymp4", "fixed_width", giphy::Rendition::Mp4),
+            ("tinywebp", "fixed_width", giphy::Rendition::Webp),
+        ] {
+            if let Some(object) = value
+                .images
+                .get(rendition)
+                .and_then(|image| image.as_media_object(field))
+            {
+                media_formats.insert(format.to_owned(), object);
+            }
+        }
+
+        Self {
+            id: value.id,
+            provider: "giphy".to_owned(),
+            media_formats,
+            url: value.url,
+        }
+    }
+}
+
+impl From<giphy::CategoryResponse> for CategoryResponse {
+    fn from(value: giphy::CategoryResponse) -> Self {
+        Self {
+            title: value.name,
+            image: value
+                .gif
+                .and_then(|gif| {
+                    gif.images
+                        .get("fixed_width")
+                        .and_then(|image| image.url.clone())
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
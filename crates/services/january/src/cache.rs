@@ -0,0 +1,596 @@
+use std::{
+    future::Future,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use moka::future::Cache;
+use redis_kiss::{get_connection, AsyncCommands};
+use reqwest::header::{HeaderMap, AGE, CACHE_CONTROL, ETAG};
+use revolt_config::{config, JanuaryCache};
+use revolt_files::{
+    delete_from_s3, fetch_variant_from_s3, list_variants_in_s3, upload_variant_to_s3,
+};
+use revolt_models::v0::Embed;
+use revolt_result::{create_error, Error, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::OnceCell;
+
+/// In-memory caches local to this instance
+struct MemoryCaches {
+    /// Cache for proxy results
+    proxy: Cache<String, Result<(String, Vec<u8>)>>,
+    /// Cache for embed results
+    embed: Cache<String, Embed>,
+}
+
+static MEMORY: OnceCell<MemoryCaches> = OnceCell::const_new();
+
+/// Get the in-memory caches, building them from configuration on first use
+async fn memory() -> &'static MemoryCaches {
+    MEMORY
+        .get_or_init(|| async {
+            let config = config().await.january.cache;
+
+            MemoryCaches {
+                proxy: Cache::builder()
+                    .weigher(|_key, value: &Result<(String, Vec<u8>)>| -> u32 {
+                        std::mem::size_of::<Result<(String, Vec<u8>)>>() as u32
+                            + if let Ok((url, vec)) = value {
+                                url.len().try_into().unwrap_or(u32::MAX)
+                                    + vec.len().try_into().unwrap_or(u32::MAX)
+                            } else {
+                                std::mem::size_of::<Error>() as u32
+                            }
+                    })
+                    .max_capacity(config.proxy_capacity)
+                    .time_to_live(Duration::from_secs(config.memory_ttl))
+                    .build(),
+                embed: Cache::builder()
+                    .max_capacity(config.embed_capacity)
+                    .time_to_live(Duration::from_secs(config.memory_ttl))
+                    .build(),
+            }
+        })
+        .await
+}
+
+/// How long upstream allows a response to be reused for
+#[derive(Debug, Default, Clone)]
+pub struct Freshness {
+    /// Lifetime requested through Cache-Control
+    pub max_age: Option<u64>,
+    /// Validator used to revalidate the response
+    pub etag: Option<String>,
+    /// Whether the response may be stored in a shared cache
+    pub shareable: bool,
+}
+
+impl Freshness {
+    /// Read freshness information from response headers
+    pub fn from_headers(headers: &HeaderMap) -> Freshness {
+        let mut max_age = None;
+        let mut s_maxage = None;
+        let mut shareable = true;
+
+        if let Some(cache_control) = headers
+            .get(CACHE_CONTROL)
+            .and_then(|value| value.to_str().ok())
+        {
+            for directive in cache_control.split(',') {
+                let directive = directive.trim().to_ascii_lowercase();
+                match directive.split_once('=') {
+                    Some(("max-age", value)) => max_age = value.trim_matches('"').parse().ok(),
+                    Some(("s-maxage", value)) => s_maxage = value.trim_matches('"').parse().ok(),
+                    _ => match directive.as_str() {
+                        "no-store" | "private" => shareable = false,
+                        "no-cache" => s_maxage = Some(0),
+                        _ => {}
+                    },
+                }
+            }
+        }
+
+        // Time already spent in upstream caches counts towards the lifetime
+        let age: u64 = headers
+            .get(AGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or_default();
+
+        Freshness {
+            max_age: s_maxage
+                .or(max_age)
+                .map(|max_age: u64| max_age.saturating_sub(age)),
+            etag: headers
+                .get(ETAG)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned),
+            shareable,
+        }
+    }
+
+    /// Work out how long to keep the response in the shared cache
+    fn ttl(&self, config: &JanuaryCache) -> u64 {
+        self.max_age
+            .unwrap_or(config.default_ttl)
+            .clamp(config.min_ttl, config.max_ttl)
+    }
+
+    /// Work out how long to retain the response, allowing time to revalidate it
+    fn retain(&self, config: &JanuaryCache) -> u64 {
+        if self.etag.is_some() {
+            self.ttl(config) + config.max_ttl
+        } else {
+            self.ttl(config)
+        }
+    }
+}
+
+/// Result of fetching a resource which may already be cached
+pub enum Fetched<T> {
+    /// Fresh copy of the resource
+    Modified(T, Freshness),
+    /// Cached copy is still valid
+    Unchanged(Freshness),
+}
+
+/// Entry in the shared cache
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    /// Cached value
+    value: T,
+    /// Unix timestamp after which the entry must be revalidated
+    expires_at: u64,
+    /// Validator to revalidate the entry with
+    etag: Option<String>,
+}
+
+/// Record of consecutive failures to fetch a resource
+#[derive(Serialize, Deserialize)]
+struct Failure {
+    /// Number of consecutive failures
+    attempts: u32,
+    /// Unix timestamp before which the resource should not be requested again
+    retry_at: u64,
+    /// Error to respond with in the meantime
+    error: Error,
+}
+
+/// Current unix timestamp in seconds
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// Shared cache key for a given kind of resource and URL
+fn key(kind: &str, url: &str) -> String {
+    format!("january:{kind}:{:x}", Sha256::digest(url.as_bytes()))
+}
+
+/// Read a JSON value from Redis
+async fn get_json<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let mut conn = get_connection().await.ok()?;
+    let value: Option<String> = conn.get(key).await.ok()?;
+    value.and_then(|value| serde_json::from_str(&value).ok())
+}
+
+/// Write a JSON value to Redis
+async fn set_json<T: Serialize>(key: &str, value: &T, ttl: u64) {
+    if let (Ok(mut conn), Ok(value)) = (get_connection().await, serde_json::to_string(value)) {
+        let _: Option<()> = conn.set_ex(key, value, ttl as usize).await.ok();
+    }
+}
+
+/// Delete a key from Redis
+async fn delete(key: &str) {
+    if let Ok(mut conn) = get_connection().await {
+        let _: Option<()> = conn.del(key).await.ok();
+    }
+}
+
+/// Check whether requests for a resource are currently backed off
+async fn check_failure(key: &str) -> Result<()> {
+    match get_json::<Failure>(&format!("{key}:failure")).await {
+        Some(failure) if failure.retry_at > now() => Err(failure.error),
+        _ => Ok(()),
+    }
+}
+
+/// Record a failure to fetch a resource, backing off further requests
+async fn record_failure(key: &str, config: &JanuaryCache, error: &Error) {
+    let key = format!("{key}:failure");
+    let attempts = get_json::<Failure>(&key)
+        .await
+        .map(|failure| failure.attempts)
+        .unwrap_or_default()
+        .saturating_add(1);
+
+    let backoff = config
+        .failure_backoff
+        .saturating_mul(1 << (attempts - 1).min(16))
+        .min(config.max_failure_backoff);
+
+    set_json(
+        &key,
+        &Failure {
+            attempts,
+            retry_at: now() + backoff,
+            error: error.clone(),
+        },
+        // Forget about past failures once the maximum backoff has passed again
+        backoff + config.max_failure_backoff,
+    )
+    .await;
+}
+
+/// Store an entry, keeping it around past expiry if it can be revalidated
+async fn store_entry<T: Serialize>(
+    key: &str,
+    config: &JanuaryCache,
+    value: T,
+    freshness: Freshness,
+) {
+    let retain = freshness.retain(config);
+    set_json(
+        key,
+        &Entry {
+            value,
+            expires_at: now() + freshness.ttl(config),
+            etag: freshness.etag,
+        },
+        retain,
+    )
+    .await;
+}
+
+/// Look up an embed in the in-memory cache
+pub async fn peek_embed(url: &str) -> Option<Embed> {
+    memory().await.embed.get(url).await
+}
+
+/// Look up an embed, generating it if it isn't cached
+///
+/// The generator is given the ETag of any stale cached copy to revalidate against.
+pub async fn embed<F, Fut>(url: &str, generate: F) -> Result<Embed>
+where
+    F: FnOnce(Option<String>) -> Fut,
+    Fut: Future<Output = Result<Fetched<Embed>>>,
+{
+    let memory = &memory().await.embed;
+    if let Some(hit) = memory.get(url).await {
+        return Ok(hit);
+    }
+
+    let config = config().await.january.cache;
+    if !config.shared {
+        let embed = match generate(None).await? {
+            Fetched::Modified(embed, _) => embed,
+            Fetched::Unchanged(_) => return Err(create_error!(ProxyError)),
+        };
+
+        memory.insert(url.to_owned(), embed.clone()).await;
+        return Ok(embed);
+    }
+
+    let key = key("embed", url);
+    check_failure(&key).await?;
+
+    let stale = match get_json::<Entry<Embed>>(&key).await {
+        Some(entry) if entry.expires_at > now() => {
+            memory.insert(url.to_owned(), entry.value.clone()).await;
+            return Ok(entry.value);
+        }
+        Some(entry) if entry.etag.is_some() => Some(entry),
+        _ => None,
+    };
+
+    let (embed, freshness) = match generate(stale.as_ref().and_then(|e| e.etag.clone())).await {
+        Ok(Fetched::Modified(embed, freshness)) => (embed, freshness),
+        Ok(Fetched::Unchanged(freshness)) => match stale {
+            Some(entry) => (
+                entry.value,
+                Freshness {
+                    etag: freshness.etag.or(entry.etag),
+                    ..freshness
+                },
+            ),
+            None => return Err(create_error!(ProxyError)),
+        },
+        Err(error) => {
+            record_failure(&key, &config, &error).await;
+            return Err(error);
+        }
+    };
+
+    if freshness.shareable {
+        store_entry(&key, &config, embed.clone(), freshness).await;
+    }
+
+    delete(&format!("{key}:failure")).await;
+    memory.insert(url.to_owned(), embed.clone()).await;
+    Ok(embed)
+}
+
+/// Fetch proxied file contents from the shared cache
+async fn fetch_contents(key: &str, config: &JanuaryCache) -> Option<Vec<u8>> {
+    if config.store_proxied_files {
+        fetch_variant_from_s3(&config_bucket().await, &storage_path(key)).await
+    } else {
+        let mut conn = get_connection().await.ok()?;
+        conn.get(format!("{key}:contents")).await.ok()?
+    }
+}
+
+/// Store proxied file contents in the shared cache
+///
+/// Returns whether the contents were stored.
+async fn store_contents(key: &str, config: &JanuaryCache, contents: &[u8], ttl: u64) -> bool {
+    if config.store_proxied_files {
+        upload_variant_to_s3(&config_bucket().await, &storage_path(key), contents)
+            .await
+            .is_ok()
+    } else if contents.len() <= config.redis_max_size {
+        if let Ok(mut conn) = get_connection().await {
+            conn.set_ex::<_, _, ()>(format!("{key}:contents"), contents, ttl as usize)
+                .await
+                .is_ok()
+        } else {
+            false
+        }
+    } else {
+        false
+    }
+}
+
+/// Bucket proxied files are stored in
+async fn config_bucket() -> String {
+    config().await.files.s3.default_bucket
+}
+
+/// Path proxied files are stored at
+fn storage_path(key: &str) -> String {
+    key.replace(':', "/")
+}
+
+/// How often to look for proxied files which are no longer cached (in seconds)
+const SWEEP_INTERVAL: u64 = 60 * 60;
+
+/// How long a proxied file may go without an entry before it is swept (in seconds)
+///
+/// Contents are uploaded before their entry is written.
+const SWEEP_GRACE_PERIOD: u64 = 10 * 60;
+
+/// Periodically delete proxied files from S3 once their shared cache entry has expired
+pub async fn sweep_proxied_files() {
+    loop {
+        tokio::time::sleep(Duration::from_secs(SWEEP_INTERVAL)).await;
+
+        let config = config().await.january.cache;
+        if !config.shared || !config.store_proxied_files {
+            continue;
+        }
+
+        let bucket = config_bucket().await;
+        let files = match list_variants_in_s3(&bucket, &storage_path("january:proxy:")).await {
+            Ok(files) => files,
+            Err(error) => {
+                tracing::error!("Failed to list proxied files: {error:?}");
+                continue;
+            }
+        };
+
+        // Without Redis we can't tell which files are still in use
+        let Ok(mut conn) = get_connection().await else {
+            continue;
+        };
+
+        let mut swept = 0;
+        for (path, last_modified) in files {
+            if (last_modified.max(0) as u64) + SWEEP_GRACE_PERIOD > now() {
+                continue;
+            }
+
+            let exists: bool = match conn.exists(path.replace('/', ":")).await {
+                Ok(exists) => exists,
+                Err(_) => break,
+            };
+
+            if !exists {
+                match delete_from_s3(&bucket, &path).await {
+                    Ok(_) => swept += 1,
+                    Err(error) => tracing::error!("Failed to delete {path}: {error:?}"),
+                }
+            }
+        }
+
+        if swept > 0 {
+            tracing::info!("Swept {swept} expired proxied files");
+        }
+    }
+}
+
+/// Look up a proxied file, fetching it if it isn't cached
+///
+/// The fetcher is given the ETag of any stale cached copy to revalidate against.
+pub async fn proxy<F, Fut>(url: &str, fetch: F) -> Result<(String, Vec<u8>)>
+where
+    F: FnOnce(Option<String>) -> Fut,
+    Fut: Future<Output = Result<Fetched<(String, Vec<u8>)>>>,
+{
+    let memory = &memory().await.proxy;
+    if let Some(hit) = memory.get(url).await {
+        return hit;
+    }
+
+    let config = config().await.january.cache;
+    if !config.shared {
+        let result = match fetch(None).await {
+            Ok(Fetched::Modified(file, _)) => Ok(file),
+            Ok(Fetched::Unchanged(_)) => Err(create_error!(ProxyError)),
+            Err(error) => Err(error),
+        };
+
+        memory.insert(url.to_owned(), result.clone()).await;
+        return result;
+    }
+
+    let key = key("proxy", url);
+    check_failure(&key).await?;
+
+    // Entries only hold the content type, the contents are stored separately
+    let mut stale = None;
+    if let Some(entry) = get_json::<Entry<String>>(&key).await {
+        if let Some(contents) = fetch_contents(&key, &config).await {
+            if entry.expires_at > now() {
+                let file = (entry.value, contents);
+                memory.insert(url.to_owned(), Ok(file.clone())).await;
+                return Ok(file);
+            } else if entry.etag.is_some() {
+                stale = Some((entry, contents));
+            }
+        }
+    }
+
+    let result = match fetch(stale.as_ref().and_then(|(e, _)| e.etag.clone())).await {
+        Ok(Fetched::Modified(file, freshness)) => Ok((file, freshness, true)),
+        Ok(Fetched::Unchanged(freshness)) => match stale {
+            Some((entry, contents)) => Ok((
+                (entry.value, contents),
+                Freshness {
+                    etag: freshness.etag.or(entry.etag),
+                    ..freshness
+                },
+                false,
+            )),
+            None => Err(create_error!(ProxyError)),
+        },
+        Err(error) => Err(error),
+    };
+
+    match result {
+        Ok(((mime, contents), freshness, modified)) => {
+            if freshness.shareable {
+                // Unchanged contents in S3 don't need to be uploaded again
+                let stored = if modified || !config.store_proxied_files {
+                    store_contents(&key, &config, &contents, freshness.retain(&config)).await
+                } else {
+                    true
+                };
+
+                if stored {
+                    store_entry(&key, &config, mime.clone(), freshness).await;
+                }
+            }
+
+            delete(&format!("{key}:failure")).await;
+
+            let file = (mime, contents);
+            memory.insert(url.to_owned(), Ok(file.clone())).await;
+            Ok(file)
+        }
+        Err(error) => {
+            record_failure(&key, &config, &error).await;
+            memory.insert(url.to_owned(), Err(error.clone())).await;
+            Err(error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderMap, HeaderValue, AGE, CACHE_CONTROL, ETAG};
+    use revolt_config::JanuaryCache;
+
+    use super::{key, storage_path, Freshness};
+
+    fn headers(values: &[(reqwest::header::HeaderName, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in values {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+
+        headers
+    }
+
+    fn config() -> JanuaryCache {
+        JanuaryCache {
+            embed_capacity: 0,
+            proxy_capacity: 0,
+            memory_ttl: 60,
+            shared: true,
+            default_ttl: 3600,
+            min_ttl: 60,
+            max_ttl: 86400,
+            store_proxied_files: true,
+            redis_max_size: 0,
+            failure_backoff: 30,
+            max_failure_backoff: 3600,
+        }
+    }
+
+    #[test]
+    fn reads_freshness_from_headers() {
+        let freshness = Freshness::from_headers(&headers(&[
+            (CACHE_CONTROL, "public, max-age=600"),
+            (AGE, "100"),
+            (ETAG, "\"abc\""),
+        ]));
+
+        assert_eq!(freshness.max_age, Some(500));
+        assert_eq!(freshness.etag.as_deref(), Some("\"abc\""));
+        assert!(freshness.shareable);
+
+        // Shared caches follow s-maxage over max-age
+        let freshness =
+            Freshness::from_headers(&headers(&[(CACHE_CONTROL, "max-age=600, s-maxage=60")]));
+        assert_eq!(freshness.max_age, Some(60));
+
+        let freshness = Freshness::from_headers(&headers(&[(CACHE_CONTROL, "no-cache")]));
+        assert_eq!(freshness.max_age, Some(0));
+
+        for value in ["private, max-age=600", "no-store"] {
+            assert!(!Freshness::from_headers(&headers(&[(CACHE_CONTROL, value)])).shareable);
+        }
+
+        let freshness = Freshness::from_headers(&HeaderMap::new());
+        assert_eq!(freshness.max_age, None);
+        assert!(freshness.shareable);
+    }
+
+    #[test]
+    fn clamps_lifetimes() {
+        let config = config();
+        let freshness = |max_age, etag: Option<&str>| Freshness {
+            max_age,
+            etag: etag.map(str::to_owned),
+            shareable: true,
+        };
+
+        assert_eq!(freshness(None, None).ttl(&config), 3600);
+        assert_eq!(freshness(Some(0), None).ttl(&config), 60);
+        assert_eq!(freshness(Some(600), None).ttl(&config), 600);
+        assert_eq!(freshness(Some(10_000_000), None).ttl(&config), 86400);
+
+        // Entries which can be revalidated are kept around for longer
+        assert_eq!(freshness(Some(600), None).retain(&config), 600);
+        assert_eq!(
+            freshness(Some(600), Some("etag")).retain(&config),
+            600 + 86400
+        );
+    }
+
+    #[test]
+    fn maps_keys_to_storage_paths() {
+        let key = key("proxy", "https://example.com/image.png");
+        assert!(key.starts_with("january:proxy:"));
+        assert_eq!(key, super::key("proxy", "https://example.com/image.png"));
+        assert_ne!(key, super::key("embed", "https://example.com/image.png"));
+
+        let path = storage_path(&key);
+        assert!(path.starts_with("january/proxy/"));
+        assert_eq!(path.replace('/', ":"), key);
+    }
+}
@@ -0,0 +1,117 @@
+use std::{error::Error, net::IpAddr, sync::Arc};
+
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    Url,
+};
+use revolt_config::Settings;
+use revolt_guard::{is_ip_allowed, lookup_public_host};
+
+/// Guard deciding which hosts and addresses may be requested
+pub struct HostGuard {
+    /// Hosts which may be fetched from (any if empty)
+    allowed_hosts: Vec<String>,
+    /// Hosts which may never be fetched from
+    blocked_hosts: Vec<String>,
+}
+
+impl HostGuard {
+    /// Create a new guard from configuration
+    pub fn from_config(config: &Settings) -> HostGuard {
+        let mut blocked_hosts = config.january.blocked_hosts.clone();
+
+        // Never allow January to request itself
+        if let Some(host) = Url::parse(&config.hosts.january)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_owned))
+        {
+            blocked_hosts.push(host);
+        }
+
+        HostGuard {
+            allowed_hosts: config.january.allowed_hosts.clone(),
+            blocked_hosts,
+        }
+    }
+
+    /// Check whether a host name may be requested
+    pub fn is_host_allowed(&self, host: &str) -> bool {
+        let host = host.trim_end_matches('.').to_lowercase();
+
+        (self.allowed_hosts.is_empty()
+            || self
+                .allowed_hosts
+                .iter()
+                .any(|pattern| host_matches(&host, pattern)))
+            && !self
+                .blocked_hosts
+                .iter()
+                .any(|pattern| host_matches(&host, pattern))
+    }
+
+    /// Check whether a URL may be requested
+    ///
+    /// Host names are resolved through [`GuardedResolver`], so only
+    /// IP literals need to have their address checked here.
+    pub fn is_url_allowed(&self, url: &Url) -> bool {
+        if !matches!(url.scheme(), "http" | "https") {
+            return false;
+        }
+
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+
+        if let Ok(ip) = host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+        {
+            if !is_ip_allowed(ip) {
+                return false;
+            }
+        }
+
+        self.is_host_allowed(host)
+    }
+}
+
+/// Check whether a host matches a pattern or is a subdomain of it
+fn host_matches(host: &str, pattern: &str) -> bool {
+    let pattern = pattern.trim_end_matches('.').to_lowercase();
+    host == pattern || host.ends_with(&format!(".{pattern}"))
+}
+
+/// DNS resolver which only ever hands out addresses January may connect to
+///
+/// Connections are made to the addresses checked here, so a host cannot
+/// pass validation and then rebind to an internal address.
+pub struct GuardedResolver {
+    /// Guard to check host names against
+    guard: Arc<HostGuard>,
+}
+
+impl GuardedResolver {
+    /// Create a new resolver using the given guard
+    pub fn new(guard: Arc<HostGuard>) -> GuardedResolver {
+        GuardedResolver { guard }
+    }
+}
+
+impl Resolve for GuardedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let guard = self.guard.clone();
+
+        Box::pin(async move {
+            if !guard.is_host_allowed(name.as_str()) {
+                return Err(Box::<dyn Error + Send + Sync>::from(format!(
+                    "host {} is not allowed",
+                    name.as_str()
+                )));
+            }
+
+            let addrs = lookup_public_host(name.as_str()).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
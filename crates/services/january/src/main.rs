@@ -10,6 +10,9 @@ use utoipa::{
 use utoipa_scalar::{Scalar, Servable as ScalarServable};
 
 mod api;
+pub mod cache;
+pub mod guard;
+pub mod oembed;
 pub mod requests;
 pub mod website_embed;
 
@@ -60,6 +63,9 @@ async fn main() -> Result<(), std::io::Error> {
         }
     }
 
+    // Clean up proxied files which have fallen out of the shared cache
+    tokio::spawn(cache::sweep_proxied_files());
+
     // Configure Axum and router
     let app = Router::new()
         .merge(Scalar::with_url("/scalar", ApiDoc::openapi()))
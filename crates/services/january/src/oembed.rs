@@ -0,0 +1,460 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::Url;
+use revolt_models::v0::{Image, ImageSize, WebsiteMetadata};
+use scraper::{Html, Selector};
+use serde::{Deserialize, Deserializer};
+
+use crate::requests::Request;
+
+lazy_static! {
+    /// Bundled oEmbed provider registry
+    static ref PROVIDERS: ProviderRegistry =
+        ProviderRegistry::from_json(include_str!("oembed_providers.json"))
+            .expect("valid oEmbed provider registry");
+}
+
+/// Provider entry as found in the registry file
+///
+/// Follows the format of the registry published at https://oembed.com/providers.json
+#[derive(Deserialize)]
+struct ProviderDefinition {
+    provider_name: String,
+    endpoints: Vec<EndpointDefinition>,
+}
+
+/// Endpoint entry as found in the registry file
+#[derive(Deserialize)]
+struct EndpointDefinition {
+    #[serde(default)]
+    schemes: Vec<String>,
+    url: String,
+}
+
+/// oEmbed endpoint and the URLs it serves
+struct Endpoint {
+    /// Name of the provider
+    provider_name: String,
+    /// Patterns for URLs handled by this endpoint
+    schemes: Vec<Regex>,
+    /// URL of the endpoint
+    url: String,
+}
+
+/// Registry of known oEmbed providers
+pub struct ProviderRegistry {
+    endpoints: Vec<Endpoint>,
+}
+
+impl ProviderRegistry {
+    /// Load a registry from its JSON representation
+    pub fn from_json(json: &str) -> serde_json::Result<ProviderRegistry> {
+        let providers: Vec<ProviderDefinition> = serde_json::from_str(json)?;
+
+        Ok(ProviderRegistry {
+            endpoints: providers
+                .into_iter()
+                .flat_map(|provider| {
+                    let provider_name = provider.provider_name;
+                    provider
+                        .endpoints
+                        .into_iter()
+                        .map(move |endpoint| Endpoint {
+                            provider_name: provider_name.clone(),
+                            schemes: endpoint
+                                .schemes
+                                .iter()
+                                .filter_map(|scheme| scheme_to_regex(scheme))
+                                .collect(),
+                            url: endpoint.url,
+                        })
+                })
+                .collect(),
+        })
+    }
+
+    /// Find the endpoint to query for a given URL
+    pub fn find_endpoint(&self, url: &str) -> Option<Url> {
+        self.endpoints
+            .iter()
+            .find(|endpoint| endpoint.schemes.iter().any(|scheme| scheme.is_match(url)))
+            .and_then(|endpoint| {
+                tracing::debug!("Using {} oEmbed endpoint for {url}", endpoint.provider_name);
+                endpoint_url(&endpoint.url, url)
+            })
+    }
+}
+
+/// Convert a registry URL scheme (using `*` as a wildcard) into a regex
+///
+/// Schemes are matched regardless of whether the URL uses http or https.
+fn scheme_to_regex(scheme: &str) -> Option<Regex> {
+    let scheme = scheme
+        .strip_prefix("https://")
+        .or_else(|| scheme.strip_prefix("http://"))
+        .unwrap_or(scheme);
+
+    let pattern = regex::escape(scheme).replace("\\*", ".*");
+    Regex::new(&format!("^https?://{pattern}$")).ok()
+}
+
+/// Build the URL to request oEmbed data for a given URL from an endpoint
+fn endpoint_url(endpoint: &str, url: &str) -> Option<Url> {
+    let mut endpoint = Url::parse(&endpoint.replace("{format}", "json")).ok()?;
+    endpoint
+        .query_pairs_mut()
+        .append_pair("url", url)
+        .append_pair("format", "json");
+
+    Some(endpoint)
+}
+
+/// Find an oEmbed endpoint advertised by a document
+pub fn discover(document: &Html, original_url: &str) -> Option<String> {
+    let selector =
+        Selector::parse("link[rel~=\"alternate\"][type=\"application/json+oembed\"]").ok()?;
+
+    let href = document.select(&selector).next()?.value().attr("href")?;
+
+    // Resolve relative links against the page
+    Url::parse(original_url)
+        .ok()?
+        .join(href.trim())
+        .ok()
+        .map(|url| url.to_string())
+}
+
+/// Type specific oEmbed response data
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum OEmbedKind {
+    /// Static photo
+    Photo {
+        url: String,
+        #[serde(default, deserialize_with = "dimension")]
+        width: Option<usize>,
+        #[serde(default, deserialize_with = "dimension")]
+        height: Option<usize>,
+    },
+    /// Playable video
+    Video {
+        html: String,
+        #[serde(default, deserialize_with = "dimension")]
+        width: Option<usize>,
+        #[serde(default, deserialize_with = "dimension")]
+        height: Option<usize>,
+    },
+    /// Rich HTML content
+    Rich {
+        html: String,
+        #[serde(default, deserialize_with = "dimension")]
+        width: Option<usize>,
+        #[serde(default, deserialize_with = "dimension")]
+        height: Option<usize>,
+    },
+    /// Generic link without any content
+    Link,
+}
+
+/// oEmbed response
+#[derive(Deserialize, Debug, Clone)]
+pub struct OEmbed {
+    /// Type specific data
+    #[serde(flatten)]
+    pub kind: OEmbedKind,
+    /// Title of the resource
+    pub title: Option<String>,
+    /// Name of the author of the resource
+    pub author_name: Option<String>,
+    /// Name of the provider of the resource
+    pub provider_name: Option<String>,
+    /// URL to a thumbnail of the resource
+    pub thumbnail_url: Option<String>,
+    /// Width of the thumbnail
+    #[serde(default, deserialize_with = "dimension")]
+    pub thumbnail_width: Option<usize>,
+    /// Height of the thumbnail
+    #[serde(default, deserialize_with = "dimension")]
+    pub thumbnail_height: Option<usize>,
+}
+
+/// Deserialize a dimension which providers may send as a number or a string
+fn dimension<'de, D>(deserializer: D) -> Result<Option<usize>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::Number(number)) => number
+                .as_u64()
+                .or_else(|| number.as_f64().map(|value| value as u64))
+                .map(|value| value as usize),
+            Some(serde_json::Value::String(value)) => value.trim().parse().ok(),
+            _ => None,
+        },
+    )
+}
+
+impl OEmbed {
+    /// Fetch oEmbed data for a given URL
+    ///
+    /// Uses the provider registry first, falling back to an endpoint discovered in the page.
+    pub async fn fetch(url: &str, discovered: Option<String>) -> Option<OEmbed> {
+        let endpoint = PROVIDERS
+            .find_endpoint(url)
+            .map(|endpoint| endpoint.to_string())
+            .or(discovered)?;
+
+        let body = Request::new(&endpoint).await.ok()?.body().await.ok()?;
+        OEmbed::from_slice(&body)
+    }
+
+    /// Parse an oEmbed response
+    pub fn from_slice(body: &[u8]) -> Option<OEmbed> {
+        serde_json::from_slice(body)
+            .inspect_err(|err| tracing::debug!("Invalid oEmbed response: {err:?}"))
+            .ok()
+    }
+
+    /// Source of the iframe used to embed video or rich content
+    pub fn iframe_src(&self) -> Option<String> {
+        let html = match &self.kind {
+            OEmbedKind::Video { html, .. } | OEmbedKind::Rich { html, .. } => html,
+            _ => return None,
+        };
+
+        let selector = Selector::parse("iframe").ok()?;
+        Html::parse_fragment(html)
+            .select(&selector)
+            .next()?
+            .value()
+            .attr("src")
+            .map(|src| src.trim().to_owned())
+    }
+
+    /// Merge oEmbed data into website metadata
+    ///
+    /// Information from the page itself is kept where present,
+    /// except for photos which oEmbed describes authoritatively.
+    pub fn apply(self, metadata: &mut WebsiteMetadata) {
+        if metadata.title.is_none() {
+            metadata.title = self.title;
+        }
+
+        if metadata.site_name.is_none() {
+            metadata.site_name = self.provider_name;
+        }
+
+        if metadata.author_name.is_none() {
+            metadata.author_name = self.author_name;
+        }
+
+        let thumbnail = self.thumbnail_url.map(|url| Image {
+            url,
+            width: self.thumbnail_width.unwrap_or_default(),
+            height: self.thumbnail_height.unwrap_or_default(),
+            size: ImageSize::Preview,
+            placeholder: None,
+        });
+
+        match self.kind {
+            OEmbedKind::Photo { url, width, height } => {
+                metadata.image = Some(Image {
+                    url,
+                    width: width.unwrap_or_default(),
+                    height: height.unwrap_or_default(),
+                    size: ImageSize::Large,
+                    placeholder: None,
+                });
+            }
+            OEmbedKind::Video { .. } => {
+                if metadata.image.is_none() {
+                    metadata.image = thumbnail.map(|image| Image {
+                        size: ImageSize::Large,
+                        ..image
+                    });
+                }
+            }
+            OEmbedKind::Rich { .. } | OEmbedKind::Link => {
+                if metadata.image.is_none() {
+                    metadata.image = thumbnail;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use revolt_models::v0::{ImageSize, Special, WebsiteMetadata};
+    use scraper::Html;
+
+    use super::{discover, OEmbed, OEmbedKind, ProviderRegistry, PROVIDERS};
+
+    macro_rules! fixture {
+        ($name: expr) => {
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/fixtures/oembed/",
+                $name
+            ))
+        };
+    }
+
+    fn empty_metadata() -> WebsiteMetadata {
+        WebsiteMetadata {
+            url: None,
+            original_url: None,
+            special: None,
+            title: None,
+            description: None,
+            image: None,
+            video: None,
+            site_name: None,
+            author_name: None,
+            icon_url: None,
+            colour: None,
+        }
+    }
+
+    #[test]
+    fn discovers_endpoint_from_document() {
+        let document = Html::parse_document(fixture!("discovery.html"));
+
+        assert_eq!(
+            discover(&document, "https://blog.example.com/posts/hello-world").as_deref(),
+            Some("https://blog.example.com/oembed?url=https%3A%2F%2Fblog.example.com%2Fposts%2Fhello-world&format=json")
+        );
+
+        assert_eq!(
+            discover(
+                &Html::parse_document("<html></html>"),
+                "https://example.com"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn registry_matches_schemes() {
+        let endpoint = PROVIDERS
+            .find_endpoint("https://www.youtube.com/watch?v=dQw4w9WgXcQ")
+            .expect("YouTube endpoint");
+
+        assert_eq!(endpoint.host_str(), Some("www.youtube.com"));
+        assert_eq!(
+            endpoint.query(),
+            Some("url=https%3A%2F%2Fwww.youtube.com%2Fwatch%3Fv%3DdQw4w9WgXcQ&format=json")
+        );
+
+        let endpoint = PROVIDERS
+            .find_endpoint("http://vimeo.com/76979871")
+            .expect("Vimeo endpoint");
+
+        assert_eq!(endpoint.path(), "/api/oembed.json");
+        assert!(PROVIDERS
+            .find_endpoint("https://example.com/watch")
+            .is_none());
+    }
+
+    #[test]
+    fn registry_loads_custom_providers() {
+        let registry = ProviderRegistry::from_json(
+            r#"[{ "provider_name": "Example", "endpoints": [{ "schemes": ["https://example.com/v/*"], "url": "https://example.com/oembed" }] }]"#,
+        )
+        .unwrap();
+
+        assert!(registry.find_endpoint("https://example.com/v/1").is_some());
+        assert!(registry.find_endpoint("https://example.com/w/1").is_none());
+    }
+
+    #[test]
+    fn maps_photo() {
+        let oembed = OEmbed::from_slice(fixture!("photo.json").as_bytes()).expect("photo");
+        let mut metadata = empty_metadata();
+        oembed.apply(&mut metadata);
+
+        let image = metadata.image.expect("image");
+        assert_eq!(
+            image.url,
+            "https://live.staticflickr.com/65535/53000000000_abcdef1234_b.jpg"
+        );
+        assert_eq!((image.width, image.height), (1024, 683));
+        assert!(matches!(image.size, ImageSize::Large));
+        assert_eq!(metadata.title.as_deref(), Some("Sunset over the bay"));
+        assert_eq!(metadata.site_name.as_deref(), Some("Flickr"));
+        assert_eq!(metadata.author_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(metadata.description, None);
+    }
+
+    #[test]
+    fn maps_video() {
+        let oembed = OEmbed::from_slice(fixture!("video.json").as_bytes()).expect("video");
+        assert!(matches!(
+            oembed.kind,
+            OEmbedKind::Video {
+                width: Some(200),
+                height: Some(113),
+                ..
+            }
+        ));
+
+        assert_eq!(
+            oembed.iframe_src().as_deref(),
+            Some("https://www.youtube.com/embed/dQw4w9WgXcQ?feature=oembed")
+        );
+
+        // Page metadata takes priority over oEmbed
+        let mut metadata = empty_metadata();
+        metadata.title = Some("From the page".to_owned());
+        oembed.apply(&mut metadata);
+
+        let image = metadata.image.expect("thumbnail");
+        assert_eq!(
+            image.url,
+            "https://i.ytimg.com/vi/dQw4w9WgXcQ/hqdefault.jpg"
+        );
+        assert_eq!((image.width, image.height), (480, 360));
+        assert!(matches!(image.size, ImageSize::Large));
+        assert_eq!(metadata.title.as_deref(), Some("From the page"));
+        assert_eq!(metadata.site_name.as_deref(), Some("YouTube"));
+    }
+
+    #[tokio::test]
+    async fn maps_rich_to_special() {
+        let oembed = OEmbed::from_slice(fixture!("rich.json").as_bytes()).expect("rich");
+        let src = oembed.iframe_src().expect("iframe");
+
+        let mut metadata = empty_metadata();
+        oembed.apply(&mut metadata);
+
+        let image = metadata.image.as_ref().expect("thumbnail");
+        assert!(matches!(image.size, ImageSize::Preview));
+
+        crate::website_embed::populate_special_from_iframe(&src, &mut metadata).await;
+        assert!(matches!(
+            metadata.special,
+            Some(Special::Spotify { ref content_type, ref id })
+                if content_type == "track" && id == "4cOdK2wGLETKBW3PvgPWqT"
+        ));
+    }
+
+    #[test]
+    fn maps_link() {
+        let oembed = OEmbed::from_slice(fixture!("link.json").as_bytes()).expect("link");
+        assert!(oembed.iframe_src().is_none());
+
+        let mut metadata = empty_metadata();
+        oembed.apply(&mut metadata);
+
+        assert!(metadata.image.is_none());
+        assert_eq!(metadata.title.as_deref(), Some("Release notes"));
+        assert_eq!(metadata.site_name.as_deref(), Some("Example"));
+    }
+
+    #[test]
+    fn rejects_invalid_responses() {
+        assert!(OEmbed::from_slice(b"<html></html>").is_none());
+        assert!(OEmbed::from_slice(br#"{"type": "unknown", "version": "1.0"}"#).is_none());
+    }
+}
@@ -4,61 +4,93 @@ use mime::Mime;
 use regex::Regex;
 use reqwest::{
     header::{self, CONTENT_TYPE},
-    redirect, Client, Response,
+    redirect, Client, Response, StatusCode, Url,
 };
 use revolt_config::report_internal_error;
-use revolt_files::{create_thumbnail, decode_image, image_size_vec, is_valid_image, video_size};
+use revolt_files::{
+    create_placeholder, create_thumbnail, decode_image, image_size_vec, is_valid_image,
+    video_poster, video_size,
+};
 use revolt_models::v0::{Embed, Image, ImageSize, Video};
-use revolt_result::{create_error, Error, Result};
+use revolt_result::{create_error, Result};
 use std::{
     io::{Cursor, Write},
+    sync::Arc,
     time::Duration,
 };
+use tokio::sync::OnceCell;
 
-lazy_static! {
-    /// Request client
-    static ref CLIENT: Client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(10)) // TODO config
-        .connect_timeout(Duration::from_secs(5)) // TODO config
-        .redirect(redirect::Policy::custom(|attempt| {
-            if attempt.previous().len() > 5 { // TODO config
-                attempt.error("too many redirects")
-            } else if attempt.url().host_str() == Some("jan.revolt.chat") { // TODO config
-                attempt.stop()
-            } else {
-                attempt.follow()
-            }
-        }))
-        .build()
-        .expect("reqwest Client");
+use crate::{
+    cache::{self, Fetched, Freshness},
+    guard::{GuardedResolver, HostGuard},
+};
+
+/// Request client and the guard it enforces
+static CLIENT: OnceCell<(Client, Arc<HostGuard>)> = OnceCell::const_new();
+
+/// Get the request client, building it from configuration on first use
+async fn client() -> &'static (Client, Arc<HostGuard>) {
+    CLIENT
+        .get_or_init(|| async {
+            let config = revolt_config::config().await;
+            let guard = Arc::new(HostGuard::from_config(&config));
+            let redirect_guard = guard.clone();
+
+            let client = reqwest::Client::builder()
+                .timeout(Duration::from_secs(10)) // TODO config
+                .connect_timeout(Duration::from_secs(5)) // TODO config
+                .dns_resolver(Arc::new(GuardedResolver::new(guard.clone())))
+                .redirect(redirect::Policy::custom(move |attempt| {
+                    if attempt.previous().len() > 5 {
+                        // TODO config
+                        attempt.error("too many redirects")
+                    } else if !redirect_guard.is_url_allowed(attempt.url()) {
+                        attempt.error("redirect to disallowed url")
+                    } else {
+                        attempt.follow()
+                    }
+                }))
+                .build()
+                .expect("reqwest Client");
+
+            (client, guard)
+        })
+        .await
+}
+
+/// Read the body of a response, giving up once it exceeds the configured limit
+async fn read_body(mut response: Response) -> Result<Vec<u8>> {
+    let max = revolt_config::config().await.january.max_body_size;
+
+    if response
+        .content_length()
+        .is_some_and(|length| length as usize > max)
+    {
+        return Err(create_error!(FileTooLarge { max }));
+    }
 
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|_| create_error!(ProxyError))?
+    {
+        if bytes.len() + chunk.len() > max {
+            return Err(create_error!(FileTooLarge { max }));
+        }
+
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(bytes)
+}
+
+lazy_static! {
     /// Spoof User Agent as Discord
     static ref RE_USER_AGENT_SPOOFING_AS_DISCORD: Regex = Regex::new("^(?:(?:https?:)?//)?(?:(?:vx|fx)?twitter|(?:fixv|fixup)?x|(?:old\\.|new\\.|www\\.)reddit).com").expect("valid regex");
 
     /// Regex for matching new Reddit URLs
     static ref RE_URL_NEW_REDDIT: Regex = Regex::new("^(?:(?:https?:)?//)?(?:(?:new\\.|www\\.)?reddit).com").expect("valid regex");
-
-    /// Cache for proxy results
-    static ref PROXY_CACHE: moka::future::Cache<String, Result<(String, Vec<u8>)>> = moka::future::Cache::builder()
-        .weigher(|_key, value: &Result<(String, Vec<u8>)>| -> u32 {
-            std::mem::size_of::<Result<(String, Vec<u8>)>>() as u32 + if let Ok((url, vec)) = value {
-                url.len().try_into().unwrap_or(u32::MAX) +
-                vec.len().try_into().unwrap_or(u32::MAX)
-            } else {
-                std::mem::size_of::<Error>() as u32
-            }
-        })
-        // TODO config
-        .max_capacity(512 * 1024 * 1024) // Cache up to 512MiB in memory
-        .time_to_live(Duration::from_secs(60)) // For up to 1 minute
-        .build();
-
-    /// Cache for embed results
-    static ref EMBED_CACHE: moka::future::Cache<String, Embed> = moka::future::Cache::builder()
-        // TODO config
-        .max_capacity(10_000) // Cache up to 10k embeds
-        .time_to_live(Duration::from_secs(60)) // For up to 1 minute
-        .build();
 }
 
 /// Information about a successful request
@@ -70,55 +102,47 @@ pub struct Request {
 impl Request {
     /// Proxy a given URL
     pub async fn proxy_file(url: &str) -> Result<(String, Vec<u8>)> {
-        if let Some(hit) = PROXY_CACHE.get(url).await {
-            hit
-        } else {
-            let Request { response, mime } = Request::new(url).await?;
-
-            if matches!(mime.type_(), mime::IMAGE | mime::VIDEO) {
-                let bytes = report_internal_error!(response.bytes().await);
-
-                let result = match bytes {
-                    Ok(bytes) => {
-                        if matches!(mime.type_(), mime::IMAGE) {
-                            let reader = &mut Cursor::new(&bytes);
-
-                            if matches!(mime.subtype(), mime::GIF) {
-                                if is_valid_image(reader, "image/gif") {
-                                    Ok(("image/gif".to_owned(), bytes.to_vec()))
-                                } else {
-                                    Err(create_error!(FileTypeNotAllowed))
-                                }
-                            } else {
-                                Ok((
-                                    "image/webp".to_owned(),
-                                    create_thumbnail(
-                                        decode_image(reader, mime.as_ref())?,
-                                        "attachments",
-                                    )
-                                    .await,
-                                ))
-                            }
-                        } else {
-                            let mut file = report_internal_error!(tempfile::NamedTempFile::new())?;
-                            report_internal_error!(file.write_all(&bytes))?;
-
-                            if video_size(&file).is_some() {
-                                Ok((mime.to_string(), bytes.to_vec()))
-                            } else {
-                                Err(create_error!(FileTypeNotAllowed))
-                            }
-                        }
-                    }
-                    Err(err) => Err(err),
+        cache::proxy(url, |etag| async move {
+            let (Request { response, mime }, freshness) =
+                match Request::revalidate(url, etag.as_deref()).await? {
+                    Fetched::Modified(request, freshness) => (request, freshness),
+                    Fetched::Unchanged(freshness) => return Ok(Fetched::Unchanged(freshness)),
                 };
 
-                PROXY_CACHE.insert(url.to_owned(), result.clone()).await;
-                result
-            } else {
-                Err(create_error!(FileTypeNotAllowed))
+            if !matches!(mime.type_(), mime::IMAGE | mime::VIDEO) {
+                return Err(create_error!(FileTypeNotAllowed));
             }
-        }
+
+            let bytes = read_body(response).await?;
+            let file = if matches!(mime.type_(), mime::IMAGE) {
+                let reader = &mut Cursor::new(&bytes);
+
+                if matches!(mime.subtype(), mime::GIF) {
+                    if is_valid_image(reader, "image/gif") {
+                        ("image/gif".to_owned(), bytes)
+                    } else {
+                        return Err(create_error!(FileTypeNotAllowed));
+                    }
+                } else {
+                    (
+                        "image/webp".to_owned(),
+                        create_thumbnail(decode_image(reader, mime.as_ref())?, "attachments").await,
+                    )
+                }
+            } else {
+                let mut file = report_internal_error!(tempfile::NamedTempFile::new())?;
+                report_internal_error!(file.write_all(&bytes))?;
+
+                if video_size(&file).is_some() {
+                    (mime.to_string(), bytes)
+                } else {
+                    return Err(create_error!(FileTypeNotAllowed));
+                }
+            };
+
+            Ok(Fetched::Modified(file, freshness))
+        })
+        .await
     }
 
     /// Fetch metadata for an image
@@ -126,7 +150,7 @@ impl Request {
         url: &str,
         request: Option<Request>,
     ) -> Result<Option<Image>> {
-        if let Some(hit) = EMBED_CACHE.get(url).await {
+        if let Some(hit) = cache::peek_embed(url).await {
             match hit {
                 Embed::Image(img) => Ok(Some(img)),
                 _ => Ok(None),
@@ -143,15 +167,16 @@ impl Request {
                 }
             };
 
-            if let Some((width, height)) = image_size_vec(
-                &report_internal_error!(request.response.bytes().await)?,
-                request.mime.as_ref(),
-            ) {
+            let bytes = read_body(request.response).await?;
+            if let Some((width, height)) = image_size_vec(&bytes, request.mime.as_ref()) {
                 Ok(Some(Image {
                     url: url.to_owned(),
                     width,
                     height,
                     size: ImageSize::Large,
+                    placeholder: decode_image(&mut Cursor::new(&bytes), request.mime.as_ref())
+                        .ok()
+                        .and_then(|image| create_placeholder(&image)),
                 }))
             } else {
                 Ok(None)
@@ -164,7 +189,7 @@ impl Request {
         url: &str,
         request: Option<Request>,
     ) -> Result<Option<Video>> {
-        if let Some(hit) = EMBED_CACHE.get(url).await {
+        if let Some(hit) = cache::peek_embed(url).await {
             match hit {
                 Embed::Video(vid) => Ok(Some(vid)),
                 _ => Ok(None),
@@ -182,15 +207,14 @@ impl Request {
             };
 
             let mut file = report_internal_error!(tempfile::NamedTempFile::new())?;
-            report_internal_error!(
-                file.write_all(&report_internal_error!(response.bytes().await)?)
-            )?;
+            report_internal_error!(file.write_all(&read_body(response).await?))?;
 
             if let Some((width, height)) = video_size(&file) {
                 Ok(Some(Video {
                     url: url.to_owned(),
                     width: width as usize,
                     height: height as usize,
+                    placeholder: video_poster(&file).and_then(|image| create_placeholder(&image)),
                 }))
             } else {
                 Ok(None)
@@ -209,10 +233,13 @@ impl Request {
         }
 
         // Generate the actual embed
-        if let Some(hit) = EMBED_CACHE.get(&url).await {
-            Ok(hit)
-        } else {
-            let request = Request::new(&url).await?;
+        let url = &url;
+        cache::embed(url, |etag| async move {
+            let (request, freshness) = match Request::revalidate(url, etag.as_deref()).await? {
+                Fetched::Modified(request, freshness) => (request, freshness),
+                Fetched::Unchanged(freshness) => return Ok(Fetched::Unchanged(freshness)),
+            };
+
             let embed = match (request.mime.type_(), request.mime.subtype()) {
                 (_, mime::HTML) => {
                     let content_type = request
@@ -230,34 +257,52 @@ impl Request {
                     let encoding =
                         Encoding::for_label(encoding_name.as_bytes()).unwrap_or(&UTF_8_INIT);
 
-                    let bytes = report_internal_error!(request.response.bytes().await)?;
+                    let bytes = read_body(request.response).await?;
                     let (text, _, _) = encoding.decode(&bytes);
 
-                    crate::website_embed::create_website_embed(&url, &text)
+                    crate::website_embed::create_website_embed(url, &text)
                         .await
                         .map(Embed::Website)
                         .unwrap_or_default()
                 }
-                (mime::IMAGE, _) => Request::fetch_image_metadata(&url, Some(request))
+                (mime::IMAGE, _) => Request::fetch_image_metadata(url, Some(request))
                     .await
                     .map(|res| res.map(Embed::Image).unwrap_or_default())
                     .unwrap_or_default(),
-                (mime::VIDEO, _) => Request::fetch_video_metadata(&url, Some(request))
+                (mime::VIDEO, _) => Request::fetch_video_metadata(url, Some(request))
                     .await
                     .map(|res| res.map(Embed::Video).unwrap_or_default())
                     .unwrap_or_default(),
                 _ => Embed::None,
             };
 
-            EMBED_CACHE.insert(url.to_owned(), embed.clone()).await;
-            Ok(embed)
-        }
+            Ok(Fetched::Modified(embed, freshness))
+        })
+        .await
     }
 
     /// Send a new request to a service
     pub async fn new(url: &str) -> Result<Request> {
-        let response = CLIENT
-            .get(url)
+        match Request::revalidate(url, None).await? {
+            Fetched::Modified(request, _) => Ok(request),
+            Fetched::Unchanged(_) => Err(create_error!(ProxyError)),
+        }
+    }
+
+    /// Send a new request to a service, revalidating a cached copy if an ETag is given
+    pub async fn revalidate(url: &str, etag: Option<&str>) -> Result<Fetched<Request>> {
+        let (client, guard) = client().await;
+        let parsed = Url::parse(url).map_err(|_| create_error!(ProxyError))?;
+        if !guard.is_url_allowed(&parsed) {
+            return Err(create_error!(ProxyError));
+        }
+
+        let mut request = client.get(parsed);
+        if let Some(etag) = etag {
+            request = request.header(header::IF_NONE_MATCH, etag);
+        }
+
+        let response = request
             .header(
                 "User-Agent",
                 if RE_USER_AGENT_SPOOFING_AS_DISCORD.is_match(url) {
@@ -271,6 +316,12 @@ impl Request {
             .await
             .map_err(|_| create_error!(ProxyError))?;
 
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(Fetched::Unchanged(Freshness::from_headers(
+                response.headers(),
+            )));
+        }
+
         if !response.status().is_success() {
             tracing::error!("{:?}", response);
             return Err(create_error!(ProxyError));
@@ -287,12 +338,27 @@ impl Request {
             .parse()
             .map_err(|_| create_error!(ProxyError))?;
 
-        Ok(Request { response, mime })
+        let freshness = Freshness::from_headers(response.headers());
+        Ok(Fetched::Modified(Request { response, mime }, freshness))
+    }
+
+    /// Read the body of this response
+    pub async fn body(self) -> Result<Vec<u8>> {
+        read_body(self.response).await
     }
 
     /// Check if something exists
     pub async fn exists(url: &str) -> bool {
-        if let Ok(response) = CLIENT.head(url).send().await {
+        let (client, guard) = client().await;
+        let Ok(url) = Url::parse(url) else {
+            return false;
+        };
+
+        if !guard.is_url_allowed(&url) {
+            return false;
+        }
+
+        if let Ok(response) = client.head(url).send().await {
             response.status().is_success()
         } else {
             false
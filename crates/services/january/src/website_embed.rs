@@ -7,9 +7,11 @@ use revolt_models::v0::{
 };
 use scraper::{Html, Selector};
 
+use crate::oembed::OEmbed;
+
 /// Create website metadata from URL and document
 pub async fn create_website_embed(original_url: &str, document: &str) -> Option<WebsiteMetadata> {
-    let (mut meta, mut link) = {
+    let (mut meta, mut link, oembed_endpoint) = {
         let document = Html::parse_document(document);
 
         // create selectors
@@ -39,7 +41,10 @@ pub async fn create_website_embed(original_url: &str, document: &str) -> Option<
             }
         }
 
-        (meta, link)
+        // find advertised oEmbed endpoint
+        let oembed_endpoint = crate::oembed::discover(&document, original_url);
+
+        (meta, link, oembed_endpoint)
     };
 
     // build metadata
@@ -88,6 +93,7 @@ pub async fn create_website_embed(original_url: &str, document: &str) -> Option<
                         .parse()
                         .unwrap_or(0),
                     size,
+                    placeholder: None,
                 }
             }),
         video: meta
@@ -115,6 +121,7 @@ pub async fn create_website_embed(original_url: &str, document: &str) -> Option<
                         .unwrap_or_default()
                         .parse()
                         .unwrap_or(0),
+                    placeholder: None,
                 }
             }),
         icon_url: link
@@ -133,6 +140,7 @@ pub async fn create_website_embed(original_url: &str, document: &str) -> Option<
             }),
         colour: meta.remove("theme-color").map(|s| s.trim().to_owned()),
         site_name: meta.remove("og:site_name").map(|s| s.trim().to_owned()),
+        author_name: None,
         url: meta
             .remove("og:url")
             .or_else(|| Some(original_url.to_owned())),
@@ -140,12 +148,31 @@ pub async fn create_website_embed(original_url: &str, document: &str) -> Option<
         special: None,
     };
 
+    // merge in oEmbed data if the provider offers it
+    let iframe_src = if let Some(oembed) = OEmbed::fetch(original_url, oembed_endpoint).await {
+        let iframe_src = oembed.iframe_src();
+        oembed.apply(&mut metadata);
+        iframe_src
+    } else {
+        None
+    };
+
     // populate extra metadata for popular websites
     populate_special(original_url.to_owned(), &mut metadata).await;
 
+    // fall back to the player embedded through oEmbed
+    if metadata.special.is_none() {
+        if let Some(src) = iframe_src {
+            populate_special_from_iframe(&src, &mut metadata).await;
+        }
+    }
+
     // fetch video size if missing
     if metadata.special.is_none() {
-        if let Some(Video { width, height, url }) = &metadata.video {
+        if let Some(Video {
+            width, height, url, ..
+        }) = &metadata.video
+        {
             if width == &0 || height == &0 {
                 metadata.video =
                     match crate::requests::Request::fetch_video_metadata(url, None).await {
@@ -188,6 +215,30 @@ pub async fn create_website_embed(original_url: &str, document: &str) -> Option<
     }
 }
 
+/// Populate special content from the source of an embedded player
+pub async fn populate_special_from_iframe(src: &str, metadata: &mut WebsiteMetadata) {
+    let mut player = WebsiteMetadata {
+        url: Some(src.to_owned()),
+        original_url: Some(src.to_owned()),
+        special: None,
+        title: None,
+        description: None,
+        image: None,
+        video: metadata.video.clone(),
+        site_name: None,
+        author_name: None,
+        icon_url: None,
+        colour: None,
+    };
+
+    populate_special(src.to_owned(), &mut player).await;
+
+    if player.special.is_some() {
+        metadata.special = player.special;
+        metadata.colour = player.colour.or(metadata.colour.take());
+    }
+}
+
 pub async fn populate_special(original_url: String, metadata: &mut WebsiteMetadata) {
     lazy_static! {
         static ref RE_YOUTUBE: Regex = Regex::new("^(?:(?:https?:)?//)?(?:(?:www|m)\\.)?(?:(?:youtube\\.com|youtu.be))(?:/(?:[\\w\\-]+\\?v=|embed/|v/)?)([\\w\\-]+)(?:\\S+)?$").unwrap();
@@ -198,7 +249,7 @@ pub async fn populate_special(original_url: String, metadata: &mut WebsiteMetada
         static ref RE_TWITCH_VOD: Regex = Regex::new("^(?:https?://)?(?:www\\.|go\\.)?twitch\\.tv/videos/([0-9]+)($|\\?)").unwrap();
         static ref RE_TWITCH_CLIP: Regex = Regex::new("^(?:https?://)?(?:www\\.|go\\.)?twitch\\.tv/(?:[a-z0-9_]+)/clip/([A-z0-9_-]+)($|\\?)").unwrap();
 
-        static ref RE_SPOTIFY: Regex = Regex::new("^(?:https?://)?open.spotify.com/(track|user|artist|album|playlist)/([A-z0-9]+)").unwrap();
+        static ref RE_SPOTIFY: Regex = Regex::new("^(?:https?://)?open.spotify.com/(?:embed/)?(track|user|artist|album|playlist)/([A-z0-9]+)").unwrap();
         static ref RE_SOUNDCLOUD: Regex = Regex::new("^(?:https?://)?soundcloud.com/([a-zA-Z0-9-]+)/([A-z0-9-]+)").unwrap();
         static ref RE_BANDCAMP: Regex = Regex::new("^(?:https?://)?(?:[A-z0-9_-]+).bandcamp.com/(track|album)/([A-z0-9_-]+)").unwrap();
         static ref RE_APPLE_MUSIC: Regex = Regex::new("^(?:https?://)?music\\.apple\\.com/(?:[a-z]{2}/)?album/(?:[a-zA-Z0-9-]+)/(\\d+)(?:\\?i=(\\d+))?").unwrap();
@@ -234,6 +285,7 @@ pub async fn populate_special(original_url: String, metadata: &mut WebsiteMetada
             metadata.colour.take();
             metadata.icon_url.take();
             metadata.site_name.take();
+            metadata.author_name.take();
 
             // Verify the video exists
             if !crate::requests::Request::exists(&format!(
@@ -27,6 +27,7 @@ deps() {
     crates/core/config/src \
     crates/core/database/src \
     crates/core/files/src \
+    crates/core/guard/src \
     crates/core/models/src \
     crates/core/parser/src \
     crates/core/permissions/src \
@@ -51,6 +52,7 @@ deps() {
     tee crates/core/config/src/lib.rs |
     tee crates/core/database/src/lib.rs |
     tee crates/core/files/src/lib.rs |
+    tee crates/core/guard/src/lib.rs |
     tee crates/core/models/src/lib.rs |
     tee crates/core/parser/src/lib.rs |
     tee crates/core/permissions/src/lib.rs |
@@ -74,6 +76,7 @@ apps() {
     crates/daemons/pushd/src/main.rs \
     crates/core/config/src/lib.rs \
     crates/core/database/src/lib.rs \
+    crates/core/guard/src/lib.rs \
     crates/core/models/src/lib.rs \
     crates/core/parser/src/lib.rs \
     crates/core/permissions/src/lib.rs \